@@ -0,0 +1,79 @@
+//! # Grant the site admin role to a User, creating the User if needed
+//!
+//! Usage: `caster-admin <username>`, where the username is the token "sub" claim
+#![forbid(unsafe_code)]
+
+use anyhow::{anyhow, Result};
+use tracing_subscriber::prelude::*;
+
+use caster_api::Context;
+use caster_domains::{
+    audit_events::model::CreateAuditEventInput,
+    role_grants::model::CreateRoleGrantInput,
+    users::model::{ALL_USERS, SITE_ADMIN_ROLE},
+};
+use caster_utils::config::get_config;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::EnvFilter::new(
+            std::env::var("RUST_LOG").unwrap_or_else(|_| "info".into()),
+        ))
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let username = std::env::args()
+        .nth(1)
+        .ok_or_else(|| anyhow!("Usage: caster-admin <username>"))?;
+
+    let config = get_config();
+    let context = Context::init(config).await?;
+
+    let user = context.users.get_or_create(&username).await?;
+
+    let user = context
+        .users
+        .get_by_username(&user.username, &true)
+        .await?
+        .ok_or_else(|| anyhow!("Unable to find User with username: {}", username))?;
+
+    let is_site_admin = user.roles.iter().any(|role| {
+        role.role_key == SITE_ADMIN_ROLE
+            && role.resource_table == "users"
+            && role.resource_id == ALL_USERS
+    });
+
+    if is_site_admin {
+        println!("\n>> {} is already a site admin\n", username);
+
+        return Ok(());
+    }
+
+    let grant = context
+        .role_grants
+        .create(&CreateRoleGrantInput {
+            role_key: SITE_ADMIN_ROLE.to_string(),
+            user_id: user.id.clone(),
+            resource_table: "users".to_string(),
+            resource_id: ALL_USERS.to_string(),
+        })
+        .await?;
+
+    context
+        .audit_events
+        .create(&CreateAuditEventInput {
+            actor_id: None,
+            action: "create".to_string(),
+            resource_table: "role_grants".to_string(),
+            resource_id: grant.id.clone(),
+            before: None,
+            after: Some(serde_json::to_value(&grant)?),
+            request_id: None,
+        })
+        .await?;
+
+    println!("\n>> Granted the site admin role to {}\n", username);
+
+    Ok(())
+}
@@ -7,4 +7,13 @@ pub mod connections;
 /// Event handler
 pub mod handler;
 
+/// Notifications sent to subscribed and authenticated connections
+pub mod notifications;
+
+/// Chat Messages sent to the connections that have joined an Episode's chat
+pub mod chat;
+
+/// The viewers of each Episode, tracked for the connections watching it
+pub mod presence;
+
 mod router;
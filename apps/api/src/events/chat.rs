@@ -0,0 +1,39 @@
+use std::sync::Arc;
+use tokio::sync::broadcast::{error::RecvError, Receiver};
+
+use super::messages::OutgoingMessage;
+use crate::Context;
+use caster_domains::domain_events::model::DomainEvent;
+
+/// Forward changes to chat Messages to the connections that have joined the Episode's chat
+pub async fn forward_chat_messages(ctx: Arc<Context>, mut events: Receiver<DomainEvent>) {
+    loop {
+        let (episode_id, message) = match events.recv().await {
+            Ok(DomainEvent::MessagePosted(message)) => (
+                message.episode_id.clone(),
+                OutgoingMessage::MessagePosted { message },
+            ),
+            Ok(DomainEvent::MessageUpdated(message)) => (
+                message.episode_id.clone(),
+                OutgoingMessage::MessageUpdated { message },
+            ),
+            Ok(DomainEvent::MessageDeleted(message)) => (
+                message.episode_id.clone(),
+                OutgoingMessage::MessageDeleted {
+                    episode_id: message.episode_id,
+                    message_id: message.id,
+                },
+            ),
+            Ok(_) => continue,
+            Err(RecvError::Lagged(skipped)) => {
+                warn!("Skipped {} domain events while forwarding chat", skipped);
+                continue;
+            }
+            Err(RecvError::Closed) => break,
+        };
+
+        ctx.connections
+            .send_to_chat(&episode_id, message.into(), None)
+            .await;
+    }
+}
@@ -1,39 +1,262 @@
 use axum::extract::ws::Message;
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 use tokio::sync::{
-    mpsc::{self, UnboundedSender},
+    mpsc::{error::TrySendError, Sender},
     RwLock,
 };
 use ulid::Ulid;
 
 /// Our state of currently connected users.
-///
-/// - Key is their connection id
-/// - Value is a sender of `axum::extract::ws::Message`
 #[derive(Default)]
-pub struct Connections(Arc<RwLock<HashMap<String, mpsc::UnboundedSender<Message>>>>);
+pub struct Connections {
+    /// - Key is their connection id
+    /// - Value is a sender of `axum::extract::ws::Message`, with a bounded queue
+    senders: Arc<RwLock<HashMap<String, Sender<Message>>>>,
+
+    /// - Key is a Show id
+    /// - Value is the set of connection ids subscribed to it
+    subscriptions: Arc<RwLock<HashMap<String, HashSet<String>>>>,
+
+    /// - Key is a User id
+    /// - Value is the set of connection ids authenticated as that User
+    users: Arc<RwLock<HashMap<String, HashSet<String>>>>,
+
+    /// - Key is an Episode id
+    /// - Value is the set of connection ids that have joined its chat
+    chats: Arc<RwLock<HashMap<String, HashSet<String>>>>,
+
+    /// - Key is an Episode id
+    /// - Value is the set of connection ids watching it
+    watchers: Arc<RwLock<HashMap<String, HashSet<String>>>>,
+}
 
 impl Connections {
-    /// Send a Message to the given connection at the given id
+    /// Send a Message to the given connection at the given id. Connections that have fallen too
+    /// far behind to queue it are evicted by dropping their sender, which closes their socket. The
+    /// rest of their state is cleaned up by their handler with `remove`.
     pub async fn send(&self, conn_id: &str, message: Message) {
-        if let Some(connection) = self.0.read().await.get(conn_id) {
-            if let Err(_disconnected) = connection.send(message) {
-                // The tx is disconnected
+        let result = self
+            .senders
+            .read()
+            .await
+            .get(conn_id)
+            .map(|connection| connection.try_send(message));
+
+        match result {
+            Some(Err(TrySendError::Full(_))) => {
+                warn!("Send queue full, evicting(uid={})", conn_id);
+
+                self.senders.write().await.remove(conn_id);
             }
+            Some(Err(TrySendError::Closed(_))) => {
+                // The rx is disconnected
+            }
+            Some(Ok(())) | None => (),
         }
     }
 
-    ///. Inserts a connection into the hash map, and returns the id
-    pub async fn insert(&self, tx: UnboundedSender<Message>) -> String {
+    /// Whether a connection with the given id is still connected
+    pub async fn contains(&self, conn_id: &str) -> bool {
+        self.senders.read().await.contains_key(conn_id)
+    }
+
+    ///. Inserts a connection into the hash map, owned by the given User if authenticated, and
+    /// returns the id
+    pub async fn insert(&self, tx: Sender<Message>, user_id: Option<&str>) -> String {
         let conn_id = Ulid::new().to_string();
 
-        self.0.write().await.insert(conn_id.clone(), tx);
+        self.senders.write().await.insert(conn_id.clone(), tx);
+
+        if let Some(user_id) = user_id {
+            self.users
+                .write()
+                .await
+                .entry(user_id.to_string())
+                .or_default()
+                .insert(conn_id.clone());
+        }
 
         conn_id
     }
 
-    /// Removees a connection from the hash map
+    /// Removees a connection from the hash map, along with its subscriptions
     pub async fn remove(&self, conn_id: &str) {
-        self.0.write().await.remove(conn_id);
+        self.senders.write().await.remove(conn_id);
+
+        for index in [
+            &self.subscriptions,
+            &self.users,
+            &self.chats,
+            &self.watchers,
+        ] {
+            index.write().await.retain(|_key, conn_ids| {
+                conn_ids.remove(conn_id);
+
+                !conn_ids.is_empty()
+            });
+        }
+    }
+
+    /// Subscribe a connection to notifications about a Show
+    pub async fn subscribe(&self, conn_id: &str, show_id: &str) {
+        self.subscriptions
+            .write()
+            .await
+            .entry(show_id.to_string())
+            .or_default()
+            .insert(conn_id.to_string());
+    }
+
+    /// Unsubscribe a connection from notifications about a Show
+    pub async fn unsubscribe(&self, conn_id: &str, show_id: &str) {
+        let mut subscriptions = self.subscriptions.write().await;
+
+        if let Some(conn_ids) = subscriptions.get_mut(show_id) {
+            conn_ids.remove(conn_id);
+
+            if conn_ids.is_empty() {
+                subscriptions.remove(show_id);
+            }
+        }
+    }
+
+    /// Send a Message to every connection subscribed to a Show
+    pub async fn send_to_subscribers(&self, show_id: &str, message: Message) {
+        let conn_ids: Vec<String> = self
+            .subscriptions
+            .read()
+            .await
+            .get(show_id)
+            .map(|conn_ids| conn_ids.iter().cloned().collect())
+            .unwrap_or_default();
+
+        for conn_id in conn_ids {
+            self.send(&conn_id, message.clone()).await;
+        }
+    }
+
+    /// Join a connection to the chat about an Episode
+    pub async fn join_chat(&self, conn_id: &str, episode_id: &str) {
+        self.chats
+            .write()
+            .await
+            .entry(episode_id.to_string())
+            .or_default()
+            .insert(conn_id.to_string());
+    }
+
+    /// Remove a connection from the chat about an Episode
+    pub async fn leave_chat(&self, conn_id: &str, episode_id: &str) {
+        let mut chats = self.chats.write().await;
+
+        if let Some(conn_ids) = chats.get_mut(episode_id) {
+            conn_ids.remove(conn_id);
+
+            if conn_ids.is_empty() {
+                chats.remove(episode_id);
+            }
+        }
+    }
+
+    /// Whether a connection has joined the chat about an Episode
+    pub async fn in_chat(&self, conn_id: &str, episode_id: &str) -> bool {
+        self.chats
+            .read()
+            .await
+            .get(episode_id)
+            .is_some_and(|conn_ids| conn_ids.contains(conn_id))
+    }
+
+    /// Send a Message to every connection that has joined the chat about an Episode, except for
+    /// the given connection if any
+    pub async fn send_to_chat(&self, episode_id: &str, message: Message, except: Option<&str>) {
+        let conn_ids: Vec<String> = self
+            .chats
+            .read()
+            .await
+            .get(episode_id)
+            .map(|conn_ids| {
+                conn_ids
+                    .iter()
+                    .filter(|conn_id| Some(conn_id.as_str()) != except)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        for conn_id in conn_ids {
+            self.send(&conn_id, message.clone()).await;
+        }
+    }
+
+    /// Mark a connection as watching an Episode, returning false if it already was
+    pub async fn watch(&self, conn_id: &str, episode_id: &str) -> bool {
+        self.watchers
+            .write()
+            .await
+            .entry(episode_id.to_string())
+            .or_default()
+            .insert(conn_id.to_string())
+    }
+
+    /// Mark a connection as no longer watching an Episode, returning false if it wasn't
+    pub async fn unwatch(&self, conn_id: &str, episode_id: &str) -> bool {
+        let mut watchers = self.watchers.write().await;
+
+        let Some(conn_ids) = watchers.get_mut(episode_id) else {
+            return false;
+        };
+
+        let removed = conn_ids.remove(conn_id);
+
+        if conn_ids.is_empty() {
+            watchers.remove(episode_id);
+        }
+
+        removed
+    }
+
+    /// The ids of the Episodes that a connection is watching
+    pub async fn watched_by(&self, conn_id: &str) -> Vec<String> {
+        self.watchers
+            .read()
+            .await
+            .iter()
+            .filter(|(_episode_id, conn_ids)| conn_ids.contains(conn_id))
+            .map(|(episode_id, _conn_ids)| episode_id.clone())
+            .collect()
+    }
+
+    /// Send a Message to every connection watching an Episode
+    pub async fn send_to_watchers(&self, episode_id: &str, message: Message) {
+        let conn_ids: Vec<String> = self
+            .watchers
+            .read()
+            .await
+            .get(episode_id)
+            .map(|conn_ids| conn_ids.iter().cloned().collect())
+            .unwrap_or_default();
+
+        for conn_id in conn_ids {
+            self.send(&conn_id, message.clone()).await;
+        }
+    }
+
+    /// Send a Message to every connection authenticated as a User
+    pub async fn send_to_user(&self, user_id: &str, message: Message) {
+        let conn_ids: Vec<String> = self
+            .users
+            .read()
+            .await
+            .get(user_id)
+            .map(|conn_ids| conn_ids.iter().cloned().collect())
+            .unwrap_or_default();
+
+        for conn_id in conn_ids {
+            self.send(&conn_id, message.clone()).await;
+        }
     }
 }
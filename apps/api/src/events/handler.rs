@@ -1,56 +1,153 @@
-use axum::extract::ws::WebSocket;
-use futures::{SinkExt, StreamExt, TryFutureExt};
-use std::sync::Arc;
-use tokio::sync::mpsc;
-use tokio_stream::wrappers::UnboundedReceiverStream;
+use axum::extract::ws::{Message, WebSocket};
+use futures::{SinkExt, StreamExt};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+use tokio::{
+    sync::mpsc,
+    time::{interval_at, Instant},
+};
+use tokio_stream::wrappers::ReceiverStream;
 
-use crate::{events::router::route_message, Context};
+use crate::{
+    events::{
+        presence::{leave_all, refresh_presence},
+        router::route_message,
+    },
+    rate_limit::{client_key, Bucket, Decision},
+    Context,
+};
 use caster_auth::authenticate::Subject;
+use caster_domains::users::model::User;
 
-use super::messages::IncomingMessage;
+use super::messages::{
+    ErrorCode, IncomingEnvelope, OutgoingEnvelope, OutgoingMessage, PROTOCOL_VERSION,
+};
 
 /// Handle `WebSocket` connections by setting up a message handler that deserializes them and
-/// determines how to handle
-pub async fn handle(socket: WebSocket, ctx: Arc<Context>, _sub: Subject) {
+/// determines how to handle. The server pings each connection regularly, and evicts it if
+/// nothing has been heard from the client within the heartbeat timeout.
+pub async fn handle(
+    socket: WebSocket,
+    ctx: Arc<Context>,
+    sub: Subject,
+    user: Option<User>,
+    addr: SocketAddr,
+) {
     let (mut ws_write, mut ws_read) = socket.split();
 
-    let (tx, rx) = mpsc::unbounded_channel();
-    let mut rx = UnboundedReceiverStream::new(rx);
+    let (tx, rx) = mpsc::channel(ctx.config.events.send_queue);
+    let mut rx = ReceiverStream::new(rx);
 
     tokio::task::spawn(async move {
         while let Some(message) = rx.next().await {
-            ws_write
-                .send(message)
-                .unwrap_or_else(|e| {
-                    eprintln!("websocket send error: {}", e);
-                })
-                .await;
+            if let Err(e) = ws_write.send(message).await {
+                warn!("WebSocket send error: {}", e);
+                break;
+            }
         }
+
+        // The connection has been removed, so let the client know
+        let _ = ws_write.close().await;
     });
 
-    let conn_id = ctx.connections.insert(tx).await;
+    let conn_id = ctx
+        .connections
+        .insert(tx, user.as_ref().map(|user| user.id.as_str()))
+        .await;
+    let client = client_key(&sub, &addr);
 
-    while let Some(result) = ws_read.next().await {
-        let msg = match result {
-            Ok(msg) => msg,
-            Err(e) => {
-                eprintln!("websocket error(uid={}): {}", conn_id, e);
-                break;
+    let timeout = Duration::from_secs(ctx.config.events.heartbeat_timeout);
+    let period = Duration::from_secs(ctx.config.events.heartbeat_interval);
+    let mut heartbeat = interval_at(Instant::now() + period, period);
+    let mut last_seen = Instant::now();
+
+    loop {
+        tokio::select! {
+            result = ws_read.next() => {
+                let msg = match result {
+                    Some(Ok(msg)) => msg,
+                    Some(Err(e)) => {
+                        warn!("WebSocket error(uid={}): {}", conn_id, e);
+                        break;
+                    }
+                    None => break,
+                };
+
+                // Any frame from the client, including a Pong, shows that it is still alive
+                last_seen = Instant::now();
+
+                if let Some(response) = handle_message(&ctx, &conn_id, user.as_ref(), &client, msg).await {
+                    ctx.connections.send(&conn_id, response.into()).await;
+                }
+            }
+            _ = heartbeat.tick() => {
+                if !ctx.connections.contains(&conn_id).await {
+                    // Evicted because the send queue was full
+                    break;
+                }
+
+                if last_seen.elapsed() > timeout {
+                    warn!("Heartbeat timeout, evicting(uid={})", conn_id);
+                    break;
+                }
+
+                ctx.connections.send(&conn_id, Message::Ping(vec![])).await;
+
+                refresh_presence(&ctx, &conn_id).await;
             }
-        };
+        }
+    }
+
+    debug!("Connection closed(uid={})", conn_id);
+
+    leave_all(&ctx, &conn_id).await;
+
+    ctx.connections.remove(&conn_id).await;
+}
 
-        match IncomingMessage::from_message(msg) {
-            Ok(Some(message)) => route_message(ctx.clone(), &conn_id, message).await,
-            Ok(None) => {
-                // pass
+/// Parse and route a single `WebSocket` Message, returning the response to send back if it was
+/// a request
+async fn handle_message(
+    ctx: &Arc<Context>,
+    conn_id: &str,
+    user: Option<&User>,
+    client: &str,
+    msg: Message,
+) -> Option<OutgoingEnvelope> {
+    // Drop data frames that exceed the client's budget before parsing them, so that malformed
+    // requests count too. Control frames like the heartbeat's Pong are left out.
+    if matches!(msg, Message::Text(_) | Message::Binary(_)) {
+        match ctx.rate_limiter.check(Bucket::Message, client).await {
+            Ok(Decision::Allowed) => (),
+            Ok(Decision::Limited { retry_after }) => {
+                return Some(OutgoingMessage::RateLimited { retry_after }.into());
             }
             Err(err) => {
-                eprintln!("json error(uid={}): {}", conn_id, err);
+                error!("Unable to check rate limit(uid={}): {}", conn_id, err);
             }
         }
     }
 
-    eprintln!("good bye user: {}", conn_id);
+    let envelope = match IncomingEnvelope::from_message(msg) {
+        Ok(Some(envelope)) => envelope,
+        Ok(None) => return None,
+        Err(err) => {
+            return Some(OutgoingMessage::error(ErrorCode::InvalidMessage, err.to_string()).into());
+        }
+    };
 
-    ctx.connections.remove(&conn_id).await;
+    let IncomingEnvelope { v, id, message } = envelope;
+
+    if v != PROTOCOL_VERSION {
+        return Some(OutgoingEnvelope::reply(
+            id,
+            OutgoingMessage::error(
+                ErrorCode::UnsupportedVersion,
+                format!("Unsupported protocol version: {}", v),
+            ),
+        ));
+    }
+
+    let response = route_message(ctx.clone(), conn_id, user, message).await;
+
+    Some(OutgoingEnvelope::reply(id, response))
 }
@@ -2,16 +2,43 @@ use axum::extract::ws::Message;
 use fake::Dummy;
 use serde::{Deserialize, Serialize};
 
-/// Incoming `WebSocket` messages from clients
+use caster_domains::{messages::model::Message as ChatMessage, notifications::model::Notification};
+
+/// The current version of the `WebSocket` protocol
+pub const PROTOCOL_VERSION: u32 = 1;
+
+fn protocol_version() -> u32 {
+    PROTOCOL_VERSION
+}
+
+/// A request from a client, wrapping an `IncomingMessage` with the protocol version it was
+/// written for and an optional id that the response will echo back
 #[derive(Clone, Debug, Dummy, Serialize, Deserialize, Eq, PartialEq)]
-#[serde(tag = "type")]
-pub enum IncomingMessage {
-    /// A Ping message, which should echo back a Pong
-    Ping,
+pub struct IncomingEnvelope {
+    /// The protocol version, which defaults to the current one
+    #[serde(default = "protocol_version")]
+    pub v: u32,
+
+    /// A client-supplied request id
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// The request
+    #[serde(flatten)]
+    pub message: IncomingMessage,
 }
 
-impl IncomingMessage {
-    /// Create a new `IncomingMessage` from a `WebSocket` Message
+impl IncomingEnvelope {
+    /// Create a new `IncomingEnvelope` for the current protocol version
+    pub fn new(id: Option<String>, message: IncomingMessage) -> Self {
+        Self {
+            v: PROTOCOL_VERSION,
+            id,
+            message,
+        }
+    }
+
+    /// Create a new `IncomingEnvelope` from a `WebSocket` Message, ignoring non-text frames
     pub fn from_message(msg: Message) -> Result<Option<Self>, serde_json::Error> {
         let msg = if let Ok(message) = msg.to_text() {
             message
@@ -19,7 +46,111 @@ impl IncomingMessage {
             return Ok(None);
         };
 
-        serde_json::from_str(msg)
+        serde_json::from_str(msg).map(Some)
+    }
+}
+
+/// Incoming `WebSocket` messages from clients
+#[derive(Clone, Debug, Dummy, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(tag = "type")]
+pub enum IncomingMessage {
+    /// A Ping message, which should echo back a Pong
+    Ping,
+
+    /// Start receiving notifications about a Show
+    Subscribe {
+        /// The Show id
+        show_id: String,
+    },
+
+    /// Stop receiving notifications about a Show
+    Unsubscribe {
+        /// The Show id
+        show_id: String,
+    },
+
+    /// Start receiving the chat about an Episode, which requires permission to read it
+    JoinChat {
+        /// The Episode id
+        episode_id: String,
+    },
+
+    /// Stop receiving the chat about an Episode
+    LeaveChat {
+        /// The Episode id
+        episode_id: String,
+    },
+
+    /// Let the others in a joined chat know that the User is typing
+    Typing {
+        /// The Episode id
+        episode_id: String,
+    },
+
+    /// Start watching an Episode, which counts the connection as a viewer and sends it
+    /// `PresenceChanged` messages
+    Watch {
+        /// The Episode id
+        episode_id: String,
+    },
+
+    /// Stop watching an Episode
+    Unwatch {
+        /// The Episode id
+        episode_id: String,
+    },
+}
+
+/// The reasons that a request can fail
+#[derive(Clone, Copy, Debug, Dummy, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// The request could not be parsed
+    InvalidMessage,
+    /// The request was written for a protocol version that the server doesn't speak
+    UnsupportedVersion,
+    /// The request requires an authenticated User
+    Unauthorized,
+    /// The User doesn't have permission to make the request
+    Forbidden,
+    /// The requested resource doesn't exist
+    NotFound,
+    /// The request can't be made in the connection's current state
+    BadRequest,
+    /// Something went wrong on the server
+    Internal,
+}
+
+/// A server-side response to a client, wrapping an `OutgoingMessage` with the protocol version
+/// and, for responses to requests, the id of the request
+#[derive(Clone, Debug, Dummy, Serialize, Deserialize, Eq, PartialEq)]
+pub struct OutgoingEnvelope {
+    /// The protocol version
+    pub v: u32,
+
+    /// The id of the request that this responds to, if it had one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// The response or event
+    #[serde(flatten)]
+    pub message: OutgoingMessage,
+}
+
+impl OutgoingEnvelope {
+    /// Create a response to the request with the given id
+    pub fn reply(id: Option<String>, message: OutgoingMessage) -> Self {
+        Self {
+            v: PROTOCOL_VERSION,
+            id,
+            message,
+        }
+    }
+}
+
+impl From<OutgoingMessage> for OutgoingEnvelope {
+    fn from(message: OutgoingMessage) -> Self {
+        Self::reply(None, message)
     }
 }
 
@@ -29,10 +160,97 @@ impl IncomingMessage {
 pub enum OutgoingMessage {
     /// A Pong message, which is the response to a Ping
     Pong,
+
+    /// The request succeeded
+    Ack,
+
+    /// The request failed
+    Error {
+        /// The reason for the failure
+        code: ErrorCode,
+        /// A description of the failure
+        message: String,
+    },
+
+    /// The client has sent too many messages, and the last one was dropped
+    RateLimited {
+        /// The number of seconds until the client may send messages again
+        retry_after: u64,
+    },
+
+    /// An Episode of a subscribed Show has been published
+    EpisodePublished {
+        /// The Show id
+        show_id: String,
+        /// The Episode id
+        episode_id: String,
+        /// The Episode title
+        title: String,
+    },
+
+    /// A Notification has been sent to the connected User
+    Notification {
+        /// The new Notification
+        notification: Notification,
+    },
+
+    /// A Message has been posted in a joined chat
+    MessagePosted {
+        /// The new Message
+        message: ChatMessage,
+    },
+
+    /// A Message has been edited in a joined chat
+    MessageUpdated {
+        /// The updated Message
+        message: ChatMessage,
+    },
+
+    /// A Message has been removed from a joined chat
+    MessageDeleted {
+        /// The Episode id
+        episode_id: String,
+        /// The Message id
+        message_id: String,
+    },
+
+    /// Someone is typing in a joined chat
+    Typing {
+        /// The Episode id
+        episode_id: String,
+        /// The id of the Profile that is typing
+        profile_id: String,
+    },
+
+    /// Someone has started or stopped watching a watched Episode
+    PresenceChanged {
+        /// The Episode id
+        episode_id: String,
+        /// The number of distinct viewers
+        viewer_count: u64,
+    },
+}
+
+impl OutgoingMessage {
+    /// Create an Error response
+    pub fn error(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self::Error {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<OutgoingEnvelope> for Message {
+    fn from(envelope: OutgoingEnvelope) -> Message {
+        Message::Text(
+            serde_json::to_string(&envelope).expect("Unable to serialize OutgoingEnvelope"),
+        )
+    }
 }
 
 impl From<OutgoingMessage> for Message {
     fn from(msg: OutgoingMessage) -> Message {
-        Message::Text(serde_json::to_string(&msg).expect("Unable to serialize OutgointMessage"))
+        OutgoingEnvelope::from(msg).into()
     }
 }
@@ -0,0 +1,204 @@
+use std::{collections::HashSet, sync::Arc};
+use tokio::sync::broadcast::{error::RecvError, Receiver};
+
+use super::messages::OutgoingMessage;
+use crate::Context;
+use caster_domains::{
+    domain_events::model::DomainEvent,
+    episodes::model::Episode,
+    messages::{mention::mentioned_usernames, model::Message},
+    notifications::model::{CreateNotificationInput, Notification},
+};
+
+/// Forward published Episodes to the connections subscribed to their Show
+pub async fn forward_published_episodes(ctx: Arc<Context>, mut published: Receiver<Episode>) {
+    loop {
+        match published.recv().await {
+            Ok(episode) => {
+                ctx.connections
+                    .send_to_subscribers(
+                        &episode.show_id,
+                        OutgoingMessage::EpisodePublished {
+                            show_id: episode.show_id.clone(),
+                            episode_id: episode.id.clone(),
+                            title: episode.title.clone(),
+                        }
+                        .into(),
+                    )
+                    .await;
+            }
+            Err(RecvError::Lagged(skipped)) => {
+                warn!("Skipped {} published Episode notifications", skipped);
+            }
+            Err(RecvError::Closed) => break,
+        }
+    }
+}
+
+/// Notify the followers of a Show whenever one of its Episodes is published
+pub async fn notify_followers(ctx: Arc<Context>, mut published: Receiver<Episode>) {
+    loop {
+        match published.recv().await {
+            Ok(episode) => {
+                if let Err(err) = send_to_followers(&ctx, &episode).await {
+                    error!(
+                        "Error while notifying followers of Episode {}: {}",
+                        episode.id, err
+                    );
+                }
+            }
+            Err(RecvError::Lagged(skipped)) => {
+                warn!("Skipped follower notifications for {} Episodes", skipped);
+            }
+            Err(RecvError::Closed) => break,
+        }
+    }
+}
+
+/// Create a Notification about a published Episode for each follower of its Show
+async fn send_to_followers(ctx: &Context, episode: &Episode) -> anyhow::Result<()> {
+    let user_ids = ctx.follows.get_follower_user_ids(&episode.show_id).await?;
+
+    let inputs: Vec<CreateNotificationInput> = user_ids
+        .iter()
+        .map(|user_id| CreateNotificationInput::episode_published(user_id, episode))
+        .collect();
+
+    for notification in ctx.notifications.create_many(&inputs).await? {
+        ctx.notification_publisher.notify(&notification);
+    }
+
+    Ok(())
+}
+
+/// Notify the Users mentioned in chat Messages as they are posted or edited
+pub async fn notify_mentioned(ctx: Arc<Context>, mut events: Receiver<DomainEvent>) {
+    loop {
+        let (message, edited) = match events.recv().await {
+            Ok(DomainEvent::MessagePosted(message)) => (message, false),
+            Ok(DomainEvent::MessageUpdated(message)) => (message, true),
+            Ok(_) => continue,
+            Err(RecvError::Lagged(skipped)) => {
+                warn!("Skipped {} domain events while notifying mentions", skipped);
+                continue;
+            }
+            Err(RecvError::Closed) => break,
+        };
+
+        if let Err(err) = send_to_mentioned(&ctx, &message, edited).await {
+            error!(
+                "Error while notifying Users mentioned in Message {}: {}",
+                message.id, err
+            );
+        }
+    }
+}
+
+/// Create a Notification for each active User mentioned in a Message, other than its author. When
+/// a Message is edited, only the Users it didn't mention before are notified.
+async fn send_to_mentioned(ctx: &Context, message: &Message, edited: bool) -> anyhow::Result<()> {
+    let mut usernames = mentioned_usernames(&message.text);
+
+    if usernames.is_empty() {
+        return Ok(());
+    }
+
+    if edited {
+        let previous: HashSet<String> = ctx
+            .messages
+            .get_edits(&message.id)
+            .await?
+            .iter()
+            .flat_map(|edit| mentioned_usernames(&edit.text))
+            .collect();
+
+        usernames.retain(|username| !previous.contains(username));
+    }
+
+    let author_id = ctx
+        .profiles
+        .get(&message.profile_id, &false)
+        .await?
+        .and_then(|profile| profile.user_id);
+
+    let mut inputs = Vec::with_capacity(usernames.len());
+
+    for username in &usernames {
+        if let Some(user) = ctx.users.get_by_username(username, &false).await? {
+            if user.is_active && author_id.as_ref() != Some(&user.id) {
+                inputs.push(CreateNotificationInput::mentioned(&user.id, message));
+            }
+        }
+    }
+
+    for notification in ctx.notifications.create_many(&inputs).await? {
+        ctx.notification_publisher.notify(&notification);
+    }
+
+    Ok(())
+}
+
+/// Notify Users when they are granted a Role, however it was granted. Creating a Show publishes
+/// the creator's admin grant right after the Show itself, and creators aren't notified about that.
+pub async fn notify_role_granted(ctx: Arc<Context>, mut events: Receiver<DomainEvent>) {
+    let mut created_show_id: Option<String> = None;
+
+    loop {
+        let event = events.recv().await;
+
+        // Only the event published right after a new Show can be its creator's grant
+        let just_created = created_show_id.take();
+
+        let grant = match event {
+            Ok(DomainEvent::ShowCreated(show)) => {
+                created_show_id = Some(show.id);
+                continue;
+            }
+            Ok(DomainEvent::RoleGranted(grant)) => grant,
+            Ok(_) => continue,
+            Err(RecvError::Lagged(skipped)) => {
+                warn!("Skipped {} domain events while notifying grants", skipped);
+                continue;
+            }
+            Err(RecvError::Closed) => break,
+        };
+
+        if grant.resource_table == "shows" && just_created.as_ref() == Some(&grant.resource_id) {
+            continue;
+        }
+
+        let input = CreateNotificationInput::role_granted(&grant);
+
+        match ctx.notifications.create_many(&[input]).await {
+            Ok(created) => {
+                for notification in &created {
+                    ctx.notification_publisher.notify(notification);
+                }
+            }
+            Err(err) => error!(
+                "Error while notifying User {} of RoleGrant {}: {}",
+                grant.user_id, grant.id, err
+            ),
+        }
+    }
+}
+
+/// Forward new Notifications to the connections authenticated as their User
+pub async fn forward_notifications(ctx: Arc<Context>, mut created: Receiver<Notification>) {
+    loop {
+        match created.recv().await {
+            Ok(notification) => {
+                ctx.connections
+                    .send_to_user(
+                        &notification.user_id.clone(),
+                        OutgoingMessage::Notification { notification }.into(),
+                    )
+                    .await;
+            }
+            Err(RecvError::Lagged(skipped)) => {
+                warn!("Skipped {} Notifications", skipped);
+            }
+            Err(RecvError::Closed) => break,
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use super::messages::OutgoingMessage;
+use crate::Context;
+use caster_domains::presence::model::viewer_count;
+
+/// Send the current number of viewers of an Episode to the connections watching it
+pub async fn broadcast_presence(ctx: &Arc<Context>, episode_id: &str) {
+    let viewers = match ctx.presence.get_viewers(episode_id).await {
+        Ok(viewers) => viewers,
+        Err(err) => {
+            warn!(
+                "Unable to count the viewers of Episode {}: {}",
+                episode_id, err
+            );
+            return;
+        }
+    };
+
+    ctx.connections
+        .send_to_watchers(
+            episode_id,
+            OutgoingMessage::PresenceChanged {
+                episode_id: episode_id.to_string(),
+                viewer_count: viewer_count(&viewers),
+            }
+            .into(),
+        )
+        .await;
+}
+
+/// Keep a live connection's presence from expiring
+pub async fn refresh_presence(ctx: &Arc<Context>, conn_id: &str) {
+    let episode_ids = ctx.connections.watched_by(conn_id).await;
+
+    if episode_ids.is_empty() {
+        return;
+    }
+
+    if let Err(err) = ctx.presence.heartbeat(&episode_ids, conn_id).await {
+        warn!("Unable to refresh presence(uid={}): {}", conn_id, err);
+    }
+}
+
+/// Stop a connection from watching each Episode it was watching, and let the remaining
+/// viewers know
+pub async fn leave_all(ctx: &Arc<Context>, conn_id: &str) {
+    for episode_id in ctx.connections.watched_by(conn_id).await {
+        ctx.connections.unwatch(conn_id, &episode_id).await;
+
+        if let Err(err) = ctx.presence.leave(&episode_id, conn_id).await {
+            warn!(
+                "Unable to leave Episode {}(uid={}): {}",
+                episode_id, conn_id, err
+            );
+        }
+
+        broadcast_presence(ctx, &episode_id).await;
+    }
+}
@@ -1,18 +1,201 @@
 use std::sync::Arc;
 
-use super::messages::{
-    IncomingMessage::{self, Ping},
-    OutgoingMessage::Pong,
+use super::{
+    messages::{
+        ErrorCode,
+        IncomingMessage::{
+            self, JoinChat, LeaveChat, Ping, Subscribe, Typing, Unsubscribe, Unwatch, Watch,
+        },
+        OutgoingMessage::{self, Ack, Pong},
+    },
+    presence::broadcast_presence,
 };
 use crate::Context;
+use caster_domains::users::model::User;
 
-/// Route `WebSocket` messages to handlers
-pub async fn route_message(ctx: Arc<Context>, conn_id: &str, message: IncomingMessage) {
+/// Route `WebSocket` messages to handlers, returning the response to send back
+pub async fn route_message(
+    ctx: Arc<Context>,
+    conn_id: &str,
+    user: Option<&User>,
+    message: IncomingMessage,
+) -> OutgoingMessage {
     match message {
-        Ping => handle_ping(&ctx, conn_id).await,
+        Ping => Pong,
+        Subscribe { show_id } => handle_subscribe(&ctx, conn_id, &show_id).await,
+        Unsubscribe { show_id } => {
+            ctx.connections.unsubscribe(conn_id, &show_id).await;
+
+            Ack
+        }
+        JoinChat { episode_id } => handle_join_chat(&ctx, conn_id, user, &episode_id).await,
+        LeaveChat { episode_id } => {
+            ctx.connections.leave_chat(conn_id, &episode_id).await;
+
+            Ack
+        }
+        Typing { episode_id } => handle_typing(&ctx, conn_id, user, &episode_id).await,
+        Watch { episode_id } => handle_watch(&ctx, conn_id, user, &episode_id).await,
+        Unwatch { episode_id } => handle_unwatch(&ctx, conn_id, &episode_id).await,
     }
 }
 
-async fn handle_ping(ctx: &Arc<Context>, conn_id: &str) {
-    ctx.connections.send(conn_id, Pong.into()).await;
+fn internal_error(conn_id: &str, action: &str, err: impl std::fmt::Display) -> OutgoingMessage {
+    error!("Error while handling {}(uid={}): {}", action, conn_id, err);
+
+    OutgoingMessage::error(ErrorCode::Internal, "Internal server error")
+}
+
+async fn handle_subscribe(ctx: &Arc<Context>, conn_id: &str, show_id: &str) -> OutgoingMessage {
+    // Only subscribe to Shows that exist
+    match ctx.shows.get(show_id).await {
+        Ok(Some(_)) => {
+            ctx.connections.subscribe(conn_id, show_id).await;
+
+            Ack
+        }
+        Ok(None) => OutgoingMessage::error(ErrorCode::NotFound, "Show not found"),
+        Err(err) => internal_error(conn_id, "subscribe", err),
+    }
+}
+
+async fn handle_join_chat(
+    ctx: &Arc<Context>,
+    conn_id: &str,
+    user: Option<&User>,
+    episode_id: &str,
+) -> OutgoingMessage {
+    // Only authenticated Users can read chats
+    let Some(user) = user else {
+        return OutgoingMessage::error(ErrorCode::Unauthorized, "Unauthorized");
+    };
+
+    // Load the Show as well, so that inherited permissions apply
+    let episode = match ctx.episodes.get(episode_id, &true).await {
+        Ok(Some(episode)) => episode,
+        Ok(None) => return OutgoingMessage::error(ErrorCode::NotFound, "Episode not found"),
+        Err(err) => return internal_error(conn_id, "join chat", err),
+    };
+
+    match ctx
+        .policies
+        .current()
+        .is_allowed(user.clone(), "episode_read_chat", episode)
+    {
+        Ok(true) => {
+            ctx.connections.join_chat(conn_id, episode_id).await;
+
+            Ack
+        }
+        Ok(false) => OutgoingMessage::error(ErrorCode::Forbidden, "Forbidden"),
+        Err(err) => internal_error(conn_id, "join chat", err),
+    }
+}
+
+async fn handle_typing(
+    ctx: &Arc<Context>,
+    conn_id: &str,
+    user: Option<&User>,
+    episode_id: &str,
+) -> OutgoingMessage {
+    // Only connections that have joined the chat can be typing in it
+    let Some(user) = user else {
+        return OutgoingMessage::error(ErrorCode::Unauthorized, "Unauthorized");
+    };
+
+    if !ctx.connections.in_chat(conn_id, episode_id).await {
+        return OutgoingMessage::error(ErrorCode::BadRequest, "Chat not joined");
+    }
+
+    let profile = match ctx.profiles.get_by_user_id(&user.id, &false).await {
+        Ok(Some(profile)) => profile,
+        Ok(None) => return OutgoingMessage::error(ErrorCode::NotFound, "Profile not found"),
+        Err(err) => return internal_error(conn_id, "typing", err),
+    };
+
+    ctx.connections
+        .send_to_chat(
+            episode_id,
+            OutgoingMessage::Typing {
+                episode_id: episode_id.to_string(),
+                profile_id: profile.id,
+            }
+            .into(),
+            Some(conn_id),
+        )
+        .await;
+
+    Ack
+}
+
+async fn handle_watch(
+    ctx: &Arc<Context>,
+    conn_id: &str,
+    user: Option<&User>,
+    episode_id: &str,
+) -> OutgoingMessage {
+    let not_found = || OutgoingMessage::error(ErrorCode::NotFound, "Episode not found");
+
+    // Load the Show as well, so that inherited permissions apply
+    let episode = match ctx.episodes.get(episode_id, &true).await {
+        Ok(Some(episode)) => episode,
+        Ok(None) => return not_found(),
+        Err(err) => return internal_error(conn_id, "watch", err),
+    };
+
+    // Unpublished Episodes are hidden from those who can't manage them
+    if !episode.is_published() {
+        let Some((user, show)) = user.zip(episode.show.clone()) else {
+            return not_found();
+        };
+
+        match ctx
+            .policies
+            .current()
+            .is_allowed(user.clone(), "manage_episodes", show)
+        {
+            Ok(true) => (),
+            Ok(false) => return not_found(),
+            Err(err) => return internal_error(conn_id, "watch", err),
+        }
+    }
+
+    // Authenticated viewers are listed by their Profile
+    let profile_id = match user {
+        Some(user) => match ctx.profiles.get_by_user_id(&user.id, &false).await {
+            Ok(profile) => profile.map(|profile| profile.id),
+            Err(err) => return internal_error(conn_id, "watch", err),
+        },
+        None => None,
+    };
+
+    if !ctx.connections.watch(conn_id, episode_id).await {
+        // Already watching
+        return Ack;
+    }
+
+    if let Err(err) = ctx.presence.join(episode_id, conn_id, profile_id).await {
+        ctx.connections.unwatch(conn_id, episode_id).await;
+
+        return internal_error(conn_id, "watch", err);
+    }
+
+    broadcast_presence(ctx, episode_id).await;
+
+    Ack
+}
+
+async fn handle_unwatch(ctx: &Arc<Context>, conn_id: &str, episode_id: &str) -> OutgoingMessage {
+    if !ctx.connections.unwatch(conn_id, episode_id).await {
+        // Not watching
+        return Ack;
+    }
+
+    if let Err(err) = ctx.presence.leave(episode_id, conn_id).await {
+        return internal_error(conn_id, "unwatch", err);
+    }
+
+    broadcast_presence(ctx, episode_id).await;
+
+    Ack
 }
@@ -0,0 +1,146 @@
+use chrono::{DateTime, NaiveDateTime, Timelike};
+use rss::{
+    extension::itunes::{ITunesChannelExtensionBuilder, ITunesItemExtensionBuilder},
+    ChannelBuilder, EnclosureBuilder, GuidBuilder, ImageBuilder, Item, ItemBuilder,
+};
+
+use caster_domains::{episodes::model::Episode, shows::model::Show};
+
+/// The format used for HTTP dates, like "Sun, 06 Nov 1994 08:49:37 GMT"
+const HTTP_DATE: &str = "%a, %d %b %Y %H:%M:%S GMT";
+
+/// A podcast feed for a Show and its Episodes
+pub struct Feed<'a> {
+    show: &'a Show,
+    episodes: &'a [Episode],
+}
+
+impl<'a> Feed<'a> {
+    /// Create a new Feed for the given Show and published Episodes, most recent first
+    pub fn new(show: &'a Show, episodes: &'a [Episode]) -> Self {
+        Self { show, episodes }
+    }
+
+    /// The most recent time that the Show or any of its Episodes was updated, truncated to the
+    /// second because that is all HTTP dates can express
+    pub fn last_modified(&self) -> NaiveDateTime {
+        let updated_at = self
+            .episodes
+            .iter()
+            .map(|episode| episode.updated_at)
+            .fold(self.show.updated_at, NaiveDateTime::max);
+
+        updated_at.with_nanosecond(0).unwrap_or(updated_at)
+    }
+
+    /// The `Last-Modified` header value
+    pub fn last_modified_header(&self) -> String {
+        self.last_modified().format(HTTP_DATE).to_string()
+    }
+
+    /// The `ETag` header value, which also changes when an Episode is removed
+    pub fn etag(&self) -> String {
+        format!(
+            "\"{}-{}-{}\"",
+            self.show.id,
+            self.last_modified().and_utc().timestamp(),
+            self.episodes.len()
+        )
+    }
+
+    /// Determine whether a client's cached copy is still current, based on the conditional
+    /// request headers. `If-None-Match` takes precedence over `If-Modified-Since` when both are
+    /// present.
+    pub fn is_fresh(&self, if_none_match: Option<&str>, if_modified_since: Option<&str>) -> bool {
+        if let Some(if_none_match) = if_none_match {
+            let etag = self.etag();
+
+            return if_none_match
+                .split(',')
+                .map(|tag| tag.trim().trim_start_matches("W/"))
+                .any(|tag| tag == "*" || tag == etag);
+        }
+
+        if_modified_since
+            .and_then(|since| DateTime::parse_from_rfc2822(since).ok())
+            .is_some_and(|since| self.last_modified() <= since.naive_utc())
+    }
+
+    /// Render the Feed as RSS 2.0 with iTunes podcast tags
+    pub fn render(&self, base_url: &str) -> String {
+        let link = format!("{}/shows/{}", base_url.trim_end_matches('/'), self.show.id);
+
+        let image = self.show.picture.as_ref().map(|picture| {
+            ImageBuilder::default()
+                .url(picture.clone())
+                .title(self.show.title.clone())
+                .link(link.clone())
+                .build()
+        });
+
+        let itunes = ITunesChannelExtensionBuilder::default()
+            .summary(self.show.summary.clone())
+            .image(self.show.picture.clone())
+            .build();
+
+        let items: Vec<Item> = self.episodes.iter().map(render_episode).collect();
+
+        ChannelBuilder::default()
+            .title(self.show.title.clone())
+            .link(link)
+            .description(self.show.summary.clone().unwrap_or_default())
+            .image(image)
+            .last_build_date(Some(rfc2822(self.last_modified())))
+            .itunes_ext(Some(itunes))
+            .items(items)
+            .build()
+            .to_string()
+    }
+}
+
+/// Render an Episode as a feed item
+fn render_episode(episode: &Episode) -> Item {
+    let itunes = ITunesItemExtensionBuilder::default()
+        .summary(episode.summary.clone())
+        .image(episode.picture.clone())
+        .duration(episode.duration.map(|duration| duration.to_string()))
+        .season(episode.season_number.map(|season| season.to_string()))
+        .episode(episode.episode_number.map(|number| number.to_string()))
+        .explicit(Some(episode.explicit.to_string()))
+        .build();
+
+    // Podcast apps can't play an Episode without the media file
+    let enclosure = episode.media_url.as_ref().map(|url| {
+        EnclosureBuilder::default()
+            .url(url.clone())
+            .mime_type(
+                episode
+                    .media_type
+                    .clone()
+                    .unwrap_or_else(|| "audio/mpeg".to_string()),
+            )
+            .length(episode.media_length.unwrap_or_default().to_string())
+            .build()
+    });
+
+    ItemBuilder::default()
+        .title(Some(episode.title.clone()))
+        .description(episode.summary.clone())
+        .guid(Some(
+            GuidBuilder::default()
+                .value(episode.id.clone())
+                .permalink(false)
+                .build(),
+        ))
+        .enclosure(enclosure)
+        .pub_date(Some(rfc2822(
+            episode.publish_at.unwrap_or(episode.created_at),
+        )))
+        .itunes_ext(Some(itunes))
+        .build()
+}
+
+/// Format a UTC date for RSS
+fn rfc2822(date: NaiveDateTime) -> String {
+    date.and_utc().to_rfc2822()
+}
@@ -4,10 +4,27 @@ use std::sync::Arc;
 
 use crate::Context;
 use caster_domains::{
+    audit_events::resolver::AuditEventsQuery,
+    authorization::resolver::AuthorizationQuery,
+    categories::{
+        resolver::{CategoriesMutation, CategoriesQuery},
+        service::{CategoryLoader, ShowCategoriesLoader},
+    },
     episodes::{
         resolver::{EpisodesMutation, EpisodesQuery},
         service::EpisodeLoader,
     },
+    follows::{
+        resolver::{FollowsMutation, FollowsQuery},
+        service::FollowerCountLoader,
+    },
+    invitations::resolver::{InvitationsMutation, InvitationsQuery},
+    messages::{
+        resolver::{MessagesMutation, MessagesQuery},
+        service::{ReactionCountsLoader, ReplyCountLoader},
+    },
+    notifications::resolver::{NotificationsMutation, NotificationsQuery},
+    presence::{resolver::PresenceQuery, service::ViewerCountLoader},
     profiles::{
         resolver::{ProfilesMutation, ProfilesQuery},
         service::ProfileLoader,
@@ -17,15 +34,31 @@ use caster_domains::{
         resolver::{ShowsMutation, ShowsQuery},
         service::ShowLoader,
     },
+    tags::service::ShowTagsLoader,
     users::{
         resolver::{UsersMutation, UsersQuery},
         service::UserLoader,
     },
+    webhooks::resolver::{WebhooksMutation, WebhooksQuery},
 };
 
 /// The GraphQL top-level Query type
 #[derive(MergedObject, Default)]
-pub struct Query(UsersQuery, ProfilesQuery, ShowsQuery, EpisodesQuery);
+pub struct Query(
+    UsersQuery,
+    ProfilesQuery,
+    ShowsQuery,
+    EpisodesQuery,
+    AuditEventsQuery,
+    AuthorizationQuery,
+    InvitationsQuery,
+    CategoriesQuery,
+    FollowsQuery,
+    MessagesQuery,
+    NotificationsQuery,
+    PresenceQuery,
+    WebhooksQuery,
+);
 
 /// The GraphQL top-level Mutation type
 #[derive(MergedObject, Default)]
@@ -34,6 +67,12 @@ pub struct Mutation(
     ProfilesMutation,
     ShowsMutation,
     EpisodesMutation,
+    InvitationsMutation,
+    CategoriesMutation,
+    FollowsMutation,
+    MessagesMutation,
+    NotificationsMutation,
+    WebhooksMutation,
 );
 
 /// The application's top-level merged GraphQL schema
@@ -48,12 +87,18 @@ pub fn create_schema(ctx: Arc<Context>) -> Result<GraphQLSchema> {
     let role_grant_loader = RoleGrantLoader::new(&ctx.role_grants);
     let show_loader = ShowLoader::new(&ctx.shows);
     let episode_loader = EpisodeLoader::new(&ctx.episodes);
+    let category_loader = CategoryLoader::new(&ctx.categories);
+    let show_categories_loader = ShowCategoriesLoader::new(&ctx.categories);
+    let show_tags_loader = ShowTagsLoader::new(&ctx.tags);
+    let follower_count_loader = FollowerCountLoader::new(&ctx.follows);
+    let reply_count_loader = ReplyCountLoader::new(&ctx.messages);
+    let reaction_counts_loader = ReactionCountsLoader::new(&ctx.messages);
+    let viewer_count_loader = ViewerCountLoader::new(&ctx.presence);
 
     // Inject the initialized services into the `Schema` instance.
     Ok(
         Schema::build(Query::default(), Mutation::default(), EmptySubscription)
             .data(ctx.config)
-            .data(ctx.oso.clone())
             .data(ctx.users.clone())
             .data(DataLoader::new(user_loader, tokio::spawn))
             .data(DataLoader::new(profile_loader, tokio::spawn))
@@ -62,8 +107,25 @@ pub fn create_schema(ctx: Arc<Context>) -> Result<GraphQLSchema> {
             .data(ctx.role_grants.clone())
             .data(ctx.shows.clone())
             .data(ctx.episodes.clone())
+            .data(ctx.episode_publisher.clone())
+            .data(ctx.audit_events.clone())
+            .data(ctx.invitations.clone())
+            .data(ctx.categories.clone())
+            .data(ctx.follows.clone())
+            .data(ctx.messages.clone())
+            .data(ctx.notifications.clone())
+            .data(ctx.presence.clone())
+            .data(ctx.webhooks.clone())
+            .data(ctx.storage.clone())
             .data(DataLoader::new(show_loader, tokio::spawn))
             .data(DataLoader::new(episode_loader, tokio::spawn))
+            .data(DataLoader::new(category_loader, tokio::spawn))
+            .data(DataLoader::new(show_categories_loader, tokio::spawn))
+            .data(DataLoader::new(show_tags_loader, tokio::spawn))
+            .data(DataLoader::new(follower_count_loader, tokio::spawn))
+            .data(DataLoader::new(reply_count_loader, tokio::spawn))
+            .data(DataLoader::new(reaction_counts_loader, tokio::spawn))
+            .data(DataLoader::new(viewer_count_loader, tokio::spawn))
             .finish(),
     )
 }
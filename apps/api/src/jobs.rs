@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use crate::Context;
+
+/// Purge deleted records after their retention period
+pub mod purge;
+
+/// Reload authorization policies when they change
+pub mod policies;
+
+/// Publish scheduled Episodes when they are due
+pub mod publish;
+
+/// Deliver Show and Episode changes to webhooks
+pub mod webhooks;
+
+/// Tests
+#[cfg(test)]
+mod tests;
+
+/// Spawn the background jobs that run alongside the server
+pub fn spawn_jobs(ctx: Arc<Context>) {
+    tokio::spawn(purge::run(ctx.clone()));
+    tokio::spawn(publish::run(ctx.clone()));
+    tokio::spawn(webhooks::run(ctx.clone()));
+    tokio::spawn(policies::run(ctx));
+}
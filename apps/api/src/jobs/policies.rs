@@ -0,0 +1,67 @@
+use std::sync::Arc;
+use tokio::{
+    signal::unix::{signal, SignalKind},
+    time::{interval, Duration},
+};
+
+use crate::Context;
+
+/// Reload the authorization policies on SIGHUP, or when the files in the configured directory
+/// change. Nothing is watched when using the built-in policies.
+pub async fn run(ctx: Arc<Context>) {
+    let Some(dir) = ctx.policies.dir().map(|dir| dir.display().to_string()) else {
+        return;
+    };
+
+    let mut hangup = match signal(SignalKind::hangup()) {
+        Ok(hangup) => Some(hangup),
+        Err(err) => {
+            error!(
+                "Unable to listen for SIGHUP, policies will reload on change only: {}",
+                err
+            );
+
+            None
+        }
+    };
+
+    let mut timer = interval(Duration::from_secs(ctx.config.policies.watch_interval));
+    let mut last = ctx.policies.fingerprint().unwrap_or_default();
+
+    info!("Watching for authorization policy changes in {}", dir);
+
+    loop {
+        let hung_up = tokio::select! {
+            _ = timer.tick() => false,
+            Some(()) = async {
+                match hangup.as_mut() {
+                    Some(hangup) => hangup.recv().await,
+                    None => std::future::pending().await,
+                }
+            } => true,
+        };
+
+        let current = match ctx.policies.fingerprint() {
+            Ok(current) => current,
+            Err(err) => {
+                error!("Unable to read authorization policies in {}: {}", dir, err);
+
+                continue;
+            }
+        };
+
+        if !hung_up && current == last {
+            continue;
+        }
+
+        last = current;
+
+        match ctx.policies.reload() {
+            Ok(()) => info!("Reloaded authorization policies from {}", dir),
+            Err(err) => error!(
+                "Unable to reload authorization policies, keeping the current ones: {}",
+                err
+            ),
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use anyhow::Result;
+use chrono::Utc;
+use std::sync::Arc;
+use tokio::time::{interval, Duration as Interval};
+
+use crate::Context;
+
+/// Publish scheduled Episodes that are due, notifying subscribers, and return the number published
+pub async fn publish_scheduled(ctx: &Context) -> Result<usize> {
+    let published = ctx
+        .episodes
+        .publish_scheduled(Utc::now().naive_utc())
+        .await?;
+
+    Ok(published.len())
+}
+
+/// Publish scheduled Episodes on the configured interval
+pub async fn run(ctx: Arc<Context>) {
+    let mut timer = interval(Interval::from_secs(ctx.config.publishing.interval));
+
+    loop {
+        timer.tick().await;
+
+        match publish_scheduled(&ctx).await {
+            Ok(0) => (),
+            Ok(count) => info!("Published {} scheduled Episodes", count),
+            Err(err) => error!("Error while publishing scheduled Episodes: {}", err),
+        }
+    }
+}
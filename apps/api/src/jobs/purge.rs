@@ -0,0 +1,35 @@
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use tokio::time::{interval, Duration as Interval};
+
+use crate::Context;
+
+/// Permanently remove deleted Shows, Episodes, and Profiles, along with expired Invitations, that
+/// are past the retention period, returning the number of records removed
+pub async fn purge_deleted(ctx: &Context) -> Result<u64> {
+    let deleted_before = Utc::now().naive_utc() - Duration::days(ctx.config.purge.retention_days);
+
+    // Related RoleGrants, Episodes, and Messages are removed by the database on delete
+    let episodes = ctx.episodes.purge(deleted_before).await?;
+    let shows = ctx.shows.purge(deleted_before).await?;
+    let profiles = ctx.profiles.purge(deleted_before).await?;
+    let invitations = ctx.invitations.purge_expired(deleted_before).await?;
+
+    Ok(episodes + shows + profiles + invitations)
+}
+
+/// Purge deleted records on the configured interval
+pub async fn run(ctx: Arc<Context>) {
+    let mut timer = interval(Interval::from_secs(ctx.config.purge.interval));
+
+    loop {
+        timer.tick().await;
+
+        match purge_deleted(&ctx).await {
+            Ok(0) => (),
+            Ok(count) => info!("Purged {} deleted records", count),
+            Err(err) => error!("Error while purging deleted records: {}", err),
+        }
+    }
+}
@@ -0,0 +1 @@
+mod webhooks_test;
@@ -0,0 +1,69 @@
+use std::net::IpAddr;
+
+use crate::jobs::webhooks::is_public;
+
+/// Parse an address for a test case
+fn ip(addr: &str) -> IpAddr {
+    addr.parse().expect("Invalid IP address")
+}
+
+#[test]
+fn test_webhooks_is_public() {
+    for addr in [
+        "93.184.216.34",
+        "8.8.8.8",
+        "2606:2800:220:1:248:1893:25c8:1946",
+        "::ffff:93.184.216.34",
+        "64:ff9b::5db8:d822",
+    ] {
+        assert!(is_public(ip(addr)), "{} should be public", addr);
+    }
+}
+
+#[test]
+fn test_webhooks_is_public_ipv4_reserved() {
+    for addr in [
+        "127.0.0.1",
+        "10.0.0.1",
+        "172.16.0.1",
+        "192.168.1.1",
+        "169.254.169.254",
+        "0.0.0.0",
+        "0.1.2.3",
+        "100.64.0.1",
+        "192.0.2.1",
+        "255.255.255.255",
+        // Multicast
+        "224.0.0.1",
+        "239.255.255.250",
+        // Reserved for future use
+        "240.0.0.1",
+        "250.1.2.3",
+    ] {
+        assert!(!is_public(ip(addr)), "{} should not be public", addr);
+    }
+}
+
+#[test]
+fn test_webhooks_is_public_ipv6_reserved() {
+    for addr in [
+        "::1",
+        "::",
+        "fc00::1",
+        "fd12:3456:789a::1",
+        "fe80::1",
+        // Multicast
+        "ff02::1",
+        "ff0e::101",
+        // IPv4-mapped private addresses
+        "::ffff:127.0.0.1",
+        "::ffff:169.254.169.254",
+        // NAT64 addresses wrapping private IPv4 addresses
+        "64:ff9b::7f00:1",
+        "64:ff9b::10.0.0.1",
+        "64:ff9b::a9fe:a9fe",
+        "64:ff9b::e000:1",
+    ] {
+        assert!(!is_public(ip(addr)), "{} should not be public", addr);
+    }
+}
@@ -0,0 +1,315 @@
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use futures::future::{join_all, BoxFuture};
+use hmac::{Hmac, Mac};
+use hyper::{
+    client::{connect::dns::Name, HttpConnector},
+    header::CONTENT_TYPE,
+    service::Service,
+    Body, Client, Method, Request, Uri,
+};
+use hyper_tls::HttpsConnector;
+use sea_orm::ActiveEnum;
+use serde_json::json;
+use sha2::Sha256;
+use std::{
+    io,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    sync::Arc,
+    task::{self, Poll},
+    vec,
+};
+use tokio::{
+    net::lookup_host,
+    time::{interval, timeout, Duration as Interval},
+};
+
+use crate::Context;
+use caster_domains::webhooks::delivery::{DeliveryAttempt, PendingDelivery};
+use caster_utils::config::Webhooks as WebhooksConfig;
+
+/// The number of events or deliveries to handle at a time
+const BATCH_SIZE: u64 = 100;
+
+/// The longest to wait between attempts, in seconds, no matter how many have failed
+const MAX_RETRY_DELAY: u64 = 86_400;
+
+/// The header with the kind of event being delivered
+pub const EVENT_HEADER: &str = "X-Caster-Event";
+
+/// The header with the id of the delivery, which stays the same across retries
+pub const DELIVERY_HEADER: &str = "X-Caster-Delivery";
+
+/// The header with the Unix timestamp that the delivery was signed at
+pub const TIMESTAMP_HEADER: &str = "X-Caster-Timestamp";
+
+/// The header with the signature of the delivery
+pub const SIGNATURE_HEADER: &str = "X-Caster-Signature";
+
+/// Sign a delivery body with a Webhook's secret, returning the hex-encoded HMAC-SHA256 of the
+/// timestamp and the body joined by a period. Receivers compute the same value to verify that a
+/// delivery came from this server, and that it hasn't been tampered with or replayed.
+pub fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Queue deliveries for new outbox events, then attempt the deliveries that are due, and return
+/// the number attempted
+pub async fn deliver_webhooks(ctx: &Context) -> Result<usize> {
+    while ctx.webhooks.dispatch_events(BATCH_SIZE).await? == BATCH_SIZE {}
+
+    let config = &ctx.config.webhooks;
+    let client = webhook_client(config);
+
+    let mut attempted = 0;
+
+    loop {
+        let now = Utc::now().naive_utc();
+
+        // Hold the claimed deliveries until every request in the batch has had time to finish
+        let lease_until = now + seconds(config.timeout.saturating_mul(2));
+
+        let pending = ctx.webhooks.claim_due(now, lease_until, BATCH_SIZE).await?;
+
+        let claimed = pending.len();
+
+        let results = join_all(pending.into_iter().map(|pending| {
+            let client = &client;
+
+            async move {
+                let attempt = attempt_delivery(client, config, &pending).await;
+
+                ctx.webhooks
+                    .record_attempt(&pending.delivery.id, &attempt)
+                    .await
+            }
+        }))
+        .await;
+
+        for result in results {
+            result?;
+        }
+
+        attempted += claimed;
+
+        if (claimed as u64) < BATCH_SIZE {
+            return Ok(attempted);
+        }
+    }
+}
+
+/// Post an event to a Webhook, and decide whether to retry if it fails
+async fn attempt_delivery(
+    client: &WebhookClient,
+    config: &WebhooksConfig,
+    pending: &PendingDelivery,
+) -> DeliveryAttempt {
+    let PendingDelivery {
+        delivery,
+        webhook,
+        event,
+    } = pending;
+
+    let event_type = event.event_type.to_value();
+
+    let body = json!({
+        "id": event.id,
+        "type": event_type,
+        "createdAt": event.created_at,
+        "showId": event.show_id,
+        "data": event.payload,
+    })
+    .to_string();
+
+    let timestamp = Utc::now().timestamp();
+    let signature = sign(&webhook.secret, timestamp, body.as_bytes());
+
+    let req = Request::builder()
+        .method(Method::POST)
+        .uri(&webhook.url)
+        .header(CONTENT_TYPE, "application/json")
+        .header(EVENT_HEADER, event_type)
+        .header(DELIVERY_HEADER, &delivery.id)
+        .header(TIMESTAMP_HEADER, timestamp)
+        .header(SIGNATURE_HEADER, format!("sha256={}", signature))
+        .body(Body::from(body));
+
+    let (response_status, error) = match req {
+        Err(err) => (None, Some(format!("Invalid request: {}", err))),
+        Ok(req) if !allowed_literal(req.uri(), config) => (
+            None,
+            Some(format!("{} is not a public address", host(req.uri()))),
+        ),
+        Ok(req) => match timeout(Interval::from_secs(config.timeout), client.request(req)).await {
+            Err(_elapsed) => (
+                None,
+                Some(format!("Timed out after {} seconds", config.timeout)),
+            ),
+            Ok(Err(err)) => (None, Some(format!("Request failed: {}", err))),
+            Ok(Ok(resp)) if resp.status().is_success() => (Some(resp.status().as_u16()), None),
+            Ok(Ok(resp)) => (
+                Some(resp.status().as_u16()),
+                Some(format!("Unexpected response status: {}", resp.status())),
+            ),
+        },
+    };
+
+    // Back off exponentially, until the last attempt has been made
+    let attempts = u32::try_from(delivery.attempts).unwrap_or(0) + 1;
+
+    let retry_at = (error.is_some() && attempts < config.max_attempts).then(|| {
+        let delay = config
+            .retry_delay
+            .saturating_mul(2u64.saturating_pow(attempts - 1));
+
+        Utc::now().naive_utc() + seconds(delay.min(MAX_RETRY_DELAY))
+    });
+
+    DeliveryAttempt {
+        response_status: response_status.map(i32::from),
+        error,
+        retry_at,
+    }
+}
+
+/// An http/https client for delivering webhooks
+type WebhookClient = Client<HttpsConnector<HttpConnector<PublicResolver>>>;
+
+/// Create a client that only connects to the addresses allowed by the config
+fn webhook_client(config: &WebhooksConfig) -> WebhookClient {
+    let mut http = HttpConnector::new_with_resolver(PublicResolver {
+        allow_private: config.allow_private_addresses,
+    });
+
+    // Allow https urls through to the TLS connector
+    http.enforce_http(false);
+
+    Client::builder().build(HttpsConnector::new_with_connector(http))
+}
+
+/// Resolves webhook hosts, dropping any addresses that aren't public unless the config allows
+/// them. The addresses are checked as the connection is made, rather than when the url is saved,
+/// so that a public hostname can't later be pointed at an internal service.
+#[derive(Clone)]
+struct PublicResolver {
+    allow_private: bool,
+}
+
+impl Service<Name> for PublicResolver {
+    type Response = vec::IntoIter<SocketAddr>;
+    type Error = io::Error;
+    type Future = BoxFuture<'static, io::Result<Self::Response>>;
+
+    fn poll_ready(&mut self, _cx: &mut task::Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, name: Name) -> Self::Future {
+        let allow_private = self.allow_private;
+
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|addr| allow_private || is_public(addr.ip()))
+                .collect();
+
+            if addrs.is_empty() {
+                return Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    format!("{} does not resolve to a public address", name),
+                ));
+            }
+
+            Ok(addrs.into_iter())
+        })
+    }
+}
+
+/// The host of a url, without the brackets around an IPv6 address
+fn host(uri: &Uri) -> &str {
+    uri.host()
+        .unwrap_or_default()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+}
+
+/// Check urls with an IP address for a host, which are connected to without being resolved
+fn allowed_literal(uri: &Uri, config: &WebhooksConfig) -> bool {
+    config.allow_private_addresses || host(uri).parse().map_or(true, is_public)
+}
+
+/// Whether an address is reachable on the public internet, rather than being a loopback,
+/// private, link-local (including cloud metadata services), multicast, or otherwise reserved
+/// address
+pub(crate) fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [first, second, ..] = ip.octets();
+
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                // "This network" and shared carrier-grade NAT addresses
+                || first == 0
+                || (first == 100 && (second & 0xc0) == 64)
+                // Reserved for future use
+                || first >= 240)
+        }
+        IpAddr::V6(ip) => {
+            if let Some(mapped) = ip.to_ipv4_mapped() {
+                return is_public(IpAddr::V4(mapped));
+            }
+
+            let segments = ip.segments();
+
+            // NAT64 addresses reach the IPv4 address embedded in their last 32 bits
+            if segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+                let [.., high, low] = segments;
+
+                return is_public(IpAddr::V4(Ipv4Addr::from(
+                    (u32::from(high) << 16) | u32::from(low),
+                )));
+            }
+
+            let first = segments[0];
+
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                // Unique local and link-local addresses
+                || (first & 0xfe00) == 0xfc00
+                || (first & 0xffc0) == 0xfe80)
+        }
+    }
+}
+
+/// Convert a number of seconds from the config into a `Duration`
+fn seconds(secs: u64) -> Duration {
+    Duration::seconds(i64::try_from(secs).unwrap_or(i64::MAX / 1000))
+}
+
+/// Deliver webhooks on the configured interval
+pub async fn run(ctx: Arc<Context>) {
+    let mut timer = interval(Interval::from_secs(ctx.config.webhooks.interval));
+
+    loop {
+        timer.tick().await;
+
+        match deliver_webhooks(&ctx).await {
+            Ok(0) => (),
+            Ok(count) => info!("Attempted {} webhook deliveries", count),
+            Err(err) => error!("Error while delivering webhooks: {}", err),
+        }
+    }
+}
@@ -3,44 +3,59 @@
 
 use anyhow::Result;
 use axum::{
-    extract::Extension,
-    routing::{get, IntoMakeService},
+    extract::{connect_info::IntoMakeServiceWithConnectInfo, Extension},
+    routing::get,
     Router, Server,
 };
 use graphql::create_schema;
 use hyper::server::conn::AddrIncoming;
-use oso::{Oso, PolarClass};
-use router::{events_handler, graphiql, graphql_handler, health_handler};
+use rate_limit::{init_rate_limiter, RateLimiter};
+use router::{events_handler, feed_handler, graphiql, graphql_handler, health_handler};
 use sea_orm::DatabaseConnection;
-use std::sync::Arc;
-use tower_http::trace::{self, TraceLayer};
+use std::{net::SocketAddr, sync::Arc};
+use tower_http::{
+    services::ServeDir,
+    trace::{self, TraceLayer},
+};
 
 use caster_auth::jwks::get_jwks;
 use caster_domains::{
+    audit_events::service::{AuditEventsService, DefaultAuditEventsService},
+    categories::service::{CategoriesService, DefaultCategoriesService},
+    domain_events::{model::DomainEvent, publisher::DomainEventPublisher},
     episodes::{
-        model::Episode,
+        publisher::EpisodePublisher,
         service::{DefaultEpisodesService, EpisodesService},
-        AUTHORIZATION as EPISODES_AUTHZ,
     },
-    profiles::{
-        model::Profile,
-        service::{DefaultProfilesService, ProfilesService},
-        AUTHORIZATION as PROFILES_AUTHZ,
+    follows::service::{DefaultFollowsService, FollowsService},
+    invitations::service::{DefaultInvitationsService, InvitationsService},
+    messages::{
+        filter::BannedWords,
+        service::{DefaultMessagesService, MessagesService},
     },
-    role_grants::service::{DefaultRoleGrantsService, RoleGrantsService},
-    shows::{
-        model::Show,
-        service::{DefaultShowsService, ShowsService},
-        AUTHORIZATION as SHOWS_AUTHZ,
+    notifications::{
+        publisher::NotificationPublisher,
+        service::{DefaultNotificationsService, NotificationsService},
     },
-    users::{
-        model::User,
-        service::{UsersService, UsersServiceTrait},
-        AUTHORIZATION as USERS_AUTHZ,
+    presence::service::{init_presence, PresenceService},
+    profiles::service::{DefaultProfilesService, ProfilesService},
+    role_grants::service::{DefaultRoleGrantsService, RoleGrantsService},
+    shows::service::{DefaultShowsService, ShowsService},
+    tags::service::{DefaultTagsService, TagsService},
+    users::service::{UsersService, UsersServiceTrait},
+    webhooks::service::{DefaultWebhooksService, WebhooksService},
+};
+use caster_storage::storage::{init_storage, Storage};
+use caster_utils::config::{Config, StorageBackend};
+use events::{
+    chat::forward_chat_messages,
+    connections::Connections,
+    notifications::{
+        forward_notifications, forward_published_episodes, notify_followers, notify_mentioned,
+        notify_role_granted,
     },
 };
-use caster_utils::config::Config;
-use events::connections::Connections;
+use policies::Policies;
 
 mod router;
 
@@ -50,6 +65,18 @@ pub mod graphql;
 /// `WebSocket` Events
 pub mod events;
 
+/// Per-client rate limiting
+pub mod rate_limit;
+
+/// Background jobs
+pub mod jobs;
+
+/// Authorization policies
+pub mod policies;
+
+/// Podcast RSS feeds
+pub mod feeds;
+
 /// Dependencies needed by the resolvers
 pub struct Context {
     /// The app config
@@ -58,8 +85,8 @@ pub struct Context {
     /// The database connections
     pub db: Arc<DatabaseConnection>,
 
-    /// The `Oso` authorization library
-    pub oso: Oso,
+    /// The `Oso` authorization policies, which may be reloaded while the server is running
+    pub policies: Policies,
 
     /// The `User` entity service
     pub users: Arc<dyn UsersServiceTrait>,
@@ -76,8 +103,50 @@ pub struct Context {
     /// The `Episode` entity service
     pub episodes: Arc<dyn EpisodesService>,
 
+    /// The `Category` entity service
+    pub categories: Arc<dyn CategoriesService>,
+
+    /// The `Tag` entity service
+    pub tags: Arc<dyn TagsService>,
+
+    /// The `Follow` entity service
+    pub follows: Arc<dyn FollowsService>,
+
+    /// The `Message` entity service
+    pub messages: Arc<dyn MessagesService>,
+
+    /// The `AuditEvent` entity service
+    pub audit_events: Arc<dyn AuditEventsService>,
+
+    /// The `Invitation` entity service
+    pub invitations: Arc<dyn InvitationsService>,
+
+    /// The `Notification` entity service
+    pub notifications: Arc<dyn NotificationsService>,
+
+    /// The `Webhook` entity service
+    pub webhooks: Arc<dyn WebhooksService>,
+
+    /// Publishes changes made by the domain services
+    pub events: DomainEventPublisher,
+
+    /// Notifies listeners when Episodes are published
+    pub episode_publisher: EpisodePublisher,
+
+    /// Notifies listeners when Notifications are created
+    pub notification_publisher: NotificationPublisher,
+
     /// WebSockets connections currently active on this server
     pub connections: Connections,
+
+    /// The connections watching each Episode, which may be shared between servers
+    pub presence: Arc<dyn PresenceService>,
+
+    /// Per-client request budgets
+    pub rate_limiter: Arc<dyn RateLimiter>,
+
+    /// Where uploaded files are kept
+    pub storage: Arc<dyn Storage>,
 }
 
 /// Intialize dependencies
@@ -86,43 +155,105 @@ impl Context {
     pub async fn init(config: &'static Config) -> Result<Self> {
         let db = Arc::new(sea_orm::Database::connect(&config.database.url).await?);
 
-        // Set up authorization
-        let mut oso = Oso::new();
+        // Set up authorization, failing fast if the policies are invalid
+        let policies = Policies::init(config.policies.dir.as_deref())?;
 
         let connections = Connections::default();
+        let rate_limiter = init_rate_limiter(config).await?;
+        let presence = init_presence(config).await?;
+        let storage = init_storage(config)?;
 
-        oso.register_class(User::get_polar_class_builder().name("User").build())?;
-        oso.register_class(Profile::get_polar_class_builder().name("Profile").build())?;
-        oso.register_class(Show::get_polar_class_builder().name("Show").build())?;
-        oso.register_class(Episode::get_polar_class_builder().name("Episode").build())?;
+        // Relay published Episodes from the domain services to the Episode listeners
+        let events = DomainEventPublisher::default();
+        let episode_publisher = EpisodePublisher::default();
+        {
+            let episode_publisher = episode_publisher.clone();
 
-        oso.load_str(&[USERS_AUTHZ, PROFILES_AUTHZ, SHOWS_AUTHZ, EPISODES_AUTHZ].join("\n"))?;
+            events.add_subscriber(move |event| {
+                if let DomainEvent::EpisodePublished(episode) = event {
+                    episode_publisher.notify(episode);
+                }
+            });
+        }
 
         Ok(Self {
             config,
             users: Arc::new(UsersService::new(&db)),
             profiles: Arc::new(DefaultProfilesService::new(&db)),
-            role_grants: Arc::new(DefaultRoleGrantsService::new(&db)),
-            shows: Arc::new(DefaultShowsService::new(&db)),
-            episodes: Arc::new(DefaultEpisodesService::new(&db)),
-            oso,
+            role_grants: Arc::new(DefaultRoleGrantsService::new(&db, &events)),
+            shows: Arc::new(DefaultShowsService::new(&db, &events)),
+            episodes: Arc::new(DefaultEpisodesService::new(&db, &events)),
+            categories: Arc::new(DefaultCategoriesService::new(&db)),
+            tags: Arc::new(DefaultTagsService::new(&db)),
+            follows: Arc::new(DefaultFollowsService::new(&db)),
+            messages: Arc::new(DefaultMessagesService::new(
+                &db,
+                &events,
+                BannedWords::new(&config.chat.banned_words),
+            )),
+            audit_events: Arc::new(DefaultAuditEventsService::new(&db)),
+            invitations: Arc::new(DefaultInvitationsService::new(&db, &events)),
+            notifications: Arc::new(DefaultNotificationsService::new(&db)),
+            webhooks: Arc::new(DefaultWebhooksService::new(&db)),
+            events,
+            episode_publisher,
+            notification_publisher: NotificationPublisher::default(),
+            policies,
             db,
             connections,
+            presence,
+            rate_limiter,
+            storage,
         })
     }
 }
 
 /// Start the server and return the bound address and a `Future`.
-pub async fn run(ctx: Arc<Context>) -> Result<Server<AddrIncoming, IntoMakeService<Router>>> {
+pub async fn run(
+    ctx: Arc<Context>,
+) -> Result<Server<AddrIncoming, IntoMakeServiceWithConnectInfo<Router, SocketAddr>>> {
     let port = ctx.config.port;
     let jwks = get_jwks(ctx.config).await;
 
     let schema = create_schema(ctx.clone())?;
 
-    let app = Router::new()
+    // Relay published Episodes to subscribed WebSocket connections
+    tokio::spawn(forward_published_episodes(
+        ctx.clone(),
+        ctx.episode_publisher.subscribe(),
+    ));
+
+    // Notify followers about published Episodes, and relay each new Notification to its User
+    tokio::spawn(notify_followers(
+        ctx.clone(),
+        ctx.episode_publisher.subscribe(),
+    ));
+    tokio::spawn(forward_notifications(
+        ctx.clone(),
+        ctx.notification_publisher.subscribe(),
+    ));
+
+    // Relay changes to chat Messages to the connections that have joined each chat
+    tokio::spawn(forward_chat_messages(ctx.clone(), ctx.events.subscribe()));
+
+    // Notify Users when they are mentioned in a chat Message
+    tokio::spawn(notify_mentioned(ctx.clone(), ctx.events.subscribe()));
+
+    // Notify Users when they are granted a Role
+    tokio::spawn(notify_role_granted(ctx.clone(), ctx.events.subscribe()));
+
+    let mut app = Router::new()
         .route("/health", get(health_handler))
         .route("/graphql", get(graphiql).post(graphql_handler))
         .route("/events", get(events_handler))
+        .route("/shows/:id/feed.xml", get(feed_handler));
+
+    // Serve uploaded files directly when they are kept on the local filesystem
+    if ctx.config.storage.backend == StorageBackend::Local {
+        app = app.nest_service("/uploads", ServeDir::new(&ctx.config.storage.path));
+    }
+
+    let app = app
         .layer(
             TraceLayer::new_for_http()
                 .make_span_with(trace::DefaultMakeSpan::new().level(tracing::Level::INFO))
@@ -137,7 +268,10 @@ pub async fn run(ctx: Arc<Context>) -> Result<Server<AddrIncoming, IntoMakeServi
             .parse()
             .expect("Unable to parse bind address"),
     )
-    .serve(app.into_make_service());
+    .serve(app.into_make_service_with_connect_info::<SocketAddr>());
 
     Ok(server)
 }
+
+#[macro_use]
+extern crate log;
@@ -5,7 +5,7 @@ use anyhow::Result;
 use std::sync::Arc;
 use tracing_subscriber::prelude::*;
 
-use caster_api::{run, Context};
+use caster_api::{jobs::spawn_jobs, run, Context};
 use caster_utils::config::get_config;
 
 #[macro_use]
@@ -24,6 +24,8 @@ async fn main() -> Result<()> {
     let config = get_config();
     let context = Arc::new(Context::init(config).await?);
 
+    spawn_jobs(context.clone());
+
     let server = run(context).await?;
     let addr = server.local_addr();
 
@@ -0,0 +1,130 @@
+use anyhow::{anyhow, Result};
+use oso::{Oso, PolarClass};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::RwLock,
+    time::SystemTime,
+};
+
+use caster_domains::{
+    categories::{model::Category, AUTHORIZATION as CATEGORIES_AUTHZ},
+    episodes::{model::Episode, AUTHORIZATION as EPISODES_AUTHZ},
+    profiles::{model::Profile, AUTHORIZATION as PROFILES_AUTHZ},
+    shows::{model::Show, AUTHORIZATION as SHOWS_AUTHZ},
+    users::{model::User, AUTHORIZATION as USERS_AUTHZ},
+};
+
+/// The modification time of each policy file, used to detect changes
+pub type Fingerprint = Vec<(PathBuf, Option<SystemTime>)>;
+
+/// The `Oso` authorization policies, either built in or loaded from a directory of `.polar` files
+pub struct Policies {
+    /// The directory to load `.polar` files from, if not using the built-in policies
+    dir: Option<PathBuf>,
+
+    /// The current `Oso` instance, replaced as a whole when the policies are reloaded
+    oso: RwLock<Oso>,
+}
+
+impl Policies {
+    /// Load and validate the policies, failing if they can't be compiled
+    pub fn init(dir: Option<&str>) -> Result<Self> {
+        let dir = dir.map(PathBuf::from);
+        let oso = build(dir.as_deref())?;
+
+        Ok(Self {
+            dir,
+            oso: RwLock::new(oso),
+        })
+    }
+
+    /// The directory that policies are loaded from, if not using the built-in policies
+    pub fn dir(&self) -> Option<&Path> {
+        self.dir.as_deref()
+    }
+
+    /// Get the current `Oso` instance. Each caller keeps the same policies for as long as it holds
+    /// on to the instance, even if they are reloaded in the meantime.
+    pub fn current(&self) -> Oso {
+        self.oso
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Compile the policies into a fresh `Oso` instance and swap it in. If the policies can't be
+    /// compiled, the current instance is kept.
+    pub fn reload(&self) -> Result<()> {
+        let oso = build(self.dir.as_deref())?;
+
+        *self
+            .oso
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = oso;
+
+        Ok(())
+    }
+
+    /// Describe the current state of the policy files, so that changes can be detected
+    pub fn fingerprint(&self) -> Result<Fingerprint> {
+        let Some(dir) = self.dir() else {
+            return Ok(vec![]);
+        };
+
+        Ok(policy_files(dir)?
+            .into_iter()
+            .map(|path| {
+                let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+
+                (path, modified)
+            })
+            .collect())
+    }
+}
+
+/// Create a new `Oso` instance with the app's classes registered and the policies loaded
+fn build(dir: Option<&Path>) -> Result<Oso> {
+    let mut oso = Oso::new();
+
+    oso.register_class(User::get_polar_class_builder().name("User").build())?;
+    oso.register_class(Profile::get_polar_class_builder().name("Profile").build())?;
+    oso.register_class(Show::get_polar_class_builder().name("Show").build())?;
+    oso.register_class(Episode::get_polar_class_builder().name("Episode").build())?;
+    oso.register_class(Category::get_polar_class_builder().name("Category").build())?;
+
+    if let Some(dir) = dir {
+        let files = policy_files(dir)?;
+
+        if files.is_empty() {
+            return Err(anyhow!("No .polar files found in {}", dir.display()));
+        }
+
+        oso.load_files(files)?;
+    } else {
+        oso.load_str(
+            &[
+                USERS_AUTHZ,
+                PROFILES_AUTHZ,
+                SHOWS_AUTHZ,
+                EPISODES_AUTHZ,
+                CATEGORIES_AUTHZ,
+            ]
+            .join("\n"),
+        )?;
+    }
+
+    Ok(oso)
+}
+
+/// List the `.polar` files in a directory, in a stable order
+fn policy_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "polar"))
+        .collect();
+
+    files.sort();
+
+    Ok(files)
+}
@@ -0,0 +1,241 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use redis::{aio::ConnectionManager, Script};
+use std::{
+    collections::HashMap,
+    fmt,
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::Mutex;
+
+use caster_auth::authenticate::Subject;
+use caster_utils::config::{Config, RateLimit, RateLimitBackend};
+
+/// The separate budgets that requests are counted against
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Bucket {
+    /// GraphQL queries
+    Query,
+    /// GraphQL mutations
+    Mutation,
+    /// Incoming `WebSocket` messages
+    Message,
+}
+
+impl fmt::Display for Bucket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Bucket::Query => write!(f, "query"),
+            Bucket::Mutation => write!(f, "mutation"),
+            Bucket::Message => write!(f, "message"),
+        }
+    }
+}
+
+/// The outcome of a rate limit check
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Decision {
+    /// The request is within budget
+    Allowed,
+    /// The budget is exhausted, and the client should wait before trying again
+    Limited {
+        /// The number of seconds until the current window resets
+        retry_after: u64,
+    },
+}
+
+/// Identify the client a request should be counted against, preferring the token Subject
+/// and falling back to the client IP address
+pub fn client_key(sub: &Subject, addr: &SocketAddr) -> String {
+    match sub {
+        Subject(Some(username)) => format!("sub:{}", username),
+        Subject(None) => format!("ip:{}", addr.ip()),
+    }
+}
+
+/// A RateLimiter counts requests per client within fixed windows
+#[async_trait]
+pub trait RateLimiter: Sync + Send {
+    /// Count a request from the given client against the given budget
+    async fn check(&self, bucket: Bucket, key: &str) -> Result<Decision>;
+}
+
+/// Create a `RateLimiter` based on the configured backend
+pub async fn init_rate_limiter(config: &'static Config) -> Result<Arc<dyn RateLimiter>> {
+    let limits = config.rate_limit.clone();
+
+    if !limits.enabled {
+        return Ok(Arc::new(DisabledRateLimiter));
+    }
+
+    match limits.backend {
+        RateLimitBackend::Memory => Ok(Arc::new(MemoryRateLimiter::new(limits))),
+        RateLimitBackend::Redis => Ok(Arc::new(
+            RedisRateLimiter::new(limits, &config.redis.url).await?,
+        )),
+    }
+}
+
+/// Look up the configured budget for a bucket
+fn budget(limits: &RateLimit, bucket: Bucket) -> u64 {
+    match bucket {
+        Bucket::Query => limits.queries,
+        Bucket::Mutation => limits.mutations,
+        Bucket::Message => limits.messages,
+    }
+}
+
+/// A `RateLimiter` that allows everything, used when rate limiting is disabled
+pub struct DisabledRateLimiter;
+
+#[async_trait]
+impl RateLimiter for DisabledRateLimiter {
+    async fn check(&self, _bucket: Bucket, _key: &str) -> Result<Decision> {
+        Ok(Decision::Allowed)
+    }
+}
+
+/// A counter for the current window
+struct Window {
+    started: Instant,
+    count: u64,
+}
+
+/// The counters for every client, along with when expired ones were last dropped
+struct Windows {
+    counters: HashMap<(Bucket, String), Window>,
+    pruned: Instant,
+}
+
+/// A `RateLimiter` that keeps counters in memory, local to this process
+pub struct MemoryRateLimiter {
+    limits: RateLimit,
+    windows: Mutex<Windows>,
+}
+
+impl MemoryRateLimiter {
+    /// Create a new `MemoryRateLimiter` instance
+    pub fn new(limits: RateLimit) -> Self {
+        Self {
+            limits,
+            windows: Mutex::new(Windows {
+                counters: HashMap::new(),
+                pruned: Instant::now(),
+            }),
+        }
+    }
+}
+
+#[async_trait]
+impl RateLimiter for MemoryRateLimiter {
+    async fn check(&self, bucket: Bucket, key: &str) -> Result<Decision> {
+        let length = Duration::from_secs(self.limits.window);
+        let now = Instant::now();
+
+        let mut windows = self.windows.lock().await;
+
+        // Drop expired windows at most once per window, so that idle clients don't accumulate
+        // without scanning every client on each request
+        if now.duration_since(windows.pruned) >= length {
+            windows
+                .counters
+                .retain(|_, window| now.duration_since(window.started) < length);
+            windows.pruned = now;
+        }
+
+        let window = windows
+            .counters
+            .entry((bucket, key.to_string()))
+            .or_insert(Window {
+                started: now,
+                count: 0,
+            });
+
+        // Start a new window for clients whose last one has expired but not yet been dropped
+        if now.duration_since(window.started) >= length {
+            window.started = now;
+            window.count = 0;
+        }
+
+        if window.count >= budget(&self.limits, bucket) {
+            let remaining = length.saturating_sub(now.duration_since(window.started));
+
+            return Ok(Decision::Limited {
+                retry_after: remaining.as_secs().max(1),
+            });
+        }
+
+        window.count += 1;
+
+        Ok(Decision::Allowed)
+    }
+}
+
+/// Count a request and start the window's clock if it hasn't been started, atomically so that a
+/// counter can't be left without an expiration. Returns the count and the milliseconds left in
+/// the window.
+const INCR_SCRIPT: &str = r"
+local count = redis.call('INCR', KEYS[1])
+local ttl = redis.call('PTTL', KEYS[1])
+
+if ttl < 0 then
+    redis.call('PEXPIRE', KEYS[1], ARGV[1])
+    ttl = tonumber(ARGV[1])
+end
+
+return {count, ttl}
+";
+
+/// A `RateLimiter` that keeps counters in Redis, shared between processes
+pub struct RedisRateLimiter {
+    limits: RateLimit,
+    redis: ConnectionManager,
+    script: Script,
+}
+
+impl RedisRateLimiter {
+    /// Create a new `RedisRateLimiter` instance connected to the given url
+    pub async fn new(limits: RateLimit, url: &str) -> Result<Self> {
+        // The Redis url is configured without a scheme by default
+        let url = if url.contains("://") {
+            url.to_string()
+        } else {
+            format!("redis://{}", url)
+        };
+
+        let client = redis::Client::open(url)?;
+        let redis = ConnectionManager::new(client).await?;
+
+        Ok(Self {
+            limits,
+            redis,
+            script: Script::new(INCR_SCRIPT),
+        })
+    }
+}
+
+#[async_trait]
+impl RateLimiter for RedisRateLimiter {
+    async fn check(&self, bucket: Bucket, key: &str) -> Result<Decision> {
+        let key = format!("rate_limit:{}:{}", bucket, key);
+        let mut redis = self.redis.clone();
+
+        let (count, ttl): (u64, i64) = self
+            .script
+            .key(&key)
+            .arg(self.limits.window * 1000)
+            .invoke_async(&mut redis)
+            .await?;
+
+        if count > budget(&self.limits, bucket) {
+            // Round up to whole seconds
+            let retry_after = ttl.max(1).unsigned_abs().div_ceil(1000);
+
+            return Ok(Decision::Limited { retry_after });
+        }
+
+        Ok(Decision::Allowed)
+    }
+}
@@ -1,15 +1,47 @@
-use std::sync::Arc;
+use std::{net::SocketAddr, sync::Arc};
 
-use async_graphql::http::GraphiQLSource;
+use async_graphql::{
+    http::GraphiQLSource,
+    parser::{
+        parse_query,
+        types::{DocumentOperations, OperationType},
+    },
+    ErrorExtensions, Pos, Request, ServerError,
+};
 use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
 use axum::{
-    extract::{Extension, WebSocketUpgrade},
+    extract::{ConnectInfo, Extension, Path, WebSocketUpgrade},
     response::{Html, IntoResponse, Response},
 };
+use hyper::{
+    header::{
+        HeaderValue, CACHE_CONTROL, CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH,
+        LAST_MODIFIED, RETRY_AFTER,
+    },
+    HeaderMap, StatusCode,
+};
 use serde_json::json;
+use ulid::Ulid;
 
-use crate::{events, graphql::GraphQLSchema, Context};
+use crate::{
+    events,
+    feeds::Feed,
+    graphql::GraphQLSchema,
+    rate_limit::{client_key, Bucket, Decision},
+    Context,
+};
 use caster_auth::authenticate::Subject;
+use caster_domains::{
+    episodes::{
+        model::EpisodeStatus,
+        queries::{EpisodeCondition, EpisodesOrderBy},
+    },
+    users::model::User,
+};
+use caster_utils::{
+    errors::graphql_error,
+    request::{RequestId, REQUEST_ID_HEADER},
+};
 
 // Health
 // ------
@@ -31,27 +63,194 @@ pub async fn graphiql() -> impl IntoResponse {
     Html(GraphiQLSource::build().endpoint("/graphql").finish())
 }
 
+/// Determine which rate limiting budget a GraphQL request counts against
+fn request_bucket(request: &Request) -> Bucket {
+    // Documents that fail to parse are counted as queries, and rejected during execution
+    let doc = if let Ok(doc) = parse_query(&request.query) {
+        doc
+    } else {
+        return Bucket::Query;
+    };
+
+    let operation = match (&doc.operations, &request.operation_name) {
+        (DocumentOperations::Single(operation), _) => Some(operation),
+        (DocumentOperations::Multiple(operations), Some(name)) => operations.get(name.as_str()),
+        // A single named operation may be executed without an operation name
+        (DocumentOperations::Multiple(operations), None) if operations.len() == 1 => {
+            operations.values().next()
+        }
+        (DocumentOperations::Multiple(_), None) => None,
+    };
+
+    match operation.map(|op| op.node.ty) {
+        Some(OperationType::Mutation) => Bucket::Mutation,
+        _ => Bucket::Query,
+    }
+}
+
+/// Build a 429 response with a GraphQL error explaining when to retry
+fn rate_limited_response(retry_after: u64) -> Response {
+    let error = graphql_error("Too Many Requests", StatusCode::TOO_MANY_REQUESTS)
+        .extend_with(|_err, e| e.set("retryAfter", retry_after));
+
+    let response = async_graphql::Response::from_errors(vec![ServerError {
+        extensions: error.extensions,
+        ..ServerError::new(error.message, Some(Pos::default()))
+    }]);
+
+    let mut response = GraphQLResponse::from(response).into_response();
+
+    *response.status_mut() = StatusCode::TOO_MANY_REQUESTS;
+    response
+        .headers_mut()
+        .insert(RETRY_AFTER, retry_after.into());
+
+    response
+}
+
+/// Use the request id provided by an upstream proxy if it is reasonable, or generate a new one
+fn request_id(headers: &HeaderMap) -> RequestId {
+    headers
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| !id.is_empty() && id.len() <= 128)
+        .map_or_else(
+            || RequestId(Ulid::new().to_string()),
+            |id| RequestId(id.to_string()),
+        )
+}
+
+/// Retrieve the request User, treating deactivated Users as unauthenticated
+async fn active_user(ctx: &Context, sub: Subject) -> (Subject, Option<User>) {
+    let user = if let Subject(Some(ref username)) = sub {
+        ctx.users
+            .get_by_username(username, &true)
+            .await
+            .unwrap_or(None)
+    } else {
+        None
+    };
+
+    match user {
+        Some(user) if !user.is_active => (Subject(None), None),
+        user => (sub, user),
+    }
+}
+
 /// Handle GraphQL Requests
 pub async fn graphql_handler(
     Extension(schema): Extension<GraphQLSchema>,
     Extension(ctx): Extension<Arc<Context>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     sub: Subject,
     req: GraphQLRequest,
-) -> GraphQLResponse {
+) -> Response {
+    let req = req.into_inner();
+    let request_id = request_id(&headers);
+
+    // Count the request against the client's budget before doing any work
+    let bucket = request_bucket(&req);
+    match ctx
+        .rate_limiter
+        .check(bucket, &client_key(&sub, &addr))
+        .await
+    {
+        Ok(Decision::Allowed) => (),
+        Ok(Decision::Limited { retry_after }) => return rate_limited_response(retry_after),
+        Err(err) => {
+            // Fail open if the counters are unavailable
+            warn!("Unable to check rate limit: {}", err);
+        }
+    }
+
     // Retrieve the request User, if username is present
-    let user = if let Subject(Some(ref username)) = sub {
-        ctx.users
-            .get_by_username(username, &true)
-            .await
-            .unwrap_or(None)
+    let (sub, user) = active_user(&ctx, sub).await;
+
+    // Add the Subject, optional User, request id, and current authorization policies to the context
+    let request = req
+        .data(sub)
+        .data(user)
+        .data(request_id.clone())
+        .data(ctx.policies.current());
+
+    let mut response = GraphQLResponse::from(schema.execute(request).await).into_response();
+
+    if let Ok(value) = HeaderValue::from_str(&request_id.0) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+
+    response
+}
+
+// Feeds
+// -----
+
+/// Handle podcast feed requests for a Show, allowing podcast apps to poll with conditional requests
+pub async fn feed_handler(
+    Extension(ctx): Extension<Arc<Context>>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    let show = match ctx.shows.get(&id).await {
+        Ok(Some(show)) => show,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(err) => {
+            error!("Error while fetching Show for feed: {}", err);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let episodes = match ctx
+        .episodes
+        .get_many(
+            Some(EpisodeCondition {
+                show_id: Some(show.id.clone()),
+                status: Some(EpisodeStatus::Published),
+                ..Default::default()
+            }),
+            Some(vec![EpisodesOrderBy::PublishAtDesc]),
+            None,
+            None,
+            &false,
+        )
+        .await
+    {
+        Ok(episodes) => episodes.data,
+        Err(err) => {
+            error!("Error while fetching Episodes for feed: {}", err);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let feed = Feed::new(&show, &episodes);
+
+    let header = |name| headers.get(name).and_then(|value| value.to_str().ok());
+    let fresh = feed.is_fresh(header(IF_NONE_MATCH), header(IF_MODIFIED_SINCE));
+
+    let mut response = if fresh {
+        StatusCode::NOT_MODIFIED.into_response()
     } else {
-        None
+        (
+            [(CONTENT_TYPE, "application/rss+xml; charset=utf-8")],
+            feed.render(&ctx.config.feeds.base_url),
+        )
+            .into_response()
     };
 
-    // Add the Subject and optional User to the context
-    let request = req.into_inner().data(sub).data(user);
+    let response_headers = response.headers_mut();
+
+    response_headers.insert(CACHE_CONTROL, HeaderValue::from_static("no-cache"));
 
-    schema.execute(request).await.into()
+    if let Ok(etag) = HeaderValue::from_str(&feed.etag()) {
+        response_headers.insert(ETAG, etag);
+    }
+
+    if let Ok(last_modified) = HeaderValue::from_str(&feed.last_modified_header()) {
+        response_headers.insert(LAST_MODIFIED, last_modified);
+    }
+
+    response
 }
 
 // WebSocket
@@ -60,8 +259,11 @@ pub async fn graphql_handler(
 /// Handle WebSocket upgrade requests
 pub async fn events_handler(
     Extension(ctx): Extension<Arc<Context>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
     sub: Subject,
     ws: WebSocketUpgrade,
 ) -> Response {
-    ws.on_upgrade(|socket| events::handler::handle(socket, ctx, sub))
+    let (sub, user) = active_user(&ctx, sub).await;
+
+    ws.on_upgrade(move |socket| events::handler::handle(socket, ctx, sub, user, addr))
 }
@@ -0,0 +1,163 @@
+use anyhow::Result;
+use fake::{faker::internet::en::FreeEmail, Fake, Faker};
+use hyper::body::to_bytes;
+use pretty_assertions::assert_eq;
+use serde_json::{json, Value};
+use ulid::Ulid;
+
+use caster_domains::shows::mutations::CreateShowInput;
+
+#[cfg(test)]
+mod test_utils;
+
+use test_utils::TestUtils;
+
+const CREATE_SHOW: &str = "
+    mutation CreateShow($input: CreateShowInput!) {
+        createShow(input: $input) {
+            show {
+                id
+            }
+        }
+    }
+";
+
+const UPDATE_SHOW: &str = "
+    mutation UpdateShow($id: ID!, $input: UpdateShowInput!) {
+        updateShow(id: $id, input: $input) {
+            show {
+                id
+            }
+        }
+    }
+";
+
+const GET_AUDIT_EVENTS: &str = "
+    query GetAuditEvents($resourceTable: String!, $resourceId: String!) {
+        getAuditEvents(resourceTable: $resourceTable, resourceId: $resourceId) {
+            data {
+                actorId
+                action
+                resourceTable
+                resourceId
+                before
+                after
+                requestId
+            }
+            total
+        }
+    }
+";
+
+/***
+ * Query: `getAuditEvents`
+ */
+
+/// It records the changes made to a Show
+#[tokio::test]
+#[ignore]
+async fn test_audit_events_for_show() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let email: String = FreeEmail().fake();
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let (user, _) = utils.create_user_and_profile(&username, &email).await?;
+
+    let req = utils.graphql.query(
+        CREATE_SHOW,
+        json!({ "input": { "title": "Test Show" } }),
+        Some(&token),
+    )?;
+    let resp = utils.http_client.request(req).await?;
+
+    let body = to_bytes(resp.into_body()).await?;
+    let json: Value = serde_json::from_slice(&body)?;
+
+    let show_id = json["data"]["createShow"]["show"]["id"]
+        .as_str()
+        .expect("Missing Show id")
+        .to_string();
+
+    let mut req = utils.graphql.query(
+        UPDATE_SHOW,
+        json!({ "id": show_id, "input": { "title": "Updated Show" } }),
+        Some(&token),
+    )?;
+    req.headers_mut()
+        .insert("x-request-id", "test-request-id".parse()?);
+
+    let resp = utils.http_client.request(req).await?;
+
+    assert_eq!(resp.headers()["x-request-id"], "test-request-id");
+
+    let req = utils.graphql.query(
+        GET_AUDIT_EVENTS,
+        json!({ "resourceTable": "shows", "resourceId": show_id }),
+        Some(&token),
+    )?;
+    let resp = utils.http_client.request(req).await?;
+
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+    let json: Value = serde_json::from_slice(&body)?;
+
+    let json_events = &json["data"]["getAuditEvents"];
+
+    assert_eq!(status, 200);
+    assert_eq!(json_events["total"], 2);
+
+    // The most recent change comes first
+    assert_eq!(json_events["data"][0]["actorId"], user.id);
+    assert_eq!(json_events["data"][0]["action"], "update");
+    assert_eq!(json_events["data"][0]["resourceTable"], "shows");
+    assert_eq!(json_events["data"][0]["resourceId"], show_id);
+    assert_eq!(json_events["data"][0]["before"]["title"], "Test Show");
+    assert_eq!(json_events["data"][0]["after"]["title"], "Updated Show");
+    assert_eq!(json_events["data"][0]["requestId"], "test-request-id");
+
+    assert_eq!(json_events["data"][1]["action"], "create");
+    assert_eq!(json_events["data"][1]["before"], Value::Null);
+    assert_eq!(json_events["data"][1]["after"]["title"], "Test Show");
+    assert!(json_events["data"][1]["requestId"].is_string());
+
+    Ok(())
+}
+
+/// It requires the admin role for the resource
+#[tokio::test]
+#[ignore]
+async fn test_audit_events_requires_authz() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    // Create a User
+    let _ = utils.ctx.users.create(&username).await?;
+
+    let mut show_input: CreateShowInput = Faker.fake();
+    show_input.title = "Test Show".to_string();
+
+    let show = utils.ctx.shows.create(&show_input).await?;
+
+    let req = utils.graphql.query(
+        GET_AUDIT_EVENTS,
+        json!({ "resourceTable": "shows", "resourceId": show.id }),
+        Some(&token),
+    )?;
+    let resp = utils.http_client.request(req).await?;
+
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(json["errors"][0]["message"], "Forbidden");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 403);
+
+    Ok(())
+}
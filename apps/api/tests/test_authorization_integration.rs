@@ -0,0 +1,252 @@
+use anyhow::Result;
+use fake::{Fake, Faker};
+use hyper::body::to_bytes;
+use pretty_assertions::assert_eq;
+use serde_json::{json, Value};
+use ulid::Ulid;
+
+use caster_domains::{role_grants::model::CreateRoleGrantInput, shows::mutations::CreateShowInput};
+
+#[cfg(test)]
+mod test_utils;
+
+use test_utils::TestUtils;
+
+/***
+ * Query: `checkPermission`
+ */
+
+const CHECK_PERMISSION: &str = "
+    query CheckPermission($resourceTable: String!, $resourceId: String!, $action: String!) {
+        checkPermission(resourceTable: $resourceTable, resourceId: $resourceId, action: $action) {
+            allowed
+            roles
+            actions
+        }
+    }
+";
+
+/// It allows an action granted by one of the User's roles
+#[tokio::test]
+#[ignore]
+async fn test_check_permission_allowed() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    // Create a User
+    let user = utils.ctx.users.create(&username).await?;
+
+    let mut show_input: CreateShowInput = Faker.fake();
+    show_input.title = "Test Show".to_string();
+
+    let show = utils.ctx.shows.create(&show_input).await?;
+
+    // Grant the manager role to this User for this Show
+    utils
+        .ctx
+        .role_grants
+        .create(&CreateRoleGrantInput {
+            role_key: "manager".to_string(),
+            user_id: user.id.clone(),
+            resource_table: "shows".to_string(),
+            resource_id: show.id.clone(),
+        })
+        .await?;
+
+    let req = utils.graphql.query(
+        CHECK_PERMISSION,
+        json!({
+            "resourceTable": "shows",
+            "resourceId": show.id,
+            "action": "update",
+        }),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    let json_check = &json["data"]["checkPermission"];
+
+    assert_eq!(status, 200);
+    assert_eq!(json_check["allowed"], true);
+    assert_eq!(json_check["roles"], json!(["guest", "manager"]));
+    assert_eq!(
+        json_check["actions"],
+        json!(["create", "manage_episodes", "update"])
+    );
+
+    Ok(())
+}
+
+/// It denies an action not granted by any of the User's roles
+#[tokio::test]
+#[ignore]
+async fn test_check_permission_denied() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    // Create a User
+    let user = utils.ctx.users.create(&username).await?;
+
+    let mut show_input: CreateShowInput = Faker.fake();
+    show_input.title = "Test Show".to_string();
+
+    let show = utils.ctx.shows.create(&show_input).await?;
+
+    // Grant the manager role to this User for this Show
+    utils
+        .ctx
+        .role_grants
+        .create(&CreateRoleGrantInput {
+            role_key: "manager".to_string(),
+            user_id: user.id.clone(),
+            resource_table: "shows".to_string(),
+            resource_id: show.id.clone(),
+        })
+        .await?;
+
+    let req = utils.graphql.query(
+        CHECK_PERMISSION,
+        json!({
+            "resourceTable": "shows",
+            "resourceId": show.id,
+            "action": "delete",
+        }),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    let json_check = &json["data"]["checkPermission"];
+
+    assert_eq!(status, 200);
+    assert_eq!(json_check["allowed"], false);
+    assert_eq!(json_check["roles"], json!(["guest", "manager"]));
+    assert_eq!(
+        json_check["actions"],
+        json!(["create", "manage_episodes", "update"])
+    );
+
+    Ok(())
+}
+
+/// It requires authentication
+#[tokio::test]
+#[ignore]
+async fn test_check_permission_requires_authn() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let mut show_input: CreateShowInput = Faker.fake();
+    show_input.title = "Test Show".to_string();
+
+    let show = utils.ctx.shows.create(&show_input).await?;
+
+    let req = utils.graphql.query(
+        CHECK_PERMISSION,
+        json!({
+            "resourceTable": "shows",
+            "resourceId": show.id,
+            "action": "update",
+        }),
+        None,
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(json["errors"][0]["message"], "Unauthorized");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 401);
+
+    Ok(())
+}
+
+/// It rejects resource tables without a policy
+#[tokio::test]
+#[ignore]
+async fn test_check_permission_unsupported_table() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    // Create a User
+    let _ = utils.ctx.users.create(&username).await?;
+
+    let req = utils.graphql.query(
+        CHECK_PERMISSION,
+        json!({
+            "resourceTable": "role_grants",
+            "resourceId": "test-id",
+            "action": "update",
+        }),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(json["errors"][0]["message"], "Unsupported resource table");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 400);
+
+    Ok(())
+}
+
+/// It returns an error if no resource is found
+#[tokio::test]
+#[ignore]
+async fn test_check_permission_not_found() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    // Create a User
+    let _ = utils.ctx.users.create(&username).await?;
+
+    let req = utils.graphql.query(
+        CHECK_PERMISSION,
+        json!({
+            "resourceTable": "shows",
+            "resourceId": "test-id",
+            "action": "update",
+        }),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(json["errors"][0]["message"], "Unable to find resource");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 404);
+
+    Ok(())
+}
@@ -0,0 +1,308 @@
+use anyhow::Result;
+use hyper::body::to_bytes;
+use pretty_assertions::assert_eq;
+use serde_json::{json, Value};
+use ulid::Ulid;
+
+use caster_domains::categories::{model::Category, mutations::CreateCategoryInput};
+
+#[cfg(test)]
+mod test_utils;
+
+use test_utils::TestUtils;
+
+/// A slug that won't collide with Categories left behind by other test runs
+fn unique_slug(prefix: &str) -> String {
+    format!("{}-{}", prefix, Ulid::new().to_string().to_lowercase())
+}
+
+/// Create a Category directly through the service
+async fn create_category(
+    utils: &TestUtils,
+    name: &str,
+    parent_id: Option<&str>,
+) -> Result<Category> {
+    let category = utils
+        .ctx
+        .categories
+        .create(&CreateCategoryInput {
+            name: name.to_string(),
+            slug: unique_slug(&name.to_lowercase()),
+            parent_id: parent_id.map(String::from),
+        })
+        .await?;
+
+    Ok(category)
+}
+
+/// Send a GraphQL request and return the JSON response body
+async fn request(
+    utils: &TestUtils,
+    query: &str,
+    variables: Value,
+    token: Option<&str>,
+) -> Result<Value> {
+    let req = utils.graphql.query(query, variables, token)?;
+
+    let resp = utils.http_client.request(req).await?;
+
+    assert_eq!(resp.status(), 200);
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    Ok(serde_json::from_slice(&body)?)
+}
+
+/***
+ * Mutation: `createCategory`
+ */
+
+const CREATE_CATEGORY: &str = "
+    mutation CreateCategory($input: CreateCategoryInput!) {
+        createCategory(input: $input) {
+            category {
+                id
+                name
+                slug
+                parent {
+                    id
+                }
+            }
+        }
+    }
+";
+
+/// It allows site admins to create nested Categories
+#[tokio::test]
+#[ignore]
+async fn test_category_create() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    utils.create_site_admin(&username).await?;
+
+    let parent = create_category(&utils, "Arts", None).await?;
+    let slug = unique_slug("music");
+
+    let json = request(
+        &utils,
+        CREATE_CATEGORY,
+        json!({
+            "input": {
+                "name": "Music",
+                "slug": slug,
+                "parentId": parent.id,
+            }
+        }),
+        Some(&token),
+    )
+    .await?;
+
+    let json_category = &json["data"]["createCategory"]["category"];
+
+    assert_eq!(json_category["name"], "Music");
+    assert_eq!(json_category["slug"], slug);
+    assert_eq!(json_category["parent"]["id"], parent.id);
+
+    // Slugs must be unique
+    let json = request(
+        &utils,
+        CREATE_CATEGORY,
+        json!({ "input": { "name": "Other Music", "slug": slug } }),
+        Some(&token),
+    )
+    .await?;
+
+    assert_eq!(
+        json["errors"][0]["message"],
+        "A Category with that slug already exists"
+    );
+    assert_eq!(json["errors"][0]["extensions"]["code"], 409);
+
+    Ok(())
+}
+
+/// It only allows site admins to create Categories
+#[tokio::test]
+#[ignore]
+async fn test_category_create_authz() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    utils.ctx.users.create(&username).await?;
+
+    let variables = json!({ "input": { "name": "Music", "slug": unique_slug("music") } });
+
+    for (token, message, code) in [
+        (None, "Unauthorized", 401),
+        (Some(token.as_str()), "Forbidden", 403),
+    ] {
+        let json = request(&utils, CREATE_CATEGORY, variables.clone(), token).await?;
+
+        assert_eq!(json["errors"][0]["message"], message);
+        assert_eq!(json["errors"][0]["extensions"]["code"], code);
+    }
+
+    Ok(())
+}
+
+/***
+ * Mutation: `updateCategory`
+ */
+
+const UPDATE_CATEGORY: &str = "
+    mutation UpdateCategory($id: ID!, $input: UpdateCategoryInput!) {
+        updateCategory(id: $id, input: $input) {
+            category {
+                id
+                parent {
+                    id
+                }
+            }
+        }
+    }
+";
+
+/// It refuses to nest a Category beneath itself or its descendants
+#[tokio::test]
+#[ignore]
+async fn test_category_update_cycle() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    utils.create_site_admin(&username).await?;
+
+    let parent = create_category(&utils, "Arts", None).await?;
+    let child = create_category(&utils, "Music", Some(&parent.id)).await?;
+    let grandchild = create_category(&utils, "Jazz", Some(&child.id)).await?;
+
+    for parent_id in [&parent.id, &grandchild.id] {
+        let json = request(
+            &utils,
+            UPDATE_CATEGORY,
+            json!({ "id": parent.id, "input": { "parentId": parent_id } }),
+            Some(&token),
+        )
+        .await?;
+
+        assert_eq!(
+            json["errors"][0]["message"],
+            "A Category can't be nested beneath itself"
+        );
+        assert_eq!(json["errors"][0]["extensions"]["code"], 400);
+    }
+
+    // Moving a Category to the top level is fine
+    let json = request(
+        &utils,
+        UPDATE_CATEGORY,
+        json!({ "id": grandchild.id, "input": { "parentId": null } }),
+        Some(&token),
+    )
+    .await?;
+
+    assert_eq!(
+        json["data"]["updateCategory"]["category"]["parent"],
+        Value::Null
+    );
+
+    Ok(())
+}
+
+/***
+ * Shows with Categories and Tags
+ */
+
+const CREATE_SHOW: &str = "
+    mutation CreateShow($input: CreateShowInput!) {
+        createShow(input: $input) {
+            show {
+                id
+            }
+        }
+    }
+";
+
+const GET_MANY_SHOWS: &str = "
+    query GetManyShows($where: ShowCondition) {
+        getManyShows(where: $where) {
+            data {
+                id
+                categories {
+                    id
+                }
+                tags
+            }
+            total
+        }
+    }
+";
+
+/// It lists Shows under Categories and Tags, and filters by them
+#[tokio::test]
+#[ignore]
+async fn test_category_shows() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    utils.ctx.users.create(&username).await?;
+
+    let parent = create_category(&utils, "Arts", None).await?;
+    let child = create_category(&utils, "Music", Some(&parent.id)).await?;
+
+    let tag = unique_slug("tag");
+
+    let json = request(
+        &utils,
+        CREATE_SHOW,
+        json!({
+            "input": {
+                "title": "Test Show",
+                "categoryIds": [child.id],
+                "tags": [format!(" {} ", tag.to_uppercase()), tag, ""],
+            }
+        }),
+        Some(&token),
+    )
+    .await?;
+
+    let show_id = json["data"]["createShow"]["show"]["id"].clone();
+
+    // Shows listed under a nested Category are found by its parent, and Tags are normalized
+    for condition in [
+        json!({ "categoryIds": [parent.id] }),
+        json!({ "categoryIds": [child.id] }),
+        json!({ "tags": [tag.to_uppercase()] }),
+    ] {
+        let json = request(&utils, GET_MANY_SHOWS, json!({ "where": condition }), None).await?;
+
+        let json_result = &json["data"]["getManyShows"];
+
+        assert_eq!(json_result["total"], 1);
+        assert_eq!(json_result["data"][0]["id"], show_id);
+        assert_eq!(json_result["data"][0]["categories"][0]["id"], child.id);
+        assert_eq!(json_result["data"][0]["tags"], json!([tag]));
+    }
+
+    // Unknown Categories are rejected
+    let json = request(
+        &utils,
+        CREATE_SHOW,
+        json!({ "input": { "title": "Test Show", "categoryIds": ["unknown"] } }),
+        Some(&token),
+    )
+    .await?;
+
+    assert_eq!(json["errors"][0]["message"], "Unable to find Category");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 400);
+
+    Ok(())
+}
@@ -1,725 +1,983 @@
-// use anyhow::Result;
-// use fake::{Fake, Faker};
-// use hyper::body::to_bytes;
-// use pretty_assertions::assert_eq;
-// use serde_json::{json, Value};
-// use ulid::Ulid;
-
-// use caster_domains::{role_grants::model::CreateRoleGrantInput, shows::mutations::CreateShowInput};
-
-// #[cfg(test)]
-// mod test_utils;
-
-// use test_utils::TestUtils;
-
-// /***
-//  * Mutation: `createEpisode`
-//  */
-// const CREATE_EPISODE: &str = "
-//     mutation CreateEpisode($input: CreateEpisodeInput!) {
-//         createEpisode(input: $input) {
-//             episode {
-//                 id
-//                 title
-//                 summary
-//                 picture
-//                 show {
-//                     id
-//                 }
-//             }
-//         }
-//     }
-// ";
-
-// /// It creates a new episode
-// #[tokio::test]
-// #[ignore]
-// async fn test_episode_create() -> Result<()> {
-//     let utils = TestUtils::init().await?;
-//     let ctx = utils.ctx.clone();
-
-//     let username = Ulid::new().to_string();
-//     let token = utils.create_jwt(&username);
-
-//     // Create a user and a show
-//     let user = ctx.users.create(&username).await?;
-
-//     let mut show_input: CreateShowInput = Faker.fake();
-//     show_input.title = "Test Show".to_string();
-
-//     let show = ctx.shows.create(&show_input).await?;
-
-//     // Grant the manager role to this user for this episode's show
-//     ctx.role_grants
-//         .create(&CreateRoleGrantInput {
-//             role_key: "manager".to_string(),
-//             user_id: user.id.clone(),
-//             resource_table: "shows".to_string(),
-//             resource_id: show.id.clone(),
-//         })
-//         .await?;
-
-//     let req = utils.graphql.query(
-//         CREATE_EPISODE,
-//         json!({
-//             "input": {
-//                 "title": "Test Episode 1",
-//                 "showId": show.id.clone(),
-//             }
-//         }),
-//         Some(&token),
-//     )?;
-
-//     let resp = utils.http_client.request(req).await?;
-//     let status = resp.status();
-
-//     let body = to_bytes(resp.into_body()).await?;
-//     let json: Value = serde_json::from_slice(&body)?;
-
-//     let json_episode = &json["data"]["createEpisode"]["episode"];
-//     let json_show = &json_episode["show"];
-
-//     assert_eq!(status, 200);
-//     assert_eq!(json_episode["title"], "Test Episode 1");
-//     assert_eq!(json_show["id"], show.id.clone());
-
-//     Ok(())
-// }
-
-// /// It requires a title and a showId
-// #[tokio::test]
-// #[ignore]
-// async fn test_episode_create_requires_title_show_id() -> Result<()> {
-//     let utils = TestUtils::init().await?;
-
-//     let username = Ulid::new().to_string();
-//     let token = utils.create_jwt(&username);
-
-//     let req = utils
-//         .graphql
-//         .query(CREATE_EPISODE, json!({ "input": {}}), Some(&token))?;
-
-//     let resp = utils.http_client.request(req).await?;
-//     let status = resp.status();
-
-//     let body = to_bytes(resp.into_body()).await?;
-//     let json: Value = serde_json::from_slice(&body)?;
-
-//     assert_eq!(status, 200);
-//     assert_eq!(
-//         json["errors"][0]["message"],
-//         r#"Invalid value for argument "input", field "title" of type "String!" is required but not provided"#
-//     );
-
-//     // Now provide the "email" and try again
-//     let req = utils.graphql.query(
-//         CREATE_EPISODE,
-//         json!({
-//             "input": {
-//                 "title": "Test Episode 1",
-//             }
-//         }),
-//         Some(&token),
-//     )?;
-
-//     let resp = utils.http_client.request(req).await?;
-//     let status = resp.status();
-
-//     let body = to_bytes(resp.into_body()).await?;
-//     let json: Value = serde_json::from_slice(&body)?;
-
-//     assert_eq!(status, 200);
-//     assert_eq!(
-//         json["errors"][0]["message"],
-//         r#"Invalid value for argument "input", field "showId" of type "String!" is required but not provided"#
-//     );
-
-//     Ok(())
-// }
-
-// /// It requires authentication
-// #[tokio::test]
-// #[ignore]
-// async fn test_episode_create_authn() -> Result<()> {
-//     let TestUtils {
-//         http_client,
-//         graphql,
-//         ctx,
-//         ..
-//     } = TestUtils::init().await?;
-
-//     let mut show_input: CreateShowInput = Faker.fake();
-//     show_input.title = "Test Show".to_string();
-
-//     let show = ctx.shows.create(&show_input).await?;
-
-//     let req = graphql.query(
-//         CREATE_EPISODE,
-//         json!({
-//             "input": {
-//                 "title": "dummy-title",
-//                 "showId": show.id
-//             }
-//         }),
-//         None,
-//     )?;
-
-//     let resp = http_client.request(req).await?;
-//     let status = resp.status();
-
-//     let body = to_bytes(resp.into_body()).await?;
-
-//     let json: Value = serde_json::from_slice(&body)?;
-
-//     assert_eq!(status, 200);
-//     assert_eq!(json["errors"][0]["message"], "Unauthorized");
-//     assert_eq!(json["errors"][0]["extensions"]["code"], 401);
-
-//     Ok(())
-// }
-
-// /// It requires authorization
-// #[tokio::test]
-// #[ignore]
-// async fn test_episode_create_authz() -> Result<()> {
-//     let utils = TestUtils::init().await?;
-//     let ctx = utils.ctx.clone();
-
-//     let username = Ulid::new().to_string();
-//     let token = utils.create_jwt(&username);
-
-//     let mut show_input: CreateShowInput = Faker.fake();
-//     show_input.title = "Test Show".to_string();
-
-//     let show = ctx.shows.create(&show_input).await?;
-
-//     // Create a user with this username
-//     let _ = ctx.users.create(&username).await?;
-
-//     let req = utils.graphql.query(
-//         CREATE_EPISODE,
-//         json!({
-//             "input": {
-//                 "title": "Test Episode 1",
-//                 "showId": show.id,
-//             }
-//         }),
-//         Some(&token),
-//     )?;
-
-//     let resp = utils.http_client.request(req).await?;
-//     let status = resp.status();
-
-//     let body = to_bytes(resp.into_body()).await?;
-
-//     let json: Value = serde_json::from_slice(&body)?;
-
-//     assert_eq!(status, 200);
-//     assert_eq!(json["errors"][0]["message"], "Forbidden");
-//     assert_eq!(json["errors"][0]["extensions"]["code"], 403);
-
-//     Ok(())
-// }
-
-// /***
-//  * Query: `getEpisode`
-//  */
-// const GET_EPISODE: &str = "
-//     query GetEpisode($id: ID!) {
-//         getEpisode(id: $id) {
-//             id
-//             title
-//             summary
-//             picture
-//             show {
-//                 id
-//             }
-//         }
-//     }
-// ";
-
-// /// It retrieves an existing episode
-// #[tokio::test]
-// #[ignore]
-// async fn test_episode_get() -> Result<()> {
-//     let utils = TestUtils::init().await?;
-
-//     let username = Ulid::new().to_string();
-//     let token = utils.create_jwt(&username);
-
-//     let (show, episode) = utils
-//         .create_show_and_episode("Test Show", "Test Episode 1")
-//         .await?;
-
-//     let req = utils
-//         .graphql
-//         .query(GET_EPISODE, json!({ "id": episode.id,}), Some(&token))?;
-
-//     let resp = utils.http_client.request(req).await?;
-//     let status = resp.status();
-
-//     let body = to_bytes(resp.into_body()).await?;
-
-//     let json: Value = serde_json::from_slice(&body)?;
-
-//     let json_episode = &json["data"]["getEpisode"];
-//     let json_show = &json_episode["show"];
-
-//     assert_eq!(status, 200);
-//     assert_eq!(json_episode["id"], episode.id);
-//     assert_eq!(json_episode["title"], "Test Episode 1");
-//     assert_eq!(json_show["id"], show.id);
-
-//     Ok(())
-// }
-
-// /// It returns nothing when no episode is found
-// #[tokio::test]
-// #[ignore]
-// async fn test_episode_get_empty() -> Result<()> {
-//     let utils = TestUtils::init().await?;
-
-//     let username = Ulid::new().to_string();
-//     let token = utils.create_jwt(&username);
-
-//     let req = utils
-//         .graphql
-//         .query(GET_EPISODE, json!({ "id": "dummy-id",}), Some(&token))?;
-
-//     let resp = utils.http_client.request(req).await?;
-//     let status = resp.status();
-
-//     let body = to_bytes(resp.into_body()).await?;
-//     let json: Value = serde_json::from_slice(&body)?;
-
-//     assert_eq!(status, 200);
-//     assert_eq!(json["data"]["getEpisode"], Value::Null);
-
-//     Ok(())
-// }
-
-// /***
-//  * Query: `getManyEpisodes`
-//  */
-// const GET_MANY_EPISODES: &str = "
-//     query GetManyEpisodes(
-//         $where: EpisodeCondition
-//         $orderBy: [EpisodesOrderBy!]
-//         $pageSize: Int
-//         $page: Int
-//     ) {
-//         getManyEpisodes(
-//             where: $where
-//             orderBy: $orderBy
-//             pageSize: $pageSize
-//             page: $page
-//         ) {
-//             data {
-//                 id
-//                 title
-//                 summary
-//                 picture
-//                 show {
-//                     id
-//                 }
-//             }
-//             count
-//             total
-//             page
-//             pageCount
-//         }
-//     }
-// ";
-
-// /// It queries existing episodes
-// #[tokio::test]
-// #[ignore]
-// async fn test_episode_get_many() -> Result<()> {
-//     let utils = TestUtils::init().await?;
-
-//     let username = Ulid::new().to_string();
-//     let token = utils.create_jwt(&username);
-
-//     let (show, episode) = utils
-//         .create_show_and_episode("Test Show", "Test Episode 1")
-//         .await?;
-
-//     let (other_show, other_episode) = utils
-//         .create_show_and_episode("Test Show 2", "Test Episode 1")
-//         .await?;
-
-//     let req = utils.graphql.query(
-//         GET_MANY_EPISODES,
-//         json!({
-//             "where": {
-//                 "idsIn": vec![episode.id.clone(), other_episode.id.clone()],
-//             }
-//         }),
-//         Some(&token),
-//     )?;
-//     let resp = utils.http_client.request(req).await?;
-
-//     let status = resp.status();
-
-//     let body = to_bytes(resp.into_body()).await?;
-
-//     let json: Value = serde_json::from_slice(&body)?;
-
-//     let json_episode = &json["data"]["getManyEpisodes"]["data"][0];
-//     let json_show = &json_episode["show"];
-
-//     let json_other_episode = &json["data"]["getManyEpisodes"]["data"][1];
-//     let json_other_show = &json_other_episode["show"];
-
-//     assert_eq!(status, 200);
-
-//     assert_eq!(json["data"]["getManyEpisodes"]["count"], 2);
-//     assert_eq!(json["data"]["getManyEpisodes"]["total"], 2);
-//     assert_eq!(json["data"]["getManyEpisodes"]["page"], 1);
-//     assert_eq!(json["data"]["getManyEpisodes"]["pageCount"], 1);
-
-//     assert_eq!(json_episode["id"], episode.id);
-//     assert_eq!(json_episode["title"], "Test Episode 1");
-//     assert_eq!(json_show["id"], show.id);
-
-//     assert_eq!(json_other_episode["id"], other_episode.id);
-//     assert_eq!(json_other_episode["title"], "Test Episode 1");
-//     assert_eq!(json_other_show["id"], other_show.id);
-
-//     Ok(())
-// }
-
-// /***
-//  * Mutation: `updateEpisode`
-//  */
-// const UPDATE_EPISODE: &str = "
-//     mutation UpdateEpisode($id: ID!, $input: UpdateEpisodeInput!) {
-//         updateEpisode(id: $id, input: $input) {
-//             episode {
-//                 id
-//                 title
-//                 summary
-//                 picture
-//                 show {
-//                     id
-//                 }
-//             }
-//         }
-//     }
-// ";
-
-// /// It updates an existing episode
-// #[tokio::test]
-// #[ignore]
-// async fn test_episode_update() -> Result<()> {
-//     let utils = TestUtils::init().await?;
-//     let ctx = utils.ctx.clone();
-
-//     let username = Ulid::new().to_string();
-//     let token = utils.create_jwt(&username);
-
-//     // Create a user with this username
-//     let user = ctx.users.create(&username).await?;
-
-//     let (show, episode) = utils
-//         .create_show_and_episode("Test Show", "Test Episode 1")
-//         .await?;
-
-//     // Grant the manager role to this user for this episode's show
-//     ctx.role_grants
-//         .create(&CreateRoleGrantInput {
-//             role_key: "manager".to_string(),
-//             user_id: user.id.clone(),
-//             resource_table: "shows".to_string(),
-//             resource_id: show.id.clone(),
-//         })
-//         .await?;
-
-//     let req = utils.graphql.query(
-//         UPDATE_EPISODE,
-//         json!({
-//             "id": episode.id,
-//             "input": {
-//                 "summary": "Test Summary"
-//             }
-//         }),
-//         Some(&token),
-//     )?;
-//     let resp = utils.http_client.request(req).await?;
-
-//     let status = resp.status();
-
-//     let body = to_bytes(resp.into_body()).await?;
-
-//     let json: Value = serde_json::from_slice(&body)?;
-
-//     let json_episode = &json["data"]["updateEpisode"]["episode"];
-//     let json_show = &json_episode["show"];
-
-//     assert_eq!(status, 200);
-
-//     assert_eq!(json_episode["id"], episode.id);
-//     assert_eq!(json_episode["title"], "Test Episode 1");
-//     assert_eq!(json_episode["summary"], "Test Summary");
-//     assert_eq!(json_show["id"], show.id);
-
-//     Ok(())
-// }
-
-// /// It returns an error if no existing episode was found
-// #[tokio::test]
-// #[ignore]
-// async fn test_episode_update_not_found() -> Result<()> {
-//     let TestUtils {
-//         http_client,
-//         graphql,
-//         ..
-//     } = TestUtils::init().await?;
-
-//     let req = graphql.query(
-//         UPDATE_EPISODE,
-//         json!({
-//             "id": "test-id",
-//             "input": {
-//                 "summary": "Test Summary"
-//             }
-//         }),
-//         None,
-//     )?;
-
-//     let resp = http_client.request(req).await?;
-//     let status = resp.status();
-
-//     let body = to_bytes(resp.into_body()).await?;
-//     let json: Value = serde_json::from_slice(&body)?;
-
-//     assert_eq!(status, 200);
-//     assert_eq!(
-//         json["errors"][0]["message"],
-//         "Unable to find existing Episode"
-//     );
-//     assert_eq!(json["errors"][0]["extensions"]["code"], 404);
-
-//     Ok(())
-// }
-
-// /// It requires authentication
-// #[tokio::test]
-// #[ignore]
-// async fn test_episode_update_authn() -> Result<()> {
-//     let utils = TestUtils::init().await?;
-
-//     let (_, episode) = utils
-//         .create_show_and_episode("Test Show", "Test Episode 1")
-//         .await?;
-
-//     let req = utils.graphql.query(
-//         UPDATE_EPISODE,
-//         json!({
-//             "id": episode.id,
-//             "input": {
-//                 "summary": "Test Summary"
-//             }
-//         }),
-//         None,
-//     )?;
-
-//     let resp = utils.http_client.request(req).await?;
-//     let status = resp.status();
-
-//     let body = to_bytes(resp.into_body()).await?;
-
-//     let json: Value = serde_json::from_slice(&body)?;
-
-//     assert_eq!(status, 200);
-//     assert_eq!(json["errors"][0]["message"], "Unauthorized");
-//     assert_eq!(json["errors"][0]["extensions"]["code"], 401);
-
-//     Ok(())
-// }
-
-// /// It requires authorization
-// #[tokio::test]
-// #[ignore]
-// async fn test_episode_update_authz() -> Result<()> {
-//     let utils = TestUtils::init().await?;
-//     let ctx = utils.ctx.clone();
-
-//     let username = Ulid::new().to_string();
-//     let token = utils.create_jwt(&username);
-
-//     // Create a user with this username
-//     let _ = ctx.users.create(&username).await?;
-
-//     let (_, episode) = utils
-//         .create_show_and_episode("Test Show 2", "Test Episode 1")
-//         .await?;
-
-//     let req = utils.graphql.query(
-//         UPDATE_EPISODE,
-//         json!({
-//             "id": episode.id,
-//             "input": {
-//                 "summary": "Test Summary"
-//             }
-//         }),
-//         Some(&token),
-//     )?;
-
-//     let resp = utils.http_client.request(req).await?;
-//     let status = resp.status();
-
-//     let body = to_bytes(resp.into_body()).await?;
-
-//     let json: Value = serde_json::from_slice(&body)?;
-
-//     assert_eq!(status, 200);
-//     assert_eq!(json["errors"][0]["message"], "Forbidden");
-//     assert_eq!(json["errors"][0]["extensions"]["code"], 403);
-
-//     Ok(())
-// }
-
-// /***
-//  * Mutation: `deleteEpisode`
-//  */
-// const DELETE_EPISODE: &str = "
-//     mutation DeleteEpisode($id: ID!) {
-//         deleteEpisode(id: $id)
-//     }
-// ";
-
-// /// It deletes an existing user episode
-// #[tokio::test]
-// #[ignore]
-// async fn test_episode_delete() -> Result<()> {
-//     let utils = TestUtils::init().await?;
-//     let ctx = utils.ctx.clone();
-
-//     let username = Ulid::new().to_string();
-//     let token = utils.create_jwt(&username);
-
-//     // Create a user with this username
-//     let user = ctx.users.create(&username).await?;
-
-//     let (show, episode) = utils
-//         .create_show_and_episode("Test Show", "Test Episode 1")
-//         .await?;
-
-//     // Grant the manager role to this user for this episode's show
-//     ctx.role_grants
-//         .create(&CreateRoleGrantInput {
-//             role_key: "manager".to_string(),
-//             user_id: user.id.clone(),
-//             resource_table: "shows".to_string(),
-//             resource_id: show.id.clone(),
-//         })
-//         .await?;
-
-//     let req = utils
-//         .graphql
-//         .query(DELETE_EPISODE, json!({"id": episode.id}), Some(&token))?;
-//     let resp = utils.http_client.request(req).await?;
+use anyhow::Result;
+use fake::{Fake, Faker};
+use hyper::body::to_bytes;
+use pretty_assertions::assert_eq;
+use serde_json::{json, Value};
+use ulid::Ulid;
+
+use caster_domains::{role_grants::model::CreateRoleGrantInput, shows::mutations::CreateShowInput};
+
+#[cfg(test)]
+mod test_utils;
+
+use test_utils::TestUtils;
+
+/***
+ * Mutation: `createEpisode`
+ */
+const CREATE_EPISODE: &str = "
+    mutation CreateEpisode($input: CreateEpisodeInput!) {
+        createEpisode(input: $input) {
+            episode {
+                id
+                title
+                summary
+                picture
+                show {
+                    id
+                }
+            }
+        }
+    }
+";
+
+/// It creates a new episode
+#[tokio::test]
+#[ignore]
+async fn test_episode_create() -> Result<()> {
+    let utils = TestUtils::init().await?;
+    let ctx = utils.ctx.clone();
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    // Create a user and a show
+    let user = ctx.users.create(&username).await?;
+
+    let mut show_input: CreateShowInput = Faker.fake();
+    show_input.title = "Test Show".to_string();
+
+    let show = ctx.shows.create(&show_input).await?;
+
+    // Grant the manager role to this user for this episode's show
+    ctx.role_grants
+        .create(&CreateRoleGrantInput {
+            role_key: "manager".to_string(),
+            user_id: user.id.clone(),
+            resource_table: "shows".to_string(),
+            resource_id: show.id.clone(),
+        })
+        .await?;
+
+    let req = utils.graphql.query(
+        CREATE_EPISODE,
+        json!({
+            "input": {
+                "title": "Test Episode 1",
+                "showId": show.id.clone(),
+            }
+        }),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+    let json: Value = serde_json::from_slice(&body)?;
+
+    let json_episode = &json["data"]["createEpisode"]["episode"];
+    let json_show = &json_episode["show"];
+
+    assert_eq!(status, 200);
+    assert_eq!(json_episode["title"], "Test Episode 1");
+    assert_eq!(json_show["id"], show.id.clone());
+
+    Ok(())
+}
+
+const CREATE_EPISODE_MEDIA: &str = "
+    mutation CreateEpisode($input: CreateEpisodeInput!) {
+        createEpisode(input: $input) {
+            episode {
+                id
+                mediaUrl
+                mediaType
+                mediaLength
+                duration
+                seasonNumber
+                episodeNumber
+                explicit
+            }
+        }
+    }
+";
+
+/// It creates a new episode with media details
+#[tokio::test]
+#[ignore]
+async fn test_episode_create_media() -> Result<()> {
+    let utils = TestUtils::init().await?;
+    let ctx = utils.ctx.clone();
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let user = ctx.users.create(&username).await?;
+
+    let mut show_input: CreateShowInput = Faker.fake();
+    show_input.title = "Test Show".to_string();
+
+    let show = ctx.shows.create(&show_input).await?;
+
+    ctx.role_grants
+        .create(&CreateRoleGrantInput {
+            role_key: "manager".to_string(),
+            user_id: user.id.clone(),
+            resource_table: "shows".to_string(),
+            resource_id: show.id.clone(),
+        })
+        .await?;
+
+    let req = utils.graphql.query(
+        CREATE_EPISODE_MEDIA,
+        json!({
+            "input": {
+                "title": "Test Episode 1",
+                "showId": show.id.clone(),
+                "mediaUrl": "https://cdn.example.com/episode-1.mp3",
+                "mediaType": "audio/mpeg",
+                "mediaLength": 31_457_280,
+                "duration": 1_965,
+                "seasonNumber": 1,
+                "episodeNumber": 3,
+                "explicit": true,
+            }
+        }),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+    let json: Value = serde_json::from_slice(&body)?;
+
+    let json_episode = &json["data"]["createEpisode"]["episode"];
+
+    assert_eq!(status, 200);
+    assert_eq!(
+        json_episode["mediaUrl"],
+        "https://cdn.example.com/episode-1.mp3"
+    );
+    assert_eq!(json_episode["mediaType"], "audio/mpeg");
+    assert_eq!(json_episode["mediaLength"], 31_457_280);
+    assert_eq!(json_episode["duration"], 1_965);
+    assert_eq!(json_episode["seasonNumber"], 1);
+    assert_eq!(json_episode["episodeNumber"], 3);
+    assert_eq!(json_episode["explicit"], true);
+
+    // Negative numbers are rejected
+    let req = utils.graphql.query(
+        CREATE_EPISODE_MEDIA,
+        json!({
+            "input": {
+                "title": "Test Episode 2",
+                "showId": show.id.clone(),
+                "duration": -1,
+            }
+        }),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+
+    let body = to_bytes(resp.into_body()).await?;
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert!(json["errors"][0]["message"]
+        .as_str()
+        .unwrap_or_default()
+        .contains("must be greater than or equal to 0"));
+
+    Ok(())
+}
+
+/// It requires a title and a showId
+#[tokio::test]
+#[ignore]
+async fn test_episode_create_requires_title_show_id() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let req = utils
+        .graphql
+        .query(CREATE_EPISODE, json!({ "input": {}}), Some(&token))?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(
+        json["errors"][0]["message"],
+        r#"Invalid value for argument "input", field "title" of type "String!" is required but not provided"#
+    );
+
+    // Now provide the "email" and try again
+    let req = utils.graphql.query(
+        CREATE_EPISODE,
+        json!({
+            "input": {
+                "title": "Test Episode 1",
+            }
+        }),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(
+        json["errors"][0]["message"],
+        r#"Invalid value for argument "input", field "showId" of type "String!" is required but not provided"#
+    );
+
+    Ok(())
+}
+
+/// It requires authentication
+#[tokio::test]
+#[ignore]
+async fn test_episode_create_authn() -> Result<()> {
+    let TestUtils {
+        http_client,
+        graphql,
+        ctx,
+        ..
+    } = TestUtils::init().await?;
+
+    let mut show_input: CreateShowInput = Faker.fake();
+    show_input.title = "Test Show".to_string();
+
+    let show = ctx.shows.create(&show_input).await?;
+
+    let req = graphql.query(
+        CREATE_EPISODE,
+        json!({
+            "input": {
+                "title": "dummy-title",
+                "showId": show.id
+            }
+        }),
+        None,
+    )?;
+
+    let resp = http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(json["errors"][0]["message"], "Unauthorized");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 401);
+
+    Ok(())
+}
+
+/// It requires authorization
+#[tokio::test]
+#[ignore]
+async fn test_episode_create_authz() -> Result<()> {
+    let utils = TestUtils::init().await?;
+    let ctx = utils.ctx.clone();
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let mut show_input: CreateShowInput = Faker.fake();
+    show_input.title = "Test Show".to_string();
+
+    let show = ctx.shows.create(&show_input).await?;
+
+    // Create a user with this username
+    let _ = ctx.users.create(&username).await?;
+
+    let req = utils.graphql.query(
+        CREATE_EPISODE,
+        json!({
+            "input": {
+                "title": "Test Episode 1",
+                "showId": show.id,
+            }
+        }),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(json["errors"][0]["message"], "Forbidden");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 403);
+
+    Ok(())
+}
+
+/***
+ * Query: `getEpisode`
+ */
+const GET_EPISODE: &str = "
+    query GetEpisode($id: ID!) {
+        getEpisode(id: $id) {
+            id
+            title
+            summary
+            picture
+            show {
+                id
+            }
+        }
+    }
+";
+
+/// It retrieves an existing episode
+#[tokio::test]
+#[ignore]
+async fn test_episode_get() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let (show, episode) = utils
+        .create_show_and_episode("Test Show", "Test Episode 1")
+        .await?;
+
+    let req = utils
+        .graphql
+        .query(GET_EPISODE, json!({ "id": episode.id,}), Some(&token))?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    let json_episode = &json["data"]["getEpisode"];
+    let json_show = &json_episode["show"];
+
+    assert_eq!(status, 200);
+    assert_eq!(json_episode["id"], episode.id);
+    assert_eq!(json_episode["title"], "Test Episode 1");
+    assert_eq!(json_show["id"], show.id);
+
+    Ok(())
+}
+
+/// It returns nothing when no episode is found
+#[tokio::test]
+#[ignore]
+async fn test_episode_get_empty() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let req = utils
+        .graphql
+        .query(GET_EPISODE, json!({ "id": "dummy-id",}), Some(&token))?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(json["data"]["getEpisode"], Value::Null);
+
+    Ok(())
+}
+
+/***
+ * Query: `getManyEpisodes`
+ */
+const GET_MANY_EPISODES: &str = "
+    query GetManyEpisodes(
+        $where: EpisodeCondition
+        $orderBy: [EpisodesOrderBy!]
+        $pageSize: Int
+        $page: Int
+    ) {
+        getManyEpisodes(
+            where: $where
+            orderBy: $orderBy
+            pageSize: $pageSize
+            page: $page
+        ) {
+            data {
+                id
+                title
+                summary
+                picture
+                show {
+                    id
+                }
+            }
+            count
+            total
+            page
+            pageCount
+        }
+    }
+";
+
+/// It queries existing episodes
+#[tokio::test]
+#[ignore]
+async fn test_episode_get_many() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let (show, episode) = utils
+        .create_show_and_episode("Test Show", "Test Episode 1")
+        .await?;
+
+    let (other_show, other_episode) = utils
+        .create_show_and_episode("Test Show 2", "Test Episode 1")
+        .await?;
+
+    let req = utils.graphql.query(
+        GET_MANY_EPISODES,
+        json!({
+            "where": {
+                "idsIn": vec![episode.id.clone(), other_episode.id.clone()],
+            },
+            "orderBy": ["ID_ASC"]
+        }),
+        Some(&token),
+    )?;
+    let resp = utils.http_client.request(req).await?;
+
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    let json_episode = &json["data"]["getManyEpisodes"]["data"][0];
+    let json_show = &json_episode["show"];
+
+    let json_other_episode = &json["data"]["getManyEpisodes"]["data"][1];
+    let json_other_show = &json_other_episode["show"];
+
+    assert_eq!(status, 200);
+
+    assert_eq!(json["data"]["getManyEpisodes"]["count"], 2);
+    assert_eq!(json["data"]["getManyEpisodes"]["total"], 2);
+    assert_eq!(json["data"]["getManyEpisodes"]["page"], 1);
+    assert_eq!(json["data"]["getManyEpisodes"]["pageCount"], 1);
+
+    assert_eq!(json_episode["id"], episode.id);
+    assert_eq!(json_episode["title"], "Test Episode 1");
+    assert_eq!(json_show["id"], show.id);
+
+    assert_eq!(json_other_episode["id"], other_episode.id);
+    assert_eq!(json_other_episode["title"], "Test Episode 1");
+    assert_eq!(json_other_show["id"], other_show.id);
+
+    Ok(())
+}
+
+/// It filters episodes by permissions granted for them or their show
+#[tokio::test]
+#[ignore]
+async fn test_episode_get_many_permission() -> Result<()> {
+    let utils = TestUtils::init().await?;
+    let ctx = utils.ctx.clone();
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let user = ctx.users.create(&username).await?;
+
+    let (show, show_episode) = utils
+        .create_show_and_episode("Test Show", "Test Episode 1")
+        .await?;
+
+    let (_, guest_episode) = utils
+        .create_show_and_episode("Test Show 2", "Test Episode 2")
+        .await?;
+
+    let (_, other_episode) = utils
+        .create_show_and_episode("Test Show 3", "Test Episode 3")
+        .await?;
+
+    // Grant the guest role for the first show, and for the second show's episode only
+    for (resource_table, resource_id) in [("shows", &show.id), ("episodes", &guest_episode.id)] {
+        ctx.role_grants
+            .create(&CreateRoleGrantInput {
+                role_key: "guest".to_string(),
+                user_id: user.id.clone(),
+                resource_table: resource_table.to_string(),
+                resource_id: resource_id.clone(),
+            })
+            .await?;
+    }
+
+    let req = utils.graphql.query(
+        GET_MANY_EPISODES,
+        json!({
+            "where": {
+                "idsIn": vec![
+                    show_episode.id.clone(),
+                    guest_episode.id.clone(),
+                    other_episode.id.clone(),
+                ],
+                "permission": "episode_chat",
+            },
+            "orderBy": ["TITLE_ASC"],
+        }),
+        Some(&token),
+    )?;
+    let resp = utils.http_client.request(req).await?;
+
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    let json_result = &json["data"]["getManyEpisodes"];
+
+    assert_eq!(status, 200);
+    assert_eq!(json_result["total"], 2);
+    assert_eq!(json_result["data"][0]["id"], show_episode.id);
+    assert_eq!(json_result["data"][1]["id"], guest_episode.id);
+
+    Ok(())
+}
+
+/***
+ * Mutation: `updateEpisode`
+ */
+const UPDATE_EPISODE: &str = "
+    mutation UpdateEpisode($id: ID!, $input: UpdateEpisodeInput!) {
+        updateEpisode(id: $id, input: $input) {
+            episode {
+                id
+                title
+                summary
+                picture
+                show {
+                    id
+                }
+            }
+        }
+    }
+";
+
+/// It updates an existing episode
+#[tokio::test]
+#[ignore]
+async fn test_episode_update() -> Result<()> {
+    let utils = TestUtils::init().await?;
+    let ctx = utils.ctx.clone();
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    // Create a user with this username
+    let user = ctx.users.create(&username).await?;
+
+    let (show, episode) = utils
+        .create_show_and_episode("Test Show", "Test Episode 1")
+        .await?;
+
+    // Grant the manager role to this user for this episode's show
+    ctx.role_grants
+        .create(&CreateRoleGrantInput {
+            role_key: "manager".to_string(),
+            user_id: user.id.clone(),
+            resource_table: "shows".to_string(),
+            resource_id: show.id.clone(),
+        })
+        .await?;
+
+    let req = utils.graphql.query(
+        UPDATE_EPISODE,
+        json!({
+            "id": episode.id,
+            "input": {
+                "summary": "Test Summary"
+            }
+        }),
+        Some(&token),
+    )?;
+    let resp = utils.http_client.request(req).await?;
+
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    let json_episode = &json["data"]["updateEpisode"]["episode"];
+    let json_show = &json_episode["show"];
+
+    assert_eq!(status, 200);
+
+    assert_eq!(json_episode["id"], episode.id);
+    assert_eq!(json_episode["title"], "Test Episode 1");
+    assert_eq!(json_episode["summary"], "Test Summary");
+    assert_eq!(json_show["id"], show.id);
+
+    Ok(())
+}
+
+/// It returns an error if no existing episode was found
+#[tokio::test]
+#[ignore]
+async fn test_episode_update_not_found() -> Result<()> {
+    let TestUtils {
+        http_client,
+        graphql,
+        ..
+    } = TestUtils::init().await?;
+
+    let req = graphql.query(
+        UPDATE_EPISODE,
+        json!({
+            "id": "test-id",
+            "input": {
+                "summary": "Test Summary"
+            }
+        }),
+        None,
+    )?;
+
+    let resp = http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(
+        json["errors"][0]["message"],
+        "Unable to find existing Episode"
+    );
+    assert_eq!(json["errors"][0]["extensions"]["code"], 404);
+
+    Ok(())
+}
+
+/// It requires authentication
+#[tokio::test]
+#[ignore]
+async fn test_episode_update_authn() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let (_, episode) = utils
+        .create_show_and_episode("Test Show", "Test Episode 1")
+        .await?;
+
+    let req = utils.graphql.query(
+        UPDATE_EPISODE,
+        json!({
+            "id": episode.id,
+            "input": {
+                "summary": "Test Summary"
+            }
+        }),
+        None,
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(json["errors"][0]["message"], "Unauthorized");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 401);
+
+    Ok(())
+}
+
+/// It requires authorization
+#[tokio::test]
+#[ignore]
+async fn test_episode_update_authz() -> Result<()> {
+    let utils = TestUtils::init().await?;
+    let ctx = utils.ctx.clone();
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    // Create a user with this username
+    let _ = ctx.users.create(&username).await?;
+
+    let (_, episode) = utils
+        .create_show_and_episode("Test Show 2", "Test Episode 1")
+        .await?;
+
+    let req = utils.graphql.query(
+        UPDATE_EPISODE,
+        json!({
+            "id": episode.id,
+            "input": {
+                "summary": "Test Summary"
+            }
+        }),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(json["errors"][0]["message"], "Forbidden");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 403);
+
+    Ok(())
+}
+
+/***
+ * Mutation: `deleteEpisode`
+ */
+const DELETE_EPISODE: &str = "
+    mutation DeleteEpisode($id: ID!) {
+        deleteEpisode(id: $id)
+    }
+";
+
+/// It deletes an existing user episode
+#[tokio::test]
+#[ignore]
+async fn test_episode_delete() -> Result<()> {
+    let utils = TestUtils::init().await?;
+    let ctx = utils.ctx.clone();
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    // Create a user with this username
+    let user = ctx.users.create(&username).await?;
+
+    let (show, episode) = utils
+        .create_show_and_episode("Test Show", "Test Episode 1")
+        .await?;
+
+    // Grant the manager role to this user for this episode's show
+    ctx.role_grants
+        .create(&CreateRoleGrantInput {
+            role_key: "manager".to_string(),
+            user_id: user.id.clone(),
+            resource_table: "shows".to_string(),
+            resource_id: show.id.clone(),
+        })
+        .await?;
+
+    let req = utils
+        .graphql
+        .query(DELETE_EPISODE, json!({"id": episode.id}), Some(&token))?;
+    let resp = utils.http_client.request(req).await?;
 
-//     let status = resp.status();
+    let status = resp.status();
 
-//     let body = to_bytes(resp.into_body()).await?;
+    let body = to_bytes(resp.into_body()).await?;
 
-//     let json: Value = serde_json::from_slice(&body)?;
+    let json: Value = serde_json::from_slice(&body)?;
 
-//     assert_eq!(status, 200);
-//     assert!(json["data"]["deleteEpisode"].as_bool().unwrap());
+    assert_eq!(status, 200);
+    assert!(json["data"]["deleteEpisode"].as_bool().unwrap());
 
-//     Ok(())
-// }
+    Ok(())
+}
 
-// /// It returns an error if no existing episode was found
-// #[tokio::test]
-// #[ignore]
-// async fn test_episode_delete_not_found() -> Result<()> {
-//     let TestUtils {
-//         http_client,
-//         graphql,
-//         ..
-//     } = TestUtils::init().await?;
+/// It returns an error if no existing episode was found
+#[tokio::test]
+#[ignore]
+async fn test_episode_delete_not_found() -> Result<()> {
+    let TestUtils {
+        http_client,
+        graphql,
+        ..
+    } = TestUtils::init().await?;
 
-//     let req = graphql.query(DELETE_EPISODE, json!({"id": "test-id"}), None)?;
+    let req = graphql.query(DELETE_EPISODE, json!({"id": "test-id"}), None)?;
 
-//     let resp = http_client.request(req).await?;
-//     let status = resp.status();
+    let resp = http_client.request(req).await?;
+    let status = resp.status();
 
-//     let body = to_bytes(resp.into_body()).await?;
-//     let json: Value = serde_json::from_slice(&body)?;
+    let body = to_bytes(resp.into_body()).await?;
+    let json: Value = serde_json::from_slice(&body)?;
 
-//     assert_eq!(status, 200);
-//     assert_eq!(
-//         json["errors"][0]["message"],
-//         "Unable to find existing Episode"
-//     );
-//     assert_eq!(json["errors"][0]["extensions"]["code"], 404);
+    assert_eq!(status, 200);
+    assert_eq!(
+        json["errors"][0]["message"],
+        "Unable to find existing Episode"
+    );
+    assert_eq!(json["errors"][0]["extensions"]["code"], 404);
 
-//     Ok(())
-// }
+    Ok(())
+}
 
-// /// It requires authentication
-// #[tokio::test]
-// #[ignore]
-// async fn test_episode_delete_authn() -> Result<()> {
-//     let utils = TestUtils::init().await?;
+/// It requires authentication
+#[tokio::test]
+#[ignore]
+async fn test_episode_delete_authn() -> Result<()> {
+    let utils = TestUtils::init().await?;
 
-//     let (_, episode) = utils
-//         .create_show_and_episode("Test Show", "Test Episode 1")
-//         .await?;
+    let (_, episode) = utils
+        .create_show_and_episode("Test Show", "Test Episode 1")
+        .await?;
 
-//     let req = utils
-//         .graphql
-//         .query(DELETE_EPISODE, json!({"id": episode.id}), None)?;
-//     let resp = utils.http_client.request(req).await?;
+    let req = utils
+        .graphql
+        .query(DELETE_EPISODE, json!({"id": episode.id}), None)?;
+    let resp = utils.http_client.request(req).await?;
 
-//     let status = resp.status();
+    let status = resp.status();
 
-//     let body = to_bytes(resp.into_body()).await?;
+    let body = to_bytes(resp.into_body()).await?;
 
-//     let json: Value = serde_json::from_slice(&body)?;
+    let json: Value = serde_json::from_slice(&body)?;
 
-//     assert_eq!(status, 200);
-//     assert_eq!(json["errors"][0]["message"], "Unauthorized");
-//     assert_eq!(json["errors"][0]["extensions"]["code"], 401);
+    assert_eq!(status, 200);
+    assert_eq!(json["errors"][0]["message"], "Unauthorized");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 401);
 
-//     Ok(())
-// }
+    Ok(())
+}
 
-// /// It requires authorization
-// #[tokio::test]
-// #[ignore]
-// async fn test_episode_delete_authz() -> Result<()> {
-//     let utils = TestUtils::init().await?;
-//     let ctx = utils.ctx.clone();
+/// It requires authorization
+#[tokio::test]
+#[ignore]
+async fn test_episode_delete_authz() -> Result<()> {
+    let utils = TestUtils::init().await?;
+    let ctx = utils.ctx.clone();
 
-//     let username = Ulid::new().to_string();
-//     let token = utils.create_jwt(&username);
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
 
-//     // Create a user with this username
-//     let _ = ctx.users.create(&username).await?;
+    // Create a user with this username
+    let _ = ctx.users.create(&username).await?;
 
-//     let (_, episode) = utils
-//         .create_show_and_episode("Test Show 2", "Test Episode 1")
-//         .await?;
+    let (_, episode) = utils
+        .create_show_and_episode("Test Show 2", "Test Episode 1")
+        .await?;
 
-//     let req = utils
-//         .graphql
-//         .query(DELETE_EPISODE, json!({"id": episode.id}), Some(&token))?;
+    let req = utils
+        .graphql
+        .query(DELETE_EPISODE, json!({"id": episode.id}), Some(&token))?;
 
-//     let resp = utils.http_client.request(req).await?;
-//     let status = resp.status();
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
 
-//     let body = to_bytes(resp.into_body()).await?;
+    let body = to_bytes(resp.into_body()).await?;
 
-//     let json: Value = serde_json::from_slice(&body)?;
+    let json: Value = serde_json::from_slice(&body)?;
 
-//     assert_eq!(status, 200);
-//     assert_eq!(json["errors"][0]["message"], "Forbidden");
-//     assert_eq!(json["errors"][0]["extensions"]["code"], 403);
+    assert_eq!(status, 200);
+    assert_eq!(json["errors"][0]["message"], "Forbidden");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 403);
 
-//     Ok(())
-// }
+    Ok(())
+}
+
+/// It allows site admins to delete any episode
+#[tokio::test]
+#[ignore]
+async fn test_episode_delete_site_admin() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    // Create a site admin with no roles for the Show
+    let _ = utils.create_site_admin(&username).await?;
+
+    let (_, episode) = utils
+        .create_show_and_episode("Test Show", "Test Episode 1")
+        .await?;
+
+    let req = utils
+        .graphql
+        .query(DELETE_EPISODE, json!({"id": episode.id}), Some(&token))?;
+    let resp = utils.http_client.request(req).await?;
+
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert!(json["data"]["deleteEpisode"].as_bool().unwrap());
+
+    Ok(())
+}
+
+/***
+ * Authorization: Episode chat
+ */
+
+/// It allows guests of a show to chat about each of its episodes
+#[tokio::test]
+#[ignore]
+async fn test_episode_chat_show_guest() -> Result<()> {
+    let utils = TestUtils::init().await?;
+    let ctx = utils.ctx.clone();
+
+    let username = Ulid::new().to_string();
+
+    // Create a user with this username
+    let user = ctx.users.create(&username).await?;
+
+    let (show, episode) = utils
+        .create_show_and_episode("Test Show", "Test Episode 1")
+        .await?;
+
+    let (_, other_episode) = utils
+        .create_show_and_episode("Other Show", "Other Episode 1")
+        .await?;
+
+    // Grant the guest role to this user for this episode's show
+    ctx.role_grants
+        .create(&CreateRoleGrantInput {
+            role_key: "guest".to_string(),
+            user_id: user.id.clone(),
+            resource_table: "shows".to_string(),
+            resource_id: show.id.clone(),
+        })
+        .await?;
+
+    let user = ctx.users.get_by_username(&username, &true).await?.unwrap();
+    let episode = ctx.episodes.get(&episode.id, &true).await?.unwrap();
+    let other_episode = ctx.episodes.get(&other_episode.id, &true).await?.unwrap();
+
+    let oso = ctx.policies.current();
+
+    assert!(oso.is_allowed(user.clone(), "episode_chat", episode.clone())?);
+    assert!(oso.is_allowed(user.clone(), "episode_read_chat", episode.clone())?);
+    assert!(!oso.is_allowed(user.clone(), "update", episode)?);
+    assert!(!oso.is_allowed(user, "episode_chat", other_episode)?);
+
+    Ok(())
+}
@@ -1,12 +1,53 @@
 use anyhow::Result;
-use futures_util::StreamExt;
-use tokio_tungstenite::tungstenite::Message;
+use futures_util::{SinkExt, StreamExt};
+use pretty_assertions::assert_eq;
+use std::time::Duration;
+use tokio::time::{sleep, timeout, Instant};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
 
-use caster_api::events::messages::{IncomingMessage, OutgoingMessage};
+use caster_api::events::messages::{
+    ErrorCode, IncomingEnvelope, IncomingMessage, OutgoingEnvelope, OutgoingMessage,
+};
+use caster_utils::config::{get_config, Config};
 
 mod test_utils;
 use test_utils::TestUtils;
 
+/// Create a config with a heartbeat that is quick to time out
+fn heartbeat_config() -> &'static Config {
+    let mut config = get_config().clone();
+
+    config.events.heartbeat_interval = 1;
+    config.events.heartbeat_timeout = 2;
+
+    Box::leak(Box::new(config))
+}
+
+/// Send each request over a new connection, and return the responses
+async fn request(utils: &TestUtils, requests: Vec<String>) -> Result<Vec<OutgoingEnvelope>> {
+    let url = url::Url::parse(&format!(
+        "ws://localhost:{port}/events",
+        port = utils.addr.port()
+    ))?;
+
+    let (ws_stream, _) = connect_async(url).await?;
+    let (mut write, mut read) = ws_stream.split();
+
+    let mut responses = Vec::with_capacity(requests.len());
+
+    for request in requests {
+        write.send(Message::Text(request)).await?;
+
+        let message = timeout(Duration::from_secs(1), read.next())
+            .await?
+            .expect("Connection closed")?;
+
+        responses.push(serde_json::from_str(message.to_text()?)?);
+    }
+
+    Ok(responses)
+}
+
 #[tokio::test]
 #[ignore]
 async fn test_ping() -> Result<()> {
@@ -20,7 +61,8 @@ async fn test_ping() -> Result<()> {
                 let data = message.unwrap().into_data();
                 let result = std::str::from_utf8(&data).unwrap();
 
-                let expected = serde_json::to_string(&OutgoingMessage::Pong).unwrap();
+                let expected =
+                    serde_json::to_string(&OutgoingEnvelope::from(OutgoingMessage::Pong)).unwrap();
 
                 assert_eq!(&expected, result);
             })
@@ -29,3 +71,156 @@ async fn test_ping() -> Result<()> {
 
     Ok(())
 }
+
+/// It responds to every request with the id that the client supplied
+#[tokio::test]
+#[ignore]
+async fn test_request_ids() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let requests = [
+        IncomingMessage::Ping,
+        IncomingMessage::Unsubscribe {
+            show_id: "test-show".to_string(),
+        },
+        IncomingMessage::Subscribe {
+            show_id: "missing-show".to_string(),
+        },
+    ]
+    .into_iter()
+    .enumerate()
+    .map(|(index, message)| {
+        serde_json::to_string(&IncomingEnvelope::new(Some(index.to_string()), message))
+    })
+    .collect::<Result<Vec<_>, _>>()?;
+
+    let responses = request(&utils, requests).await?;
+
+    assert_eq!(
+        responses,
+        vec![
+            OutgoingEnvelope::reply(Some("0".to_string()), OutgoingMessage::Pong),
+            OutgoingEnvelope::reply(Some("1".to_string()), OutgoingMessage::Ack),
+            OutgoingEnvelope::reply(
+                Some("2".to_string()),
+                OutgoingMessage::error(ErrorCode::NotFound, "Show not found")
+            ),
+        ]
+    );
+
+    Ok(())
+}
+
+/// It responds with an Error to requests that can't be handled
+#[tokio::test]
+#[ignore]
+async fn test_request_errors() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let responses = request(
+        &utils,
+        vec![
+            "not json".to_string(),
+            r#"{"v": 99, "id": "future", "type": "Ping"}"#.to_string(),
+            r#"{"id": "anonymous", "type": "JoinChat", "episode_id": "test-episode"}"#.to_string(),
+        ],
+    )
+    .await?;
+
+    assert!(matches!(
+        &responses[0],
+        OutgoingEnvelope {
+            id: None,
+            message: OutgoingMessage::Error {
+                code: ErrorCode::InvalidMessage,
+                ..
+            },
+            ..
+        }
+    ));
+
+    assert_eq!(
+        responses[1],
+        OutgoingEnvelope::reply(
+            Some("future".to_string()),
+            OutgoingMessage::error(
+                ErrorCode::UnsupportedVersion,
+                "Unsupported protocol version: 99"
+            )
+        )
+    );
+
+    assert_eq!(
+        responses[2],
+        OutgoingEnvelope::reply(
+            Some("anonymous".to_string()),
+            OutgoingMessage::error(ErrorCode::Unauthorized, "Unauthorized")
+        )
+    );
+
+    Ok(())
+}
+
+/// It keeps connections that answer the server's pings, and evicts the ones that don't
+#[tokio::test]
+#[ignore]
+async fn test_heartbeat() -> Result<()> {
+    let utils = TestUtils::init_with_config(heartbeat_config()).await?;
+
+    let url = url::Url::parse(&format!(
+        "ws://localhost:{port}/events",
+        port = utils.addr.port()
+    ))?;
+
+    let (live_stream, _) = connect_async(url.clone()).await?;
+    let (mut live_write, mut live_read) = live_stream.split();
+
+    let (stale_stream, _) = connect_async(url).await?;
+    let (_stale_write, mut stale_read) = stale_stream.split();
+
+    // Keep reading from the live connection, which answers each ping with a pong
+    let deadline = Instant::now() + Duration::from_millis(3500);
+    let mut pings = 0;
+
+    while let Ok(message) = timeout(deadline - Instant::now(), live_read.next()).await {
+        if let Message::Ping(_) = message.expect("Connection closed")? {
+            pings += 1;
+        }
+    }
+
+    assert!(pings >= 2);
+
+    live_write
+        .send(Message::Text(serde_json::to_string(
+            &IncomingMessage::Ping,
+        )?))
+        .await?;
+
+    let pong = timeout(Duration::from_secs(1), live_read.next())
+        .await?
+        .expect("Connection closed")?;
+
+    assert_eq!(
+        pong.into_text()?,
+        serde_json::to_string(&OutgoingEnvelope::from(OutgoingMessage::Pong))?
+    );
+
+    // The stale connection hasn't read anything, so it has never answered a ping
+    sleep(Duration::from_millis(500)).await;
+
+    let mut closed = false;
+
+    while let Ok(Some(message)) = timeout(Duration::from_secs(1), stale_read.next()).await {
+        match message {
+            Ok(Message::Close(_)) | Err(_) => {
+                closed = true;
+                break;
+            }
+            Ok(_) => (),
+        }
+    }
+
+    assert!(closed);
+
+    Ok(())
+}
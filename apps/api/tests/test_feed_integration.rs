@@ -0,0 +1,190 @@
+use anyhow::Result;
+use fake::{Fake, Faker};
+use hyper::{body::to_bytes, Body, Request};
+use pretty_assertions::assert_eq;
+use ulid::Ulid;
+
+use caster_domains::{
+    episodes::{model::EpisodeStatus, mutations::CreateEpisodeInput},
+    shows::{model::Show, mutations::CreateShowInput},
+};
+
+#[cfg(test)]
+mod test_utils;
+
+use test_utils::TestUtils;
+
+/// Build a feed request for a Show, with optional extra headers
+fn feed_request(
+    utils: &TestUtils,
+    show_id: &str,
+    headers: &[(&str, &str)],
+) -> Result<Request<Body>> {
+    let mut req = Request::get(format!(
+        "http://localhost:{port}/shows/{show_id}/feed.xml",
+        port = utils.addr.port()
+    ));
+
+    for (name, value) in headers {
+        req = req.header(*name, *value);
+    }
+
+    Ok(req.body(Body::empty())?)
+}
+
+/// Create a Show with a couple of Episodes
+async fn create_show_with_episodes(utils: &TestUtils) -> Result<Show> {
+    let mut show_input: CreateShowInput = Faker.fake();
+    show_input.title = "Test Show & Friends".to_string();
+    show_input.summary = Some("A show about <testing>".to_string());
+
+    let show = utils.ctx.shows.create(&show_input).await?;
+
+    for (number, title) in [(1, "Episode One"), (2, "Episode Two")] {
+        let mut episode_input: CreateEpisodeInput = Faker.fake();
+        episode_input.title = title.to_string();
+        episode_input.show_id = show.id.clone();
+        episode_input.media_url = Some(format!("https://cdn.example.com/{number}.mp3"));
+        episode_input.media_type = Some("audio/mpeg".to_string());
+        episode_input.media_length = Some(1024);
+        episode_input.duration = Some(1800);
+        episode_input.episode_number = Some(number);
+        episode_input.explicit = Some(false);
+        episode_input.status = Some(EpisodeStatus::Published);
+
+        utils.ctx.episodes.create(&episode_input, &false).await?;
+    }
+
+    // Drafts are left out of the feed
+    let mut draft_input: CreateEpisodeInput = Faker.fake();
+    draft_input.title = "Episode Draft".to_string();
+    draft_input.show_id = show.id.clone();
+    draft_input.status = Some(EpisodeStatus::Draft);
+
+    utils.ctx.episodes.create(&draft_input, &false).await?;
+
+    Ok(show)
+}
+
+/// It renders an RSS feed with iTunes tags for a Show and its Episodes
+#[tokio::test]
+#[ignore]
+async fn test_feed_render() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let show = create_show_with_episodes(&utils).await?;
+
+    let resp = utils
+        .http_client
+        .request(feed_request(&utils, &show.id, &[])?)
+        .await?;
+
+    let status = resp.status();
+    let headers = resp.headers().clone();
+
+    let body = to_bytes(resp.into_body()).await?;
+    let body = String::from_utf8(body.to_vec())?;
+
+    assert_eq!(status, 200);
+    assert_eq!(
+        headers["content-type"],
+        "application/rss+xml; charset=utf-8"
+    );
+    assert!(headers.contains_key("etag"));
+    assert!(headers.contains_key("last-modified"));
+
+    assert!(body.contains("<rss"));
+    assert!(body.contains("xmlns:itunes=\"http://www.itunes.com/dtds/podcast-1.0.dtd\""));
+    assert!(body.contains("<title>Test Show &amp; Friends</title>"));
+    assert!(body.contains("<itunes:summary>A show about &lt;testing&gt;</itunes:summary>"));
+
+    // Episodes are listed most recent first
+    let one = body
+        .find("<title>Episode One</title>")
+        .expect("Missing Episode One");
+    let two = body
+        .find("<title>Episode Two</title>")
+        .expect("Missing Episode Two");
+
+    assert!(two < one);
+    assert!(!body.contains("<title>Episode Draft</title>"));
+
+    // Episodes include their media
+    assert!(body.contains(
+        r#"<enclosure url="https://cdn.example.com/1.mp3" length="1024" type="audio/mpeg"/>"#
+    ));
+    assert!(body.contains("<itunes:duration>1800</itunes:duration>"));
+    assert!(body.contains("<itunes:episode>2</itunes:episode>"));
+    assert!(body.contains("<itunes:explicit>false</itunes:explicit>"));
+
+    Ok(())
+}
+
+/// It responds with 304 Not Modified when the client's copy is current
+#[tokio::test]
+#[ignore]
+async fn test_feed_conditional() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let show = create_show_with_episodes(&utils).await?;
+
+    let resp = utils
+        .http_client
+        .request(feed_request(&utils, &show.id, &[])?)
+        .await?;
+
+    let etag = resp.headers()["etag"].to_str()?.to_string();
+    let last_modified = resp.headers()["last-modified"].to_str()?.to_string();
+
+    let resp = utils
+        .http_client
+        .request(feed_request(&utils, &show.id, &[("if-none-match", &etag)])?)
+        .await?;
+
+    assert_eq!(resp.status(), 304);
+    assert_eq!(resp.headers()["etag"], etag.as_str());
+
+    let resp = utils
+        .http_client
+        .request(feed_request(
+            &utils,
+            &show.id,
+            &[("if-modified-since", &last_modified)],
+        )?)
+        .await?;
+
+    assert_eq!(resp.status(), 304);
+
+    // A new Episode changes the feed
+    let mut episode_input: CreateEpisodeInput = Faker.fake();
+    episode_input.show_id = show.id.clone();
+    episode_input.status = Some(EpisodeStatus::Published);
+
+    utils.ctx.episodes.create(&episode_input, &false).await?;
+
+    let resp = utils
+        .http_client
+        .request(feed_request(&utils, &show.id, &[("if-none-match", &etag)])?)
+        .await?;
+
+    assert_eq!(resp.status(), 200);
+    assert_ne!(resp.headers()["etag"], etag.as_str());
+
+    Ok(())
+}
+
+/// It responds with 404 Not Found for unknown Shows
+#[tokio::test]
+#[ignore]
+async fn test_feed_not_found() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let resp = utils
+        .http_client
+        .request(feed_request(&utils, &Ulid::new().to_string(), &[])?)
+        .await?;
+
+    assert_eq!(resp.status(), 404);
+
+    Ok(())
+}
@@ -0,0 +1,316 @@
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use hyper::body::to_bytes;
+use pretty_assertions::assert_eq;
+use serde_json::{json, Value};
+use ulid::Ulid;
+
+use caster_domains::{
+    episodes::{
+        model::{Episode, EpisodeStatus},
+        mutations::CreateEpisodeInput,
+    },
+    shows::{model::Show, mutations::CreateShowInput},
+};
+
+#[cfg(test)]
+mod test_utils;
+
+use test_utils::TestUtils;
+
+/// Create a Show directly through the service
+async fn create_show(utils: &TestUtils, title: &str) -> Result<Show> {
+    let show = utils
+        .ctx
+        .shows
+        .create(&CreateShowInput {
+            title: title.to_string(),
+            ..Default::default()
+        })
+        .await?;
+
+    Ok(show)
+}
+
+/// Create an Episode for a Show, published the given number of hours ago unless it's a draft
+async fn create_episode(
+    utils: &TestUtils,
+    show: &Show,
+    title: &str,
+    hours_ago: Option<i64>,
+) -> Result<Episode> {
+    let episode = utils
+        .ctx
+        .episodes
+        .create(
+            &CreateEpisodeInput {
+                title: title.to_string(),
+                show_id: show.id.clone(),
+                status: hours_ago.map(|_| EpisodeStatus::Published),
+                publish_at: hours_ago.map(|hours| Utc::now().naive_utc() - Duration::hours(hours)),
+                ..Default::default()
+            },
+            &false,
+        )
+        .await?;
+
+    Ok(episode)
+}
+
+/// Send a GraphQL request and return the JSON response body
+async fn request(
+    utils: &TestUtils,
+    query: &str,
+    variables: Value,
+    token: Option<&str>,
+) -> Result<Value> {
+    let req = utils.graphql.query(query, variables, token)?;
+
+    let resp = utils.http_client.request(req).await?;
+
+    assert_eq!(resp.status(), 200);
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    Ok(serde_json::from_slice(&body)?)
+}
+
+/***
+ * Mutations: `followShow` and `unfollowShow`
+ */
+
+const FOLLOW_SHOW: &str = "
+    mutation FollowShow($showId: String!) {
+        followShow(showId: $showId) {
+            show {
+                id
+                followerCount
+            }
+        }
+    }
+";
+
+const UNFOLLOW_SHOW: &str = "
+    mutation UnfollowShow($showId: String!) {
+        unfollowShow(showId: $showId) {
+            show {
+                id
+                followerCount
+            }
+        }
+    }
+";
+
+/// It follows and unfollows Shows, counting their followers
+#[tokio::test]
+#[ignore]
+async fn test_follow_show() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let email = format!("{}@email.com", username);
+    let token = utils.create_jwt(&username);
+
+    utils.create_user_and_profile(&username, &email).await?;
+
+    let show = create_show(&utils, "Test Show").await?;
+
+    // Following twice has no further effect
+    for _ in 0..2 {
+        let json = request(
+            &utils,
+            FOLLOW_SHOW,
+            json!({ "showId": show.id }),
+            Some(&token),
+        )
+        .await?;
+
+        let json_show = &json["data"]["followShow"]["show"];
+
+        assert_eq!(json_show["id"], show.id);
+        assert_eq!(json_show["followerCount"], 1);
+    }
+
+    let json = request(
+        &utils,
+        UNFOLLOW_SHOW,
+        json!({ "showId": show.id }),
+        Some(&token),
+    )
+    .await?;
+
+    assert_eq!(json["data"]["unfollowShow"]["show"]["followerCount"], 0);
+
+    // Unknown Shows can't be followed
+    let json = request(
+        &utils,
+        FOLLOW_SHOW,
+        json!({ "showId": "unknown" }),
+        Some(&token),
+    )
+    .await?;
+
+    assert_eq!(json["errors"][0]["message"], "Unable to find existing Show");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 404);
+
+    // Anonymous Users can't follow Shows
+    let json = request(&utils, FOLLOW_SHOW, json!({ "showId": show.id }), None).await?;
+
+    assert_eq!(json["errors"][0]["message"], "Unauthorized");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 401);
+
+    Ok(())
+}
+
+/***
+ * Field: `Profile.followedShows`
+ */
+
+const GET_PROFILE: &str = "
+    query GetProfile($id: ID!) {
+        getProfile(id: $id) {
+            id
+            followedShows {
+                id
+            }
+        }
+    }
+";
+
+/// It only shows the followed Shows to the Profile's own User
+#[tokio::test]
+#[ignore]
+async fn test_follow_followed_shows() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let email = format!("{}@email.com", username);
+    let token = utils.create_jwt(&username);
+
+    let (_, profile) = utils.create_user_and_profile(&username, &email).await?;
+
+    let other_username = Ulid::new().to_string();
+    let other_email = format!("{}@email.com", other_username);
+    let other_token = utils.create_jwt(&other_username);
+
+    utils
+        .create_user_and_profile(&other_username, &other_email)
+        .await?;
+
+    let show = create_show(&utils, "Test Show").await?;
+
+    utils.ctx.follows.follow(&profile.id, &show.id).await?;
+
+    let json = request(
+        &utils,
+        GET_PROFILE,
+        json!({ "id": profile.id }),
+        Some(&token),
+    )
+    .await?;
+
+    assert_eq!(
+        json["data"]["getProfile"]["followedShows"],
+        json!([{ "id": show.id }])
+    );
+
+    for token in [Some(other_token.as_str()), None] {
+        let json = request(&utils, GET_PROFILE, json!({ "id": profile.id }), token).await?;
+
+        assert_eq!(json["data"]["getProfile"]["followedShows"], Value::Null);
+    }
+
+    Ok(())
+}
+
+/***
+ * Query: `getMyFeed`
+ */
+
+const GET_MY_FEED: &str = "
+    query GetMyFeed($first: Int, $after: String) {
+        getMyFeed(first: $first, after: $after) {
+            data {
+                id
+            }
+            count
+            endCursor
+            hasNextPage
+        }
+    }
+";
+
+/// It pages through the published Episodes of followed Shows, newest first
+#[tokio::test]
+#[ignore]
+async fn test_follow_feed() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let email = format!("{}@email.com", username);
+    let token = utils.create_jwt(&username);
+
+    let (_, profile) = utils.create_user_and_profile(&username, &email).await?;
+
+    let show = create_show(&utils, "Test Show").await?;
+    let other_show = create_show(&utils, "Other Show").await?;
+    let unfollowed = create_show(&utils, "Unfollowed Show").await?;
+
+    let oldest = create_episode(&utils, &show, "Oldest", Some(3)).await?;
+    let newest = create_episode(&utils, &other_show, "Newest", Some(1)).await?;
+    let middle = create_episode(&utils, &show, "Middle", Some(2)).await?;
+
+    // Drafts and Episodes from other Shows are left out
+    create_episode(&utils, &show, "Draft", None).await?;
+    create_episode(&utils, &unfollowed, "Unfollowed", Some(1)).await?;
+
+    for show in [&show, &other_show] {
+        utils.ctx.follows.follow(&profile.id, &show.id).await?;
+    }
+
+    let mut after = Value::Null;
+    let mut ids = vec![];
+
+    for expected_next in [true, true, false] {
+        let json = request(
+            &utils,
+            GET_MY_FEED,
+            json!({ "first": 1, "after": after }),
+            Some(&token),
+        )
+        .await?;
+
+        let json_result = &json["data"]["getMyFeed"];
+
+        assert_eq!(json_result["count"], 1);
+        assert_eq!(json_result["hasNextPage"], expected_next);
+
+        ids.push(json_result["data"][0]["id"].clone());
+        after = json_result["endCursor"].clone();
+    }
+
+    assert_eq!(
+        ids,
+        vec![json!(newest.id), json!(middle.id), json!(oldest.id)]
+    );
+
+    // Malformed cursors are rejected
+    let json = request(
+        &utils,
+        GET_MY_FEED,
+        json!({ "after": "invalid" }),
+        Some(&token),
+    )
+    .await?;
+
+    assert_eq!(json["errors"][0]["message"], "Invalid cursor");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 400);
+
+    // Anonymous Users don't have a feed
+    let json = request(&utils, GET_MY_FEED, json!({}), None).await?;
+
+    assert_eq!(json["errors"][0]["message"], "Unauthorized");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 401);
+
+    Ok(())
+}
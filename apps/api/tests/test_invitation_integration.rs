@@ -0,0 +1,770 @@
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use fake::{faker::internet::en::FreeEmail, Fake, Faker};
+use hyper::body::to_bytes;
+use pretty_assertions::assert_eq;
+use serde_json::{json, Value};
+use ulid::Ulid;
+
+use caster_domains::{
+    invitations::model::{CreateInvitationInput, InvitationStatus},
+    role_grants::model::CreateRoleGrantInput,
+    shows::{model::Show, mutations::CreateShowInput},
+    users::model::User,
+};
+
+#[cfg(test)]
+mod test_utils;
+
+use test_utils::TestUtils;
+
+/// Create a Show with the given User as an admin
+async fn create_show_with_admin(utils: &TestUtils, user: &User) -> Result<Show> {
+    let mut show_input: CreateShowInput = Faker.fake();
+    show_input.title = "Test Show".to_string();
+
+    let (show, _grant) = utils
+        .ctx
+        .shows
+        .create_with_admin(&show_input, &user.id)
+        .await?;
+
+    Ok(show)
+}
+
+/// Check whether a User holds a Role for a Show
+async fn has_role(utils: &TestUtils, username: &str, role_key: &str, show: &Show) -> Result<bool> {
+    let user = utils
+        .ctx
+        .users
+        .get_by_username(username, &true)
+        .await?
+        .expect("User not found");
+
+    Ok(user.roles.iter().any(|role| {
+        role.role_key == role_key && role.resource_table == "shows" && role.resource_id == show.id
+    }))
+}
+
+/***
+ * Mutation: `inviteToShow`
+ */
+
+const INVITE_TO_SHOW: &str = "
+    mutation InviteToShow($input: InviteToShowInput!) {
+        inviteToShow(input: $input) {
+            invitation {
+                id
+                showId
+                roleKey
+                userId
+                inviterId
+                status
+            }
+        }
+    }
+";
+
+/// It invites a User by username
+#[tokio::test]
+#[ignore]
+async fn test_invitation_invite_username() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let user = utils.ctx.users.create(&username).await?;
+    let show = create_show_with_admin(&utils, &user).await?;
+
+    let invitee_username = Ulid::new().to_string();
+    let invitee = utils.ctx.users.create(&invitee_username).await?;
+
+    let req = utils.graphql.query(
+        INVITE_TO_SHOW,
+        json!({
+            "input": {
+                "showId": show.id,
+                "roleKey": "manager",
+                "username": invitee_username,
+            }
+        }),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    let json_invitation = &json["data"]["inviteToShow"]["invitation"];
+
+    assert_eq!(status, 200);
+    assert_eq!(json_invitation["showId"], show.id);
+    assert_eq!(json_invitation["roleKey"], "manager");
+    assert_eq!(json_invitation["userId"], invitee.id);
+    assert_eq!(json_invitation["inviterId"], user.id);
+    assert_eq!(json_invitation["status"], "PENDING");
+
+    // The Role isn't granted until the Invitation is accepted
+    assert!(!has_role(&utils, &invitee_username, "manager", &show).await?);
+
+    Ok(())
+}
+
+/// It invites a User by Profile email
+#[tokio::test]
+#[ignore]
+async fn test_invitation_invite_email() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let user = utils.ctx.users.create(&username).await?;
+    let show = create_show_with_admin(&utils, &user).await?;
+
+    let email: String = FreeEmail().fake();
+    let (invitee, _profile) = utils
+        .create_user_and_profile(&Ulid::new().to_string(), &email)
+        .await?;
+
+    let req = utils.graphql.query(
+        INVITE_TO_SHOW,
+        json!({
+            "input": {
+                "showId": show.id,
+                "roleKey": "guest",
+                "email": email,
+            }
+        }),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    let json_invitation = &json["data"]["inviteToShow"]["invitation"];
+
+    assert_eq!(status, 200);
+    assert_eq!(json_invitation["userId"], invitee.id);
+    assert_eq!(json_invitation["roleKey"], "guest");
+
+    Ok(())
+}
+
+/// It requires the manage_roles permission for the Show
+#[tokio::test]
+#[ignore]
+async fn test_invitation_invite_authz() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let user = utils.ctx.users.create(&username).await?;
+
+    let owner = utils.ctx.users.create(&Ulid::new().to_string()).await?;
+    let show = create_show_with_admin(&utils, &owner).await?;
+
+    // Managers can't invite others
+    utils
+        .ctx
+        .role_grants
+        .create(&CreateRoleGrantInput {
+            role_key: "manager".to_string(),
+            user_id: user.id.clone(),
+            resource_table: "shows".to_string(),
+            resource_id: show.id.clone(),
+        })
+        .await?;
+
+    let invitee_username = Ulid::new().to_string();
+    let _ = utils.ctx.users.create(&invitee_username).await?;
+
+    let req = utils.graphql.query(
+        INVITE_TO_SHOW,
+        json!({
+            "input": {
+                "showId": show.id,
+                "roleKey": "manager",
+                "username": invitee_username,
+            }
+        }),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(json["errors"][0]["message"], "Forbidden");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 403);
+
+    Ok(())
+}
+
+/// It rejects unknown Roles
+#[tokio::test]
+#[ignore]
+async fn test_invitation_invite_unknown_role() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let user = utils.ctx.users.create(&username).await?;
+    let show = create_show_with_admin(&utils, &user).await?;
+
+    let invitee_username = Ulid::new().to_string();
+    let _ = utils.ctx.users.create(&invitee_username).await?;
+
+    let req = utils.graphql.query(
+        INVITE_TO_SHOW,
+        json!({
+            "input": {
+                "showId": show.id,
+                "roleKey": "owner",
+                "username": invitee_username,
+            }
+        }),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(json["errors"][0]["message"], "Unknown role");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 400);
+
+    Ok(())
+}
+
+/// It requires exactly one of username or email
+#[tokio::test]
+#[ignore]
+async fn test_invitation_invite_requires_invitee() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let user = utils.ctx.users.create(&username).await?;
+    let show = create_show_with_admin(&utils, &user).await?;
+
+    let req = utils.graphql.query(
+        INVITE_TO_SHOW,
+        json!({
+            "input": {
+                "showId": show.id,
+                "roleKey": "manager",
+            }
+        }),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(
+        json["errors"][0]["message"],
+        "Provide either a username or an email"
+    );
+    assert_eq!(json["errors"][0]["extensions"]["code"], 400);
+
+    Ok(())
+}
+
+/***
+ * Query: `getPendingInvitations`
+ */
+
+const GET_PENDING_INVITATIONS: &str = "
+    query GetPendingInvitations {
+        getPendingInvitations {
+            id
+            showId
+        }
+    }
+";
+
+/// It lists the current User's pending, unexpired Invitations
+#[tokio::test]
+#[ignore]
+async fn test_invitation_get_pending() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let owner = utils.ctx.users.create(&Ulid::new().to_string()).await?;
+    let show = create_show_with_admin(&utils, &owner).await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let user = utils.ctx.users.create(&username).await?;
+
+    let invitation = utils
+        .ctx
+        .invitations
+        .create(&CreateInvitationInput {
+            show_id: show.id.clone(),
+            role_key: "guest".to_string(),
+            user_id: user.id.clone(),
+            inviter_id: Some(owner.id.clone()),
+            expires_at: Utc::now().naive_utc() + Duration::days(1),
+        })
+        .await?;
+
+    // An expired Invitation is left out
+    let _ = utils
+        .ctx
+        .invitations
+        .create(&CreateInvitationInput {
+            show_id: show.id.clone(),
+            role_key: "manager".to_string(),
+            user_id: user.id.clone(),
+            inviter_id: Some(owner.id.clone()),
+            expires_at: Utc::now().naive_utc() - Duration::days(1),
+        })
+        .await?;
+
+    let req = utils
+        .graphql
+        .query(GET_PENDING_INVITATIONS, Value::Null, Some(&token))?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(
+        json["data"]["getPendingInvitations"],
+        json!([{"id": invitation.id, "showId": show.id}])
+    );
+
+    Ok(())
+}
+
+/***
+ * Mutation: `acceptInvitation`
+ */
+
+const ACCEPT_INVITATION: &str = "
+    mutation AcceptInvitation($id: ID!) {
+        acceptInvitation(id: $id) {
+            invitation {
+                id
+                status
+            }
+        }
+    }
+";
+
+/// It grants the Role to the invitee
+#[tokio::test]
+#[ignore]
+async fn test_invitation_accept() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let owner = utils.ctx.users.create(&Ulid::new().to_string()).await?;
+    let show = create_show_with_admin(&utils, &owner).await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let user = utils.ctx.users.create(&username).await?;
+
+    let invitation = utils
+        .ctx
+        .invitations
+        .create(&CreateInvitationInput {
+            show_id: show.id.clone(),
+            role_key: "manager".to_string(),
+            user_id: user.id.clone(),
+            inviter_id: Some(owner.id.clone()),
+            expires_at: Utc::now().naive_utc() + Duration::days(1),
+        })
+        .await?;
+
+    let req = utils.graphql.query(
+        ACCEPT_INVITATION,
+        json!({"id": invitation.id}),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    let json_invitation = &json["data"]["acceptInvitation"]["invitation"];
+
+    assert_eq!(status, 200);
+    assert_eq!(json_invitation["id"], invitation.id);
+    assert_eq!(json_invitation["status"], "ACCEPTED");
+
+    assert!(has_role(&utils, &username, "manager", &show).await?);
+
+    // It can't be accepted twice
+    let req = utils.graphql.query(
+        ACCEPT_INVITATION,
+        json!({"id": invitation.id}),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(
+        json["errors"][0]["message"],
+        "Invitation is no longer pending"
+    );
+    assert_eq!(json["errors"][0]["extensions"]["code"], 400);
+
+    Ok(())
+}
+
+/// It only allows the invitee to respond
+#[tokio::test]
+#[ignore]
+async fn test_invitation_accept_authz() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let owner = utils.ctx.users.create(&Ulid::new().to_string()).await?;
+    let show = create_show_with_admin(&utils, &owner).await?;
+
+    let invitee = utils.ctx.users.create(&Ulid::new().to_string()).await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let _ = utils.ctx.users.create(&username).await?;
+
+    let invitation = utils
+        .ctx
+        .invitations
+        .create(&CreateInvitationInput {
+            show_id: show.id.clone(),
+            role_key: "manager".to_string(),
+            user_id: invitee.id.clone(),
+            inviter_id: Some(owner.id.clone()),
+            expires_at: Utc::now().naive_utc() + Duration::days(1),
+        })
+        .await?;
+
+    let req = utils.graphql.query(
+        ACCEPT_INVITATION,
+        json!({"id": invitation.id}),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(json["errors"][0]["message"], "Forbidden");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 403);
+
+    assert!(!has_role(&utils, &username, "manager", &show).await?);
+
+    Ok(())
+}
+
+/// It rejects expired Invitations
+#[tokio::test]
+#[ignore]
+async fn test_invitation_accept_expired() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let owner = utils.ctx.users.create(&Ulid::new().to_string()).await?;
+    let show = create_show_with_admin(&utils, &owner).await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let user = utils.ctx.users.create(&username).await?;
+
+    let invitation = utils
+        .ctx
+        .invitations
+        .create(&CreateInvitationInput {
+            show_id: show.id.clone(),
+            role_key: "manager".to_string(),
+            user_id: user.id.clone(),
+            inviter_id: Some(owner.id.clone()),
+            expires_at: Utc::now().naive_utc() - Duration::days(1),
+        })
+        .await?;
+
+    let req = utils.graphql.query(
+        ACCEPT_INVITATION,
+        json!({"id": invitation.id}),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(json["errors"][0]["message"], "Invitation has expired");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 400);
+
+    assert!(!has_role(&utils, &username, "manager", &show).await?);
+
+    Ok(())
+}
+
+/***
+ * Mutation: `declineInvitation`
+ */
+
+const DECLINE_INVITATION: &str = "
+    mutation DeclineInvitation($id: ID!) {
+        declineInvitation(id: $id) {
+            invitation {
+                id
+                status
+            }
+        }
+    }
+";
+
+/// It declines an Invitation without granting the Role
+#[tokio::test]
+#[ignore]
+async fn test_invitation_decline() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let owner = utils.ctx.users.create(&Ulid::new().to_string()).await?;
+    let show = create_show_with_admin(&utils, &owner).await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let user = utils.ctx.users.create(&username).await?;
+
+    let invitation = utils
+        .ctx
+        .invitations
+        .create(&CreateInvitationInput {
+            show_id: show.id.clone(),
+            role_key: "manager".to_string(),
+            user_id: user.id.clone(),
+            inviter_id: Some(owner.id.clone()),
+            expires_at: Utc::now().naive_utc() + Duration::days(1),
+        })
+        .await?;
+
+    let req = utils.graphql.query(
+        DECLINE_INVITATION,
+        json!({"id": invitation.id}),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    let json_invitation = &json["data"]["declineInvitation"]["invitation"];
+
+    assert_eq!(status, 200);
+    assert_eq!(json_invitation["status"], "DECLINED");
+
+    let declined = utils.ctx.invitations.get(&invitation.id).await?.unwrap();
+
+    assert_eq!(declined.status, InvitationStatus::Declined);
+    assert!(!has_role(&utils, &username, "manager", &show).await?);
+
+    Ok(())
+}
+
+/***
+ * Mutation: `transferShowOwnership`
+ */
+
+const TRANSFER_SHOW_OWNERSHIP: &str = "
+    mutation TransferShowOwnership($input: TransferShowOwnershipInput!) {
+        transferShowOwnership(input: $input) {
+            show {
+                id
+            }
+        }
+    }
+";
+
+/// It grants admin to another User and removes the current User's own
+#[tokio::test]
+#[ignore]
+async fn test_invitation_transfer_ownership() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let user = utils.ctx.users.create(&username).await?;
+    let show = create_show_with_admin(&utils, &user).await?;
+
+    let owner_username = Ulid::new().to_string();
+    let _ = utils.ctx.users.create(&owner_username).await?;
+
+    let req = utils.graphql.query(
+        TRANSFER_SHOW_OWNERSHIP,
+        json!({
+            "input": {
+                "showId": show.id,
+                "username": owner_username,
+                "removeOwnAdmin": true,
+            }
+        }),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(json["data"]["transferShowOwnership"]["show"]["id"], show.id);
+
+    assert!(has_role(&utils, &owner_username, "admin", &show).await?);
+    assert!(!has_role(&utils, &username, "admin", &show).await?);
+
+    Ok(())
+}
+
+/// It keeps the current User's admin Role unless asked to remove it
+#[tokio::test]
+#[ignore]
+async fn test_invitation_transfer_ownership_keep_admin() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let user = utils.ctx.users.create(&username).await?;
+    let show = create_show_with_admin(&utils, &user).await?;
+
+    let email: String = FreeEmail().fake();
+    let owner_username = Ulid::new().to_string();
+    let _ = utils
+        .create_user_and_profile(&owner_username, &email)
+        .await?;
+
+    let req = utils.graphql.query(
+        TRANSFER_SHOW_OWNERSHIP,
+        json!({
+            "input": {
+                "showId": show.id,
+                "email": email,
+            }
+        }),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(json["data"]["transferShowOwnership"]["show"]["id"], show.id);
+
+    assert!(has_role(&utils, &owner_username, "admin", &show).await?);
+    assert!(has_role(&utils, &username, "admin", &show).await?);
+
+    Ok(())
+}
+
+/// It requires the admin Role for the Show
+#[tokio::test]
+#[ignore]
+async fn test_invitation_transfer_ownership_authz() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let user = utils.ctx.users.create(&username).await?;
+
+    let owner = utils.ctx.users.create(&Ulid::new().to_string()).await?;
+    let show = create_show_with_admin(&utils, &owner).await?;
+
+    utils
+        .ctx
+        .role_grants
+        .create(&CreateRoleGrantInput {
+            role_key: "manager".to_string(),
+            user_id: user.id.clone(),
+            resource_table: "shows".to_string(),
+            resource_id: show.id.clone(),
+        })
+        .await?;
+
+    let req = utils.graphql.query(
+        TRANSFER_SHOW_OWNERSHIP,
+        json!({
+            "input": {
+                "showId": show.id,
+                "username": username,
+            }
+        }),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(json["errors"][0]["message"], "Forbidden");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 403);
+
+    assert!(!has_role(&utils, &username, "admin", &show).await?);
+
+    Ok(())
+}
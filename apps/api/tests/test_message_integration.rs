@@ -0,0 +1,758 @@
+use anyhow::Result;
+use futures_util::{
+    stream::{SplitSink, SplitStream},
+    SinkExt, StreamExt,
+};
+use hyper::body::to_bytes;
+use pretty_assertions::assert_eq;
+use serde_json::{json, Value};
+use tokio::{
+    net::TcpStream,
+    time::{timeout, Duration},
+};
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{client::IntoClientRequest, Message},
+    MaybeTlsStream, WebSocketStream,
+};
+use ulid::Ulid;
+
+use caster_api::events::messages::{IncomingMessage, OutgoingEnvelope, OutgoingMessage};
+use caster_domains::{
+    episodes::{
+        model::{Episode, EpisodeStatus},
+        mutations::CreateEpisodeInput,
+    },
+    profiles::model::Profile,
+    role_grants::model::CreateRoleGrantInput,
+    shows::model::Show,
+    users::model::User,
+};
+use caster_utils::config::{get_config, Config};
+
+#[cfg(test)]
+mod test_utils;
+
+use test_utils::TestUtils;
+
+/// Create a config with a banned word to filter
+fn chat_config() -> &'static Config {
+    let mut config = get_config().clone();
+
+    config.chat.banned_words = vec!["heck".to_string()];
+
+    Box::leak(Box::new(config))
+}
+
+/// Send a GraphQL request and return the JSON response body
+async fn request(
+    utils: &TestUtils,
+    query: &str,
+    variables: Value,
+    token: Option<&str>,
+) -> Result<Value> {
+    let req = utils.graphql.query(query, variables, token)?;
+
+    let resp = utils.http_client.request(req).await?;
+
+    assert_eq!(resp.status(), 200);
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    Ok(serde_json::from_slice(&body)?)
+}
+
+/// Create a User and Profile with the given Role for a Show, returning a token for them
+async fn create_member(
+    utils: &TestUtils,
+    show: &Show,
+    role_key: &str,
+) -> Result<(User, Profile, String)> {
+    let username = Ulid::new().to_string();
+    let email = format!("{}@email.com", username);
+    let token = utils.create_jwt(&username);
+
+    let (user, profile) = utils.create_user_and_profile(&username, &email).await?;
+
+    utils
+        .ctx
+        .role_grants
+        .create(&CreateRoleGrantInput {
+            role_key: role_key.to_string(),
+            user_id: user.id.clone(),
+            resource_table: "shows".to_string(),
+            resource_id: show.id.clone(),
+        })
+        .await?;
+
+    Ok((user, profile, token))
+}
+
+/// Post a Message about an Episode and return the JSON response body
+async fn create_message(
+    utils: &TestUtils,
+    episode: &Episode,
+    text: &str,
+    token: &str,
+) -> Result<Value> {
+    request(
+        utils,
+        CREATE_MESSAGE,
+        json!({ "input": { "episodeId": episode.id, "text": text } }),
+        Some(token),
+    )
+    .await
+}
+
+/// A connection to `/events`
+type Connection = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Connect to `/events` as a User and join an Episode's chat, returning once the join has been
+/// handled
+async fn join_chat(
+    utils: &TestUtils,
+    episode: &Episode,
+    token: &str,
+) -> Result<(SplitSink<Connection, Message>, SplitStream<Connection>)> {
+    let mut req =
+        format!("ws://localhost:{port}/events", port = utils.addr.port()).into_client_request()?;
+
+    req.headers_mut()
+        .insert("Authorization", format!("Bearer {}", token).parse()?);
+
+    let (ws_stream, _) = connect_async(req).await?;
+    let (mut write, mut read) = ws_stream.split();
+
+    send(
+        &mut write,
+        IncomingMessage::JoinChat {
+            episode_id: episode.id.clone(),
+        },
+    )
+    .await?;
+
+    // The chat has been joined once the Ack arrives
+    assert_eq!(next_message(&mut read).await?, OutgoingMessage::Ack);
+
+    Ok((write, read))
+}
+
+/// Send a message over a connection
+async fn send(write: &mut SplitSink<Connection, Message>, message: IncomingMessage) -> Result<()> {
+    write
+        .send(Message::Text(serde_json::to_string(&message)?))
+        .await?;
+
+    Ok(())
+}
+
+/// Read the next message from a connection
+async fn next_message(read: &mut SplitStream<Connection>) -> Result<OutgoingMessage> {
+    let message = timeout(Duration::from_secs(2), read.next())
+        .await?
+        .expect("Connection closed")?;
+
+    let envelope: OutgoingEnvelope = serde_json::from_str(message.to_text()?)?;
+
+    Ok(envelope.message)
+}
+
+const CREATE_MESSAGE: &str = "
+    mutation CreateMessage($input: CreateMessageInput!) {
+        createMessage(input: $input) {
+            message {
+                id
+                text
+                profileId
+                episodeId
+            }
+        }
+    }
+";
+
+const GET_EPISODE_MESSAGES: &str = "
+    query GetEpisodeMessages(
+        $episodeId: String!
+        $parentId: String
+        $before: String
+        $limit: Int
+    ) {
+        getEpisodeMessages(
+            episodeId: $episodeId
+            parentId: $parentId
+            before: $before
+            limit: $limit
+        ) {
+            data {
+                id
+                text
+                parentId
+                replyCount
+            }
+            count
+            endCursor
+            hasNextPage
+        }
+    }
+";
+
+const ADD_REACTION: &str = "
+    mutation AddReaction($messageId: String!, $emoji: String!) {
+        addReaction(messageId: $messageId, emoji: $emoji) {
+            message {
+                id
+                reactions {
+                    emoji
+                    count
+                }
+            }
+        }
+    }
+";
+
+const REMOVE_REACTION: &str = "
+    mutation RemoveReaction($messageId: String!, $emoji: String!) {
+        removeReaction(messageId: $messageId, emoji: $emoji) {
+            message {
+                id
+                reactions {
+                    emoji
+                    count
+                }
+            }
+        }
+    }
+";
+
+const UPDATE_MESSAGE: &str = "
+    mutation UpdateMessage($id: String!, $input: UpdateMessageInput!) {
+        updateMessage(id: $id, input: $input) {
+            message {
+                id
+                text
+                edits {
+                    text
+                }
+            }
+        }
+    }
+";
+
+const DELETE_MESSAGE: &str = "
+    mutation DeleteMessage($id: String!) {
+        deleteMessage(id: $id)
+    }
+";
+
+const MUTE_PROFILE: &str = "
+    mutation MuteProfile($input: MuteProfileInput!) {
+        muteProfile(input: $input) {
+            mute {
+                episodeId
+                profileId
+                mutedUntil
+            }
+        }
+    }
+";
+
+const UNMUTE_PROFILE: &str = "
+    mutation UnmuteProfile($episodeId: String!, $profileId: String!) {
+        unmuteProfile(episodeId: $episodeId, profileId: $profileId)
+    }
+";
+
+/***
+ * Mutations: `createMessage` and `updateMessage`
+ */
+
+/// It posts Messages with banned words masked, and keeps the history of edits
+#[tokio::test]
+#[ignore]
+async fn test_message_create_and_update() -> Result<()> {
+    let utils = TestUtils::init_with_config(chat_config()).await?;
+
+    let (show, episode) = utils
+        .create_show_and_episode("Test Show", "Test Episode")
+        .await?;
+
+    let (_, profile, token) = create_member(&utils, &show, "guest").await?;
+    let (_, _, other_token) = create_member(&utils, &show, "guest").await?;
+
+    let json = create_message(&utils, &episode, "What the Heck!", &token).await?;
+
+    let message = &json["data"]["createMessage"]["message"];
+    let message_id = message["id"].as_str().unwrap().to_string();
+
+    assert_eq!(message["text"], "What the ****!");
+    assert_eq!(message["profileId"], profile.id);
+    assert_eq!(message["episodeId"], episode.id);
+
+    let json = request(
+        &utils,
+        UPDATE_MESSAGE,
+        json!({ "id": message_id, "input": { "text": "What a show!" } }),
+        Some(&token),
+    )
+    .await?;
+
+    let message = &json["data"]["updateMessage"]["message"];
+
+    assert_eq!(message["text"], "What a show!");
+    assert_eq!(message["edits"], json!([{ "text": "What the ****!" }]));
+
+    // Only the author can edit a Message
+    let json = request(
+        &utils,
+        UPDATE_MESSAGE,
+        json!({ "id": message_id, "input": { "text": "Hijacked" } }),
+        Some(&other_token),
+    )
+    .await?;
+
+    assert_eq!(json["errors"][0]["extensions"]["code"], 403);
+
+    Ok(())
+}
+
+/// It requires permission to chat about the Episode
+#[tokio::test]
+#[ignore]
+async fn test_message_create_authz() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let (_, episode) = utils
+        .create_show_and_episode("Test Show", "Test Episode")
+        .await?;
+
+    let username = Ulid::new().to_string();
+    let email = format!("{}@email.com", username);
+    let token = utils.create_jwt(&username);
+
+    utils.create_user_and_profile(&username, &email).await?;
+
+    let json = request(
+        &utils,
+        CREATE_MESSAGE,
+        json!({ "input": { "episodeId": episode.id, "text": "Hello" } }),
+        None,
+    )
+    .await?;
+
+    assert_eq!(json["errors"][0]["extensions"]["code"], 401);
+
+    let json = create_message(&utils, &episode, "Hello", &token).await?;
+
+    assert_eq!(json["errors"][0]["extensions"]["code"], 403);
+
+    Ok(())
+}
+
+/***
+ * Moderation: `deleteMessage`, `muteProfile`, and `unmuteProfile`
+ */
+
+/// It allows Show admins to delete any Message, and authors to delete their own
+#[tokio::test]
+#[ignore]
+async fn test_message_delete() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let (show, episode) = utils
+        .create_show_and_episode("Test Show", "Test Episode")
+        .await?;
+
+    let (_, _, token) = create_member(&utils, &show, "guest").await?;
+    let (_, _, other_token) = create_member(&utils, &show, "guest").await?;
+    let (_, _, admin_token) = create_member(&utils, &show, "admin").await?;
+
+    let mut message_ids = vec![];
+
+    for text in ["First", "Second"] {
+        let json = create_message(&utils, &episode, text, &token).await?;
+
+        message_ids.push(
+            json["data"]["createMessage"]["message"]["id"]
+                .as_str()
+                .unwrap()
+                .to_string(),
+        );
+    }
+
+    // Other guests can't moderate
+    let json = request(
+        &utils,
+        DELETE_MESSAGE,
+        json!({ "id": message_ids[0] }),
+        Some(&other_token),
+    )
+    .await?;
+
+    assert_eq!(json["errors"][0]["extensions"]["code"], 403);
+
+    let json = request(
+        &utils,
+        DELETE_MESSAGE,
+        json!({ "id": message_ids[0] }),
+        Some(&admin_token),
+    )
+    .await?;
+
+    assert_eq!(json["data"]["deleteMessage"], true);
+
+    let json = request(
+        &utils,
+        DELETE_MESSAGE,
+        json!({ "id": message_ids[1] }),
+        Some(&token),
+    )
+    .await?;
+
+    assert_eq!(json["data"]["deleteMessage"], true);
+
+    for id in &message_ids {
+        assert!(utils.ctx.messages.get(id).await?.is_none());
+    }
+
+    Ok(())
+}
+
+/// It keeps muted Profiles from chatting until they are unmuted
+#[tokio::test]
+#[ignore]
+async fn test_message_mute() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let (show, episode) = utils
+        .create_show_and_episode("Test Show", "Test Episode")
+        .await?;
+
+    let (_, profile, token) = create_member(&utils, &show, "guest").await?;
+    let (_, _, admin_token) = create_member(&utils, &show, "admin").await?;
+
+    let mute_input = json!({
+        "input": { "episodeId": episode.id, "profileId": profile.id, "duration": 600 }
+    });
+
+    // Guests can't mute each other
+    let json = request(&utils, MUTE_PROFILE, mute_input.clone(), Some(&token)).await?;
+
+    assert_eq!(json["errors"][0]["extensions"]["code"], 403);
+
+    let json = request(&utils, MUTE_PROFILE, mute_input, Some(&admin_token)).await?;
+
+    let mute = &json["data"]["muteProfile"]["mute"];
+
+    assert_eq!(mute["episodeId"], episode.id);
+    assert_eq!(mute["profileId"], profile.id);
+
+    let json = create_message(&utils, &episode, "Let me speak", &token).await?;
+
+    assert_eq!(json["errors"][0]["extensions"]["code"], 403);
+
+    let json = request(
+        &utils,
+        UNMUTE_PROFILE,
+        json!({ "episodeId": episode.id, "profileId": profile.id }),
+        Some(&admin_token),
+    )
+    .await?;
+
+    assert_eq!(json["data"]["unmuteProfile"], true);
+
+    let json = create_message(&utils, &episode, "Thank you", &token).await?;
+
+    assert_eq!(
+        json["data"]["createMessage"]["message"]["text"],
+        "Thank you"
+    );
+
+    Ok(())
+}
+
+/***
+ * Query: `getEpisodeMessages`
+ */
+
+/// It pages through the chat history newest first, with replies listed by thread
+#[tokio::test]
+#[ignore]
+async fn test_message_history() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let (show, episode) = utils
+        .create_show_and_episode("Test Show", "Test Episode")
+        .await?;
+
+    let (_, _, token) = create_member(&utils, &show, "guest").await?;
+
+    let other_episode = utils
+        .ctx
+        .episodes
+        .create(
+            &CreateEpisodeInput {
+                title: "Other Episode".to_string(),
+                show_id: show.id.clone(),
+                status: Some(EpisodeStatus::Published),
+                ..Default::default()
+            },
+            &false,
+        )
+        .await?;
+
+    let mut ids = vec![];
+
+    for text in ["First", "Second", "Third"] {
+        let json = create_message(&utils, &episode, text, &token).await?;
+
+        ids.push(
+            json["data"]["createMessage"]["message"]["id"]
+                .as_str()
+                .unwrap()
+                .to_string(),
+        );
+    }
+
+    for text in ["Reply 1", "Reply 2"] {
+        let json = request(
+            &utils,
+            CREATE_MESSAGE,
+            json!({ "input": { "episodeId": episode.id, "text": text, "parentId": ids[0] } }),
+            Some(&token),
+        )
+        .await?;
+
+        assert_eq!(json["data"]["createMessage"]["message"]["text"], text);
+    }
+
+    // Replies must be in the same Episode
+    let json = request(
+        &utils,
+        CREATE_MESSAGE,
+        json!({ "input": { "episodeId": other_episode.id, "text": "Lost", "parentId": ids[0] } }),
+        Some(&token),
+    )
+    .await?;
+
+    assert_eq!(json["errors"][0]["extensions"]["code"], 400);
+
+    let json = request(
+        &utils,
+        GET_EPISODE_MESSAGES,
+        json!({ "episodeId": episode.id, "limit": 2 }),
+        Some(&token),
+    )
+    .await?;
+
+    let page = &json["data"]["getEpisodeMessages"];
+
+    assert_eq!(page["count"], 2);
+    assert_eq!(page["data"][0]["text"], "Third");
+    assert_eq!(page["data"][1]["text"], "Second");
+    assert_eq!(page["endCursor"], ids[1]);
+    assert_eq!(page["hasNextPage"], true);
+
+    let json = request(
+        &utils,
+        GET_EPISODE_MESSAGES,
+        json!({ "episodeId": episode.id, "limit": 2, "before": page["endCursor"] }),
+        Some(&token),
+    )
+    .await?;
+
+    let page = &json["data"]["getEpisodeMessages"];
+
+    assert_eq!(page["count"], 1);
+    assert_eq!(page["data"][0]["text"], "First");
+    assert_eq!(page["data"][0]["replyCount"], 2);
+    assert_eq!(page["hasNextPage"], false);
+
+    let json = request(
+        &utils,
+        GET_EPISODE_MESSAGES,
+        json!({ "episodeId": episode.id, "parentId": ids[0] }),
+        Some(&token),
+    )
+    .await?;
+
+    let page = &json["data"]["getEpisodeMessages"];
+
+    assert_eq!(page["count"], 2);
+    assert_eq!(page["data"][0]["text"], "Reply 2");
+    assert_eq!(page["data"][0]["parentId"], ids[0]);
+    assert_eq!(page["data"][1]["text"], "Reply 1");
+
+    Ok(())
+}
+
+/// It requires permission to read the chat about the Episode
+#[tokio::test]
+#[ignore]
+async fn test_message_history_authz() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let (_, episode) = utils
+        .create_show_and_episode("Test Show", "Test Episode")
+        .await?;
+
+    let username = Ulid::new().to_string();
+    let email = format!("{}@email.com", username);
+    let token = utils.create_jwt(&username);
+
+    utils.create_user_and_profile(&username, &email).await?;
+
+    let json = request(
+        &utils,
+        GET_EPISODE_MESSAGES,
+        json!({ "episodeId": episode.id }),
+        Some(&token),
+    )
+    .await?;
+
+    assert_eq!(json["errors"][0]["extensions"]["code"], 403);
+
+    Ok(())
+}
+
+/***
+ * Mutations: `addReaction` and `removeReaction`
+ */
+
+/// It counts the emoji reactions to each Message
+#[tokio::test]
+#[ignore]
+async fn test_message_reactions() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let (show, episode) = utils
+        .create_show_and_episode("Test Show", "Test Episode")
+        .await?;
+
+    let (_, _, token) = create_member(&utils, &show, "guest").await?;
+    let (_, _, other_token) = create_member(&utils, &show, "guest").await?;
+
+    let json = create_message(&utils, &episode, "Great episode", &token).await?;
+    let message_id = json["data"]["createMessage"]["message"]["id"].clone();
+
+    // Reacting twice with the same emoji has no further effect
+    for (emoji, token) in [
+        ("👍", &token),
+        ("👍", &token),
+        ("👍", &other_token),
+        ("🎉", &other_token),
+    ] {
+        request(
+            &utils,
+            ADD_REACTION,
+            json!({ "messageId": message_id, "emoji": emoji }),
+            Some(token),
+        )
+        .await?;
+    }
+
+    let json = request(
+        &utils,
+        REMOVE_REACTION,
+        json!({ "messageId": message_id, "emoji": "🎉" }),
+        Some(&other_token),
+    )
+    .await?;
+
+    assert_eq!(
+        json["data"]["removeReaction"]["message"]["reactions"],
+        json!([{ "emoji": "👍", "count": 2 }])
+    );
+
+    let json = request(
+        &utils,
+        ADD_REACTION,
+        json!({ "messageId": message_id, "emoji": "🎉" }),
+        Some(&token),
+    )
+    .await?;
+
+    assert_eq!(
+        json["data"]["addReaction"]["message"]["reactions"],
+        json!([{ "emoji": "👍", "count": 2 }, { "emoji": "🎉", "count": 1 }])
+    );
+
+    let json = request(
+        &utils,
+        ADD_REACTION,
+        json!({ "messageId": message_id, "emoji": "lol" }),
+        Some(&token),
+    )
+    .await?;
+
+    assert_eq!(json["errors"][0]["extensions"]["code"], 400);
+
+    Ok(())
+}
+
+/***
+ * Events: joining a chat over `/events`
+ */
+
+/// It sends typing indicators and new Messages to the connections that have joined the chat
+#[tokio::test]
+#[ignore]
+async fn test_message_chat_events() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let (show, episode) = utils
+        .create_show_and_episode("Test Show", "Test Episode")
+        .await?;
+
+    let (_, _, token) = create_member(&utils, &show, "guest").await?;
+    let (_, other_profile, other_token) = create_member(&utils, &show, "guest").await?;
+
+    let (_write, mut read) = join_chat(&utils, &episode, &token).await?;
+    let (mut other_write, _other_read) = join_chat(&utils, &episode, &other_token).await?;
+
+    // Typing indicators are only sent to everyone else in the chat
+    send(
+        &mut other_write,
+        IncomingMessage::Typing {
+            episode_id: episode.id.clone(),
+        },
+    )
+    .await?;
+
+    assert_eq!(
+        next_message(&mut read).await?,
+        OutgoingMessage::Typing {
+            episode_id: episode.id.clone(),
+            profile_id: other_profile.id.clone(),
+        }
+    );
+
+    let json = create_message(&utils, &episode, "Hello, chat", &other_token).await?;
+    let message_id = json["data"]["createMessage"]["message"]["id"].clone();
+
+    let message = match next_message(&mut read).await? {
+        OutgoingMessage::MessagePosted { message } => message,
+        other => panic!("Unexpected message: {:?}", other),
+    };
+
+    assert_eq!(message.id, message_id);
+    assert_eq!(message.text, "Hello, chat");
+    assert_eq!(message.profile_id, other_profile.id);
+
+    request(
+        &utils,
+        DELETE_MESSAGE,
+        json!({ "id": message_id }),
+        Some(&other_token),
+    )
+    .await?;
+
+    assert_eq!(
+        next_message(&mut read).await?,
+        OutgoingMessage::MessageDeleted {
+            episode_id: episode.id.clone(),
+            message_id: message.id,
+        }
+    );
+
+    Ok(())
+}
@@ -0,0 +1,443 @@
+use anyhow::Result;
+use chrono::Utc;
+use fake::{faker::internet::en::FreeEmail, Fake};
+use futures_util::{SinkExt, StreamExt};
+use hyper::body::to_bytes;
+use pretty_assertions::assert_eq;
+use serde_json::{json, Value};
+use tokio::time::{timeout, Duration};
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{client::IntoClientRequest, Message},
+};
+use ulid::Ulid;
+
+use caster_api::events::messages::{IncomingMessage, OutgoingEnvelope, OutgoingMessage};
+use caster_domains::{
+    episodes::{model::EpisodeStatus, mutations::CreateEpisodeInput},
+    invitations::model::CreateInvitationInput,
+    messages::mutations::{CreateMessageInput, UpdateMessageInput},
+    notifications::model::NotificationKind,
+    role_grants::model::CreateRoleGrantInput,
+    shows::mutations::CreateShowInput,
+};
+
+#[cfg(test)]
+mod test_utils;
+
+use test_utils::TestUtils;
+
+/// Send a GraphQL request and return the JSON response body
+async fn request(
+    utils: &TestUtils,
+    query: &str,
+    variables: Value,
+    token: Option<&str>,
+) -> Result<Value> {
+    let req = utils.graphql.query(query, variables, token)?;
+
+    let resp = utils.http_client.request(req).await?;
+
+    assert_eq!(resp.status(), 200);
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    Ok(serde_json::from_slice(&body)?)
+}
+
+const TRANSFER_SHOW_OWNERSHIP: &str = "
+    mutation TransferShowOwnership($input: TransferShowOwnershipInput!) {
+        transferShowOwnership(input: $input) {
+            show {
+                id
+            }
+        }
+    }
+";
+
+/***
+ * Query: `getMyNotifications`
+ */
+
+const GET_MY_NOTIFICATIONS: &str = "
+    query GetMyNotifications($unread: Boolean) {
+        getMyNotifications(unread: $unread) {
+            data {
+                id
+                kind
+                payload
+                readAt
+            }
+            total
+        }
+    }
+";
+
+/***
+ * Mutation: `markNotificationsRead`
+ */
+
+const MARK_NOTIFICATIONS_READ: &str = "
+    mutation MarkNotificationsRead($ids: [String!]) {
+        markNotificationsRead(ids: $ids)
+    }
+";
+
+/// It notifies Users when they are granted a Role, and marks their Notifications as read
+#[tokio::test]
+#[ignore]
+async fn test_notification_role_granted() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let user = utils.ctx.users.create(&username).await?;
+
+    let (show, _) = utils
+        .ctx
+        .shows
+        .create_with_admin(
+            &CreateShowInput {
+                title: "Test Show".to_string(),
+                ..Default::default()
+            },
+            &user.id,
+        )
+        .await?;
+
+    let owner_username = Ulid::new().to_string();
+    let owner_token = utils.create_jwt(&owner_username);
+
+    let owner = utils.ctx.users.create(&owner_username).await?;
+
+    let mut created = utils.ctx.notification_publisher.subscribe();
+
+    request(
+        &utils,
+        TRANSFER_SHOW_OWNERSHIP,
+        json!({ "input": { "showId": show.id, "username": owner_username } }),
+        Some(&token),
+    )
+    .await?;
+
+    // Notifications are sent in the background, and the Show's creator isn't notified about
+    // their own admin grant
+    let notification = timeout(Duration::from_secs(2), created.recv()).await??;
+
+    assert_eq!(notification.user_id, owner.id);
+
+    let json = request(
+        &utils,
+        GET_MY_NOTIFICATIONS,
+        json!({ "unread": true }),
+        Some(&owner_token),
+    )
+    .await?;
+
+    let json_result = &json["data"]["getMyNotifications"];
+
+    assert_eq!(json_result["total"], 1);
+    assert_eq!(json_result["data"][0]["kind"], "ROLE_GRANTED");
+    assert_eq!(json_result["data"][0]["payload"]["roleKey"], "admin");
+    assert_eq!(json_result["data"][0]["payload"]["resourceId"], show.id);
+    assert_eq!(json_result["data"][0]["readAt"], Value::Null);
+
+    let notification_id = json_result["data"][0]["id"].clone();
+
+    // The current User's own Notifications are unaffected
+    let json = request(&utils, GET_MY_NOTIFICATIONS, json!({}), Some(&token)).await?;
+
+    assert_eq!(json["data"]["getMyNotifications"]["total"], 0);
+
+    // Marking Notifications a second time has no further effect
+    for expected in [1, 0] {
+        let json = request(
+            &utils,
+            MARK_NOTIFICATIONS_READ,
+            json!({ "ids": [notification_id] }),
+            Some(&owner_token),
+        )
+        .await?;
+
+        assert_eq!(json["data"]["markNotificationsRead"], expected);
+    }
+
+    let json = request(
+        &utils,
+        GET_MY_NOTIFICATIONS,
+        json!({ "unread": true }),
+        Some(&owner_token),
+    )
+    .await?;
+
+    assert_eq!(json["data"]["getMyNotifications"]["total"], 0);
+
+    let json = request(&utils, GET_MY_NOTIFICATIONS, json!({}), Some(&owner_token)).await?;
+
+    assert_ne!(
+        json["data"]["getMyNotifications"]["data"][0]["readAt"],
+        Value::Null
+    );
+
+    Ok(())
+}
+
+/// It notifies Users about Roles granted directly or by accepting an Invitation
+#[tokio::test]
+#[ignore]
+async fn test_notification_role_granted_directly() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let owner = utils.ctx.users.create(&Ulid::new().to_string()).await?;
+    let user = utils.ctx.users.create(&Ulid::new().to_string()).await?;
+
+    let (show, _) = utils
+        .ctx
+        .shows
+        .create_with_admin(
+            &CreateShowInput {
+                title: "Test Show".to_string(),
+                ..Default::default()
+            },
+            &owner.id,
+        )
+        .await?;
+
+    let mut created = utils.ctx.notification_publisher.subscribe();
+
+    let grant = utils
+        .ctx
+        .role_grants
+        .create(&CreateRoleGrantInput {
+            role_key: "guest".to_string(),
+            user_id: user.id.clone(),
+            resource_table: "shows".to_string(),
+            resource_id: show.id.clone(),
+        })
+        .await?;
+
+    let notification = timeout(Duration::from_secs(2), created.recv()).await??;
+
+    assert_eq!(notification.user_id, user.id);
+    assert_eq!(notification.kind, NotificationKind::RoleGranted);
+    assert_eq!(notification.payload["roleGrantId"], grant.id);
+    assert_eq!(notification.payload["roleKey"], "guest");
+    assert_eq!(notification.payload["resourceId"], show.id);
+
+    let invitation = utils
+        .ctx
+        .invitations
+        .create(&CreateInvitationInput {
+            show_id: show.id.clone(),
+            role_key: "manager".to_string(),
+            user_id: user.id.clone(),
+            inviter_id: Some(owner.id.clone()),
+            expires_at: Utc::now().naive_utc() + chrono::Duration::days(1),
+        })
+        .await?;
+
+    utils.ctx.invitations.accept(&invitation.id).await?;
+
+    let notification = timeout(Duration::from_secs(2), created.recv()).await??;
+
+    assert_eq!(notification.user_id, user.id);
+    assert_eq!(notification.kind, NotificationKind::RoleGranted);
+    assert_eq!(notification.payload["roleKey"], "manager");
+
+    // Nobody was notified about the Show's creation
+    let notifications = utils
+        .ctx
+        .notifications
+        .get_by_user_id(&owner.id, false, None, None)
+        .await?;
+
+    assert_eq!(notifications.total, 0);
+
+    Ok(())
+}
+
+/// It requires authentication to list Notifications
+#[tokio::test]
+#[ignore]
+async fn test_notification_unauthorized() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let json = request(&utils, GET_MY_NOTIFICATIONS, json!({}), None).await?;
+
+    assert_eq!(json["errors"][0]["message"], "Unauthorized");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 401);
+
+    Ok(())
+}
+
+/***
+ * Event: `Notification`
+ */
+
+/// It notifies followers when an Episode is published, delivering it to their connections
+#[tokio::test]
+#[ignore]
+async fn test_notification_episode_published() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let email: String = FreeEmail().fake();
+    let token = utils.create_jwt(&username);
+
+    let (user, profile) = utils.create_user_and_profile(&username, &email).await?;
+
+    let show = utils
+        .ctx
+        .shows
+        .create(&CreateShowInput {
+            title: "Test Show".to_string(),
+            ..Default::default()
+        })
+        .await?;
+
+    utils.ctx.follows.follow(&profile.id, &show.id).await?;
+
+    let mut req =
+        format!("ws://localhost:{port}/events", port = utils.addr.port()).into_client_request()?;
+
+    req.headers_mut()
+        .insert("Authorization", format!("Bearer {}", token).parse()?);
+
+    let (ws_stream, _) = connect_async(req).await?;
+    let (mut write, mut read) = ws_stream.split();
+
+    // Wait for a Pong, so that the connection is ready before the Episode is published
+    write
+        .send(Message::Text(serde_json::to_string(
+            &IncomingMessage::Ping,
+        )?))
+        .await?;
+
+    let pong = timeout(Duration::from_secs(1), read.next()).await?;
+
+    assert_eq!(
+        pong.expect("Connection closed")?.into_text()?,
+        serde_json::to_string(&OutgoingEnvelope::from(OutgoingMessage::Pong))?
+    );
+
+    let episode = utils
+        .ctx
+        .episodes
+        .create(
+            &CreateEpisodeInput {
+                title: "Test Episode".to_string(),
+                show_id: show.id.clone(),
+                status: Some(EpisodeStatus::Published),
+                ..Default::default()
+            },
+            &false,
+        )
+        .await?;
+
+    let message = timeout(Duration::from_secs(2), read.next())
+        .await?
+        .expect("Connection closed")?;
+
+    let envelope: OutgoingEnvelope = serde_json::from_str(message.to_text()?)?;
+
+    let notification = match envelope.message {
+        OutgoingMessage::Notification { notification } => notification,
+        other => panic!("Unexpected message: {:?}", other),
+    };
+
+    assert_eq!(notification.user_id, user.id);
+    assert_eq!(notification.kind, NotificationKind::EpisodePublished);
+    assert_eq!(notification.payload["episodeId"], episode.id);
+
+    // The Notification is kept for later
+    let stored = utils
+        .ctx
+        .notifications
+        .get_by_user_id(&user.id, true, None, None)
+        .await?;
+
+    assert_eq!(stored.data, vec![notification]);
+
+    Ok(())
+}
+
+/***
+ * Mentions
+ */
+
+/// It notifies Users mentioned in a chat Message, and only newly mentioned Users after an edit
+#[tokio::test]
+#[ignore]
+async fn test_notification_mentioned() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let author_username = Ulid::new().to_string();
+    let mentioned_username = Ulid::new().to_string();
+    let added_username = Ulid::new().to_string();
+
+    let (_, author) = utils
+        .create_user_and_profile(&author_username, &FreeEmail().fake::<String>())
+        .await?;
+    let (mentioned, _) = utils
+        .create_user_and_profile(&mentioned_username, &FreeEmail().fake::<String>())
+        .await?;
+    let (added, _) = utils
+        .create_user_and_profile(&added_username, &FreeEmail().fake::<String>())
+        .await?;
+
+    let (_, episode) = utils
+        .create_show_and_episode("Test Show", "Test Episode")
+        .await?;
+
+    let mut created = utils.ctx.notification_publisher.subscribe();
+
+    // Authors aren't notified about mentioning themselves
+    let message = utils
+        .ctx
+        .messages
+        .create(
+            &CreateMessageInput {
+                episode_id: episode.id.clone(),
+                text: format!("Hey @{}, it's @{}!", mentioned_username, author_username),
+                parent_id: None,
+            },
+            &author.id,
+        )
+        .await?;
+
+    let notification = timeout(Duration::from_secs(2), created.recv()).await??;
+
+    assert_eq!(notification.user_id, mentioned.id);
+    assert_eq!(notification.kind, NotificationKind::Mentioned);
+    assert_eq!(notification.payload["messageId"], message.id);
+    assert_eq!(notification.payload["episodeId"], episode.id);
+    assert_eq!(notification.payload["profileId"], author.id);
+
+    // Editing the Message only notifies the User who wasn't mentioned before
+    utils
+        .ctx
+        .messages
+        .update(
+            &message.id,
+            &UpdateMessageInput {
+                text: format!("Hey @{} and @{}", mentioned_username, added_username),
+            },
+        )
+        .await?;
+
+    let notification = timeout(Duration::from_secs(2), created.recv()).await??;
+
+    assert_eq!(notification.user_id, added.id);
+    assert_eq!(notification.kind, NotificationKind::Mentioned);
+
+    let stored = utils
+        .ctx
+        .notifications
+        .get_by_user_id(&mentioned.id, false, None, None)
+        .await?;
+
+    assert_eq!(stored.total, 1);
+
+    Ok(())
+}
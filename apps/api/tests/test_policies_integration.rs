@@ -0,0 +1,144 @@
+use anyhow::Result;
+use fake::{Fake, Faker};
+use pretty_assertions::assert_eq;
+use std::{fs, path::PathBuf};
+use ulid::Ulid;
+
+use caster_api::policies::Policies;
+use caster_domains::{
+    categories::AUTHORIZATION as CATEGORIES_AUTHZ,
+    episodes::AUTHORIZATION as EPISODES_AUTHZ,
+    profiles::AUTHORIZATION as PROFILES_AUTHZ,
+    role_grants::model::RoleGrant,
+    shows::{model::Show, AUTHORIZATION as SHOWS_AUTHZ},
+    users::{model::User, AUTHORIZATION as USERS_AUTHZ},
+};
+
+/// Write the built-in policies to a new temporary directory
+fn policy_dir() -> Result<PathBuf> {
+    let dir = std::env::temp_dir().join(format!("caster-policies-{}", Ulid::new()));
+
+    fs::create_dir_all(&dir)?;
+
+    fs::write(dir.join("users.polar"), USERS_AUTHZ)?;
+    fs::write(dir.join("profiles.polar"), PROFILES_AUTHZ)?;
+    fs::write(dir.join("shows.polar"), SHOWS_AUTHZ)?;
+    fs::write(dir.join("episodes.polar"), EPISODES_AUTHZ)?;
+    fs::write(dir.join("categories.polar"), CATEGORIES_AUTHZ)?;
+
+    Ok(dir)
+}
+
+/// Create a User with the admin role for a new Show
+fn show_admin() -> (User, Show) {
+    let show = Show {
+        id: Ulid::new().to_string(),
+        ..Faker.fake()
+    };
+
+    let user = User {
+        roles: vec![RoleGrant {
+            role_key: "admin".to_string(),
+            resource_table: "shows".to_string(),
+            resource_id: show.id.clone(),
+            ..Faker.fake()
+        }],
+        ..Faker.fake()
+    };
+
+    (user, show)
+}
+
+/// It loads policies from a directory
+#[tokio::test]
+#[ignore]
+async fn test_policies_load_dir() -> Result<()> {
+    let dir = policy_dir()?;
+
+    let policies = Policies::init(dir.to_str())?;
+
+    let (user, show) = show_admin();
+
+    assert!(policies.current().is_allowed(user, "delete", show)?);
+
+    fs::remove_dir_all(dir)?;
+
+    Ok(())
+}
+
+/// It swaps in the changed policies on reload, without affecting instances already in use
+#[tokio::test]
+#[ignore]
+async fn test_policies_reload() -> Result<()> {
+    let dir = policy_dir()?;
+
+    let policies = Policies::init(dir.to_str())?;
+    let before = policies.fingerprint()?;
+    let in_use = policies.current();
+
+    // Admins can no longer delete Shows
+    fs::write(
+        dir.join("shows.polar"),
+        SHOWS_AUTHZ.replace("\"delete\" if \"admin\";", ""),
+    )?;
+
+    policies.reload()?;
+
+    let (user, show) = show_admin();
+
+    assert!(!policies
+        .current()
+        .is_allowed(user.clone(), "delete", show.clone())?);
+    assert!(in_use.is_allowed(user, "delete", show)?);
+
+    assert_eq!(before.len(), 5);
+    assert_eq!(policies.fingerprint()?.len(), 5);
+
+    fs::remove_dir_all(dir)?;
+
+    Ok(())
+}
+
+/// It keeps the current policies if the changed ones are invalid
+#[tokio::test]
+#[ignore]
+async fn test_policies_reload_invalid() -> Result<()> {
+    let dir = policy_dir()?;
+
+    let policies = Policies::init(dir.to_str())?;
+
+    fs::write(dir.join("shows.polar"), "allow(actor, action, resource) if")?;
+
+    assert!(policies.reload().is_err());
+
+    let (user, show) = show_admin();
+
+    assert!(policies.current().is_allowed(user, "delete", show)?);
+
+    fs::remove_dir_all(dir)?;
+
+    Ok(())
+}
+
+/// It validates the policies at startup
+#[tokio::test]
+#[ignore]
+async fn test_policies_init_invalid() -> Result<()> {
+    let dir = policy_dir()?;
+
+    fs::write(dir.join("extra.polar"), "allow(actor, action, resource) if")?;
+
+    assert!(Policies::init(dir.to_str()).is_err());
+
+    // An empty directory is rejected as well
+    let empty = std::env::temp_dir().join(format!("caster-policies-{}", Ulid::new()));
+
+    fs::create_dir_all(&empty)?;
+
+    assert!(Policies::init(empty.to_str()).is_err());
+
+    fs::remove_dir_all(dir)?;
+    fs::remove_dir_all(empty)?;
+
+    Ok(())
+}
@@ -0,0 +1,293 @@
+use anyhow::Result;
+use futures_util::{
+    stream::{SplitSink, SplitStream},
+    SinkExt, StreamExt,
+};
+use hyper::body::to_bytes;
+use pretty_assertions::assert_eq;
+use serde_json::{json, Value};
+use tokio::{
+    net::TcpStream,
+    time::{timeout, Duration},
+};
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{client::IntoClientRequest, Message},
+    MaybeTlsStream, WebSocketStream,
+};
+use ulid::Ulid;
+
+use caster_api::events::messages::{ErrorCode, IncomingMessage, OutgoingEnvelope, OutgoingMessage};
+use caster_domains::{
+    episodes::{
+        model::{Episode, EpisodeStatus},
+        mutations::CreateEpisodeInput,
+    },
+    profiles::model::Profile,
+};
+
+#[cfg(test)]
+mod test_utils;
+
+use test_utils::TestUtils;
+
+/// A connection to `/events`
+type Connection = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Send a GraphQL request and return the JSON response body
+async fn request(
+    utils: &TestUtils,
+    query: &str,
+    variables: Value,
+    token: Option<&str>,
+) -> Result<Value> {
+    let req = utils.graphql.query(query, variables, token)?;
+
+    let resp = utils.http_client.request(req).await?;
+
+    assert_eq!(resp.status(), 200);
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    Ok(serde_json::from_slice(&body)?)
+}
+
+/// Create a User and Profile, returning a token for them
+async fn create_viewer(utils: &TestUtils) -> Result<(Profile, String)> {
+    let username = Ulid::new().to_string();
+    let email = format!("{}@email.com", username);
+    let token = utils.create_jwt(&username);
+
+    let (_, profile) = utils.create_user_and_profile(&username, &email).await?;
+
+    Ok((profile, token))
+}
+
+/// Connect to `/events`, authenticated if a token is given
+async fn connect(
+    utils: &TestUtils,
+    token: Option<&str>,
+) -> Result<(SplitSink<Connection, Message>, SplitStream<Connection>)> {
+    let mut req =
+        format!("ws://localhost:{port}/events", port = utils.addr.port()).into_client_request()?;
+
+    if let Some(token) = token {
+        req.headers_mut()
+            .insert("Authorization", format!("Bearer {}", token).parse()?);
+    }
+
+    let (ws_stream, _) = connect_async(req).await?;
+
+    Ok(ws_stream.split())
+}
+
+/// Send a message over a connection
+async fn send(write: &mut SplitSink<Connection, Message>, message: IncomingMessage) -> Result<()> {
+    write
+        .send(Message::Text(serde_json::to_string(&message)?))
+        .await?;
+
+    Ok(())
+}
+
+/// Read the next message from a connection
+async fn next_message(read: &mut SplitStream<Connection>) -> Result<OutgoingMessage> {
+    let message = timeout(Duration::from_secs(2), read.next())
+        .await?
+        .expect("Connection closed")?;
+
+    let envelope: OutgoingEnvelope = serde_json::from_str(message.to_text()?)?;
+
+    Ok(envelope.message)
+}
+
+/// The message sent to watchers when the number of viewers changes
+fn presence_changed(episode: &Episode, viewer_count: u64) -> OutgoingMessage {
+    OutgoingMessage::PresenceChanged {
+        episode_id: episode.id.clone(),
+        viewer_count,
+    }
+}
+
+const GET_EPISODE_PRESENCE: &str = "
+    query GetEpisodePresence($episodeId: String!) {
+        getEpisodePresence(episodeId: $episodeId) {
+            episodeId
+            viewerCount
+            profiles {
+                id
+                email
+            }
+        }
+    }
+";
+
+const GET_EPISODE: &str = "
+    query GetEpisode($id: String!) {
+        getEpisode(id: $id) {
+            id
+            viewerCount
+        }
+    }
+";
+
+/// It tracks the connections watching an Episode, and lets them know as viewers come and go
+#[tokio::test]
+#[ignore]
+async fn test_presence_watch() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let (_, episode) = utils
+        .create_show_and_episode("Test Show", "Test Episode")
+        .await?;
+
+    let (profile, token) = create_viewer(&utils).await?;
+    let (_, other_token) = create_viewer(&utils).await?;
+
+    let watch = IncomingMessage::Watch {
+        episode_id: episode.id.clone(),
+    };
+
+    let (mut write, mut read) = connect(&utils, Some(&token)).await?;
+
+    send(&mut write, watch.clone()).await?;
+
+    assert_eq!(
+        next_message(&mut read).await?,
+        presence_changed(&episode, 1)
+    );
+    assert_eq!(next_message(&mut read).await?, OutgoingMessage::Ack);
+
+    // The same Profile watching from another connection is only counted once
+    let (mut second_write, mut second_read) = connect(&utils, Some(&token)).await?;
+
+    send(&mut second_write, watch.clone()).await?;
+
+    assert_eq!(
+        next_message(&mut second_read).await?,
+        presence_changed(&episode, 1)
+    );
+    assert_eq!(
+        next_message(&mut read).await?,
+        presence_changed(&episode, 1)
+    );
+
+    // Anonymous connections each count on their own
+    let (mut anonymous_write, mut anonymous_read) = connect(&utils, None).await?;
+
+    send(&mut anonymous_write, watch).await?;
+
+    assert_eq!(
+        next_message(&mut anonymous_read).await?,
+        presence_changed(&episode, 2)
+    );
+    assert_eq!(
+        next_message(&mut read).await?,
+        presence_changed(&episode, 2)
+    );
+
+    let json = request(
+        &utils,
+        GET_EPISODE_PRESENCE,
+        json!({ "episodeId": episode.id }),
+        Some(&other_token),
+    )
+    .await?;
+
+    // The Profiles of authenticated viewers are censored for everyone else
+    assert_eq!(
+        json["data"]["getEpisodePresence"],
+        json!({
+            "episodeId": episode.id,
+            "viewerCount": 2,
+            "profiles": [{ "id": profile.id, "email": null }],
+        })
+    );
+
+    let json = request(&utils, GET_EPISODE, json!({ "id": episode.id }), None).await?;
+
+    assert_eq!(json["data"]["getEpisode"]["viewerCount"], 2);
+
+    // Viewers leave when they unwatch the Episode or disconnect
+    send(
+        &mut second_write,
+        IncomingMessage::Unwatch {
+            episode_id: episode.id.clone(),
+        },
+    )
+    .await?;
+
+    assert_eq!(
+        next_message(&mut read).await?,
+        presence_changed(&episode, 2)
+    );
+
+    drop(anonymous_write);
+    drop(anonymous_read);
+
+    assert_eq!(
+        next_message(&mut read).await?,
+        presence_changed(&episode, 1)
+    );
+
+    Ok(())
+}
+
+/// It hides unpublished Episodes from those who can't manage them
+#[tokio::test]
+#[ignore]
+async fn test_presence_unpublished() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let (show, _) = utils
+        .create_show_and_episode("Test Show", "Test Episode")
+        .await?;
+
+    let episode = utils
+        .ctx
+        .episodes
+        .create(
+            &CreateEpisodeInput {
+                title: "Test Draft".to_string(),
+                show_id: show.id.clone(),
+                status: Some(EpisodeStatus::Draft),
+                ..Default::default()
+            },
+            &false,
+        )
+        .await?;
+
+    let (_, token) = create_viewer(&utils).await?;
+
+    let (mut write, mut read) = connect(&utils, Some(&token)).await?;
+
+    send(
+        &mut write,
+        IncomingMessage::Watch {
+            episode_id: episode.id.clone(),
+        },
+    )
+    .await?;
+
+    assert_eq!(
+        next_message(&mut read).await?,
+        OutgoingMessage::error(ErrorCode::NotFound, "Episode not found")
+    );
+
+    let json = request(
+        &utils,
+        GET_EPISODE_PRESENCE,
+        json!({ "episodeId": episode.id }),
+        Some(&token),
+    )
+    .await?;
+
+    assert_eq!(json["data"], Value::Null);
+    assert_eq!(
+        json["errors"][0]["message"],
+        "Unable to find existing Episode"
+    );
+    assert_eq!(json["errors"][0]["extensions"]["code"], 404);
+
+    Ok(())
+}
@@ -664,6 +664,50 @@ async fn test_profile_update_authz() -> Result<()> {
     Ok(())
 }
 
+/// It allows site admins to update any Profile
+#[tokio::test]
+#[ignore]
+async fn test_profile_update_site_admin() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let email: String = FreeEmail().fake();
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    // Create a site admin, and a Profile for another User
+    let _ = utils.create_site_admin(&username).await?;
+
+    let (_, profile) = utils
+        .create_user_and_profile(&Ulid::new().to_string(), &email)
+        .await?;
+
+    let req = utils.graphql.query(
+        UPDATE_PROFILE,
+        json!({
+            "id": profile.id,
+            "input": {
+                "displayName": "Test Name"
+            }
+        }),
+        Some(&token),
+    )?;
+    let resp = utils.http_client.request(req).await?;
+
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    let json_profile = &json["data"]["updateProfile"]["profile"];
+
+    assert_eq!(status, 200);
+    assert_eq!(json_profile["id"], profile.id);
+    assert_eq!(json_profile["displayName"], "Test Name");
+
+    Ok(())
+}
+
 /***
  * Mutation: `deleteProfile`
  */
@@ -799,3 +843,37 @@ async fn test_profile_delete_authz() -> Result<()> {
 
     Ok(())
 }
+
+/// It allows site admins to delete any Profile
+#[tokio::test]
+#[ignore]
+async fn test_profile_delete_site_admin() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let email: String = FreeEmail().fake();
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    // Create a site admin, and a Profile for another User
+    let _ = utils.create_site_admin(&username).await?;
+
+    let (_, profile) = utils
+        .create_user_and_profile(&Ulid::new().to_string(), &email)
+        .await?;
+
+    let req = utils
+        .graphql
+        .query(DELETE_PROFILE, json!({"id": profile.id}), Some(&token))?;
+    let resp = utils.http_client.request(req).await?;
+
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert!(json["data"]["deleteProfile"].as_bool().unwrap());
+
+    Ok(())
+}
@@ -0,0 +1,340 @@
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use futures_util::{SinkExt, StreamExt};
+use hyper::body::to_bytes;
+use pretty_assertions::assert_eq;
+use serde_json::{json, Value};
+use tokio::time::{timeout, Duration as Timeout};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use ulid::Ulid;
+
+use caster_api::{
+    events::messages::{IncomingMessage, OutgoingEnvelope, OutgoingMessage},
+    jobs::publish::publish_scheduled,
+};
+use caster_domains::{
+    episodes::{
+        model::{Episode, EpisodeStatus},
+        mutations::CreateEpisodeInput,
+    },
+    role_grants::model::CreateRoleGrantInput,
+    shows::{model::Show, mutations::CreateShowInput},
+};
+
+#[cfg(test)]
+mod test_utils;
+
+use test_utils::TestUtils;
+
+/// Create a Show with a draft Episode
+async fn create_show_and_draft(utils: &TestUtils) -> Result<(Show, Episode)> {
+    let show = utils
+        .ctx
+        .shows
+        .create(&CreateShowInput {
+            title: "Test Show".to_string(),
+            ..Default::default()
+        })
+        .await?;
+
+    let episode = utils
+        .ctx
+        .episodes
+        .create(
+            &CreateEpisodeInput {
+                title: "Test Draft".to_string(),
+                show_id: show.id.clone(),
+                ..Default::default()
+            },
+            &false,
+        )
+        .await?;
+
+    Ok((show, episode))
+}
+
+/// Grant the manager role for a Show to a new User, and return a token for them
+async fn create_manager(utils: &TestUtils, show: &Show) -> Result<String> {
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let user = utils.ctx.users.create(&username).await?;
+
+    utils
+        .ctx
+        .role_grants
+        .create(&CreateRoleGrantInput {
+            role_key: "manager".to_string(),
+            user_id: user.id.clone(),
+            resource_table: "shows".to_string(),
+            resource_id: show.id.clone(),
+        })
+        .await?;
+
+    Ok(token)
+}
+
+/***
+ * Query: `getEpisode`
+ */
+const GET_EPISODE: &str = "
+    query GetEpisode($id: ID!) {
+        getEpisode(id: $id) {
+            id
+            status
+        }
+    }
+";
+
+/// It hides draft Episodes from everyone except Show managers
+#[tokio::test]
+#[ignore]
+async fn test_publishing_get_draft() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let (show, episode) = create_show_and_draft(&utils).await?;
+
+    let manager_token = create_manager(&utils, &show).await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    utils.ctx.users.create(&username).await?;
+
+    for (token, visible) in [
+        (None, false),
+        (Some(token.as_str()), false),
+        (Some(manager_token.as_str()), true),
+    ] {
+        let req = utils
+            .graphql
+            .query(GET_EPISODE, json!({ "id": episode.id }), token)?;
+
+        let resp = utils.http_client.request(req).await?;
+        let status = resp.status();
+
+        let body = to_bytes(resp.into_body()).await?;
+        let json: Value = serde_json::from_slice(&body)?;
+
+        assert_eq!(status, 200);
+
+        if visible {
+            assert_eq!(json["data"]["getEpisode"]["id"], episode.id);
+            assert_eq!(json["data"]["getEpisode"]["status"], "DRAFT");
+        } else {
+            assert_eq!(json["data"]["getEpisode"], Value::Null);
+        }
+    }
+
+    Ok(())
+}
+
+/***
+ * Query: `getManyEpisodes`
+ */
+const GET_MANY_EPISODES: &str = "
+    query GetManyEpisodes($where: EpisodeCondition) {
+        getManyEpisodes(where: $where, orderBy: [TITLE_ASC]) {
+            data {
+                id
+            }
+            total
+        }
+    }
+";
+
+/// It only lists draft Episodes for Show managers
+#[tokio::test]
+#[ignore]
+async fn test_publishing_get_many_drafts() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let (show, draft) = create_show_and_draft(&utils).await?;
+
+    let published = utils
+        .ctx
+        .episodes
+        .create(
+            &CreateEpisodeInput {
+                title: "Test Published".to_string(),
+                show_id: show.id.clone(),
+                status: Some(EpisodeStatus::Published),
+                ..Default::default()
+            },
+            &false,
+        )
+        .await?;
+
+    let manager_token = create_manager(&utils, &show).await?;
+
+    for (token, expected) in [
+        (None, vec![published.id.clone()]),
+        (
+            Some(manager_token.as_str()),
+            vec![draft.id.clone(), published.id.clone()],
+        ),
+    ] {
+        let req = utils.graphql.query(
+            GET_MANY_EPISODES,
+            json!({ "where": { "showId": show.id } }),
+            token,
+        )?;
+
+        let resp = utils.http_client.request(req).await?;
+        let status = resp.status();
+
+        let body = to_bytes(resp.into_body()).await?;
+        let json: Value = serde_json::from_slice(&body)?;
+
+        let json_result = &json["data"]["getManyEpisodes"];
+
+        assert_eq!(status, 200);
+        assert_eq!(json_result["total"], expected.len());
+
+        for (index, id) in expected.iter().enumerate() {
+            assert_eq!(&json_result["data"][index]["id"], id);
+        }
+    }
+
+    Ok(())
+}
+
+/***
+ * Mutation: `createEpisode`
+ */
+const CREATE_EPISODE: &str = "
+    mutation CreateEpisode($input: CreateEpisodeInput!) {
+        createEpisode(input: $input) {
+            episode {
+                id
+                status
+                publishAt
+            }
+        }
+    }
+";
+
+/// It requires a publishAt date to schedule an Episode
+#[tokio::test]
+#[ignore]
+async fn test_publishing_schedule_requires_publish_at() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let (show, _) = create_show_and_draft(&utils).await?;
+
+    let manager_token = create_manager(&utils, &show).await?;
+
+    let req = utils.graphql.query(
+        CREATE_EPISODE,
+        json!({
+            "input": {
+                "title": "Test Scheduled",
+                "showId": show.id,
+                "status": "SCHEDULED",
+            }
+        }),
+        Some(&manager_token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(
+        json["errors"][0]["message"],
+        "A publishAt date is required to schedule an Episode"
+    );
+    assert_eq!(json["errors"][0]["extensions"]["code"], 400);
+
+    Ok(())
+}
+
+/// It publishes scheduled Episodes when they are due and notifies subscribers
+#[tokio::test]
+#[ignore]
+async fn test_publishing_scheduled() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let (show, _) = create_show_and_draft(&utils).await?;
+
+    let manager_token = create_manager(&utils, &show).await?;
+
+    let mut ids = vec![];
+
+    for (title, publish_at) in [
+        ("Test Due", Utc::now() - Duration::minutes(5)),
+        ("Test Later", Utc::now() + Duration::days(1)),
+    ] {
+        let req = utils.graphql.query(
+            CREATE_EPISODE,
+            json!({
+                "input": {
+                    "title": title,
+                    "showId": show.id,
+                    "status": "SCHEDULED",
+                    "publishAt": publish_at.naive_utc(),
+                }
+            }),
+            Some(manager_token.as_str()),
+        )?;
+
+        let resp = utils.http_client.request(req).await?;
+
+        let body = to_bytes(resp.into_body()).await?;
+        let json: Value = serde_json::from_slice(&body)?;
+
+        let json_episode = &json["data"]["createEpisode"]["episode"];
+
+        assert_eq!(json_episode["status"], "SCHEDULED");
+
+        ids.push(json_episode["id"].as_str().unwrap().to_string());
+    }
+
+    // Subscribe to the Show, and wait for the Ack to make sure the subscription is in place
+    let url = url::Url::parse(&format!(
+        "ws://localhost:{port}/events",
+        port = utils.addr.port()
+    ))?;
+
+    let (ws_stream, _) = connect_async(url).await?;
+    let (mut write, mut read) = ws_stream.split();
+
+    write
+        .send(Message::Text(serde_json::to_string(
+            &IncomingMessage::Subscribe {
+                show_id: show.id.clone(),
+            },
+        )?))
+        .await?;
+
+    let ack = timeout(Timeout::from_secs(1), read.next()).await?;
+
+    assert_eq!(
+        ack.unwrap()?.into_text()?,
+        serde_json::to_string(&OutgoingEnvelope::from(OutgoingMessage::Ack))?
+    );
+
+    assert!(publish_scheduled(&utils.ctx).await? >= 1);
+
+    let due = utils.ctx.episodes.get(&ids[0], &false).await?.unwrap();
+    let later = utils.ctx.episodes.get(&ids[1], &false).await?.unwrap();
+
+    assert_eq!(due.status, EpisodeStatus::Published);
+    assert_eq!(later.status, EpisodeStatus::Scheduled);
+
+    let notification = timeout(Timeout::from_secs(1), read.next()).await?;
+
+    assert_eq!(
+        notification.unwrap()?.into_text()?,
+        serde_json::to_string(&OutgoingEnvelope::from(OutgoingMessage::EpisodePublished {
+            show_id: show.id.clone(),
+            episode_id: due.id.clone(),
+            title: "Test Due".to_string(),
+        }))?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,171 @@
+use anyhow::Result;
+use fake::{faker::internet::en::FreeEmail, Fake};
+use futures_util::{SinkExt, StreamExt};
+use hyper::body::to_bytes;
+use pretty_assertions::assert_eq;
+use serde_json::{json, Value};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use ulid::Ulid;
+
+use caster_api::events::messages::{IncomingMessage, OutgoingEnvelope, OutgoingMessage};
+use caster_utils::config::{get_config, Config};
+
+#[cfg(test)]
+mod test_utils;
+
+use test_utils::TestUtils;
+
+/// Create a config with small budgets that are easy to exhaust
+fn limited_config() -> &'static Config {
+    let mut config = get_config().clone();
+
+    config.rate_limit.enabled = true;
+    config.rate_limit.queries = 2;
+    config.rate_limit.mutations = 1;
+    config.rate_limit.messages = 1;
+
+    Box::leak(Box::new(config))
+}
+
+const CREATE_SHOW: &str = "
+    mutation CreateShow($input: CreateShowInput!) {
+        createShow(input: $input) {
+            show {
+                id
+            }
+        }
+    }
+";
+
+const GET_SHOW: &str = "
+    query GetShow($id: ID!) {
+        getShow(id: $id) {
+            id
+        }
+    }
+";
+
+/// It rejects mutations once the budget is exhausted
+#[tokio::test]
+#[ignore]
+async fn test_rate_limit_mutations() -> Result<()> {
+    let utils = TestUtils::init_with_config(limited_config()).await?;
+
+    let email: String = FreeEmail().fake();
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let _ = utils.create_user_and_profile(&username, &email).await?;
+
+    let variables = json!({ "input": { "title": "Test Show" } });
+
+    let req = utils
+        .graphql
+        .query(CREATE_SHOW, variables.clone(), Some(&token))?;
+    let resp = utils.http_client.request(req).await?;
+
+    assert_eq!(resp.status(), 200);
+
+    let req = utils.graphql.query(CREATE_SHOW, variables, Some(&token))?;
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+    let retry_after = resp.headers().get("retry-after").cloned();
+
+    let body = to_bytes(resp.into_body()).await?;
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 429);
+    assert!(retry_after.is_some());
+    assert_eq!(json["errors"][0]["message"], "Too Many Requests");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 429);
+    assert!(json["errors"][0]["extensions"]["retryAfter"].as_u64() > Some(0));
+
+    Ok(())
+}
+
+/// It counts queries separately from mutations
+#[tokio::test]
+#[ignore]
+async fn test_rate_limit_separate_budgets() -> Result<()> {
+    let utils = TestUtils::init_with_config(limited_config()).await?;
+
+    let email: String = FreeEmail().fake();
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let _ = utils.create_user_and_profile(&username, &email).await?;
+
+    let req = utils.graphql.query(
+        CREATE_SHOW,
+        json!({ "input": { "title": "Test Show" } }),
+        Some(&token),
+    )?;
+    let resp = utils.http_client.request(req).await?;
+
+    assert_eq!(resp.status(), 200);
+
+    // The query budget is still available after the mutation budget is used up
+    for _ in 0..2 {
+        let req = utils
+            .graphql
+            .query(GET_SHOW, json!({ "id": "dummy-id" }), Some(&token))?;
+        let resp = utils.http_client.request(req).await?;
+
+        assert_eq!(resp.status(), 200);
+    }
+
+    let req = utils
+        .graphql
+        .query(GET_SHOW, json!({ "id": "dummy-id" }), Some(&token))?;
+    let resp = utils.http_client.request(req).await?;
+
+    assert_eq!(resp.status(), 429);
+
+    // Other clients have their own budgets
+    let other_token = utils.create_jwt(&Ulid::new().to_string());
+
+    let req = utils
+        .graphql
+        .query(GET_SHOW, json!({ "id": "dummy-id" }), Some(&other_token))?;
+    let resp = utils.http_client.request(req).await?;
+
+    assert_eq!(resp.status(), 200);
+
+    Ok(())
+}
+
+/// It drops WebSocket messages once the budget is exhausted
+#[tokio::test]
+#[ignore]
+async fn test_rate_limit_messages() -> Result<()> {
+    let utils = TestUtils::init_with_config(limited_config()).await?;
+
+    let url = url::Url::parse(&format!(
+        "ws://localhost:{port}/events",
+        port = utils.addr.port()
+    ))?;
+
+    let (ws_stream, _) = connect_async(url).await?;
+    let (mut write, mut read) = ws_stream.split();
+
+    let ping = serde_json::to_string(&IncomingMessage::Ping)?;
+
+    // Malformed requests count against the budget too
+    write.send(Message::Text("not json".to_string())).await?;
+    write.send(Message::Text(ping)).await?;
+
+    let first = read.next().await.expect("Missing first message")?;
+    let second = read.next().await.expect("Missing second message")?;
+
+    let first: OutgoingEnvelope = serde_json::from_str(first.to_text()?)?;
+    let second: OutgoingEnvelope = serde_json::from_str(second.to_text()?)?;
+
+    assert!(matches!(first.message, OutgoingMessage::Error { .. }));
+    assert!(matches!(
+        second.message,
+        OutgoingMessage::RateLimited { .. }
+    ));
+    assert_eq!(second.id, None);
+
+    Ok(())
+}
@@ -293,6 +293,140 @@ async fn test_show_get_many() -> Result<()> {
     Ok(())
 }
 
+/// It filters shows by the current user's permissions
+#[tokio::test]
+#[ignore]
+async fn test_show_get_many_permission() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let user = utils.ctx.users.create(&username).await?;
+
+    let mut shows = vec![];
+
+    for (title, role_key) in [
+        ("Managed Show", Some("manager")),
+        ("Guest Show", Some("guest")),
+        ("Other Show", None),
+        ("Admin Show", Some("admin")),
+    ] {
+        let show = utils
+            .ctx
+            .shows
+            .create(&CreateShowInput {
+                title: title.to_string(),
+                ..Default::default()
+            })
+            .await?;
+
+        if let Some(role_key) = role_key {
+            utils
+                .ctx
+                .role_grants
+                .create(&CreateRoleGrantInput {
+                    role_key: role_key.to_string(),
+                    user_id: user.id.clone(),
+                    resource_table: "shows".to_string(),
+                    resource_id: show.id.clone(),
+                })
+                .await?;
+        }
+
+        shows.push(show);
+    }
+
+    let req = utils.graphql.query(
+        GET_MANY_SHOWS,
+        json!({
+            "where": {
+                "idsIn": shows.iter().map(|show| show.id.clone()).collect::<Vec<_>>(),
+                "permission": "update",
+            },
+            "orderBy": ["TITLE_DESC"],
+        }),
+        Some(&token),
+    )?;
+    let resp = utils.http_client.request(req).await?;
+
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+    let json: Value = serde_json::from_slice(&body)?;
+
+    let json_result = &json["data"]["getManyShows"];
+
+    assert_eq!(status, 200);
+    assert_eq!(json_result["total"], 2);
+    assert_eq!(json_result["data"][0]["id"], shows[0].id);
+    assert_eq!(json_result["data"][1]["id"], shows[3].id);
+
+    Ok(())
+}
+
+/// It doesn't filter shows by permission for site admins
+#[tokio::test]
+#[ignore]
+async fn test_show_get_many_permission_site_admin() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let _ = utils.create_site_admin(&username).await?;
+
+    let (show, _) = utils
+        .create_show_and_episode("Test Show", "Test Episode")
+        .await?;
+
+    let req = utils.graphql.query(
+        GET_MANY_SHOWS,
+        json!({
+            "where": {
+                "idsIn": vec![show.id.clone()],
+                "permission": "delete",
+            },
+        }),
+        Some(&token),
+    )?;
+    let resp = utils.http_client.request(req).await?;
+
+    let body = to_bytes(resp.into_body()).await?;
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(json["data"]["getManyShows"]["total"], 1);
+    assert_eq!(json["data"]["getManyShows"]["data"][0]["id"], show.id);
+
+    Ok(())
+}
+
+/// It requires authentication to filter by permission
+#[tokio::test]
+#[ignore]
+async fn test_show_get_many_permission_requires_authn() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let req = utils.graphql.query(
+        GET_MANY_SHOWS,
+        json!({
+            "where": {
+                "permission": "update",
+            },
+        }),
+        None,
+    )?;
+    let resp = utils.http_client.request(req).await?;
+
+    let body = to_bytes(resp.into_body()).await?;
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(json["errors"][0]["message"], "Unauthorized");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 401);
+
+    Ok(())
+}
+
 /***
  * Mutation: `updateShow`
  */
@@ -368,6 +502,92 @@ async fn test_show_update_simple() -> Result<()> {
     Ok(())
 }
 
+/// It rejects an update when the show has changed since it was retrieved
+#[tokio::test]
+#[ignore]
+async fn test_show_update_conflict() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    // Create a User
+    let user = utils.ctx.users.create(&username).await?;
+
+    let mut show_input: CreateShowInput = Faker.fake();
+    show_input.title = "Test Show".to_string();
+
+    let show = utils.ctx.shows.create(&show_input).await?;
+
+    // Grant the admin role to this User for this Show
+    utils
+        .ctx
+        .role_grants
+        .create(&CreateRoleGrantInput {
+            role_key: "admin".to_string(),
+            user_id: user.id.clone(),
+            resource_table: "shows".to_string(),
+            resource_id: show.id.clone(),
+        })
+        .await?;
+
+    let expected_updated_at = show.updated_at.format("%Y-%m-%dT%H:%M:%S%.f").to_string();
+
+    // The first editor's update succeeds
+    let req = utils.graphql.query(
+        UPDATE_SHOW,
+        json!({
+            "id": show.id,
+            "input": {
+                "summary": "First edit",
+                "expectedUpdatedAt": expected_updated_at,
+            }
+        }),
+        Some(&token),
+    )?;
+    let resp = utils.http_client.request(req).await?;
+
+    let body = to_bytes(resp.into_body()).await?;
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(json["data"]["updateShow"]["show"]["summary"], "First edit");
+
+    // The second editor is working from the same stale copy
+    let req = utils.graphql.query(
+        UPDATE_SHOW,
+        json!({
+            "id": show.id,
+            "input": {
+                "summary": "Second edit",
+                "expectedUpdatedAt": expected_updated_at,
+            }
+        }),
+        Some(&token),
+    )?;
+    let resp = utils.http_client.request(req).await?;
+
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(json["errors"][0]["message"], "Conflict");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 409);
+    assert_eq!(json["errors"][0]["extensions"]["current"]["id"], show.id);
+    assert_eq!(
+        json["errors"][0]["extensions"]["current"]["summary"],
+        "First edit"
+    );
+
+    // Fields hidden from the schema aren't exposed in the conflict either
+    assert!(json["errors"][0]["extensions"]["current"]
+        .get("deletedAt")
+        .is_none());
+
+    Ok(())
+}
+
 /// It returns an error if no existing show is found
 #[tokio::test]
 #[ignore]
@@ -484,6 +704,50 @@ async fn test_show_update_requires_authz() -> Result<()> {
     Ok(())
 }
 
+/// It allows site admins to update any Show
+#[tokio::test]
+#[ignore]
+async fn test_show_update_site_admin() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    // Create a site admin with no roles for the Show
+    let _ = utils.create_site_admin(&username).await?;
+
+    let mut show_input: CreateShowInput = Faker.fake();
+    show_input.title = "Test Show".to_string();
+
+    let show = utils.ctx.shows.create(&show_input).await?;
+
+    let req = utils.graphql.query(
+        UPDATE_SHOW,
+        json!({
+            "id": show.id,
+            "input": {
+                "summary": "Something else"
+            }
+        }),
+        Some(&token),
+    )?;
+    let resp = utils.http_client.request(req).await?;
+
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    let json_show = &json["data"]["updateShow"]["show"];
+
+    assert_eq!(status, 200);
+    assert_eq!(json_show["id"], show.id);
+    assert_eq!(json_show["summary"], "Something else");
+
+    Ok(())
+}
+
 /***
  * Mutation: `deleteShow`
  */
@@ -537,6 +801,10 @@ async fn test_show_delete_simple() -> Result<()> {
     assert_eq!(status, 200);
     assert!(json["data"]["deleteShow"].as_bool().unwrap());
 
+    // The Show is hidden, but kept around until it is purged
+    assert_eq!(utils.ctx.shows.get(&show.id).await?, None);
+    assert!(utils.ctx.shows.get_deleted(&show.id).await?.is_some());
+
     Ok(())
 }
 
@@ -630,3 +898,140 @@ async fn test_show_delete_requires_authz() -> Result<()> {
 
     Ok(())
 }
+
+/// It allows site admins to delete any Show
+#[tokio::test]
+#[ignore]
+async fn test_show_delete_site_admin() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    // Create a site admin with no roles for the Show
+    let _ = utils.create_site_admin(&username).await?;
+
+    let mut show_input: CreateShowInput = Faker.fake();
+    show_input.title = "Test Show".to_string();
+
+    let show = utils.ctx.shows.create(&show_input).await?;
+
+    let req = utils
+        .graphql
+        .query(DELETE_SHOW, json!({ "id": show.id }), Some(&token))?;
+    let resp = utils.http_client.request(req).await?;
+
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert!(json["data"]["deleteShow"].as_bool().unwrap());
+
+    Ok(())
+}
+
+/***
+ * Mutation: `restoreShow`
+ */
+
+const RESTORE_SHOW: &str = "
+    mutation RestoreShow($id: ID!) {
+        restoreShow(id: $id) {
+            show {
+                id
+                title
+            }
+        }
+    }
+";
+
+/// It restores a deleted show
+#[tokio::test]
+#[ignore]
+async fn test_show_restore_simple() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    // Create a User
+    let user = utils.ctx.users.create(&username).await?;
+
+    let mut show_input: CreateShowInput = Faker.fake();
+    show_input.title = "Test Show".to_string();
+
+    let show = utils.ctx.shows.create(&show_input).await?;
+
+    // Grant the admin role to this User for this Show
+    utils
+        .ctx
+        .role_grants
+        .create(&CreateRoleGrantInput {
+            role_key: "admin".to_string(),
+            user_id: user.id.clone(),
+            resource_table: "shows".to_string(),
+            resource_id: show.id.clone(),
+        })
+        .await?;
+
+    utils.ctx.shows.delete(&show.id).await?;
+
+    let req = utils
+        .graphql
+        .query(RESTORE_SHOW, json!({"id": show.id}), Some(&token))?;
+    let resp = utils.http_client.request(req).await?;
+
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(json["data"]["restoreShow"]["show"]["id"], show.id);
+    assert_eq!(json["data"]["restoreShow"]["show"]["title"], "Test Show");
+
+    assert!(utils.ctx.shows.get(&show.id).await?.is_some());
+
+    Ok(())
+}
+
+/// It requires authorization
+#[tokio::test]
+#[ignore]
+async fn test_show_restore_requires_authz() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    // Create a User
+    let _ = utils.ctx.users.create(&username).await?;
+
+    let mut show_input: CreateShowInput = Faker.fake();
+    show_input.title = "Test Show".to_string();
+
+    let show = utils.ctx.shows.create(&show_input).await?;
+
+    utils.ctx.shows.delete(&show.id).await?;
+
+    let req = utils
+        .graphql
+        .query(RESTORE_SHOW, json!({"id": show.id}), Some(&token))?;
+    let resp = utils.http_client.request(req).await?;
+
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(json["errors"][0]["message"], "Forbidden");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 403);
+
+    Ok(())
+}
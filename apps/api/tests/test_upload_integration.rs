@@ -0,0 +1,530 @@
+use anyhow::Result;
+use async_graphql::MaybeUndefined;
+use fake::{faker::internet::en::FreeEmail, Fake};
+use hyper::{body::to_bytes, Body, Request};
+use image::{DynamicImage, GenericImageView, ImageOutputFormat, RgbaImage};
+use pretty_assertions::assert_eq;
+use serde_json::{json, Value};
+use std::{io::Cursor, path::Path};
+use tempfile::{tempdir, TempDir};
+use ulid::Ulid;
+
+use caster_domains::{
+    episodes::model::Episode,
+    role_grants::model::CreateRoleGrantInput,
+    shows::{model::Show, mutations::UpdateShowInput},
+};
+use caster_testing::graphql::Upload;
+use caster_utils::config::{get_config, Config};
+
+#[cfg(test)]
+mod test_utils;
+
+use test_utils::TestUtils;
+
+/// Store uploads in a temporary directory, with an optional size limit
+fn upload_config(dir: &Path, max_size: Option<usize>) -> &'static Config {
+    let mut config = get_config().clone();
+
+    config.storage.path = dir.to_string_lossy().to_string();
+    config.storage.base_url = "http://localhost:3000/uploads".to_string();
+
+    if let Some(max_size) = max_size {
+        config.storage.max_size = max_size;
+    }
+
+    Box::leak(Box::new(config))
+}
+
+/// Initialize the test utils with a fresh upload directory
+async fn init(max_size: Option<usize>) -> Result<(TestUtils, TempDir)> {
+    let dir = tempdir()?;
+    let utils = TestUtils::init_with_config(upload_config(dir.path(), max_size)).await?;
+
+    Ok((utils, dir))
+}
+
+/// Encode a blank PNG of the given size
+fn png(width: u32, height: u32) -> Vec<u8> {
+    let mut data = Cursor::new(Vec::new());
+
+    DynamicImage::ImageRgba8(RgbaImage::new(width, height))
+        .write_to(&mut data, ImageOutputFormat::Png)
+        .expect("Unable to encode image");
+
+    data.into_inner()
+}
+
+/// Create a Show and Episode with the admin role granted to a new User, and return a token for
+/// them
+async fn create_show_with_admin(utils: &TestUtils) -> Result<(Show, Episode, String)> {
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let user = utils.ctx.users.create(&username).await?;
+
+    let (show, episode) = utils
+        .create_show_and_episode("Test Show", "Test Episode")
+        .await?;
+
+    utils
+        .ctx
+        .role_grants
+        .create(&CreateRoleGrantInput {
+            role_key: "admin".to_string(),
+            user_id: user.id.clone(),
+            resource_table: "shows".to_string(),
+            resource_id: show.id.clone(),
+        })
+        .await?;
+
+    Ok((show, episode, token))
+}
+
+/// Upload a file as the `picture` variable and return the response status and JSON body
+async fn upload(
+    utils: &TestUtils,
+    query: &str,
+    id: &str,
+    filename: &str,
+    data: &[u8],
+    token: Option<&str>,
+) -> Result<(u16, Value)> {
+    let req = utils.graphql.upload(
+        query,
+        json!({ "id": id }),
+        Upload {
+            variable: "picture",
+            filename,
+            content_type: "image/png",
+            data,
+        },
+        token,
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status().as_u16();
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    Ok((status, serde_json::from_slice(&body)?))
+}
+
+/// Find the path of a stored file based on its url
+fn stored_path(dir: &TempDir, url: &str) -> std::path::PathBuf {
+    dir.path()
+        .join(url.trim_start_matches("http://localhost:3000/uploads/"))
+}
+
+/***
+ * Mutation: `uploadShowPicture`
+ */
+const UPLOAD_SHOW_PICTURE: &str = "
+    mutation UploadShowPicture($id: ID!, $picture: Upload!) {
+        uploadShowPicture(id: $id, picture: $picture) {
+            show {
+                id
+                picture
+                pictureThumbnail
+            }
+        }
+    }
+";
+
+/// It stores an uploaded picture and its thumbnail, and saves the url on the Show
+#[tokio::test]
+#[ignore]
+async fn test_upload_show_picture() -> Result<()> {
+    let (utils, dir) = init(None).await?;
+
+    let (show, _, token) = create_show_with_admin(&utils).await?;
+
+    let data = png(800, 400);
+
+    let (status, json) = upload(
+        &utils,
+        UPLOAD_SHOW_PICTURE,
+        &show.id,
+        "cover.png",
+        &data,
+        Some(&token),
+    )
+    .await?;
+
+    let json_show = &json["data"]["uploadShowPicture"]["show"];
+    let url = json_show["picture"].as_str().expect("Missing picture");
+    let thumbnail_url = json_show["pictureThumbnail"]
+        .as_str()
+        .expect("Missing pictureThumbnail");
+
+    assert_eq!(status, 200);
+    assert_eq!(json_show["id"], show.id);
+    assert!(url.starts_with(&format!("http://localhost:3000/uploads/shows/{}/", show.id)));
+    assert!(url.ends_with(".png"));
+    assert_eq!(thumbnail_url, url.replace(".png", "-thumbnail.png"));
+
+    // The original is stored as-is, next to a thumbnail
+    assert_eq!(std::fs::read(stored_path(&dir, url))?, data);
+
+    let thumbnail = std::fs::read(stored_path(&dir, thumbnail_url))?;
+    let thumbnail = image::load_from_memory(&thumbnail)?;
+
+    assert_eq!(thumbnail.dimensions(), (256, 128));
+
+    // Both urls are saved on the Show
+    let updated = utils.ctx.shows.get(&show.id).await?.unwrap();
+
+    assert_eq!(updated.picture.as_deref(), Some(url));
+    assert_eq!(updated.picture_thumbnail.as_deref(), Some(thumbnail_url));
+
+    // The file is served by the API
+    let path = url.trim_start_matches("http://localhost:3000");
+    let resp = utils
+        .http_client
+        .request(
+            Request::get(format!(
+                "http://localhost:{port}{path}",
+                port = utils.addr.port()
+            ))
+            .body(Body::empty())?,
+        )
+        .await?;
+
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers()["content-type"], "image/png");
+    assert_eq!(to_bytes(resp.into_body()).await?.to_vec(), data);
+
+    Ok(())
+}
+
+/// It removes the files for the previous picture when a new one is uploaded
+#[tokio::test]
+#[ignore]
+async fn test_upload_show_picture_replaced() -> Result<()> {
+    let (utils, dir) = init(None).await?;
+
+    let (show, _, token) = create_show_with_admin(&utils).await?;
+
+    let (_, json) = upload(
+        &utils,
+        UPLOAD_SHOW_PICTURE,
+        &show.id,
+        "cover.png",
+        &png(100, 100),
+        Some(&token),
+    )
+    .await?;
+
+    let previous = &json["data"]["uploadShowPicture"]["show"];
+    let previous_url = previous["picture"].as_str().expect("Missing picture");
+    let previous_thumbnail = previous["pictureThumbnail"]
+        .as_str()
+        .expect("Missing pictureThumbnail");
+
+    let (status, json) = upload(
+        &utils,
+        UPLOAD_SHOW_PICTURE,
+        &show.id,
+        "cover.png",
+        &png(200, 200),
+        Some(&token),
+    )
+    .await?;
+
+    let json_show = &json["data"]["uploadShowPicture"]["show"];
+    let url = json_show["picture"].as_str().expect("Missing picture");
+    let thumbnail_url = json_show["pictureThumbnail"]
+        .as_str()
+        .expect("Missing pictureThumbnail");
+
+    assert_eq!(status, 200);
+    assert_ne!(url, previous_url);
+
+    // Only the new picture is left behind
+    assert!(stored_path(&dir, url).exists());
+    assert!(stored_path(&dir, thumbnail_url).exists());
+    assert!(!stored_path(&dir, previous_url).exists());
+    assert!(!stored_path(&dir, previous_thumbnail).exists());
+
+    Ok(())
+}
+
+/// It leaves another Show's files alone when the replaced picture url points at them
+#[tokio::test]
+#[ignore]
+async fn test_upload_show_picture_replaced_foreign() -> Result<()> {
+    let (utils, dir) = init(None).await?;
+
+    let (other, _, other_token) = create_show_with_admin(&utils).await?;
+    let (show, _, token) = create_show_with_admin(&utils).await?;
+
+    let (_, json) = upload(
+        &utils,
+        UPLOAD_SHOW_PICTURE,
+        &other.id,
+        "cover.png",
+        &png(100, 100),
+        Some(&other_token),
+    )
+    .await?;
+
+    let other_url = json["data"]["uploadShowPicture"]["show"]["picture"]
+        .as_str()
+        .expect("Missing picture");
+
+    // Point this Show's picture at the other Show's file, as a client could with updateShow
+    utils
+        .ctx
+        .shows
+        .update(
+            &show.id,
+            &UpdateShowInput {
+                picture: MaybeUndefined::Value(other_url.to_string()),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    let (status, _) = upload(
+        &utils,
+        UPLOAD_SHOW_PICTURE,
+        &show.id,
+        "cover.png",
+        &png(200, 200),
+        Some(&token),
+    )
+    .await?;
+
+    assert_eq!(status, 200);
+
+    // The other Show's picture is still there
+    assert!(stored_path(&dir, other_url).exists());
+
+    Ok(())
+}
+
+/// It rejects files that aren't images, regardless of the reported content type
+#[tokio::test]
+#[ignore]
+async fn test_upload_show_picture_unsupported() -> Result<()> {
+    let (utils, dir) = init(None).await?;
+
+    let (show, _, token) = create_show_with_admin(&utils).await?;
+
+    let (status, json) = upload(
+        &utils,
+        UPLOAD_SHOW_PICTURE,
+        &show.id,
+        "cover.png",
+        b"<html>Not an image</html>",
+        Some(&token),
+    )
+    .await?;
+
+    assert_eq!(status, 200);
+    assert_eq!(
+        json["errors"][0]["message"],
+        "Picture must be a JPEG, PNG, GIF, or WebP image"
+    );
+    assert_eq!(json["errors"][0]["extensions"]["code"], 415);
+
+    // Nothing is stored, and the Show is unchanged
+    assert!(!dir.path().join("shows").exists());
+    assert_eq!(
+        utils.ctx.shows.get(&show.id).await?.unwrap().picture,
+        show.picture
+    );
+
+    Ok(())
+}
+
+/// It rejects files that are larger than the limit
+#[tokio::test]
+#[ignore]
+async fn test_upload_show_picture_too_large() -> Result<()> {
+    let data = png(800, 400);
+
+    let (utils, dir) = init(Some(data.len() - 1)).await?;
+
+    let (show, _, token) = create_show_with_admin(&utils).await?;
+
+    let (status, json) = upload(
+        &utils,
+        UPLOAD_SHOW_PICTURE,
+        &show.id,
+        "cover.png",
+        &data,
+        Some(&token),
+    )
+    .await?;
+
+    assert_eq!(status, 200);
+    assert_eq!(json["errors"][0]["message"], "Picture is too large");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 413);
+    assert!(!dir.path().join("shows").exists());
+
+    Ok(())
+}
+
+/// It requires authentication
+#[tokio::test]
+#[ignore]
+async fn test_upload_show_picture_authn() -> Result<()> {
+    let (utils, _dir) = init(None).await?;
+
+    let (show, _, _) = create_show_with_admin(&utils).await?;
+
+    let (status, json) = upload(
+        &utils,
+        UPLOAD_SHOW_PICTURE,
+        &show.id,
+        "cover.png",
+        &png(10, 10),
+        None,
+    )
+    .await?;
+
+    assert_eq!(status, 200);
+    assert_eq!(json["errors"][0]["message"], "Unauthorized");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 401);
+
+    Ok(())
+}
+
+/// It requires authorization
+#[tokio::test]
+#[ignore]
+async fn test_upload_show_picture_authz() -> Result<()> {
+    let (utils, dir) = init(None).await?;
+
+    let (show, _, _) = create_show_with_admin(&utils).await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    utils.ctx.users.create(&username).await?;
+
+    let (status, json) = upload(
+        &utils,
+        UPLOAD_SHOW_PICTURE,
+        &show.id,
+        "cover.png",
+        &png(10, 10),
+        Some(&token),
+    )
+    .await?;
+
+    assert_eq!(status, 200);
+    assert_eq!(json["errors"][0]["message"], "Forbidden");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 403);
+    assert!(!dir.path().join("shows").exists());
+
+    Ok(())
+}
+
+/***
+ * Mutation: `uploadEpisodePicture`
+ */
+const UPLOAD_EPISODE_PICTURE: &str = "
+    mutation UploadEpisodePicture($id: ID!, $picture: Upload!) {
+        uploadEpisodePicture(id: $id, picture: $picture) {
+            episode {
+                id
+                picture
+                pictureThumbnail
+            }
+        }
+    }
+";
+
+/// It stores an uploaded picture for an Episode
+#[tokio::test]
+#[ignore]
+async fn test_upload_episode_picture() -> Result<()> {
+    let (utils, dir) = init(None).await?;
+
+    let (_, episode, token) = create_show_with_admin(&utils).await?;
+
+    let (status, json) = upload(
+        &utils,
+        UPLOAD_EPISODE_PICTURE,
+        &episode.id,
+        "episode.png",
+        &png(100, 100),
+        Some(&token),
+    )
+    .await?;
+
+    let json_episode = &json["data"]["uploadEpisodePicture"]["episode"];
+    let url = json_episode["picture"].as_str().expect("Missing picture");
+
+    assert_eq!(status, 200);
+    assert_eq!(json_episode["id"], episode.id);
+    assert!(url.starts_with(&format!(
+        "http://localhost:3000/uploads/episodes/{}/",
+        episode.id
+    )));
+    assert!(stored_path(&dir, url).exists());
+    assert_eq!(
+        json_episode["pictureThumbnail"],
+        url.replace(".png", "-thumbnail.png")
+    );
+    assert!(stored_path(&dir, &url.replace(".png", "-thumbnail.png")).exists());
+
+    Ok(())
+}
+
+/***
+ * Mutation: `uploadProfilePicture`
+ */
+const UPLOAD_PROFILE_PICTURE: &str = "
+    mutation UploadProfilePicture($id: ID!, $picture: Upload!) {
+        uploadProfilePicture(id: $id, picture: $picture) {
+            profile {
+                id
+                picture
+                pictureThumbnail
+            }
+        }
+    }
+";
+
+/// It stores an uploaded picture for the User's own Profile
+#[tokio::test]
+#[ignore]
+async fn test_upload_profile_picture() -> Result<()> {
+    let (utils, dir) = init(None).await?;
+
+    let email: String = FreeEmail().fake();
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let (_, profile) = utils.create_user_and_profile(&username, &email).await?;
+
+    let (status, json) = upload(
+        &utils,
+        UPLOAD_PROFILE_PICTURE,
+        &profile.id,
+        "avatar.png",
+        &png(64, 64),
+        Some(&token),
+    )
+    .await?;
+
+    let json_profile = &json["data"]["uploadProfilePicture"]["profile"];
+    let url = json_profile["picture"].as_str().expect("Missing picture");
+
+    assert_eq!(status, 200);
+    assert_eq!(json_profile["id"], profile.id);
+    assert!(url.starts_with(&format!(
+        "http://localhost:3000/uploads/profiles/{}/",
+        profile.id
+    )));
+    assert!(stored_path(&dir, url).exists());
+    assert_eq!(
+        json_profile["pictureThumbnail"],
+        url.replace(".png", "-thumbnail.png")
+    );
+
+    Ok(())
+}
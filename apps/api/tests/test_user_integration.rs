@@ -4,7 +4,10 @@ use hyper::body::to_bytes;
 use pretty_assertions::assert_eq;
 use serde_json::{json, Value};
 
-use caster_domains::role_grants::model::CreateRoleGrantInput;
+use caster_domains::{
+    role_grants::model::CreateRoleGrantInput,
+    users::{model::SITE_ADMIN_ROLE, mutations::UpdateUserInput},
+};
 
 #[cfg(test)]
 mod test_utils;
@@ -240,7 +243,7 @@ async fn test_user_get_or_create_current_requires_authn() -> Result<()> {
  * Query: `updateCurrentUser`
  */
 const UPDATE_CURRENT_USER: &str = "
-    mutation UpdateCurrentUser($input: UpdateUserInput!) {
+    mutation UpdateCurrentUser($input: UpdateCurrentUserInput!) {
         updateCurrentUser(input: $input) {
             user {
                 id
@@ -262,10 +265,11 @@ async fn test_user_update_current() -> Result<()> {
     let utils = TestUtils::init().await?;
 
     let username = Ulid::new().to_string();
+    let email: String = FreeEmail().fake();
     let token = utils.create_jwt(&username);
 
-    // Create a user with this username
-    let user = utils.ctx.users.get_or_create(&username).await?;
+    // Create a user with this username and a Profile
+    let (user, profile) = utils.create_user_and_profile(&username, &email).await?;
 
     // Create a sample RoleGrant to test the relation
     let role_grant = utils
@@ -282,7 +286,7 @@ async fn test_user_update_current() -> Result<()> {
     let req = utils.graphql.query(
         UPDATE_CURRENT_USER,
         json!({ "input": {
-           "isActive": false
+           "profile": { "displayName": "Updated Name" }
         }}),
         Some(&token),
     )?;
@@ -298,9 +302,114 @@ async fn test_user_update_current() -> Result<()> {
 
     assert_eq!(status, 200);
     assert_eq!(json_user["username"], username);
-    assert!(!json_user["isActive"].as_bool().unwrap());
+    assert!(json_user["isActive"].as_bool().unwrap());
     assert_eq!(json_roles[0]["roleKey"], role_grant.role_key);
 
+    let updated = utils.ctx.profiles.get(&profile.id, &false).await?.unwrap();
+
+    assert_eq!(updated.display_name, Some("Updated Name".to_string()));
+    assert_eq!(updated.email, Some(email));
+
+    Ok(())
+}
+
+/// It rejects a Profile update when the Profile has changed since it was retrieved
+#[tokio::test]
+#[ignore]
+async fn test_user_update_current_conflict() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let email: String = FreeEmail().fake();
+    let token = utils.create_jwt(&username);
+
+    let (_, profile) = utils.create_user_and_profile(&username, &email).await?;
+
+    let expected_updated_at = profile
+        .updated_at
+        .format("%Y-%m-%dT%H:%M:%S%.f")
+        .to_string();
+
+    // The first edit succeeds
+    let req = utils.graphql.query(
+        UPDATE_CURRENT_USER,
+        json!({ "input": {
+           "profile": { "displayName": "First Edit", "expectedUpdatedAt": expected_updated_at }
+        }}),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let body = to_bytes(resp.into_body()).await?;
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(json["errors"], Value::Null);
+
+    // The second edit is working from the same stale copy
+    let req = utils.graphql.query(
+        UPDATE_CURRENT_USER,
+        json!({ "input": {
+           "profile": { "displayName": "Second Edit", "expectedUpdatedAt": expected_updated_at }
+        }}),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(json["errors"][0]["message"], "Conflict");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 409);
+    assert_eq!(
+        json["errors"][0]["extensions"]["current"]["displayName"],
+        "First Edit"
+    );
+
+    let updated = utils.ctx.profiles.get(&profile.id, &false).await?.unwrap();
+
+    assert_eq!(updated.display_name, Some("First Edit".to_string()));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_user_update_current_rejects_account_fields() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let user = utils.ctx.users.create(&username).await?;
+
+    let req = utils.graphql.query(
+        UPDATE_CURRENT_USER,
+        json!({ "input": {
+           "isActive": false
+        }}),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(json["data"], Value::Null);
+    assert!(json["errors"][0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("isActive"));
+
+    let existing = utils.ctx.users.get(&user.id).await?.unwrap();
+
+    assert!(existing.is_active);
+
     Ok(())
 }
 
@@ -316,7 +425,7 @@ async fn test_user_update_current_requires_authn() -> Result<()> {
     let req = graphql.query(
         UPDATE_CURRENT_USER,
         json!({ "input": {
-           "isActive": false
+           "profile": { "displayName": "Updated Name" }
         }}),
         None,
     )?;
@@ -345,7 +454,7 @@ async fn test_user_update_current_requires_user() -> Result<()> {
     let req = utils.graphql.query(
         UPDATE_CURRENT_USER,
         json!({ "input": {
-           "isActive": false
+           "profile": { "displayName": "Updated Name" }
         }}),
         Some(&token),
     )?;
@@ -362,3 +471,199 @@ async fn test_user_update_current_requires_user() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+#[ignore]
+async fn test_user_inactive_is_unauthenticated() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    // Create a deactivated user with this username
+    let user = utils.ctx.users.create(&username).await?;
+
+    utils
+        .ctx
+        .users
+        .update(
+            &user.id,
+            &UpdateUserInput {
+                is_active: Some(false),
+                ..Default::default()
+            },
+            &false,
+        )
+        .await?;
+
+    let req = utils
+        .graphql
+        .query(GET_CURRENT_USER, Value::Null, Some(&token))?;
+
+    let resp = utils.http_client.request(req).await?;
+    let body = to_bytes(resp.into_body()).await?;
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(json["data"]["getCurrentUser"], Value::Null);
+
+    let req = utils.graphql.query(
+        GET_OR_CREATE_CURRENT_USER,
+        json!({ "input": {}}),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let body = to_bytes(resp.into_body()).await?;
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(json["errors"][0]["message"], "Unauthorized");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 401);
+
+    Ok(())
+}
+
+/***
+ * Mutation: `setUserActive`
+ */
+const SET_USER_ACTIVE: &str = "
+    mutation SetUserActive($id: String!, $isActive: Boolean!) {
+        setUserActive(id: $id, isActive: $isActive) {
+            user {
+                id
+                username
+                isActive
+            }
+        }
+    }
+";
+
+#[tokio::test]
+#[ignore]
+async fn test_user_set_active_site_admin() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    // Create a site admin with this username
+    let _ = utils.create_site_admin(&username).await?;
+
+    let user = utils.ctx.users.create(&Ulid::new().to_string()).await?;
+
+    let req = utils.graphql.query(
+        SET_USER_ACTIVE,
+        json!({ "id": user.id, "isActive": false }),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+    let json: Value = serde_json::from_slice(&body)?;
+
+    let json_user = &json["data"]["setUserActive"]["user"];
+
+    assert_eq!(status, 200);
+    assert_eq!(json_user["id"], user.id);
+    assert!(!json_user["isActive"].as_bool().unwrap());
+
+    let updated = utils.ctx.users.get(&user.id).await?.unwrap();
+
+    assert!(!updated.is_active);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_user_set_active_requires_site_admin() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    // Create a regular user with this username, with a role for another resource
+    let user = utils.ctx.users.create(&username).await?;
+
+    utils
+        .ctx
+        .role_grants
+        .create(&CreateRoleGrantInput {
+            user_id: user.id.clone(),
+            role_key: SITE_ADMIN_ROLE.to_string(),
+            resource_table: "users".to_string(),
+            resource_id: user.id.clone(),
+        })
+        .await?;
+
+    let other = utils.ctx.users.create(&Ulid::new().to_string()).await?;
+
+    let req = utils.graphql.query(
+        SET_USER_ACTIVE,
+        json!({ "id": other.id, "isActive": false }),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(json["errors"][0]["message"], "Forbidden");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 403);
+
+    let existing = utils.ctx.users.get(&other.id).await?.unwrap();
+
+    assert!(existing.is_active);
+
+    Ok(())
+}
+
+/***
+ * Mutation: `renameUser`
+ */
+const RENAME_USER: &str = "
+    mutation RenameUser($id: String!, $username: String!) {
+        renameUser(id: $id, username: $username) {
+            user {
+                id
+                username
+            }
+        }
+    }
+";
+
+#[tokio::test]
+#[ignore]
+async fn test_user_rename_site_admin() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    // Create a site admin with this username
+    let _ = utils.create_site_admin(&username).await?;
+
+    let user = utils.ctx.users.create(&Ulid::new().to_string()).await?;
+    let renamed = Ulid::new().to_string();
+
+    let req = utils.graphql.query(
+        RENAME_USER,
+        json!({ "id": user.id, "username": renamed }),
+        Some(&token),
+    )?;
+
+    let resp = utils.http_client.request(req).await?;
+    let status = resp.status();
+
+    let body = to_bytes(resp.into_body()).await?;
+    let json: Value = serde_json::from_slice(&body)?;
+
+    assert_eq!(status, 200);
+    assert_eq!(json["data"]["renameUser"]["user"]["username"], renamed);
+
+    Ok(())
+}
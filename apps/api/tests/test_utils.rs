@@ -21,13 +21,20 @@ use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, Web
 
 use caster_api::{run, Context};
 use caster_domains::{
-    episodes::{model::Episode, mutations::CreateEpisodeInput},
+    episodes::{
+        model::{Episode, EpisodeStatus},
+        mutations::CreateEpisodeInput,
+    },
     profiles::{model::Profile, mutations::CreateProfileInput},
+    role_grants::model::CreateRoleGrantInput,
     shows::{model::Show, mutations::CreateShowInput},
-    users::model::User,
+    users::model::{User, ALL_USERS, SITE_ADMIN_ROLE},
 };
 use caster_testing::graphql::GraphQL;
-use caster_utils::{config::get_config, http::http_client};
+use caster_utils::{
+    config::{get_config, Config},
+    http::http_client,
+};
 
 static HTTP_CLIENT: Lazy<Client<HttpsConnector<HttpConnector>>> = Lazy::new(http_client);
 
@@ -56,13 +63,16 @@ pub struct TestUtils {
 impl TestUtils {
     /// Initialize a new set of utils
     pub async fn init() -> Result<Self> {
+        Self::init_with_config(get_config()).await
+    }
+
+    /// Initialize a new set of utils with a customized config
+    pub async fn init_with_config(config: &'static Config) -> Result<Self> {
         tracing_subscriber::fmt()
             .with_max_level(tracing::Level::INFO)
             .with_test_writer()
             .init();
 
-        let config = get_config();
-
         // This needs to be created anew each time because the database connection can't be shared
         // when the Tokio runtime is being stopped and re-started between tests
         let ctx = Arc::new(Context::init(config).await?);
@@ -130,6 +140,24 @@ impl TestUtils {
         Ok((user, profile))
     }
 
+    /// Create a User with the site admin role
+    #[allow(dead_code)] // Since each test is an independent module, this is necessary
+    pub async fn create_site_admin(&self, username: &str) -> Result<User> {
+        let user = self.ctx.users.create(username).await?;
+
+        self.ctx
+            .role_grants
+            .create(&CreateRoleGrantInput {
+                user_id: user.id.clone(),
+                role_key: SITE_ADMIN_ROLE.to_string(),
+                resource_table: "users".to_string(),
+                resource_id: ALL_USERS.to_string(),
+            })
+            .await?;
+
+        Ok(user)
+    }
+
     /// Create a Show and Episode together
     #[allow(dead_code)] // Since each test is an independent module, this is necessary
     pub async fn create_show_and_episode(
@@ -147,6 +175,7 @@ impl TestUtils {
         let episode_input = CreateEpisodeInput {
             title: episode_title.to_string(),
             show_id: show.id.clone(),
+            status: Some(EpisodeStatus::Published),
             ..Default::default()
         };
 
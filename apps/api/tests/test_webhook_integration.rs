@@ -0,0 +1,424 @@
+use anyhow::Result;
+use async_graphql::MaybeUndefined;
+use hyper::{
+    body::to_bytes,
+    service::{make_service_fn, service_fn},
+    Body, HeaderMap, Request, Response, Server, StatusCode,
+};
+use pretty_assertions::assert_eq;
+use serde_json::{json, Value};
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+use ulid::Ulid;
+
+use caster_api::jobs::webhooks::{
+    deliver_webhooks, sign, DELIVERY_HEADER, EVENT_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+};
+use caster_domains::shows::{
+    model::Show,
+    mutations::{CreateShowInput, UpdateShowInput},
+};
+use caster_utils::config::{get_config, Config};
+
+#[cfg(test)]
+mod test_utils;
+
+use test_utils::TestUtils;
+
+/// The requests received by a stub webhook receiver
+type Received = Arc<Mutex<Vec<(HeaderMap, Vec<u8>)>>>;
+
+/// Allow deliveries to the local stub receivers
+fn local_delivery_config() -> &'static Config {
+    let mut config = get_config().clone();
+
+    config.webhooks.allow_private_addresses = true;
+
+    Box::leak(Box::new(config))
+}
+
+/// Start a local webhook receiver that records each request and responds with the given status
+async fn start_receiver(status: StatusCode) -> Result<(SocketAddr, Received)> {
+    let received: Received = Arc::default();
+    let recorded = received.clone();
+
+    let make_svc = make_service_fn(move |_conn| {
+        let recorded = recorded.clone();
+
+        async move {
+            Ok::<_, Infallible>(service_fn(move |req: Request<Body>| {
+                let recorded = recorded.clone();
+
+                async move {
+                    let (parts, body) = req.into_parts();
+                    let body = to_bytes(body).await.unwrap_or_default();
+
+                    recorded
+                        .lock()
+                        .expect("Receiver lock poisoned")
+                        .push((parts.headers, body.to_vec()));
+
+                    let mut resp = Response::new(Body::empty());
+                    *resp.status_mut() = status;
+
+                    Ok::<_, Infallible>(resp)
+                }
+            }))
+        }
+    });
+
+    let server = Server::bind(&"127.0.0.1:0".parse()?).serve(make_svc);
+    let addr = server.local_addr();
+
+    tokio::spawn(server);
+
+    Ok((addr, received))
+}
+
+/// Send a GraphQL request and return the JSON response body
+async fn request(
+    utils: &TestUtils,
+    query: &str,
+    variables: Value,
+    token: Option<&str>,
+) -> Result<Value> {
+    let req = utils.graphql.query(query, variables, token)?;
+
+    let resp = utils.http_client.request(req).await?;
+
+    assert_eq!(resp.status(), 200);
+
+    let body = to_bytes(resp.into_body()).await?;
+
+    Ok(serde_json::from_slice(&body)?)
+}
+
+/// Create a Show along with a User who administers it, and return a token for them
+async fn create_show_admin(utils: &TestUtils) -> Result<(Show, String)> {
+    let username = Ulid::new().to_string();
+    let token = utils.create_jwt(&username);
+
+    let user = utils.ctx.users.create(&username).await?;
+
+    let (show, _) = utils
+        .ctx
+        .shows
+        .create_with_admin(
+            &CreateShowInput {
+                title: "Test Show".to_string(),
+                ..Default::default()
+            },
+            &user.id,
+        )
+        .await?;
+
+    Ok((show, token))
+}
+
+/// Rename a Show, which notifies webhooks subscribed to `show.updated`
+async fn rename_show(utils: &TestUtils, show: &Show, title: &str) -> Result<Show> {
+    utils
+        .ctx
+        .shows
+        .update(
+            &show.id,
+            &UpdateShowInput {
+                title: MaybeUndefined::Value(title.to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+}
+
+/***
+ * Mutation: `createWebhook`
+ */
+
+const CREATE_WEBHOOK: &str = "
+    mutation CreateWebhook($input: CreateWebhookInput!) {
+        createWebhook(input: $input) {
+            webhook {
+                id
+                showId
+                url
+                eventTypes
+            }
+        }
+    }
+";
+
+/***
+ * Query: `getWebhookDeliveries`
+ */
+
+const GET_WEBHOOK_DELIVERIES: &str = "
+    query GetWebhookDeliveries($webhookId: String!) {
+        getWebhookDeliveries(webhookId: $webhookId) {
+            data {
+                id
+                eventType
+                status
+                attempts
+                responseStatus
+                lastError
+                deliveredAt
+                nextAttemptAt
+            }
+            total
+        }
+    }
+";
+
+/// It delivers signed events to subscribed webhooks, and logs each delivery
+#[tokio::test]
+#[ignore]
+async fn test_webhook_delivery() -> Result<()> {
+    let utils = TestUtils::init_with_config(local_delivery_config()).await?;
+
+    let (show, token) = create_show_admin(&utils).await?;
+    let (addr, received) = start_receiver(StatusCode::OK).await?;
+
+    let json = request(
+        &utils,
+        CREATE_WEBHOOK,
+        json!({
+            "input": {
+                "showId": show.id,
+                "url": format!("http://{}/hooks", addr),
+                "secret": "test-secret",
+                "eventTypes": ["SHOW_UPDATED"]
+            }
+        }),
+        Some(&token),
+    )
+    .await?;
+
+    let webhook = &json["data"]["createWebhook"]["webhook"];
+
+    assert_eq!(webhook["showId"], show.id);
+    assert_eq!(webhook["eventTypes"], json!(["SHOW_UPDATED"]));
+
+    let webhook_id = webhook["id"].clone();
+
+    rename_show(&utils, &show, "Renamed Show").await?;
+
+    deliver_webhooks(&utils.ctx).await?;
+
+    let requests = received.lock().expect("Receiver lock poisoned").clone();
+
+    // Only the subscribed event is delivered
+    assert_eq!(requests.len(), 1);
+
+    let (headers, body) = &requests[0];
+
+    assert_eq!(headers[EVENT_HEADER], "show.updated");
+
+    let timestamp: i64 = headers[TIMESTAMP_HEADER].to_str()?.parse()?;
+
+    assert_eq!(
+        headers[SIGNATURE_HEADER].to_str()?,
+        format!("sha256={}", sign("test-secret", timestamp, body))
+    );
+
+    let event: Value = serde_json::from_slice(body)?;
+
+    assert_eq!(event["type"], "show.updated");
+    assert_eq!(event["showId"], show.id);
+    assert_eq!(event["data"]["title"], "Renamed Show");
+
+    let json = request(
+        &utils,
+        GET_WEBHOOK_DELIVERIES,
+        json!({ "webhookId": webhook_id }),
+        Some(&token),
+    )
+    .await?;
+
+    let deliveries = &json["data"]["getWebhookDeliveries"];
+
+    assert_eq!(deliveries["total"], 1);
+    assert_eq!(
+        deliveries["data"][0]["id"],
+        headers[DELIVERY_HEADER].to_str()?
+    );
+    assert_eq!(deliveries["data"][0]["eventType"], "SHOW_UPDATED");
+    assert_eq!(deliveries["data"][0]["status"], "SUCCEEDED");
+    assert_eq!(deliveries["data"][0]["attempts"], 1);
+    assert_eq!(deliveries["data"][0]["responseStatus"], 200);
+    assert_ne!(deliveries["data"][0]["deliveredAt"], Value::Null);
+
+    Ok(())
+}
+
+/// It schedules a retry when the receiver fails
+#[tokio::test]
+#[ignore]
+async fn test_webhook_delivery_retry() -> Result<()> {
+    let utils = TestUtils::init_with_config(local_delivery_config()).await?;
+
+    let (show, token) = create_show_admin(&utils).await?;
+    let (addr, received) = start_receiver(StatusCode::INTERNAL_SERVER_ERROR).await?;
+
+    let json = request(
+        &utils,
+        CREATE_WEBHOOK,
+        json!({
+            "input": {
+                "showId": show.id,
+                "url": format!("http://{}/hooks", addr),
+                "secret": "test-secret",
+                "eventTypes": ["SHOW_UPDATED", "SHOW_DELETED"]
+            }
+        }),
+        Some(&token),
+    )
+    .await?;
+
+    let webhook_id = json["data"]["createWebhook"]["webhook"]["id"].clone();
+
+    rename_show(&utils, &show, "Renamed Show").await?;
+
+    deliver_webhooks(&utils.ctx).await?;
+
+    // The retry isn't due yet, so it isn't attempted again right away
+    deliver_webhooks(&utils.ctx).await?;
+
+    assert_eq!(received.lock().expect("Receiver lock poisoned").len(), 1);
+
+    let json = request(
+        &utils,
+        GET_WEBHOOK_DELIVERIES,
+        json!({ "webhookId": webhook_id }),
+        Some(&token),
+    )
+    .await?;
+
+    let delivery = &json["data"]["getWebhookDeliveries"]["data"][0];
+
+    assert_eq!(delivery["status"], "PENDING");
+    assert_eq!(delivery["attempts"], 1);
+    assert_eq!(delivery["responseStatus"], 500);
+    assert_eq!(
+        delivery["lastError"],
+        "Unexpected response status: 500 Internal Server Error"
+    );
+    assert_eq!(delivery["deliveredAt"], Value::Null);
+    assert_ne!(delivery["nextAttemptAt"], Value::Null);
+
+    Ok(())
+}
+
+/// It refuses to deliver to private addresses unless the config allows them
+#[tokio::test]
+#[ignore]
+async fn test_webhook_delivery_private_address() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let (show, token) = create_show_admin(&utils).await?;
+    let (addr, received) = start_receiver(StatusCode::OK).await?;
+
+    let mut webhook_ids = Vec::new();
+
+    // Both IP addresses and hostnames that resolve to them are refused
+    for host in ["127.0.0.1".to_string(), "localhost".to_string()] {
+        let json = request(
+            &utils,
+            CREATE_WEBHOOK,
+            json!({
+                "input": {
+                    "showId": show.id,
+                    "url": format!("http://{}:{}/hooks", host, addr.port()),
+                    "secret": "test-secret",
+                    "eventTypes": ["SHOW_UPDATED"]
+                }
+            }),
+            Some(&token),
+        )
+        .await?;
+
+        webhook_ids.push(json["data"]["createWebhook"]["webhook"]["id"].clone());
+    }
+
+    rename_show(&utils, &show, "Renamed Show").await?;
+
+    deliver_webhooks(&utils.ctx).await?;
+
+    assert_eq!(received.lock().expect("Receiver lock poisoned").len(), 0);
+
+    for webhook_id in webhook_ids {
+        let json = request(
+            &utils,
+            GET_WEBHOOK_DELIVERIES,
+            json!({ "webhookId": webhook_id }),
+            Some(&token),
+        )
+        .await?;
+
+        let delivery = &json["data"]["getWebhookDeliveries"]["data"][0];
+
+        assert_eq!(delivery["status"], "PENDING");
+        assert_eq!(delivery["responseStatus"], Value::Null);
+        assert!(delivery["lastError"]
+            .as_str()
+            .expect("Missing lastError")
+            .contains("public address"));
+    }
+
+    Ok(())
+}
+
+/// It requires the admin Role for the Show to manage webhooks
+#[tokio::test]
+#[ignore]
+async fn test_webhook_authorization() -> Result<()> {
+    let utils = TestUtils::init().await?;
+
+    let (show, token) = create_show_admin(&utils).await?;
+
+    let username = Ulid::new().to_string();
+    let other_token = utils.create_jwt(&username);
+
+    utils.ctx.users.create(&username).await?;
+
+    let input = json!({
+        "input": {
+            "showId": show.id,
+            "url": "https://example.com/hooks",
+            "secret": "test-secret",
+            "eventTypes": ["SHOW_UPDATED"]
+        }
+    });
+
+    let json = request(&utils, CREATE_WEBHOOK, input.clone(), None).await?;
+
+    assert_eq!(json["errors"][0]["message"], "Unauthorized");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 401);
+
+    let json = request(&utils, CREATE_WEBHOOK, input, Some(&other_token)).await?;
+
+    assert_eq!(json["errors"][0]["message"], "Forbidden");
+    assert_eq!(json["errors"][0]["extensions"]["code"], 403);
+
+    // The url must be one that can be posted to
+    let json = request(
+        &utils,
+        CREATE_WEBHOOK,
+        json!({
+            "input": {
+                "showId": show.id,
+                "url": "ftp://example.com/hooks",
+                "secret": "test-secret",
+                "eventTypes": ["SHOW_UPDATED"]
+            }
+        }),
+        Some(&token),
+    )
+    .await?;
+
+    assert_eq!(json["errors"][0]["extensions"]["code"], 400);
+
+    Ok(())
+}
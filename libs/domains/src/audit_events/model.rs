@@ -0,0 +1,169 @@
+#![allow(missing_docs)]
+
+use async_graphql::SimpleObject;
+use chrono::Utc;
+use fake::Dummy;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Map;
+
+use crate::users::model as user_model;
+
+/// The `AuditEvent` GraphQL and Database Model
+#[derive(
+    Clone, Debug, Dummy, Eq, PartialEq, DeriveEntityModel, Deserialize, Serialize, SimpleObject,
+)]
+#[graphql(name = "AuditEvent")]
+#[sea_orm(table_name = "audit_events")]
+pub struct Model {
+    /// The AuditEvent id
+    #[sea_orm(primary_key, column_type = "Text")]
+    pub id: String,
+
+    /// The date the change was made
+    pub created_at: DateTime,
+
+    /// The id of the User who made the change
+    #[sea_orm(column_type = "Text", nullable)]
+    pub actor_id: Option<String>,
+
+    /// The kind of change that was made, such as "create" or "update"
+    #[sea_orm(column_type = "Text")]
+    pub action: String,
+
+    /// The table of the resource that was changed
+    #[sea_orm(column_type = "Text")]
+    pub resource_table: String,
+
+    /// The id of the resource that was changed
+    #[sea_orm(column_type = "Text")]
+    pub resource_id: String,
+
+    /// The values of the changed fields before the change
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    #[dummy(default)]
+    pub before: Option<Json>,
+
+    /// The values of the changed fields after the change
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    #[dummy(default)]
+    pub after: Option<Json>,
+
+    /// The id of the request that made the change
+    #[sea_orm(column_type = "Text", nullable)]
+    pub request_id: Option<String>,
+}
+
+/// The `AuditEvent` GraphQL type is the same as the database Model
+pub type AuditEvent = Model;
+
+/// `AuditEvent` entity relationships
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "user_model::Entity",
+        from = "Column::ActorId",
+        to = "user_model::Column::Id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    Actor,
+}
+
+impl Related<user_model::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Actor.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Default for Model {
+    fn default() -> Self {
+        Self {
+            id: String::default(),
+            created_at: Utc::now().naive_utc(),
+            actor_id: Option::default(),
+            action: String::default(),
+            resource_table: String::default(),
+            resource_id: String::default(),
+            before: Option::default(),
+            after: Option::default(),
+            request_id: Option::default(),
+        }
+    }
+}
+
+/// The `CreateAuditEventInput` type
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CreateAuditEventInput {
+    /// The id of the User who made the change
+    pub actor_id: Option<String>,
+
+    /// The kind of change that was made
+    pub action: String,
+
+    /// The table of the resource that was changed
+    pub resource_table: String,
+
+    /// The id of the resource that was changed
+    pub resource_id: String,
+
+    /// The values of the changed fields before the change
+    pub before: Option<Json>,
+
+    /// The values of the changed fields after the change
+    pub after: Option<Json>,
+
+    /// The id of the request that made the change
+    pub request_id: Option<String>,
+}
+
+/// Reduce a before and after snapshot of a resource to the top-level fields that changed.
+/// Nested objects and lists hold related records, which are audited on their own.
+pub fn diff(before: Option<Json>, after: Option<Json>) -> (Option<Json>, Option<Json>) {
+    let before = before.map(without_related);
+    let after = after.map(without_related);
+
+    match (before, after) {
+        (Some(Json::Object(before)), Some(Json::Object(after))) => {
+            let mut changed_before = Map::new();
+            let mut changed_after = Map::new();
+
+            for (key, value) in &after {
+                let previous = before.get(key).cloned().unwrap_or(Json::Null);
+
+                if &previous != value {
+                    changed_before.insert(key.clone(), previous);
+                    changed_after.insert(key.clone(), value.clone());
+                }
+            }
+
+            for (key, value) in &before {
+                if !after.contains_key(key) {
+                    changed_before.insert(key.clone(), value.clone());
+                    changed_after.insert(key.clone(), Json::Null);
+                }
+            }
+
+            (
+                Some(Json::Object(changed_before)),
+                Some(Json::Object(changed_after)),
+            )
+        }
+        (before, after) => (before, after),
+    }
+}
+
+/// Strip nested related records from a snapshot
+fn without_related(value: Json) -> Json {
+    match value {
+        Json::Object(fields) => Json::Object(
+            fields
+                .into_iter()
+                .filter(|(_, value)| !value.is_object() && !value.is_array())
+                .collect(),
+        ),
+        value => value,
+    }
+}
@@ -0,0 +1,35 @@
+use async_graphql::SimpleObject;
+
+use crate::audit_events::model::AuditEvent;
+use caster_utils::pagination::ManyResponse;
+
+/// The `AuditEventsPage` result type
+#[derive(Clone, Eq, PartialEq, SimpleObject)]
+pub struct AuditEventsPage {
+    /// The list of `AuditEvents` returned for the current page
+    data: Vec<AuditEvent>,
+
+    /// The number of `AuditEvents` returned for the current page
+    count: u64,
+
+    /// Tne total number of `AuditEvents` available
+    total: u64,
+
+    /// The current page
+    page: u64,
+
+    /// The number of pages available
+    page_count: u64,
+}
+
+impl From<ManyResponse<AuditEvent>> for AuditEventsPage {
+    fn from(resp: ManyResponse<AuditEvent>) -> AuditEventsPage {
+        AuditEventsPage {
+            data: resp.data,
+            count: resp.count,
+            total: resp.total,
+            page: resp.page,
+            page_count: resp.page_count,
+        }
+    }
+}
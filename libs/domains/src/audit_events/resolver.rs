@@ -0,0 +1,193 @@
+use async_graphql::{Context, Object, Result};
+use hyper::StatusCode;
+use serde::Serialize;
+use std::sync::Arc;
+
+use super::{
+    model::{diff, CreateAuditEventInput},
+    queries::AuditEventsPage,
+    service::AuditEventsService,
+};
+use crate::{
+    authorization::resolver::is_allowed,
+    episodes::service::EpisodesService,
+    profiles::service::ProfilesService,
+    role_grants::service::RoleGrantsService,
+    shows::{model::Show, service::ShowsService},
+    users::model::User,
+};
+use caster_utils::{
+    errors::{as_graphql_error, graphql_error},
+    request::RequestId,
+};
+
+/// The Query segment owned by the AuditEvents library
+#[derive(Default)]
+pub struct AuditEventsQuery {}
+
+/// Queries for the `AuditEvent` model
+#[Object]
+impl AuditEventsQuery {
+    /// Get the history of changes made to a resource, most recent first
+    async fn get_audit_events(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "The table of the resource, such as \"shows\"")] resource_table: String,
+        #[graphql(desc = "The id of the resource")] resource_id: String,
+        page: Option<u64>,
+        page_size: Option<u64>,
+    ) -> Result<AuditEventsPage> {
+        let audit_events = ctx.data_unchecked::<Arc<dyn AuditEventsService>>();
+        let user = ctx.data_unchecked::<Option<User>>();
+
+        // Check authentication
+        let user = user
+            .as_ref()
+            .ok_or_else(|| graphql_error("Unauthorized", StatusCode::UNAUTHORIZED))?;
+
+        // Check authorization
+        if !is_resource_admin(ctx, user, &resource_table, &resource_id).await? {
+            return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
+        }
+
+        let response = audit_events
+            .get_by_resource(&resource_table, &resource_id, page, page_size)
+            .await
+            .map_err(as_graphql_error(
+                "Error while listing AuditEvents",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        Ok(response.into())
+    }
+}
+
+/// Determine whether the User administers the given resource. Deleted resources are included,
+/// so that their history is available while they await purge.
+async fn is_resource_admin(
+    ctx: &Context<'_>,
+    user: &User,
+    resource_table: &str,
+    resource_id: &str,
+) -> Result<bool> {
+    let shows = ctx.data_unchecked::<Arc<dyn ShowsService>>();
+    let episodes = ctx.data_unchecked::<Arc<dyn EpisodesService>>();
+    let profiles = ctx.data_unchecked::<Arc<dyn ProfilesService>>();
+    let role_grants = ctx.data_unchecked::<Arc<dyn RoleGrantsService>>();
+
+    let fetch_error = || {
+        as_graphql_error(
+            "Error while fetching resource",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )
+    };
+
+    // RoleGrants are administered along with the resource they grant a Role for
+    let (resource_table, resource_id) = if resource_table == "role_grants" {
+        match role_grants.get(resource_id).await.map_err(fetch_error())? {
+            Some(grant) => (grant.resource_table, grant.resource_id),
+            None => return Ok(false),
+        }
+    } else {
+        (resource_table.to_string(), resource_id.to_string())
+    };
+
+    // Episodes are administered along with their Show
+    let show_id = match resource_table.as_str() {
+        "shows" => resource_id,
+        "episodes" => {
+            let episode = match episodes
+                .get(&resource_id, &false)
+                .await
+                .map_err(fetch_error())?
+            {
+                Some(episode) => Some(episode),
+                None => episodes
+                    .get_deleted(&resource_id)
+                    .await
+                    .map_err(fetch_error())?,
+            };
+
+            match episode {
+                Some(episode) => episode.show_id,
+                None => return Ok(false),
+            }
+        }
+        "profiles" => {
+            let profile = profiles
+                .get(&resource_id, &false)
+                .await
+                .map_err(fetch_error())?;
+
+            return Ok(profile.and_then(|p| p.user_id) == Some(user.id.clone()));
+        }
+        "users" => return Ok(resource_id == user.id),
+        _ => {
+            return Err(graphql_error(
+                "Unsupported resource table",
+                StatusCode::BAD_REQUEST,
+            ))
+        }
+    };
+
+    let show: Option<Show> = match shows.get(&show_id).await.map_err(fetch_error())? {
+        Some(show) => Some(show),
+        None => shows.get_deleted(&show_id).await.map_err(fetch_error())?,
+    };
+
+    if let Some(show) = show {
+        return is_allowed(ctx, user, "audit", show);
+    }
+
+    Ok(false)
+}
+
+/// Record an `AuditEvent` for a change made by the current request, keeping only the fields
+/// that changed between the `before` and `after` snapshots. The change has already been saved by
+/// the time this is called, so failures are logged rather than returned to the client.
+pub async fn record_audit_event<B: Serialize + Sync, A: Serialize + Sync>(
+    ctx: &Context<'_>,
+    action: &str,
+    resource_table: &str,
+    resource_id: &str,
+    before: Option<&B>,
+    after: Option<&A>,
+) {
+    let audit_events = ctx.data_unchecked::<Arc<dyn AuditEventsService>>();
+    let user = ctx.data_unchecked::<Option<User>>();
+    let request_id = ctx.data_opt::<RequestId>();
+
+    let snapshots = before
+        .map(serde_json::to_value)
+        .transpose()
+        .and_then(|before| Ok((before, after.map(serde_json::to_value).transpose()?)));
+
+    let (before, after) = match snapshots {
+        Ok((before, after)) => diff(before, after),
+        Err(err) => {
+            error!(
+                "Unable to snapshot {} on {} {} for the audit log: {}",
+                action, resource_table, resource_id, err
+            );
+            return;
+        }
+    };
+
+    if let Err(err) = audit_events
+        .create(&CreateAuditEventInput {
+            actor_id: user.as_ref().map(|u| u.id.clone()),
+            action: action.to_string(),
+            resource_table: resource_table.to_string(),
+            resource_id: resource_id.to_string(),
+            before,
+            after,
+            request_id: request_id.map(|RequestId(id)| id.clone()),
+        })
+        .await
+    {
+        error!(
+            "Unable to record {} on {} {} in the audit log: {}",
+            action, resource_table, resource_id, err
+        );
+    }
+}
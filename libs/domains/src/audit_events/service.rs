@@ -0,0 +1,91 @@
+use anyhow::Result;
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sea_orm::{entity::*, query::*, DatabaseConnection, EntityTrait};
+use std::sync::Arc;
+
+use super::model::{self, AuditEvent, CreateAuditEventInput};
+use caster_utils::pagination::ManyResponse;
+
+/// An AuditEventsService records and retrieves changes made to resources
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait AuditEventsService: Sync + Send {
+    /// Get the `AuditEvent` records for a resource, most recent first
+    async fn get_by_resource(
+        &self,
+        resource_table: &str,
+        resource_id: &str,
+        page: Option<u64>,
+        page_size: Option<u64>,
+    ) -> Result<ManyResponse<AuditEvent>>;
+
+    /// Record an `AuditEvent` with the given input
+    async fn create(&self, input: &CreateAuditEventInput) -> Result<AuditEvent>;
+}
+
+/// The default `AuditEventsService` struct
+pub struct DefaultAuditEventsService {
+    /// The SeaOrm database connection
+    db: Arc<DatabaseConnection>,
+}
+
+/// The default `AuditEventsService` implementation
+impl DefaultAuditEventsService {
+    /// Create a new `AuditEventsService` instance
+    pub fn new(db: &Arc<DatabaseConnection>) -> Self {
+        Self { db: db.clone() }
+    }
+}
+
+#[async_trait]
+impl AuditEventsService for DefaultAuditEventsService {
+    async fn get_by_resource(
+        &self,
+        resource_table: &str,
+        resource_id: &str,
+        page: Option<u64>,
+        page_size: Option<u64>,
+    ) -> Result<ManyResponse<AuditEvent>> {
+        let page_num = page.unwrap_or(1);
+
+        let query = model::Entity::find()
+            .filter(model::Column::ResourceTable.eq(resource_table))
+            .filter(model::Column::ResourceId.eq(resource_id))
+            .order_by_desc(model::Column::CreatedAt)
+            .order_by_desc(model::Column::Id);
+
+        let (data, total) = if let Some(page_size) = page_size {
+            let paginator = query.paginate(&*self.db, page_size);
+            let total = paginator.num_items().await?;
+            let data: Vec<AuditEvent> = paginator.fetch_page(page_num - 1).await?;
+
+            (data, total)
+        } else {
+            let data: Vec<AuditEvent> = query.all(&*self.db).await?;
+            let total = data.len().try_into().unwrap_or(0);
+
+            (data, total)
+        };
+
+        Ok(ManyResponse::new(data, total, page_num, page_size))
+    }
+
+    async fn create(&self, input: &CreateAuditEventInput) -> Result<AuditEvent> {
+        let audit_event = model::ActiveModel {
+            actor_id: Set(input.actor_id.clone()),
+            action: Set(input.action.clone()),
+            resource_table: Set(input.resource_table.clone()),
+            resource_id: Set(input.resource_id.clone()),
+            before: Set(input.before.clone()),
+            after: Set(input.after.clone()),
+            request_id: Set(input.request_id.clone()),
+            ..Default::default()
+        }
+        .insert(&*self.db)
+        .await?;
+
+        Ok(audit_event)
+    }
+}
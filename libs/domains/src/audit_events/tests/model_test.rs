@@ -0,0 +1,31 @@
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+use crate::audit_events::model::diff;
+
+#[test]
+fn test_audit_events_diff_changed_fields() {
+    let before = json!({ "id": "test-id", "title": "Test Show", "summary": null });
+    let after = json!({ "id": "test-id", "title": "Updated Show", "summary": "A summary" });
+
+    let (before, after) = diff(Some(before), Some(after));
+
+    assert_eq!(
+        before,
+        Some(json!({ "title": "Test Show", "summary": null }))
+    );
+    assert_eq!(
+        after,
+        Some(json!({ "title": "Updated Show", "summary": "A summary" }))
+    );
+}
+
+#[test]
+fn test_audit_events_diff_skips_related() {
+    let after = json!({ "id": "test-id", "show": { "id": "show-id" }, "roles": [] });
+
+    let (before, after) = diff(None, Some(after));
+
+    assert_eq!(before, None);
+    assert_eq!(after, Some(json!({ "id": "test-id" })));
+}
@@ -0,0 +1,114 @@
+use anyhow::Result;
+use fake::{Fake, Faker};
+use pretty_assertions::assert_eq;
+use sea_orm::{DatabaseBackend, MockDatabase, Transaction};
+use serde_json::json;
+use std::sync::Arc;
+
+use crate::audit_events::{
+    model::{AuditEvent, CreateAuditEventInput},
+    service::{AuditEventsService, DefaultAuditEventsService},
+};
+use caster_utils::pagination::ManyResponse;
+
+#[tokio::test]
+async fn test_audit_events_service_get_by_resource() -> Result<()> {
+    let mut audit_event: AuditEvent = Faker.fake();
+    audit_event.resource_table = "shows".to_string();
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![audit_event.clone()]])
+            .into_connection(),
+    );
+
+    let service = DefaultAuditEventsService::new(&db);
+
+    let result = service
+        .get_by_resource("shows", &audit_event.resource_id, None, None)
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(
+        result,
+        ManyResponse {
+            data: vec![audit_event.clone()],
+            count: 1,
+            total: 1,
+            page: 1,
+            page_count: 1,
+        }
+    );
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"SELECT "audit_events"."id", "audit_events"."created_at", "audit_events"."actor_id", "audit_events"."action", "audit_events"."resource_table", "audit_events"."resource_id", "audit_events"."before", "audit_events"."after", "audit_events"."request_id" FROM "audit_events" WHERE "audit_events"."resource_table" = $1 AND "audit_events"."resource_id" = $2 ORDER BY "audit_events"."created_at" DESC, "audit_events"."id" DESC"#,
+            vec!["shows".into(), audit_event.resource_id.into()]
+        )]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_audit_events_service_create() -> Result<()> {
+    let mut audit_event: AuditEvent = Faker.fake();
+    audit_event.action = "update".to_string();
+    audit_event.resource_table = "shows".to_string();
+    audit_event.before = Some(json!({ "title": "Test Show" }));
+    audit_event.after = Some(json!({ "title": "Updated Show" }));
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![audit_event.clone()]])
+            .into_connection(),
+    );
+
+    let service = DefaultAuditEventsService::new(&db);
+
+    let result = service
+        .create(&CreateAuditEventInput {
+            actor_id: audit_event.actor_id.clone(),
+            action: audit_event.action.clone(),
+            resource_table: audit_event.resource_table.clone(),
+            resource_id: audit_event.resource_id.clone(),
+            before: audit_event.before.clone(),
+            after: audit_event.after.clone(),
+            request_id: audit_event.request_id.clone(),
+        })
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, audit_event);
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"INSERT INTO "audit_events" ("actor_id", "action", "resource_table", "resource_id", "before", "after", "request_id") VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING "id", "created_at", "actor_id", "action", "resource_table", "resource_id", "before", "after", "request_id""#,
+            vec![
+                audit_event.actor_id.into(),
+                audit_event.action.into(),
+                audit_event.resource_table.into(),
+                audit_event.resource_id.into(),
+                audit_event.before.into(),
+                audit_event.after.into(),
+                audit_event.request_id.into(),
+            ]
+        )]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,7 @@
+//! # Authorization
+
+/// Model
+pub mod model;
+
+/// GraphQL Resolver
+pub mod resolver;
@@ -0,0 +1,14 @@
+use async_graphql::SimpleObject;
+
+/// The result of checking whether the current `User` may take an action on a resource
+#[derive(Clone, Debug, Default, Eq, PartialEq, SimpleObject)]
+pub struct PermissionCheck {
+    /// Whether the action is allowed
+    pub allowed: bool,
+
+    /// The roles held for the resource, including those inherited from related resources
+    pub roles: Vec<String>,
+
+    /// Every action that is allowed on the resource
+    pub actions: Vec<String>,
+}
@@ -0,0 +1,208 @@
+use async_graphql::{Context, Object, Result};
+use hyper::StatusCode;
+use oso::{Oso, PolarValue, ToPolar};
+use std::{fmt::Debug, sync::Arc};
+
+use super::model::PermissionCheck;
+use crate::{
+    episodes::{model::Episode, service::EpisodesService},
+    profiles::service::ProfilesService,
+    shows::{model::Show, service::ShowsService},
+    users::{model::User, service::UsersServiceTrait},
+};
+use caster_utils::{
+    config::Config,
+    errors::{as_graphql_error, graphql_error},
+    request::RequestId,
+};
+
+/// The Query segment for Authorization
+#[derive(Default)]
+pub struct AuthorizationQuery {}
+
+/// Queries for authorization decisions
+#[Object]
+impl AuthorizationQuery {
+    /// Check whether the current User may take an action on a resource, along with the roles and
+    /// actions that were considered
+    async fn check_permission(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "The table of the resource, such as \"shows\"")] resource_table: String,
+        #[graphql(desc = "The id of the resource")] resource_id: String,
+        #[graphql(desc = "The action to check, such as \"update\"")] action: String,
+    ) -> Result<PermissionCheck> {
+        let user = ctx.data_unchecked::<Option<User>>();
+        let shows = ctx.data_unchecked::<Arc<dyn ShowsService>>();
+        let episodes = ctx.data_unchecked::<Arc<dyn EpisodesService>>();
+        let profiles = ctx.data_unchecked::<Arc<dyn ProfilesService>>();
+        let users = ctx.data_unchecked::<Arc<dyn UsersServiceTrait>>();
+
+        // Check authentication
+        let user = user
+            .as_ref()
+            .ok_or_else(|| graphql_error("Unauthorized", StatusCode::UNAUTHORIZED))?;
+
+        let fetch_error = || {
+            as_graphql_error(
+                "Error while fetching resource",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+        };
+
+        let not_found = || graphql_error("Unable to find resource", StatusCode::NOT_FOUND);
+
+        // Deleted resources are included, since some actions (like "restore") apply to them
+        match resource_table.as_str() {
+            "shows" => {
+                let show = match shows.get(&resource_id).await.map_err(fetch_error())? {
+                    Some(show) => Some(show),
+                    None => shows
+                        .get_deleted(&resource_id)
+                        .await
+                        .map_err(fetch_error())?,
+                }
+                .ok_or_else(not_found)?;
+
+                check_permission(ctx, user, &action, show)
+            }
+            "episodes" => {
+                let episode = if let Some(episode) = episodes
+                    .get(&resource_id, &true)
+                    .await
+                    .map_err(fetch_error())?
+                {
+                    episode
+                } else {
+                    let episode = episodes
+                        .get_deleted(&resource_id)
+                        .await
+                        .map_err(fetch_error())?
+                        .ok_or_else(not_found)?;
+
+                    // Load the Show as well, so that inherited permissions apply
+                    let show: Option<Show> =
+                        shows.get(&episode.show_id).await.map_err(fetch_error())?;
+
+                    Episode { show, ..episode }
+                };
+
+                check_permission(ctx, user, &action, episode)
+            }
+            "profiles" => {
+                let profile = profiles
+                    .get(&resource_id, &false)
+                    .await
+                    .map_err(fetch_error())?
+                    .ok_or_else(not_found)?;
+
+                check_permission(ctx, user, &action, profile)
+            }
+            "users" => {
+                let resource = users
+                    .get(&resource_id)
+                    .await
+                    .map_err(fetch_error())?
+                    .ok_or_else(not_found)?;
+
+                check_permission(ctx, user, &action, resource)
+            }
+            _ => Err(graphql_error(
+                "Unsupported resource table",
+                StatusCode::BAD_REQUEST,
+            )),
+        }
+    }
+}
+
+/// Check an action and explain the decision
+fn check_permission<R: ToPolar + Clone + Debug>(
+    ctx: &Context<'_>,
+    user: &User,
+    action: &str,
+    resource: R,
+) -> Result<PermissionCheck> {
+    let oso = ctx.data_unchecked::<Oso>();
+
+    let allowed = is_allowed(ctx, user, action, resource.clone())?;
+    let (roles, actions) = explain(oso, user, resource)?;
+
+    Ok(PermissionCheck {
+        allowed,
+        roles,
+        actions,
+    })
+}
+
+/// Check whether the User may take an action on a resource. In development, denied decisions
+/// are logged with the User's roles, the actions they are allowed, and the request id when
+/// `auth.log_denied` is enabled.
+pub fn is_allowed<R: ToPolar + Clone + Debug>(
+    ctx: &Context<'_>,
+    user: &User,
+    action: &str,
+    resource: R,
+) -> Result<bool> {
+    let oso = ctx.data_unchecked::<Oso>();
+
+    let allowed = oso.is_allowed(user.clone(), action.to_string(), resource.clone())?;
+
+    let log_denied = ctx
+        .data_opt::<&'static Config>()
+        .is_some_and(|config| config.is_dev() && config.auth.log_denied);
+
+    if !allowed && log_denied {
+        let request_id = ctx
+            .data_opt::<RequestId>()
+            .map_or("none", |RequestId(id)| id.as_str());
+
+        match explain(oso, user, resource.clone()) {
+            Ok((roles, actions)) => warn!(
+                "Denied \"{}\" for User {} (request {}): roles {:?}, allowed actions {:?}, resource {:?}",
+                action, user.id, request_id, roles, actions, resource
+            ),
+            Err(err) => warn!(
+                "Denied \"{}\" for User {} (request {}), unable to explain: {}",
+                action, user.id, request_id, err.message
+            ),
+        }
+    }
+
+    Ok(allowed)
+}
+
+/// Ask Polar which roles the User holds for a resource, and which actions they allow
+fn explain<R: ToPolar + Clone>(
+    oso: &Oso,
+    user: &User,
+    resource: R,
+) -> Result<(Vec<String>, Vec<String>)> {
+    let mut roles: Vec<String> = vec![];
+
+    let query = oso.query_rule(
+        "has_role",
+        (
+            user.clone(),
+            PolarValue::Variable("role".to_string()),
+            resource.clone(),
+        ),
+    )?;
+
+    for result in query {
+        let role: String = result?.get_typed("role")?;
+
+        if !roles.contains(&role) {
+            roles.push(role);
+        }
+    }
+
+    let mut actions: Vec<String> = oso
+        .get_allowed_actions(user.clone(), resource)?
+        .into_iter()
+        .collect();
+
+    roles.sort();
+    actions.sort();
+
+    Ok((roles, actions))
+}
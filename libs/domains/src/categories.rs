@@ -0,0 +1,26 @@
+//! # Categories
+
+/// Service
+pub mod service;
+
+/// Model
+pub mod model;
+
+/// The join table between Shows and Categories
+pub mod show_category;
+
+/// GraphQL Queries
+pub mod queries;
+
+/// GraphQL Mutations
+pub mod mutations;
+
+/// GraphQL Resolver
+pub mod resolver;
+
+/// Authorization rules
+pub const AUTHORIZATION: &str = include_str!("categories/authorization.polar");
+
+/// Tests
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,79 @@
+#![allow(missing_docs)]
+
+use async_graphql::SimpleObject;
+use chrono::Utc;
+use fake::Dummy;
+use oso::PolarClass;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The Category GraphQL and Database Model
+#[derive(
+    Clone,
+    Debug,
+    Dummy,
+    Eq,
+    PartialEq,
+    DeriveEntityModel,
+    Deserialize,
+    Serialize,
+    SimpleObject,
+    PolarClass,
+)]
+#[graphql(name = "Category", complex)]
+#[sea_orm(table_name = "categories")]
+pub struct Model {
+    /// The Category id
+    #[sea_orm(primary_key, column_type = "Text")]
+    #[polar(attribute)]
+    pub id: String,
+
+    /// The date the Category was created
+    pub created_at: DateTime,
+
+    /// The date the Category was last updated
+    pub updated_at: DateTime,
+
+    /// The Category name
+    #[sea_orm(column_type = "Text")]
+    pub name: String,
+
+    /// A unique, url-friendly identifier for the Category
+    #[sea_orm(column_type = "Text")]
+    pub slug: String,
+
+    /// The id of the Category that this Category is nested under
+    #[sea_orm(column_type = "Text", nullable)]
+    pub parent_id: Option<String>,
+}
+
+/// The Category GraphQL type is the same as the database Model
+pub type Category = Model;
+
+/// Category entity relationships
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "Entity",
+        from = "Column::ParentId",
+        to = "Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Parent,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Default for Model {
+    fn default() -> Self {
+        Self {
+            id: String::default(),
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+            name: String::default(),
+            slug: String::default(),
+            parent_id: Option::default(),
+        }
+    }
+}
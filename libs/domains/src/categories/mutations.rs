@@ -0,0 +1,49 @@
+use async_graphql::{InputObject, MaybeUndefined, SimpleObject};
+use caster_utils::graphql::dummy_maybe_undef;
+use fake::{Dummy, Faker};
+use rand::Rng;
+
+use super::model::Category;
+
+/// The `CreateCategoryInput` input type
+#[derive(Clone, Default, Dummy, Eq, PartialEq, InputObject)]
+pub struct CreateCategoryInput {
+    /// The Category's name
+    pub name: String,
+
+    /// A unique, url-friendly identifier for the Category
+    pub slug: String,
+
+    /// The id of the Category to nest this Category under
+    pub parent_id: Option<String>,
+}
+
+/// The `UpdateCategoryInput` input type
+#[derive(Clone, Default, Eq, PartialEq, InputObject)]
+pub struct UpdateCategoryInput {
+    /// The Category's name
+    pub name: MaybeUndefined<String>,
+
+    /// A unique, url-friendly identifier for the Category
+    pub slug: MaybeUndefined<String>,
+
+    /// The id of the Category to nest this Category under, or null to move it to the top level
+    pub parent_id: MaybeUndefined<String>,
+}
+
+impl Dummy<Faker> for UpdateCategoryInput {
+    fn dummy_with_rng<R: Rng + ?Sized>(config: &Faker, rng: &mut R) -> Self {
+        UpdateCategoryInput {
+            name: dummy_maybe_undef(config, rng),
+            slug: dummy_maybe_undef(config, rng),
+            parent_id: dummy_maybe_undef(config, rng),
+        }
+    }
+}
+
+/// The `MutateCategoryResult` type
+#[derive(Clone, Default, Dummy, Eq, PartialEq, SimpleObject)]
+pub struct MutateCategoryResult {
+    /// The Category
+    pub category: Option<Category>,
+}
@@ -0,0 +1,101 @@
+use async_graphql::{Enum, InputObject, SimpleObject};
+
+use crate::categories::model::{self, Category};
+use caster_utils::{
+    ordering::Ordering::{self, Asc, Desc},
+    pagination::ManyResponse,
+};
+
+use CategoriesOrderBy::{
+    CreatedAtAsc, CreatedAtDesc, IdAsc, IdDesc, NameAsc, NameDesc, SlugAsc, SlugDesc, UpdatedAtAsc,
+    UpdatedAtDesc,
+};
+
+/// The `CategoriesPage` result type
+#[derive(Clone, Eq, PartialEq, SimpleObject)]
+pub struct CategoriesPage {
+    /// The list of `Categories` returned for the current page
+    data: Vec<Category>,
+
+    /// The number of `Categories` returned for the current page
+    count: u64,
+
+    /// Tne total number of `Categories` available
+    total: u64,
+
+    /// The current page
+    page: u64,
+
+    /// The number of pages available
+    page_count: u64,
+}
+
+impl From<ManyResponse<Category>> for CategoriesPage {
+    fn from(resp: ManyResponse<Category>) -> CategoriesPage {
+        CategoriesPage {
+            data: resp.data,
+            count: resp.count,
+            total: resp.total,
+            page: resp.page,
+            page_count: resp.page_count,
+        }
+    }
+}
+
+/// Conditions to filter Category listings by
+#[derive(Clone, Default, Eq, PartialEq, InputObject)]
+pub struct CategoryCondition {
+    /// The `Category`'s slug
+    pub slug: Option<String>,
+
+    /// Filter by IDs
+    pub ids_in: Option<Vec<String>>,
+
+    /// Only include `Categories` nested directly under this parent
+    pub parent_id: Option<String>,
+
+    /// Only include `Categories` without a parent
+    pub top_level: Option<bool>,
+}
+
+/// The available ordering values
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum CategoriesOrderBy {
+    /// Order ascending by "id"
+    IdAsc,
+    /// Order descending by "id"
+    IdDesc,
+    /// Order ascending by "name"
+    NameAsc,
+    /// Order descending by "name"
+    NameDesc,
+    /// Order ascending by "slug"
+    SlugAsc,
+    /// Order descending by "slug"
+    SlugDesc,
+    /// Order ascending by "createdAt"
+    CreatedAtAsc,
+    /// Order descending by "createdAt"
+    CreatedAtDesc,
+    /// Order ascending by "updatedAt"
+    UpdatedAtAsc,
+    /// Order descending by "updatedAt"
+    UpdatedAtDesc,
+}
+
+impl From<CategoriesOrderBy> for Ordering<model::Column> {
+    fn from(order_by: CategoriesOrderBy) -> Ordering<model::Column> {
+        match order_by {
+            IdAsc => Asc(model::Column::Id),
+            NameAsc => Asc(model::Column::Name),
+            SlugAsc => Asc(model::Column::Slug),
+            CreatedAtAsc => Asc(model::Column::CreatedAt),
+            UpdatedAtAsc => Asc(model::Column::UpdatedAt),
+            IdDesc => Desc(model::Column::Id),
+            NameDesc => Desc(model::Column::Name),
+            SlugDesc => Desc(model::Column::Slug),
+            CreatedAtDesc => Desc(model::Column::CreatedAt),
+            UpdatedAtDesc => Desc(model::Column::UpdatedAt),
+        }
+    }
+}
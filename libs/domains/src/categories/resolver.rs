@@ -0,0 +1,320 @@
+use async_graphql::{
+    dataloader::DataLoader, ComplexObject, Context, MaybeUndefined, Object, Result,
+};
+use hyper::StatusCode;
+use std::sync::Arc;
+
+use crate::{
+    audit_events::resolver::record_audit_event,
+    authorization::resolver::is_allowed,
+    categories::{
+        model::Category,
+        mutations::{CreateCategoryInput, MutateCategoryResult, UpdateCategoryInput},
+        queries::{CategoriesOrderBy, CategoriesPage, CategoryCondition},
+        service::{CategoriesService, CategoryLoader},
+    },
+    users::model::User,
+};
+use caster_utils::errors::{as_graphql_error, graphql_error};
+
+/// The Query segment owned by the Categories library
+#[derive(Default)]
+pub struct CategoriesQuery {}
+
+/// The Mutation segment for Categories
+#[derive(Default)]
+pub struct CategoriesMutation {}
+
+/// Queries for the `Category` model
+#[Object]
+impl CategoriesQuery {
+    async fn get_category(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "The Category id")] id: String,
+    ) -> Result<Option<Category>> {
+        let categories = ctx.data_unchecked::<Arc<dyn CategoriesService>>();
+
+        Ok(categories.get(&id).await?)
+    }
+
+    /// Get multiple Categories
+    async fn get_many_categories(
+        &self,
+        ctx: &Context<'_>,
+        r#where: Option<CategoryCondition>,
+        order_by: Option<Vec<CategoriesOrderBy>>,
+        page: Option<u64>,
+        page_size: Option<u64>,
+    ) -> Result<CategoriesPage> {
+        let categories = ctx.data_unchecked::<Arc<dyn CategoriesService>>();
+
+        let response = categories
+            .get_many(r#where, order_by, page, page_size)
+            .await
+            .map_err(as_graphql_error(
+                "Error while listing Categories",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        Ok(response.into())
+    }
+}
+
+/// Mutations for the Category model
+#[Object]
+impl CategoriesMutation {
+    /// Create a new Category
+    async fn create_category(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateCategoryInput,
+    ) -> Result<MutateCategoryResult> {
+        let categories = ctx.data_unchecked::<Arc<dyn CategoriesService>>();
+        let user = ctx.data_unchecked::<Option<User>>();
+
+        // Check authentication and authorization
+        if let Some(user) = user {
+            if !is_allowed(ctx, user, "create", Category::default())? {
+                return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
+            }
+        } else {
+            return Err(graphql_error("Unauthorized", StatusCode::UNAUTHORIZED));
+        }
+
+        check_slug(ctx, &input.slug, None).await?;
+
+        if let Some(parent_id) = &input.parent_id {
+            check_parent(ctx, parent_id, None).await?;
+        }
+
+        let category = categories.create(&input).await.map_err(as_graphql_error(
+            "Error while creating Category",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ))?;
+
+        record_audit_event(
+            ctx,
+            "create",
+            "categories",
+            &category.id,
+            None::<&Category>,
+            Some(&category),
+        )
+        .await;
+
+        Ok(MutateCategoryResult {
+            category: Some(category),
+        })
+    }
+
+    /// Update an existing Category
+    async fn update_category(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        input: UpdateCategoryInput,
+    ) -> Result<MutateCategoryResult> {
+        let categories = ctx.data_unchecked::<Arc<dyn CategoriesService>>();
+        let user = ctx.data_unchecked::<Option<User>>();
+
+        // Retrieve the existing Category for authorization
+        let existing = categories
+            .get(&id)
+            .await
+            .map_err(as_graphql_error(
+                "Error while fetching Category",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?
+            .ok_or_else(|| {
+                graphql_error("Unable to find existing Category", StatusCode::NOT_FOUND)
+            })?;
+
+        // Check authentication and authorization
+        if let Some(user) = user {
+            if !is_allowed(ctx, user, "update", existing.clone())? {
+                return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
+            }
+        } else {
+            return Err(graphql_error("Unauthorized", StatusCode::UNAUTHORIZED));
+        }
+
+        if let MaybeUndefined::Value(slug) = &input.slug {
+            check_slug(ctx, slug, Some(&id)).await?;
+        }
+
+        if let MaybeUndefined::Value(parent_id) = &input.parent_id {
+            check_parent(ctx, parent_id, Some(&id)).await?;
+        }
+
+        let category = categories
+            .update(&id, &input)
+            .await
+            .map_err(as_graphql_error(
+                "Error while updating Category",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        record_audit_event(
+            ctx,
+            "update",
+            "categories",
+            &id,
+            Some(&existing),
+            Some(&category),
+        )
+        .await;
+
+        Ok(MutateCategoryResult {
+            category: Some(category),
+        })
+    }
+
+    /// Remove an existing Category, along with any Categories nested beneath it
+    async fn delete_category(&self, ctx: &Context<'_>, id: String) -> Result<bool> {
+        let categories = ctx.data_unchecked::<Arc<dyn CategoriesService>>();
+        let user = ctx.data_unchecked::<Option<User>>();
+
+        // Retrieve the existing Category for authorization
+        let existing = categories
+            .get(&id)
+            .await
+            .map_err(as_graphql_error(
+                "Error while fetching Category",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?
+            .ok_or_else(|| {
+                graphql_error("Unable to find existing Category", StatusCode::NOT_FOUND)
+            })?;
+
+        // Check authentication and authorization
+        if let Some(user) = user {
+            if !is_allowed(ctx, user, "delete", existing.clone())? {
+                return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
+            }
+        } else {
+            return Err(graphql_error("Unauthorized", StatusCode::UNAUTHORIZED));
+        }
+
+        categories.delete(&id).await.map_err(as_graphql_error(
+            "Error while deleting Category",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ))?;
+
+        record_audit_event(
+            ctx,
+            "delete",
+            "categories",
+            &id,
+            Some(&existing),
+            None::<&Category>,
+        )
+        .await;
+
+        Ok(true)
+    }
+}
+
+/// Require a url-friendly slug that isn't already used by another Category
+async fn check_slug(ctx: &Context<'_>, slug: &str, id: Option<&str>) -> Result<()> {
+    let categories = ctx.data_unchecked::<Arc<dyn CategoriesService>>();
+
+    let valid = !slug.is_empty()
+        && slug
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+
+    if !valid {
+        return Err(graphql_error(
+            "A slug may only contain lowercase letters, numbers, and dashes",
+            StatusCode::BAD_REQUEST,
+        ));
+    }
+
+    let existing = categories
+        .get_by_slug(slug)
+        .await
+        .map_err(as_graphql_error(
+            "Error while fetching Category",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ))?;
+
+    match existing {
+        Some(existing) if Some(existing.id.as_str()) != id => Err(graphql_error(
+            "A Category with that slug already exists",
+            StatusCode::CONFLICT,
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Require a parent Category that exists, and that isn't the Category itself or nested beneath it
+async fn check_parent(ctx: &Context<'_>, parent_id: &str, id: Option<&str>) -> Result<()> {
+    let categories = ctx.data_unchecked::<Arc<dyn CategoriesService>>();
+
+    let parent = categories.get(parent_id).await.map_err(as_graphql_error(
+        "Error while fetching Category",
+        StatusCode::INTERNAL_SERVER_ERROR,
+    ))?;
+
+    if parent.is_none() {
+        return Err(graphql_error(
+            "Unable to find parent Category",
+            StatusCode::BAD_REQUEST,
+        ));
+    }
+
+    if let Some(id) = id {
+        let tree_ids = categories
+            .get_tree_ids(vec![id.to_string()])
+            .await
+            .map_err(as_graphql_error(
+                "Error while fetching Categories",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        if tree_ids.iter().any(|tree_id| tree_id == parent_id) {
+            return Err(graphql_error(
+                "A Category can't be nested beneath itself",
+                StatusCode::BAD_REQUEST,
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+#[ComplexObject]
+impl Category {
+    #[graphql(name = "parent")]
+    async fn resolve_parent(&self, ctx: &Context<'_>) -> Result<Option<Category>> {
+        if let Some(parent_id) = self.parent_id.clone() {
+            let loader = ctx.data_unchecked::<DataLoader<CategoryLoader>>();
+            let parent = loader.load_one(parent_id).await?;
+
+            return Ok(parent);
+        }
+
+        Ok(None)
+    }
+
+    /// The Categories nested directly beneath this Category
+    #[graphql(name = "children")]
+    async fn resolve_children(&self, ctx: &Context<'_>) -> Result<Vec<Category>> {
+        let categories = ctx.data_unchecked::<Arc<dyn CategoriesService>>();
+
+        let children = categories
+            .get_many(
+                Some(CategoryCondition {
+                    parent_id: Some(self.id.clone()),
+                    ..Default::default()
+                }),
+                Some(vec![CategoriesOrderBy::NameAsc]),
+                None,
+                None,
+            )
+            .await?;
+
+        Ok(children.data)
+    }
+}
@@ -0,0 +1,359 @@
+use anyhow::Result;
+use async_graphql::{
+    dataloader::Loader,
+    FieldError,
+    MaybeUndefined::{Null, Undefined, Value},
+};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sea_orm::{entity::*, query::*, ConnectionTrait, DatabaseConnection, EntityTrait};
+use std::{collections::HashMap, sync::Arc};
+
+use crate::categories::{
+    model::{self, Category},
+    mutations::{CreateCategoryInput, UpdateCategoryInput},
+    queries::{CategoriesOrderBy, CategoryCondition},
+    show_category,
+};
+use caster_utils::{ordering::Ordering, pagination::ManyResponse};
+
+/// A CategoriesService applies business logic to a dynamic CategoriesRepository implementation.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait CategoriesService: Sync + Send {
+    /// Get an individual `Category` by id
+    async fn get(&self, id: &str) -> Result<Option<Category>>;
+
+    /// Get an individual `Category` by slug
+    async fn get_by_slug(&self, slug: &str) -> Result<Option<Category>>;
+
+    /// Get a list of `Category` results matching the given ids
+    async fn get_by_ids(&self, ids: Vec<String>) -> Result<Vec<Category>>;
+
+    /// Get the `Categories` for each of the given `Show` ids, keyed by `Show` id
+    async fn get_by_show_ids(
+        &self,
+        show_ids: Vec<String>,
+    ) -> Result<HashMap<String, Vec<Category>>>;
+
+    /// Get the given `Category` ids along with the ids of every `Category` nested beneath them
+    async fn get_tree_ids(&self, ids: Vec<String>) -> Result<Vec<String>>;
+
+    /// Get multiple `Category` records
+    async fn get_many(
+        &self,
+        condition: Option<CategoryCondition>,
+        order_by: Option<Vec<CategoriesOrderBy>>,
+        page: Option<u64>,
+        page_size: Option<u64>,
+    ) -> Result<ManyResponse<Category>>;
+
+    /// Create a `Category` with the given input
+    async fn create(&self, input: &CreateCategoryInput) -> Result<Category>;
+
+    /// Update an existing `Category` by id
+    async fn update(&self, id: &str, input: &UpdateCategoryInput) -> Result<Category>;
+
+    /// Delete an existing `Category`, along with every `Category` nested beneath it
+    async fn delete(&self, id: &str) -> Result<()>;
+}
+
+/// The default `CategoriesService` struct.
+pub struct DefaultCategoriesService {
+    /// The SeaOrm database connection
+    db: Arc<DatabaseConnection>,
+}
+
+/// The default `CategoriesService` implementation
+impl DefaultCategoriesService {
+    /// Create a new `CategoriesService` instance
+    pub fn new(db: &Arc<DatabaseConnection>) -> Self {
+        Self { db: db.clone() }
+    }
+}
+
+#[async_trait]
+impl CategoriesService for DefaultCategoriesService {
+    async fn get(&self, id: &str) -> Result<Option<Category>> {
+        let category = model::Entity::find_by_id(id.to_owned())
+            .one(&*self.db)
+            .await?;
+
+        Ok(category)
+    }
+
+    async fn get_by_slug(&self, slug: &str) -> Result<Option<Category>> {
+        let category = model::Entity::find()
+            .filter(model::Column::Slug.eq(slug))
+            .one(&*self.db)
+            .await?;
+
+        Ok(category)
+    }
+
+    async fn get_by_ids(&self, ids: Vec<String>) -> Result<Vec<Category>> {
+        let categories = model::Entity::find()
+            .filter(model::Column::Id.is_in(ids))
+            .all(&*self.db)
+            .await?;
+
+        Ok(categories)
+    }
+
+    async fn get_by_show_ids(
+        &self,
+        show_ids: Vec<String>,
+    ) -> Result<HashMap<String, Vec<Category>>> {
+        let rows = show_category::Entity::find()
+            .find_also_related(model::Entity)
+            .filter(show_category::Column::ShowId.is_in(show_ids))
+            .order_by_asc(model::Column::Name)
+            .all(&*self.db)
+            .await?;
+
+        let mut categories: HashMap<String, Vec<Category>> = HashMap::new();
+
+        for (show_category, category) in rows {
+            if let Some(category) = category {
+                categories
+                    .entry(show_category.show_id)
+                    .or_default()
+                    .push(category);
+            }
+        }
+
+        Ok(categories)
+    }
+
+    async fn get_tree_ids(&self, ids: Vec<String>) -> Result<Vec<String>> {
+        category_tree_ids(&*self.db, ids).await
+    }
+
+    async fn get_many(
+        &self,
+        condition: Option<CategoryCondition>,
+        order_by: Option<Vec<CategoriesOrderBy>>,
+        page: Option<u64>,
+        page_size: Option<u64>,
+    ) -> Result<ManyResponse<Category>> {
+        let page_num = page.unwrap_or(1);
+
+        let mut query = model::Entity::find();
+
+        if let Some(condition) = condition {
+            if let Some(slug) = condition.slug {
+                query = query.filter(model::Column::Slug.eq(slug));
+            }
+
+            if let Some(ids) = condition.ids_in {
+                query = query.filter(model::Column::Id.is_in(ids));
+            }
+
+            if let Some(parent_id) = condition.parent_id {
+                query = query.filter(model::Column::ParentId.eq(parent_id));
+            }
+
+            if let Some(true) = condition.top_level {
+                query = query.filter(model::Column::ParentId.is_null());
+            }
+        }
+
+        if let Some(order_by) = order_by {
+            for order in order_by {
+                let ordering: Ordering<model::Column> = order.into();
+
+                match ordering {
+                    Ordering::Asc(column) => {
+                        query = query.order_by_asc(column);
+                    }
+                    Ordering::Desc(column) => {
+                        query = query.order_by_desc(column);
+                    }
+                }
+            }
+        }
+
+        let (data, total) = if let Some(page_size) = page_size {
+            let paginator = query.paginate(&*self.db, page_size);
+            let total = paginator.num_items().await?;
+            let data: Vec<Category> = paginator.fetch_page(page_num - 1).await?;
+
+            (data, total)
+        } else {
+            let data: Vec<Category> = query.all(&*self.db).await?;
+            let total = data.len().try_into().unwrap_or(0);
+
+            (data, total)
+        };
+
+        Ok(ManyResponse::new(data, total, page_num, page_size))
+    }
+
+    async fn create(&self, input: &CreateCategoryInput) -> Result<Category> {
+        let category = model::ActiveModel {
+            name: Set(input.name.clone()),
+            slug: Set(input.slug.clone()),
+            parent_id: Set(input.parent_id.clone()),
+            ..Default::default()
+        }
+        .insert(&*self.db)
+        .await?;
+
+        Ok(category)
+    }
+
+    async fn update(&self, id: &str, input: &UpdateCategoryInput) -> Result<Category> {
+        // Retrieve the existing Category
+        let category = model::Entity::find_by_id(id.to_owned())
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| anyhow!("Unable to find Category with id: {}", id))?;
+
+        let mut category: model::ActiveModel = category.into();
+
+        match &input.name {
+            Undefined | Null => (),
+            Value(value) => category.name = Set(value.clone()),
+        }
+
+        match &input.slug {
+            Undefined | Null => (),
+            Value(value) => category.slug = Set(value.clone()),
+        }
+
+        match &input.parent_id {
+            Undefined => (),
+            Null => category.parent_id = Set(None),
+            Value(value) => category.parent_id = Set(Some(value.clone())),
+        }
+
+        let updated: Category = category.update(&*self.db).await?;
+
+        Ok(updated)
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        let category = model::Entity::find_by_id(id.to_owned())
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| anyhow!("Unable to find Category with id: {}", id))?;
+
+        // Nested Categories and Show listings are removed along with it by the foreign keys
+        let _result = category.delete(&*self.db).await?;
+
+        Ok(())
+    }
+}
+
+/// Expand a list of `Category` ids to include every `Category` nested beneath them, one level of
+/// the tree at a time
+pub async fn category_tree_ids<C: ConnectionTrait>(
+    db: &C,
+    ids: Vec<String>,
+) -> Result<Vec<String>> {
+    let mut tree_ids = ids.clone();
+    let mut parent_ids = ids;
+
+    while !parent_ids.is_empty() {
+        let children: Vec<String> = model::Entity::find()
+            .select_only()
+            .column(model::Column::Id)
+            .filter(model::Column::ParentId.is_in(parent_ids))
+            .filter(model::Column::Id.is_not_in(tree_ids.clone()))
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        tree_ids.extend(children.iter().cloned());
+        parent_ids = children;
+    }
+
+    Ok(tree_ids)
+}
+
+/// Replace the `Categories` that a `Show` is listed under
+pub async fn set_show_categories<C: ConnectionTrait>(
+    db: &C,
+    show_id: &str,
+    category_ids: &[String],
+) -> Result<()> {
+    show_category::Entity::delete_many()
+        .filter(show_category::Column::ShowId.eq(show_id))
+        .exec(db)
+        .await?;
+
+    let mut category_ids = category_ids.to_vec();
+    category_ids.sort();
+    category_ids.dedup();
+
+    if !category_ids.is_empty() {
+        show_category::Entity::insert_many(category_ids.into_iter().map(|category_id| {
+            show_category::ActiveModel {
+                show_id: Set(show_id.to_string()),
+                category_id: Set(category_id),
+            }
+        }))
+        .exec_without_returning(db)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// A dataloader for `Category` instances
+pub struct CategoryLoader {
+    /// The SeaOrm database connection
+    categories: Arc<dyn CategoriesService>,
+}
+
+/// The default implementation for the `CategoryLoader`
+impl CategoryLoader {
+    /// Create a new instance
+    pub fn new(categories: &Arc<dyn CategoriesService>) -> Self {
+        Self {
+            categories: categories.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl Loader<String> for CategoryLoader {
+    type Value = Category;
+    type Error = FieldError;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        let categories = self.categories.get_by_ids(keys.into()).await?;
+
+        Ok(categories
+            .into_iter()
+            .map(|category| (category.id.clone(), category))
+            .collect())
+    }
+}
+
+/// A dataloader for the `Categories` that each `Show` is listed under, keyed by `Show` id
+pub struct ShowCategoriesLoader {
+    /// The SeaOrm database connection
+    categories: Arc<dyn CategoriesService>,
+}
+
+/// The default implementation for the `ShowCategoriesLoader`
+impl ShowCategoriesLoader {
+    /// Create a new instance
+    pub fn new(categories: &Arc<dyn CategoriesService>) -> Self {
+        Self {
+            categories: categories.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl Loader<String> for ShowCategoriesLoader {
+    type Value = Vec<Category>;
+    type Error = FieldError;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        Ok(self.categories.get_by_show_ids(keys.into()).await?)
+    }
+}
@@ -0,0 +1,48 @@
+#![allow(missing_docs)]
+
+use sea_orm::entity::prelude::*;
+
+use crate::{categories::model as category_model, shows::model as show_model};
+
+/// A Category that a Show is listed under
+#[derive(Clone, Debug, Eq, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "shows_categories")]
+pub struct Model {
+    /// The Show id
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Text")]
+    pub show_id: String,
+
+    /// The Category id
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Text")]
+    pub category_id: String,
+}
+
+/// Show Category entity relationships
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "show_model::Entity",
+        from = "Column::ShowId",
+        to = "show_model::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Show,
+
+    #[sea_orm(
+        belongs_to = "category_model::Entity",
+        from = "Column::CategoryId",
+        to = "category_model::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Category,
+}
+
+impl Related<category_model::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Category.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
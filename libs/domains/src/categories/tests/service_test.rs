@@ -0,0 +1,366 @@
+use anyhow::Result;
+use async_graphql::MaybeUndefined;
+use fake::{Fake, Faker};
+use pretty_assertions::assert_eq;
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Transaction, Value};
+use std::sync::Arc;
+
+use crate::categories::{
+    model::Category,
+    mutations::{CreateCategoryInput, UpdateCategoryInput},
+    queries::{CategoriesOrderBy, CategoryCondition},
+    service::{CategoriesService, DefaultCategoriesService},
+};
+use caster_utils::pagination::ManyResponse;
+
+#[tokio::test]
+async fn test_categories_service_get() -> Result<()> {
+    let category: Category = Faker.fake();
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![category.clone()]])
+            .into_connection(),
+    );
+
+    let service = DefaultCategoriesService::new(&db);
+
+    let result = service.get(&category.id).await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, Some(category.clone()));
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"SELECT "categories"."id", "categories"."created_at", "categories"."updated_at", "categories"."name", "categories"."slug", "categories"."parent_id" FROM "categories" WHERE "categories"."id" = $1 LIMIT $2"#,
+            vec![category.id.into(), 1u64.into()]
+        )]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_categories_service_get_by_show_ids() -> Result<()> {
+    let category: Category = Faker.fake();
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![maplit::btreemap! {
+                "A_show_id" => Value::from("test-show"),
+                "A_category_id" => Value::from(category.id.clone()),
+                "B_id" => Value::from(category.id.clone()),
+                "B_created_at" => Value::from(category.created_at),
+                "B_updated_at" => Value::from(category.updated_at),
+                "B_name" => Value::from(category.name.clone()),
+                "B_slug" => Value::from(category.slug.clone()),
+                "B_parent_id" => Value::from(category.parent_id.clone()),
+            }]])
+            .into_connection(),
+    );
+
+    let service = DefaultCategoriesService::new(&db);
+
+    let result = service
+        .get_by_show_ids(vec!["test-show".to_string(), "other-show".to_string()])
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(
+        result,
+        maplit::hashmap! { "test-show".to_string() => vec![category] }
+    );
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"SELECT "shows_categories"."show_id" AS "A_show_id", "shows_categories"."category_id" AS "A_category_id", "categories"."id" AS "B_id", "categories"."created_at" AS "B_created_at", "categories"."updated_at" AS "B_updated_at", "categories"."name" AS "B_name", "categories"."slug" AS "B_slug", "categories"."parent_id" AS "B_parent_id" FROM "shows_categories" LEFT JOIN "categories" ON "shows_categories"."category_id" = "categories"."id" WHERE "shows_categories"."show_id" IN ($1, $2) ORDER BY "categories"."name" ASC"#,
+            vec!["test-show".into(), "other-show".into()]
+        )]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_categories_service_get_many() -> Result<()> {
+    let category: Category = Faker.fake();
+    let other_category: Category = Faker.fake();
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![category.clone(), other_category.clone()]])
+            .into_connection(),
+    );
+
+    let service = DefaultCategoriesService::new(&db);
+
+    let result = service
+        .get_many(
+            Some(CategoryCondition {
+                top_level: Some(true),
+                ..Default::default()
+            }),
+            Some(vec![CategoriesOrderBy::NameAsc]),
+            None,
+            None,
+        )
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(
+        result,
+        ManyResponse {
+            data: vec![category, other_category],
+            count: 2,
+            total: 2,
+            page: 1,
+            page_count: 1,
+        }
+    );
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"SELECT "categories"."id", "categories"."created_at", "categories"."updated_at", "categories"."name", "categories"."slug", "categories"."parent_id" FROM "categories" WHERE "categories"."parent_id" IS NULL ORDER BY "categories"."name" ASC"#,
+            vec![]
+        )]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_categories_service_get_tree_ids() -> Result<()> {
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![
+                vec![
+                    maplit::btreemap! { "id" => Value::from("child-1") },
+                    maplit::btreemap! { "id" => Value::from("child-2") },
+                ],
+                vec![maplit::btreemap! { "id" => Value::from("grandchild") }],
+                vec![],
+            ])
+            .into_connection(),
+    );
+
+    let service = DefaultCategoriesService::new(&db);
+
+    let result = service.get_tree_ids(vec!["parent".to_string()]).await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(
+        result,
+        vec![
+            "parent".to_string(),
+            "child-1".to_string(),
+            "child-2".to_string(),
+            "grandchild".to_string()
+        ]
+    );
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![
+            Transaction::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"SELECT "categories"."id" FROM "categories" WHERE "categories"."parent_id" IN ($1) AND "categories"."id" NOT IN ($2)"#,
+                vec!["parent".into(), "parent".into()]
+            ),
+            Transaction::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"SELECT "categories"."id" FROM "categories" WHERE "categories"."parent_id" IN ($1, $2) AND "categories"."id" NOT IN ($3, $4, $5)"#,
+                vec![
+                    "child-1".into(),
+                    "child-2".into(),
+                    "parent".into(),
+                    "child-1".into(),
+                    "child-2".into()
+                ]
+            ),
+            Transaction::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"SELECT "categories"."id" FROM "categories" WHERE "categories"."parent_id" IN ($1) AND "categories"."id" NOT IN ($2, $3, $4, $5)"#,
+                vec![
+                    "grandchild".into(),
+                    "parent".into(),
+                    "child-1".into(),
+                    "child-2".into(),
+                    "grandchild".into()
+                ]
+            ),
+        ]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_categories_service_create() -> Result<()> {
+    let mut category: Category = Faker.fake();
+    category.parent_id = Some("parent".to_string());
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![category.clone()]])
+            .into_connection(),
+    );
+
+    let service = DefaultCategoriesService::new(&db);
+
+    let result = service
+        .create(&CreateCategoryInput {
+            name: category.name.clone(),
+            slug: category.slug.clone(),
+            parent_id: category.parent_id.clone(),
+        })
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, category);
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"INSERT INTO "categories" ("name", "slug", "parent_id") VALUES ($1, $2, $3) RETURNING "id", "created_at", "updated_at", "name", "slug", "parent_id""#,
+            vec![
+                category.name.into(),
+                category.slug.into(),
+                category.parent_id.into()
+            ]
+        )]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_categories_service_update() -> Result<()> {
+    let mut category: Category = Faker.fake();
+    category.parent_id = Some("parent".to_string());
+
+    let updated = Category {
+        name: "Updated Category".to_string(),
+        parent_id: None,
+        ..category.clone()
+    };
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![category.clone()], vec![updated.clone()]])
+            .into_connection(),
+    );
+
+    let service = DefaultCategoriesService::new(&db);
+
+    let result = service
+        .update(
+            &category.id,
+            &UpdateCategoryInput {
+                name: MaybeUndefined::Value(updated.name.clone()),
+                slug: MaybeUndefined::Undefined,
+                parent_id: MaybeUndefined::Null,
+            },
+        )
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, updated);
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![
+            Transaction::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"SELECT "categories"."id", "categories"."created_at", "categories"."updated_at", "categories"."name", "categories"."slug", "categories"."parent_id" FROM "categories" WHERE "categories"."id" = $1 LIMIT $2"#,
+                vec![category.id.clone().into(), 1u64.into()]
+            ),
+            Transaction::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"UPDATE "categories" SET "name" = $1, "parent_id" = $2 WHERE "categories"."id" = $3 RETURNING "id", "created_at", "updated_at", "name", "slug", "parent_id""#,
+                vec![updated.name.into(), Value::String(None), category.id.into()]
+            ),
+        ]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_categories_service_delete() -> Result<()> {
+    let category: Category = Faker.fake();
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![category.clone()]])
+            .append_exec_results(vec![MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            }])
+            .into_connection(),
+    );
+
+    let service = DefaultCategoriesService::new(&db);
+
+    service.delete(&category.id).await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![
+            Transaction::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"SELECT "categories"."id", "categories"."created_at", "categories"."updated_at", "categories"."name", "categories"."slug", "categories"."parent_id" FROM "categories" WHERE "categories"."id" = $1 LIMIT $2"#,
+                vec![category.id.clone().into(), 1u64.into()]
+            ),
+            Transaction::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"DELETE FROM "categories" WHERE "categories"."id" = $1"#,
+                vec![category.id.into()]
+            ),
+        ]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,11 @@
+//! # Domain Events
+
+/// Model
+pub mod model;
+
+/// Publisher
+pub mod publisher;
+
+/// Tests
+#[cfg(test)]
+mod tests;
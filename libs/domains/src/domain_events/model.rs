@@ -0,0 +1,44 @@
+use crate::{
+    episodes::model::Episode, messages::model::Message, role_grants::model::RoleGrant,
+    shows::model::Show,
+};
+
+/// A change made by one of the domain services, published once it has been saved
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DomainEvent {
+    /// A Show was created
+    ShowCreated(Show),
+
+    /// A Show was updated or restored
+    ShowUpdated(Show),
+
+    /// A Show was deleted, along with its Episodes
+    ShowDeleted(Show),
+
+    /// An Episode was created
+    EpisodeCreated(Episode),
+
+    /// An Episode was updated or restored
+    EpisodeUpdated(Episode),
+
+    /// An Episode was deleted
+    EpisodeDeleted(Episode),
+
+    /// An Episode became visible to everyone, either right away or on its scheduled date
+    EpisodePublished(Episode),
+
+    /// A User was granted a Role
+    RoleGranted(RoleGrant),
+
+    /// A Role was revoked from a User
+    RoleRevoked(RoleGrant),
+
+    /// A chat Message was posted about an Episode
+    MessagePosted(Message),
+
+    /// A chat Message was edited
+    MessageUpdated(Message),
+
+    /// A chat Message was deleted
+    MessageDeleted(Message),
+}
@@ -0,0 +1,71 @@
+use std::sync::{Arc, RwLock};
+use tokio::sync::broadcast;
+
+use super::model::DomainEvent;
+
+/// The number of `DomainEvents` that can be waiting for slow listeners before they miss some
+const CAPACITY: usize = 1000;
+
+/// A function called with each `DomainEvent` as it is published
+type Subscriber = Arc<dyn Fn(&DomainEvent) + Send + Sync>;
+
+/// Publishes `DomainEvents` from the domain services. Subscribers are called synchronously, in the
+/// order they were added, before `publish` returns. Listeners that `subscribe` receive each event
+/// afterwards through a broadcast channel, so that they can handle it in their own task.
+#[derive(Clone)]
+pub struct DomainEventPublisher {
+    tx: broadcast::Sender<DomainEvent>,
+    subscribers: Arc<RwLock<Vec<Subscriber>>>,
+}
+
+impl Default for DomainEventPublisher {
+    fn default() -> Self {
+        let (tx, _rx) = broadcast::channel(CAPACITY);
+
+        Self {
+            tx,
+            subscribers: Arc::default(),
+        }
+    }
+}
+
+impl DomainEventPublisher {
+    /// Publish a `DomainEvent` to every subscriber and listener
+    pub fn publish(&self, event: DomainEvent) {
+        let subscribers = self
+            .subscribers
+            .read()
+            .map(|subscribers| subscribers.clone())
+            .unwrap_or_default();
+
+        for subscriber in subscribers {
+            subscriber(&event);
+        }
+
+        // Sending only fails when nobody is listening
+        let _ = self.tx.send(event);
+    }
+
+    /// Publish each of the given `DomainEvents` in order
+    pub fn publish_all(&self, events: impl IntoIterator<Item = DomainEvent>) {
+        for event in events {
+            self.publish(event);
+        }
+    }
+
+    /// Call the given function with each `DomainEvent` as it is published. Subscribers run on the
+    /// publishing task, so they should return quickly and hand any slow work off elsewhere.
+    pub fn add_subscriber<F>(&self, subscriber: F)
+    where
+        F: Fn(&DomainEvent) + Send + Sync + 'static,
+    {
+        if let Ok(mut subscribers) = self.subscribers.write() {
+            subscribers.push(Arc::new(subscriber));
+        }
+    }
+
+    /// Listen for `DomainEvents` published from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<DomainEvent> {
+        self.tx.subscribe()
+    }
+}
@@ -0,0 +1 @@
+mod publisher_test;
@@ -0,0 +1,46 @@
+use anyhow::Result;
+use fake::{Fake, Faker};
+use pretty_assertions::assert_eq;
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    domain_events::{model::DomainEvent, publisher::DomainEventPublisher},
+    shows::model::Show,
+};
+
+#[tokio::test]
+async fn test_domain_event_publisher_publish() -> Result<()> {
+    let show: Show = Faker.fake();
+
+    let publisher = DomainEventPublisher::default();
+
+    let received: Arc<Mutex<Vec<DomainEvent>>> = Arc::default();
+    let recorded = received.clone();
+
+    publisher.add_subscriber(move |event| {
+        if let Ok(mut recorded) = recorded.lock() {
+            recorded.push(event.clone());
+        }
+    });
+
+    let mut rx = publisher.subscribe();
+
+    publisher.publish_all([
+        DomainEvent::ShowCreated(show.clone()),
+        DomainEvent::ShowDeleted(show.clone()),
+    ]);
+
+    // Subscribers have already been called by the time `publish` returns
+    assert_eq!(
+        *received.lock().expect("Subscriber lock poisoned"),
+        vec![
+            DomainEvent::ShowCreated(show.clone()),
+            DomainEvent::ShowDeleted(show.clone())
+        ]
+    );
+
+    assert_eq!(rx.recv().await?, DomainEvent::ShowCreated(show.clone()));
+    assert_eq!(rx.recv().await?, DomainEvent::ShowDeleted(show));
+
+    Ok(())
+}
@@ -16,6 +16,9 @@ pub mod mutations;
 /// GraphQL Resolver
 pub mod resolver;
 
+/// Published Episode notifications
+pub mod publisher;
+
 /// Authorization rules
 pub const AUTHORIZATION: &str = include_str!("episodes/authorization.polar");
 
@@ -1,6 +1,6 @@
 #![allow(missing_docs)]
 
-use async_graphql::SimpleObject;
+use async_graphql::{Enum, SimpleObject};
 use chrono::Utc;
 use fake::Dummy;
 use oso::PolarClass;
@@ -8,6 +8,42 @@ use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::shows::model::{self as show_model, Show};
+use caster_utils::errors::ConflictRecord;
+
+/// The publishing status of an `Episode`
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Dummy,
+    Eq,
+    PartialEq,
+    EnumIter,
+    DeriveActiveEnum,
+    Deserialize,
+    Serialize,
+    Enum,
+)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+pub enum EpisodeStatus {
+    /// Only visible to those who can manage the Show's Episodes
+    #[default]
+    #[sea_orm(string_value = "draft")]
+    Draft,
+
+    /// Waiting to be published automatically at the `publishAt` date
+    #[sea_orm(string_value = "scheduled")]
+    Scheduled,
+
+    /// Visible to everyone
+    #[sea_orm(string_value = "published")]
+    Published,
+
+    /// Taken down after being published
+    #[sea_orm(string_value = "archived")]
+    Archived,
+}
 
 /// The User GraphQL and Database Model
 #[derive(
@@ -49,10 +85,47 @@ pub struct Model {
     #[sea_orm(column_type = "Text", nullable)]
     pub picture: Option<String>,
 
+    /// A thumbnail of the Episode image, if it was uploaded
+    #[sea_orm(column_type = "Text", nullable)]
+    pub picture_thumbnail: Option<String>,
+
+    /// The URL of the Episode's audio or video file
+    #[sea_orm(column_type = "Text", nullable)]
+    pub media_url: Option<String>,
+
+    /// The MIME type of the media file, like "audio/mpeg"
+    #[sea_orm(column_type = "Text", nullable)]
+    pub media_type: Option<String>,
+
+    /// The size of the media file in bytes
+    pub media_length: Option<i64>,
+
+    /// The length of the Episode in seconds
+    pub duration: Option<i32>,
+
+    /// The season the Episode belongs to
+    pub season_number: Option<i32>,
+
+    /// The Episode's number within the Show or season
+    pub episode_number: Option<i32>,
+
+    /// Whether the Episode contains explicit content
+    pub explicit: bool,
+
+    /// The Episode's publishing status
+    pub status: EpisodeStatus,
+
+    /// The date the Episode is scheduled to be published, or was published
+    pub publish_at: Option<DateTime>,
+
     /// The Episode's Show id
     #[polar(attribute)]
     pub show_id: String,
 
+    /// The date the Episode was deleted, if it is awaiting purge
+    #[graphql(skip)]
+    pub deleted_at: Option<DateTime>,
+
     /// The associated Show
     #[sea_orm(ignore)]
     #[graphql(skip)]
@@ -63,6 +136,13 @@ pub struct Model {
 /// The Episode GraphQL type is the same as the database Model
 pub type Episode = Model;
 
+impl Model {
+    /// Whether the Episode is visible to everyone
+    pub fn is_published(&self) -> bool {
+        self.status == EpisodeStatus::Published
+    }
+}
+
 /// Episode entity relationships
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
@@ -82,6 +162,10 @@ impl Related<show_model::Entity> for Entity {
 
 impl ActiveModelBehavior for ActiveModel {}
 
+impl ConflictRecord for Model {
+    const HIDDEN_FIELDS: &'static [&'static str] = &["deleted_at", "show"];
+}
+
 impl Default for Model {
     fn default() -> Self {
         Self {
@@ -91,7 +175,18 @@ impl Default for Model {
             title: String::default(),
             summary: Option::default(),
             picture: Option::default(),
+            picture_thumbnail: Option::default(),
+            media_url: Option::default(),
+            media_type: Option::default(),
+            media_length: Option::default(),
+            duration: Option::default(),
+            season_number: Option::default(),
+            episode_number: Option::default(),
+            explicit: bool::default(),
+            status: EpisodeStatus::default(),
+            publish_at: Option::default(),
             show_id: String::default(),
+            deleted_at: Option::default(),
             show: Option::default(),
         }
     }
@@ -1,9 +1,10 @@
 use async_graphql::{InputObject, MaybeUndefined, SimpleObject};
 use caster_utils::graphql::dummy_maybe_undef;
+use chrono::NaiveDateTime;
 use fake::{Dummy, Fake, Faker};
 use rand::Rng;
 
-use super::model::Episode;
+use super::model::{Episode, EpisodeStatus};
 
 /// The `CreateEpisodeInput` input type
 #[derive(Clone, Default, Dummy, Eq, PartialEq, InputObject)]
@@ -17,6 +18,43 @@ pub struct CreateEpisodeInput {
     /// The Episode's picture
     pub picture: Option<String>,
 
+    /// The URL of the Episode's audio or video file
+    pub media_url: Option<String>,
+
+    /// The MIME type of the media file
+    pub media_type: Option<String>,
+
+    /// The size of the media file in bytes
+    #[graphql(validator(minimum = 0))]
+    #[dummy(faker = "0..10000")]
+    pub media_length: Option<i64>,
+
+    /// The length of the Episode in seconds
+    #[graphql(validator(minimum = 0))]
+    #[dummy(faker = "0..10000")]
+    pub duration: Option<i32>,
+
+    /// The season the Episode belongs to
+    #[graphql(validator(minimum = 0))]
+    #[dummy(faker = "0..10000")]
+    pub season_number: Option<i32>,
+
+    /// The Episode's number within the Show or season
+    #[graphql(validator(minimum = 0))]
+    #[dummy(faker = "0..10000")]
+    pub episode_number: Option<i32>,
+
+    /// Whether the Episode contains explicit content
+    pub explicit: Option<bool>,
+
+    /// The Episode's publishing status, which defaults to a draft
+    #[dummy(default)]
+    pub status: Option<EpisodeStatus>,
+
+    /// The date to publish a scheduled Episode, which defaults to now for published Episodes
+    #[dummy(default)]
+    pub publish_at: Option<NaiveDateTime>,
+
     /// The Episode's Show id
     pub show_id: String,
 }
@@ -33,8 +71,46 @@ pub struct UpdateEpisodeInput {
     /// The Episode's picture
     pub picture: MaybeUndefined<String>,
 
+    /// The thumbnail generated for an uploaded picture, which can't be set directly
+    #[graphql(skip)]
+    pub picture_thumbnail: MaybeUndefined<String>,
+
+    /// The URL of the Episode's audio or video file
+    pub media_url: MaybeUndefined<String>,
+
+    /// The MIME type of the media file
+    pub media_type: MaybeUndefined<String>,
+
+    /// The size of the media file in bytes
+    #[graphql(validator(minimum = 0))]
+    pub media_length: MaybeUndefined<i64>,
+
+    /// The length of the Episode in seconds
+    #[graphql(validator(minimum = 0))]
+    pub duration: MaybeUndefined<i32>,
+
+    /// The season the Episode belongs to
+    #[graphql(validator(minimum = 0))]
+    pub season_number: MaybeUndefined<i32>,
+
+    /// The Episode's number within the Show or season
+    #[graphql(validator(minimum = 0))]
+    pub episode_number: MaybeUndefined<i32>,
+
+    /// Whether the Episode contains explicit content
+    pub explicit: Option<bool>,
+
+    /// The Episode's publishing status
+    pub status: Option<EpisodeStatus>,
+
+    /// The date to publish a scheduled Episode
+    pub publish_at: MaybeUndefined<NaiveDateTime>,
+
     /// The Episode's Show id
     pub show_id: Option<String>,
+
+    /// Reject the update with a Conflict error if the Episode has been updated since this date
+    pub expected_updated_at: Option<NaiveDateTime>,
 }
 
 impl Dummy<Faker> for UpdateEpisodeInput {
@@ -43,7 +119,18 @@ impl Dummy<Faker> for UpdateEpisodeInput {
             title: Faker.fake(),
             summary: dummy_maybe_undef(config, rng),
             picture: dummy_maybe_undef(config, rng),
+            picture_thumbnail: MaybeUndefined::Undefined,
+            media_url: dummy_maybe_undef(config, rng),
+            media_type: dummy_maybe_undef(config, rng),
+            media_length: dummy_maybe_undef(config, rng),
+            duration: dummy_maybe_undef(config, rng),
+            season_number: dummy_maybe_undef(config, rng),
+            episode_number: dummy_maybe_undef(config, rng),
+            explicit: Faker.fake(),
+            status: None,
+            publish_at: MaybeUndefined::Undefined,
             show_id: Faker.fake(),
+            expected_updated_at: None,
         }
     }
 }
@@ -0,0 +1,31 @@
+use tokio::sync::broadcast;
+
+use super::model::Episode;
+
+/// The number of published `Episodes` that can be waiting for slow listeners before they miss some
+const CAPACITY: usize = 100;
+
+/// Broadcasts `Episodes` to listeners as they are published
+#[derive(Clone)]
+pub struct EpisodePublisher(broadcast::Sender<Episode>);
+
+impl Default for EpisodePublisher {
+    fn default() -> Self {
+        let (tx, _rx) = broadcast::channel(CAPACITY);
+
+        Self(tx)
+    }
+}
+
+impl EpisodePublisher {
+    /// Notify listeners that an `Episode` has been published
+    pub fn notify(&self, episode: &Episode) {
+        // Sending only fails when nobody is listening
+        let _ = self.0.send(episode.clone());
+    }
+
+    /// Listen for published `Episodes`
+    pub fn subscribe(&self) -> broadcast::Receiver<Episode> {
+        self.0.subscribe()
+    }
+}
@@ -1,13 +1,15 @@
 use async_graphql::{Enum, InputObject, SimpleObject};
 
-use super::model::{self, Episode};
+use super::model::{self, Episode, EpisodeStatus};
+use crate::role_grants::queries::GrantedRoles;
 use caster_utils::{
     ordering::Ordering::{self, Asc, Desc},
     pagination::ManyResponse,
 };
 
 use EpisodesOrderBy::{
-    CreatedAtAsc, CreatedAtDesc, IdAsc, IdDesc, ShowIdAsc, ShowIdDesc, TitleAsc, TitleDesc,
+    CreatedAtAsc, CreatedAtDesc, EpisodeNumberAsc, EpisodeNumberDesc, IdAsc, IdDesc, PublishAtAsc,
+    PublishAtDesc, SeasonNumberAsc, SeasonNumberDesc, ShowIdAsc, ShowIdDesc, TitleAsc, TitleDesc,
     UpdatedAtAsc, UpdatedAtDesc,
 };
 
@@ -43,7 +45,7 @@ impl From<ManyResponse<Episode>> for EpisodesPage {
 }
 
 /// Conditions to filter Episode listings by
-#[derive(Clone, Eq, PartialEq, InputObject)]
+#[derive(Clone, Default, Eq, PartialEq, InputObject)]
 pub struct EpisodeCondition {
     /// The `Episode`'s title
     pub title: Option<String>,
@@ -51,8 +53,37 @@ pub struct EpisodeCondition {
     /// The associated Show
     pub show_id: Option<String>,
 
+    /// The season the `Episode` belongs to
+    pub season_number: Option<i32>,
+
+    /// The `Episode`'s number within the Show or season
+    pub episode_number: Option<i32>,
+
+    /// Whether the `Episode` contains explicit content
+    pub explicit: Option<bool>,
+
+    /// The `Episode`'s publishing status
+    pub status: Option<EpisodeStatus>,
+
     /// Filter by IDs
     pub ids_in: Option<Vec<String>>,
+
+    /// Only include `Episodes` that the current `User` has this permission for
+    pub permission: Option<String>,
+
+    /// The current `User`'s roles for `Episodes` that grant the requested permission
+    #[graphql(skip)]
+    pub granted: Option<GrantedRoles>,
+
+    /// The current `User`'s roles for `Shows` that grant the requested permission to each of
+    /// their `Episodes`
+    #[graphql(skip)]
+    pub show_granted: Option<GrantedRoles>,
+
+    /// The current `User`'s roles for `Shows` that allow them to see unpublished `Episodes`.
+    /// Only published `Episodes` are included for other `Shows`.
+    #[graphql(skip)]
+    pub published_or_granted: Option<GrantedRoles>,
 }
 
 /// The available ordering values
@@ -70,6 +101,14 @@ pub enum EpisodesOrderBy {
     ShowIdAsc,
     /// Order descending by "showId"
     ShowIdDesc,
+    /// Order ascending by "seasonNumber"
+    SeasonNumberAsc,
+    /// Order descending by "seasonNumber"
+    SeasonNumberDesc,
+    /// Order ascending by "episodeNumber"
+    EpisodeNumberAsc,
+    /// Order descending by "episodeNumber"
+    EpisodeNumberDesc,
     /// Order ascending by "createdAt"
     CreatedAtAsc,
     /// Order descending by "createdAt"
@@ -78,6 +117,10 @@ pub enum EpisodesOrderBy {
     UpdatedAtAsc,
     /// Order descending by "updatedAt"
     UpdatedAtDesc,
+    /// Order ascending by "publishAt"
+    PublishAtAsc,
+    /// Order descending by "publishAt"
+    PublishAtDesc,
 }
 
 impl From<EpisodesOrderBy> for Ordering<model::Column> {
@@ -86,13 +129,19 @@ impl From<EpisodesOrderBy> for Ordering<model::Column> {
             IdAsc => Asc(model::Column::Id),
             TitleAsc => Asc(model::Column::Title),
             ShowIdAsc => Asc(model::Column::ShowId),
+            SeasonNumberAsc => Asc(model::Column::SeasonNumber),
+            EpisodeNumberAsc => Asc(model::Column::EpisodeNumber),
             CreatedAtAsc => Asc(model::Column::CreatedAt),
             UpdatedAtAsc => Asc(model::Column::UpdatedAt),
+            PublishAtAsc => Asc(model::Column::PublishAt),
             IdDesc => Desc(model::Column::Id),
             TitleDesc => Desc(model::Column::Title),
             ShowIdDesc => Desc(model::Column::ShowId),
+            SeasonNumberDesc => Desc(model::Column::SeasonNumber),
+            EpisodeNumberDesc => Desc(model::Column::EpisodeNumber),
             CreatedAtDesc => Desc(model::Column::CreatedAt),
             UpdatedAtDesc => Desc(model::Column::UpdatedAt),
+            PublishAtDesc => Desc(model::Column::PublishAt),
         }
     }
 }
@@ -1,20 +1,30 @@
-use async_graphql::{dataloader::DataLoader, ComplexObject, Context, Object, Result};
+use async_graphql::{
+    dataloader::DataLoader,
+    ComplexObject, Context,
+    MaybeUndefined::{Null, Undefined, Value},
+    Object, Result, Upload,
+};
 use hyper::StatusCode;
 use oso::Oso;
 use std::sync::Arc;
 
 use super::{
-    model::Episode,
+    model::{Episode, EpisodeStatus},
     mutations::{CreateEpisodeInput, MutateEpisodeResult, UpdateEpisodeInput},
     queries::{EpisodeCondition, EpisodesOrderBy, EpisodesPage},
     service::EpisodesService,
 };
 use crate::{
+    audit_events::resolver::record_audit_event,
+    authorization::resolver::is_allowed,
+    presence::service::ViewerCountLoader,
+    role_grants::queries::GrantedRoles,
     shows::model::Show,
     shows::service::{ShowLoader, ShowsService},
     users::model::User,
 };
-use caster_utils::errors::{as_graphql_error, graphql_error};
+use caster_storage::uploads::{remove_picture, upload_picture};
+use caster_utils::errors::{as_graphql_error, as_update_error, graphql_error};
 
 /// The Query segment owned by the Episodes library
 #[derive(Default)]
@@ -38,13 +48,25 @@ impl EpisodesQuery {
         // Check to see if the associated Show is selected
         let with_show = ctx.look_ahead().field("show").exists();
 
-        episodes
+        let episode = episodes
             .get(&id, &with_show)
             .await
             .map_err(as_graphql_error(
                 "Error while retrieving Episode",
                 StatusCode::INTERNAL_SERVER_ERROR,
-            ))
+            ))?;
+
+        match episode {
+            Some(episode) if !episode.is_published() => {
+                // Unpublished Episodes are hidden from those who can't manage them
+                if can_manage(ctx, &episode).await? {
+                    Ok(Some(episode))
+                } else {
+                    Ok(None)
+                }
+            }
+            episode => Ok(episode),
+        }
     }
 
     /// Get multiple Episodes
@@ -57,12 +79,84 @@ impl EpisodesQuery {
         page_size: Option<u64>,
     ) -> Result<EpisodesPage> {
         let episodes = ctx.data_unchecked::<Arc<dyn EpisodesService>>();
+        let user = ctx.data_unchecked::<Option<User>>();
+        let oso = ctx.data_unchecked::<Oso>();
+
+        let mut condition = r#where.unwrap_or_default();
+
+        // Only include unpublished Episodes for Shows where the User can manage them
+        let published_or_granted = if let Some(user) = user {
+            if oso.is_allowed(user.clone(), "manage_episodes", Show::default())? {
+                None
+            } else {
+                Some(
+                    GrantedRoles::for_permission(oso, user, "manage_episodes", "shows", |id| {
+                        Show {
+                            id: id.to_string(),
+                            ..Default::default()
+                        }
+                    })
+                    .map_err(as_graphql_error(
+                        "Error while checking permissions",
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                    ))?,
+                )
+            }
+        } else {
+            Some(GrantedRoles::default())
+        };
+
+        condition.published_or_granted = published_or_granted;
+
+        // Resolve a requested permission to the roles that grant it, so it can be filtered in SQL
+        if let Some(permission) = &condition.permission {
+            let user = user
+                .as_ref()
+                .ok_or_else(|| graphql_error("Unauthorized", StatusCode::UNAUTHORIZED))?;
+
+            let any_episode = Episode {
+                show: Some(Show::default()),
+                ..Default::default()
+            };
+
+            // Permissions that apply to any Episode, such as those of site admins, need no
+            // filter
+            if !oso.is_allowed(user.clone(), permission.clone(), any_episode)? {
+                let permission_error = || {
+                    as_graphql_error(
+                        "Error while checking permissions",
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                    )
+                };
+
+                let granted =
+                    GrantedRoles::for_permission(oso, user, permission, "episodes", |id| Episode {
+                        id: id.to_string(),
+                        ..Default::default()
+                    })
+                    .map_err(permission_error())?;
+
+                let show_granted =
+                    GrantedRoles::for_permission(oso, user, permission, "shows", |id| Episode {
+                        show_id: id.to_string(),
+                        show: Some(Show {
+                            id: id.to_string(),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    })
+                    .map_err(permission_error())?;
+
+                condition.granted = Some(granted);
+                condition.show_granted = Some(show_granted);
+            }
+        }
 
         // Check to see if the associated Show is selected
         let with_show = ctx.look_ahead().field("data").field("show").exists();
 
         let response = episodes
-            .get_many(r#where, order_by, page, page_size, &with_show)
+            .get_many(Some(condition), order_by, page, page_size, &with_show)
             .await
             .map_err(as_graphql_error(
                 "Error while listing Episodes",
@@ -85,7 +179,6 @@ impl EpisodesMutation {
         let shows = ctx.data_unchecked::<Arc<dyn ShowsService>>();
         let episodes = ctx.data_unchecked::<Arc<dyn EpisodesService>>();
         let user = ctx.data_unchecked::<Option<User>>();
-        let oso = ctx.data_unchecked::<Oso>();
 
         // Retrieve the related Show for authorization
         let show = shows
@@ -99,13 +192,17 @@ impl EpisodesMutation {
 
         // Check authentication and authorization
         if let Some(user) = user {
-            if !oso.is_allowed(user.clone(), "manage_episodes", show.clone())? {
+            if !is_allowed(ctx, user, "manage_episodes", show.clone())? {
                 return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
             }
         } else {
             return Err(graphql_error("Unauthorized", StatusCode::UNAUTHORIZED));
         }
 
+        if input.status == Some(EpisodeStatus::Scheduled) && input.publish_at.is_none() {
+            return Err(schedule_error());
+        }
+
         let episode = episodes
             .create(&input, &false)
             .await
@@ -114,6 +211,16 @@ impl EpisodesMutation {
                 StatusCode::INTERNAL_SERVER_ERROR,
             ))?;
 
+        record_audit_event(
+            ctx,
+            "create",
+            "episodes",
+            &episode.id,
+            None::<&Episode>,
+            Some(&episode),
+        )
+        .await;
+
         Ok(MutateEpisodeResult {
             episode: Some(Episode {
                 show: Some(show),
@@ -131,7 +238,6 @@ impl EpisodesMutation {
     ) -> Result<MutateEpisodeResult> {
         let episodes = ctx.data_unchecked::<Arc<dyn EpisodesService>>();
         let user = ctx.data_unchecked::<Option<User>>();
-        let oso = ctx.data_unchecked::<Oso>();
 
         // Retrieve the existing Episode for authorization
         let existing = episodes
@@ -145,19 +251,28 @@ impl EpisodesMutation {
                 graphql_error("Unable to find existing Episode", StatusCode::NOT_FOUND)
             })?;
 
-        let show = existing
-            .show
-            .ok_or_else(|| graphql_error("Unable to find existing Show", StatusCode::NOT_FOUND))?;
-
         // Check authentication and authorization
         if let Some(user) = user {
-            if !oso.is_allowed(user.clone(), "manage_episodes", show.clone())? {
+            if !is_allowed(ctx, user, "update", existing.clone())? {
                 return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
             }
         } else {
             return Err(graphql_error("Unauthorized", StatusCode::UNAUTHORIZED));
         }
 
+        // Scheduled Episodes need a date to be published at
+        if input.status == Some(EpisodeStatus::Scheduled) {
+            let publish_at = match &input.publish_at {
+                Undefined => existing.publish_at,
+                Null => None,
+                Value(value) => Some(*value),
+            };
+
+            if publish_at.is_none() {
+                return Err(schedule_error());
+            }
+        }
+
         // Check to see if the associated User is selected
         let with_show = ctx.look_ahead().field("episode").field("show").exists();
 
@@ -165,11 +280,96 @@ impl EpisodesMutation {
         let episode = episodes
             .update(&existing.id, &input, &with_show)
             .await
-            .map_err(as_graphql_error(
+            .map_err(as_update_error::<Episode>(
                 "Error while updating Profile",
                 StatusCode::INTERNAL_SERVER_ERROR,
             ))?;
 
+        record_audit_event(
+            ctx,
+            "update",
+            "episodes",
+            &id,
+            Some(&existing),
+            Some(&episode),
+        )
+        .await;
+
+        Ok(MutateEpisodeResult {
+            episode: Some(episode),
+        })
+    }
+
+    /// Upload a new picture for an existing Episode, along with a generated thumbnail
+    pub async fn upload_episode_picture(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        picture: Upload,
+    ) -> Result<MutateEpisodeResult> {
+        let episodes = ctx.data_unchecked::<Arc<dyn EpisodesService>>();
+        let user = ctx.data_unchecked::<Option<User>>();
+
+        // Retrieve the existing Episode for authorization
+        let existing = episodes
+            .get(&id, &true)
+            .await
+            .map_err(as_graphql_error(
+                "Error while fetching Episode",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?
+            .ok_or_else(|| {
+                graphql_error("Unable to find existing Episode", StatusCode::NOT_FOUND)
+            })?;
+
+        // Check authentication and authorization
+        if let Some(user) = user {
+            if !is_allowed(ctx, user, "update", existing.clone())? {
+                return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
+            }
+        } else {
+            return Err(graphql_error("Unauthorized", StatusCode::UNAUTHORIZED));
+        }
+
+        let prefix = format!("episodes/{}", id);
+        let stored = upload_picture(ctx, &picture, &prefix).await?;
+
+        let input = UpdateEpisodeInput {
+            picture: Value(stored.url),
+            picture_thumbnail: Value(stored.thumbnail_url),
+            ..Default::default()
+        };
+
+        // Check to see if the associated Show is selected
+        let with_show = ctx.look_ahead().field("episode").field("show").exists();
+
+        let episode = episodes
+            .update(&existing.id, &input, &with_show)
+            .await
+            .map_err(as_update_error::<Episode>(
+                "Error while updating Episode",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        record_audit_event(
+            ctx,
+            "update",
+            "episodes",
+            &id,
+            Some(&existing),
+            Some(&episode),
+        )
+        .await;
+
+        // Clean up the files for the picture that was replaced
+        remove_picture(
+            ctx,
+            &prefix,
+            existing.picture.as_deref(),
+            existing.picture_thumbnail.as_deref(),
+        )
+        .await;
+
         Ok(MutateEpisodeResult {
             episode: Some(episode),
         })
@@ -179,9 +379,8 @@ impl EpisodesMutation {
     pub async fn delete_episode(&self, ctx: &Context<'_>, id: String) -> Result<bool> {
         let episodes = ctx.data_unchecked::<Arc<dyn EpisodesService>>();
         let user = ctx.data_unchecked::<Option<User>>();
-        let oso = ctx.data_unchecked::<Oso>();
 
-        // Retrieve the related Show for authorization
+        // Retrieve the existing Episode and its Show for authorization
         let episode = episodes
             .get(&id, &true)
             .await
@@ -193,13 +392,9 @@ impl EpisodesMutation {
                 graphql_error("Unable to find existing Episode", StatusCode::NOT_FOUND)
             })?;
 
-        let show = episode
-            .show
-            .ok_or_else(|| graphql_error("Unable to find existing Show", StatusCode::NOT_FOUND))?;
-
         // Check authentication and authorization
         if let Some(user) = user {
-            if !oso.is_allowed(user.clone(), "manage_episodes", show.clone())? {
+            if !is_allowed(ctx, user, "delete", episode.clone())? {
                 return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
             }
         } else {
@@ -211,8 +406,119 @@ impl EpisodesMutation {
             StatusCode::INTERNAL_SERVER_ERROR,
         ))?;
 
+        record_audit_event(
+            ctx,
+            "delete",
+            "episodes",
+            &id,
+            Some(&episode),
+            None::<&Episode>,
+        )
+        .await;
+
         Ok(true)
     }
+
+    /// Restore a deleted Episode
+    pub async fn restore_episode(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<MutateEpisodeResult> {
+        let shows = ctx.data_unchecked::<Arc<dyn ShowsService>>();
+        let episodes = ctx.data_unchecked::<Arc<dyn EpisodesService>>();
+        let user = ctx.data_unchecked::<Option<User>>();
+
+        // Retrieve the deleted Episode
+        let existing = episodes
+            .get_deleted(&id)
+            .await
+            .map_err(as_graphql_error(
+                "Error while fetching Episode",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?
+            .ok_or_else(|| {
+                graphql_error("Unable to find deleted Episode", StatusCode::NOT_FOUND)
+            })?;
+
+        // Retrieve the related Show for authorization. If the Show has been deleted as well, it
+        // needs to be restored first.
+        let show = shows
+            .get(&existing.show_id)
+            .await
+            .map_err(as_graphql_error(
+                "Error while fetching Show",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?
+            .ok_or_else(|| graphql_error("Unable to find existing Show", StatusCode::NOT_FOUND))?;
+
+        // Check authentication and authorization
+        if let Some(user) = user {
+            let existing = Episode {
+                show: Some(show.clone()),
+                ..existing.clone()
+            };
+
+            if !is_allowed(ctx, user, "restore", existing)? {
+                return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
+            }
+        } else {
+            return Err(graphql_error("Unauthorized", StatusCode::UNAUTHORIZED));
+        }
+
+        let episode = episodes.restore(&id).await.map_err(as_graphql_error(
+            "Error while restoring Episode",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ))?;
+
+        record_audit_event(
+            ctx,
+            "restore",
+            "episodes",
+            &id,
+            Some(&existing),
+            Some(&episode),
+        )
+        .await;
+
+        Ok(MutateEpisodeResult {
+            episode: Some(Episode {
+                show: Some(show),
+                ..episode
+            }),
+        })
+    }
+}
+
+/// Determine whether the current User can manage the Episodes of an Episode's Show, which allows
+/// them to see it before it is published
+pub(crate) async fn can_manage(ctx: &Context<'_>, episode: &Episode) -> Result<bool> {
+    let user = if let Some(user) = ctx.data_unchecked::<Option<User>>() {
+        user
+    } else {
+        return Ok(false);
+    };
+
+    let show = if let Some(show) = episode.show.clone() {
+        Some(show)
+    } else {
+        let loader = ctx.data_unchecked::<DataLoader<ShowLoader>>();
+
+        loader.load_one(episode.show_id.clone()).await?
+    };
+
+    match show {
+        Some(show) => is_allowed(ctx, user, "manage_episodes", show),
+        None => Ok(false),
+    }
+}
+
+/// The error returned when an Episode is scheduled without a date
+fn schedule_error() -> async_graphql::Error {
+    graphql_error(
+        "A publishAt date is required to schedule an Episode",
+        StatusCode::BAD_REQUEST,
+    )
 }
 
 #[ComplexObject]
@@ -228,4 +534,13 @@ impl Episode {
 
         Ok(show)
     }
+
+    /// The number of distinct viewers currently watching the Episode over `/events`
+    #[graphql(name = "viewerCount")]
+    async fn resolve_viewer_count(&self, ctx: &Context<'_>) -> Result<u64> {
+        let loader = ctx.data_unchecked::<DataLoader<ViewerCountLoader>>();
+        let count = loader.load_one(self.id.clone()).await?;
+
+        Ok(count.unwrap_or_default())
+    }
 }
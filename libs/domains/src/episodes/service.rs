@@ -5,18 +5,27 @@ use async_graphql::{
     MaybeUndefined::{Null, Undefined, Value},
 };
 use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
 #[cfg(test)]
 use mockall::automock;
-use sea_orm::{entity::*, query::*, DatabaseConnection, EntityTrait};
+use sea_orm::{
+    entity::*, query::*, sea_query::Expr, DatabaseConnection, EntityTrait, TransactionTrait,
+};
 use std::{collections::HashMap, sync::Arc};
 
 use super::{
-    model::{self, Episode, EpisodeList, EpisodeOption},
+    model::{self, Episode, EpisodeList, EpisodeOption, EpisodeStatus},
     mutations::{CreateEpisodeInput, UpdateEpisodeInput},
     queries::{EpisodeCondition, EpisodesOrderBy},
 };
-use crate::shows::model as show_model;
-use caster_utils::{ordering::Ordering, pagination::ManyResponse};
+use crate::{
+    domain_events::{model::DomainEvent, publisher::DomainEventPublisher},
+    shows::model as show_model,
+    webhooks::{model::WebhookEventType, outbox::record_event},
+};
+use caster_utils::{
+    errors::Conflict, ordering::Ordering, pagination::ManyResponse, updates::update_if_unchanged,
+};
 
 /// An EpisodesService applies business logic to a dynamic EpisodesRepository implementation.
 #[cfg_attr(test, automock)]
@@ -49,28 +58,47 @@ pub trait EpisodesService: Sync + Send {
         with_show: &bool,
     ) -> Result<Episode>;
 
-    /// Delete an existing `Episode`
+    /// Delete an existing `Episode`, keeping it around to be restored until it is purged
     async fn delete(&self, id: &str) -> Result<()>;
+
+    /// Get an individual deleted `Episode` by id
+    async fn get_deleted(&self, id: &str) -> Result<Option<Episode>>;
+
+    /// Restore a deleted `Episode`
+    async fn restore(&self, id: &str) -> Result<Episode>;
+
+    /// Permanently remove `Episode` records that were deleted before the given date
+    async fn purge(&self, deleted_before: NaiveDateTime) -> Result<u64>;
+
+    /// Publish scheduled `Episode` records that were due by the given date, returning them
+    async fn publish_scheduled(&self, due_by: NaiveDateTime) -> Result<Vec<Episode>>;
 }
 
 /// The default `EpisodesService` struct.
 pub struct DefaultEpisodesService {
     /// The SeaOrm database connection
     db: Arc<DatabaseConnection>,
+
+    /// Publishes the changes made by the service
+    events: DomainEventPublisher,
 }
 
 /// The default `EpisodesService` implementation
 impl DefaultEpisodesService {
     /// Create a new `EpisodesService` instance
-    pub fn new(db: &Arc<DatabaseConnection>) -> Self {
-        Self { db: db.clone() }
+    pub fn new(db: &Arc<DatabaseConnection>, events: &DomainEventPublisher) -> Self {
+        Self {
+            db: db.clone(),
+            events: events.clone(),
+        }
     }
 }
 
 #[async_trait]
 impl EpisodesService for DefaultEpisodesService {
     async fn get(&self, id: &str, with_show: &bool) -> Result<Option<Episode>> {
-        let query = model::Entity::find_by_id(id.to_owned());
+        let query =
+            model::Entity::find_by_id(id.to_owned()).filter(model::Column::DeletedAt.is_null());
 
         let episode = if *with_show {
             query
@@ -95,6 +123,7 @@ impl EpisodesService for DefaultEpisodesService {
 
         let episodes = model::Entity::find()
             .filter(condition)
+            .filter(model::Column::DeletedAt.is_null())
             .all(&*self.db)
             .await?;
 
@@ -111,7 +140,7 @@ impl EpisodesService for DefaultEpisodesService {
     ) -> Result<ManyResponse<Episode>> {
         let page_num = page.unwrap_or(1);
 
-        let mut query = model::Entity::find();
+        let mut query = model::Entity::find().filter(model::Column::DeletedAt.is_null());
 
         if let Some(condition) = condition {
             if let Some(title) = condition.title {
@@ -122,6 +151,35 @@ impl EpisodesService for DefaultEpisodesService {
                 query = query.filter(model::Column::ShowId.eq(show_id));
             }
 
+            if let Some(season_number) = condition.season_number {
+                query = query.filter(model::Column::SeasonNumber.eq(season_number));
+            }
+
+            if let Some(episode_number) = condition.episode_number {
+                query = query.filter(model::Column::EpisodeNumber.eq(episode_number));
+            }
+
+            if let Some(explicit) = condition.explicit {
+                query = query.filter(model::Column::Explicit.eq(explicit));
+            }
+
+            if let Some(status) = condition.status {
+                query = query.filter(model::Column::Status.eq(status));
+            }
+
+            // Unpublished Episodes are only visible where the User can manage a Show's Episodes
+            if let Some(roles) = condition.published_or_granted {
+                let mut visible =
+                    Condition::any().add(model::Column::Status.eq(EpisodeStatus::Published));
+
+                if !roles.role_keys.is_empty() {
+                    visible =
+                        visible.add(model::Column::ShowId.in_subquery(roles.resource_ids("shows")));
+                }
+
+                query = query.filter(visible);
+            }
+
             if let Some(ids) = condition.ids_in {
                 let mut condition = Condition::any();
 
@@ -131,6 +189,23 @@ impl EpisodesService for DefaultEpisodesService {
 
                 query = query.filter(condition);
             }
+
+            // Roles may be granted for the Episode itself, or inherited from its Show
+            if condition.granted.is_some() || condition.show_granted.is_some() {
+                let mut granted = Condition::any();
+
+                if let Some(roles) = condition.granted {
+                    granted =
+                        granted.add(model::Column::Id.in_subquery(roles.resource_ids("episodes")));
+                }
+
+                if let Some(roles) = condition.show_granted {
+                    granted =
+                        granted.add(model::Column::ShowId.in_subquery(roles.resource_ids("shows")));
+                }
+
+                query = query.filter(granted);
+            }
         }
 
         if let Some(order_by) = order_by {
@@ -189,16 +264,53 @@ impl EpisodesService for DefaultEpisodesService {
     }
 
     async fn create(&self, input: &CreateEpisodeInput, with_show: &bool) -> Result<Episode> {
+        let status = input.status.unwrap_or_default();
+
+        // Episodes published right away are published as of now
+        let publish_at = match (input.publish_at, status) {
+            (None, EpisodeStatus::Published) => Some(Utc::now().naive_utc()),
+            (publish_at, _) => publish_at,
+        };
+
+        let txn = self.db.begin().await?;
+
         let episode = model::ActiveModel {
             title: Set(input.title.clone()),
             summary: Set(input.summary.clone()),
             picture: Set(input.picture.clone()),
+            media_url: Set(input.media_url.clone()),
+            media_type: Set(input.media_type.clone()),
+            media_length: Set(input.media_length),
+            duration: Set(input.duration),
+            season_number: Set(input.season_number),
+            episode_number: Set(input.episode_number),
+            explicit: Set(input.explicit.unwrap_or_default()),
+            status: Set(status),
+            publish_at: Set(publish_at),
             show_id: Set(input.show_id.clone()),
             ..Default::default()
         }
-        .insert(&*self.db)
+        .insert(&txn)
+        .await?;
+
+        record_event(
+            &txn,
+            &episode.show_id,
+            WebhookEventType::EpisodeCreated,
+            &episode,
+        )
         .await?;
 
+        txn.commit().await?;
+
+        self.events
+            .publish(DomainEvent::EpisodeCreated(episode.clone()));
+
+        if episode.is_published() {
+            self.events
+                .publish(DomainEvent::EpisodePublished(episode.clone()));
+        }
+
         let mut created: Episode = episode;
 
         if !with_show {
@@ -219,7 +331,8 @@ impl EpisodesService for DefaultEpisodesService {
         input: &UpdateEpisodeInput,
         with_show: &bool,
     ) -> Result<Episode> {
-        let query = model::Entity::find_by_id(id.to_owned());
+        let query =
+            model::Entity::find_by_id(id.to_owned()).filter(model::Column::DeletedAt.is_null());
 
         // Pull out the `Episode` and the related `Show`, if selected
         let (episode, show) = if *with_show {
@@ -233,6 +346,18 @@ impl EpisodesService for DefaultEpisodesService {
         }
         .ok_or_else(|| anyhow!("Unable to find Episode with id: {}", id))?;
 
+        // Reject the update if the Episode has changed since the client last retrieved it
+        if let Some(expected) = input.expected_updated_at {
+            if episode.updated_at != expected {
+                return Err(Conflict {
+                    current: Episode { show, ..episode },
+                }
+                .into());
+            }
+        }
+
+        let was_published = episode.is_published();
+
         let mut episode: model::ActiveModel = episode.into();
 
         if let Some(title) = &input.title {
@@ -251,11 +376,108 @@ impl EpisodesService for DefaultEpisodesService {
             Value(value) => episode.picture = Set(Some(value.clone())),
         }
 
+        // A new picture replaces the old thumbnail, which is only kept if one was generated for it
+        match &input.picture_thumbnail {
+            Undefined if input.picture.is_undefined() => (),
+            Undefined | Null => episode.picture_thumbnail = Set(None),
+            Value(value) => episode.picture_thumbnail = Set(Some(value.clone())),
+        }
+
+        match &input.media_url {
+            Undefined => (),
+            Null => episode.media_url = Set(None),
+            Value(value) => episode.media_url = Set(Some(value.clone())),
+        }
+
+        match &input.media_type {
+            Undefined => (),
+            Null => episode.media_type = Set(None),
+            Value(value) => episode.media_type = Set(Some(value.clone())),
+        }
+
+        match input.media_length {
+            Undefined => (),
+            Null => episode.media_length = Set(None),
+            Value(value) => episode.media_length = Set(Some(value)),
+        }
+
+        match input.duration {
+            Undefined => (),
+            Null => episode.duration = Set(None),
+            Value(value) => episode.duration = Set(Some(value)),
+        }
+
+        match input.season_number {
+            Undefined => (),
+            Null => episode.season_number = Set(None),
+            Value(value) => episode.season_number = Set(Some(value)),
+        }
+
+        match input.episode_number {
+            Undefined => (),
+            Null => episode.episode_number = Set(None),
+            Value(value) => episode.episode_number = Set(Some(value)),
+        }
+
+        if let Some(explicit) = input.explicit {
+            episode.explicit = Set(explicit);
+        }
+
+        if let Some(status) = input.status {
+            episode.status = Set(status);
+        }
+
+        match input.publish_at {
+            // Episodes published for the first time are published as of now
+            Undefined => {
+                if input.status == Some(EpisodeStatus::Published)
+                    && episode.publish_at.as_ref().is_none()
+                {
+                    episode.publish_at = Set(Some(Utc::now().naive_utc()));
+                }
+            }
+            Null => episode.publish_at = Set(None),
+            Value(value) => episode.publish_at = Set(Some(value)),
+        }
+
         if let Some(show_id) = &input.show_id {
             episode.show_id = Set(show_id.clone());
         }
 
-        let mut updated: Episode = episode.update(&*self.db).await?;
+        let txn = self.db.begin().await?;
+
+        // Make the write conditional as well, in case of a concurrent update since the read
+        let mut updated: Episode = update_if_unchanged(
+            &txn,
+            episode,
+            model::Column::Id.eq(id.to_owned()),
+            model::Column::UpdatedAt,
+            input.expected_updated_at,
+            || async {
+                self.get(id, with_show)
+                    .await?
+                    .ok_or_else(|| anyhow!("Unable to find Episode with id: {}", id))
+            },
+        )
+        .await?;
+
+        record_event(
+            &txn,
+            &updated.show_id,
+            WebhookEventType::EpisodeUpdated,
+            &updated,
+        )
+        .await?;
+
+        txn.commit().await?;
+
+        self.events
+            .publish(DomainEvent::EpisodeUpdated(updated.clone()));
+
+        if updated.is_published() && !was_published {
+            self.events
+                .publish(DomainEvent::EpisodePublished(updated.clone()));
+        }
 
         // Add back the Show from above
         updated.show = show;
@@ -264,15 +486,117 @@ impl EpisodesService for DefaultEpisodesService {
     }
 
     async fn delete(&self, id: &str) -> Result<()> {
-        let episode = model::Entity::find_by_id(id.to_owned())
-            .one(&*self.db)
+        let txn = self.db.begin().await?;
+
+        let deleted = model::Entity::update_many()
+            .col_expr(model::Column::DeletedAt, Expr::current_timestamp().into())
+            .filter(model::Column::Id.eq(id.to_owned()))
+            .filter(model::Column::DeletedAt.is_null())
+            .exec_with_returning(&txn)
             .await?
+            .pop()
             .ok_or_else(|| anyhow!("Unable to find Episode with id: {}", id))?;
 
-        let _result = episode.delete(&*self.db).await?;
+        record_event(
+            &txn,
+            &deleted.show_id,
+            WebhookEventType::EpisodeDeleted,
+            &deleted,
+        )
+        .await?;
+
+        txn.commit().await?;
+
+        self.events.publish(DomainEvent::EpisodeDeleted(deleted));
 
         Ok(())
     }
+
+    async fn get_deleted(&self, id: &str) -> Result<Option<Episode>> {
+        let episode = model::Entity::find_by_id(id.to_owned())
+            .filter(model::Column::DeletedAt.is_not_null())
+            .one(&*self.db)
+            .await?;
+
+        Ok(episode)
+    }
+
+    async fn restore(&self, id: &str) -> Result<Episode> {
+        let episode = model::Entity::find_by_id(id.to_owned())
+            .filter(model::Column::DeletedAt.is_not_null())
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| anyhow!("Unable to find deleted Episode with id: {}", id))?;
+
+        let mut episode: model::ActiveModel = episode.into();
+
+        episode.deleted_at = Set(None);
+
+        let txn = self.db.begin().await?;
+
+        let restored: Episode = episode.update(&txn).await?;
+
+        record_event(
+            &txn,
+            &restored.show_id,
+            WebhookEventType::EpisodeUpdated,
+            &restored,
+        )
+        .await?;
+
+        txn.commit().await?;
+
+        self.events
+            .publish(DomainEvent::EpisodeUpdated(restored.clone()));
+
+        Ok(restored)
+    }
+
+    async fn purge(&self, deleted_before: NaiveDateTime) -> Result<u64> {
+        let result = model::Entity::delete_many()
+            .filter(model::Column::DeletedAt.lt(deleted_before))
+            .exec(&*self.db)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+
+    async fn publish_scheduled(&self, due_by: NaiveDateTime) -> Result<Vec<Episode>> {
+        let txn = self.db.begin().await?;
+
+        let published = model::Entity::update_many()
+            .col_expr(
+                model::Column::Status,
+                Expr::value(EpisodeStatus::Published.into_value()),
+            )
+            .filter(model::Column::Status.eq(EpisodeStatus::Scheduled))
+            .filter(model::Column::PublishAt.lte(due_by))
+            .filter(model::Column::DeletedAt.is_null())
+            .exec_with_returning(&txn)
+            .await?;
+
+        for episode in &published {
+            record_event(
+                &txn,
+                &episode.show_id,
+                WebhookEventType::EpisodeUpdated,
+                episode,
+            )
+            .await?;
+        }
+
+        txn.commit().await?;
+
+        self.events
+            .publish_all(published.iter().flat_map(|episode| {
+                [
+                    DomainEvent::EpisodeUpdated(episode.clone()),
+                    DomainEvent::EpisodePublished(episode.clone()),
+                ]
+            }));
+
+        Ok(published)
+    }
 }
 
 /// A dataloader for `Episode` instances
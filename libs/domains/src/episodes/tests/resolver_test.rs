@@ -8,11 +8,12 @@ use std::sync::Arc;
 
 use crate::{
     episodes::{
-        model::Episode,
+        model::{Episode, EpisodeStatus},
         resolver::{EpisodesMutation, EpisodesQuery},
         service::{EpisodesService, MockEpisodesService},
     },
     shows::service::{MockShowsService, ShowLoader, ShowsService},
+    users::model::User,
 };
 
 fn init(
@@ -30,6 +31,7 @@ fn init(
     )
     .data(service)
     .data(DataLoader::new(show_loader, tokio::spawn))
+    .data(None::<User>)
     .finish()
 }
 
@@ -60,6 +62,7 @@ async fn test_episodes_resolver_get_simple() -> Result<()> {
     episode.id = episode_id.to_string();
     episode.title = episode_title.to_string();
     episode.show = Some(Faker.fake());
+    episode.status = EpisodeStatus::Published;
 
     let mut service = MockEpisodesService::new();
     service
@@ -84,3 +87,35 @@ async fn test_episodes_resolver_get_simple() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_episodes_resolver_get_unpublished() -> Result<()> {
+    let episode_id = "Test Episode";
+
+    let mut episode: Episode = Faker.fake();
+    episode.id = episode_id.to_string();
+    episode.show = Some(Faker.fake());
+    episode.status = EpisodeStatus::Draft;
+
+    let mut service = MockEpisodesService::new();
+    service
+        .expect_get()
+        .with(eq(episode_id), eq(&true))
+        .times(1)
+        .returning(move |_, _| Ok(Some(episode.clone())));
+
+    let schema = init(service);
+
+    let result = schema
+        .execute(
+            Request::new(GET_EPISODE).variables(Variables::from_json(json!({ "id": episode_id }))),
+        )
+        .await;
+
+    let data = result.data.into_json()?;
+
+    // Drafts are hidden from anonymous Users
+    assert_eq!(data["getEpisode"], serde_json::Value::Null);
+
+    Ok(())
+}
@@ -1,17 +1,20 @@
 use anyhow::Result;
 use async_graphql::MaybeUndefined::Undefined;
+use chrono::Utc;
 use fake::{Fake, Faker};
 use pretty_assertions::assert_eq;
-use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Transaction, Value};
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Statement, Transaction, Value};
 use std::sync::Arc;
 
 use crate::{
+    domain_events::{model::DomainEvent, publisher::DomainEventPublisher},
     episodes::{
-        model::Episode,
+        model::{Episode, EpisodeStatus},
         mutations::{CreateEpisodeInput, UpdateEpisodeInput},
         queries::{EpisodeCondition, EpisodesOrderBy},
         service::{DefaultEpisodesService, EpisodesService},
     },
+    role_grants::queries::GrantedRoles,
     shows::model::Show,
 };
 use caster_utils::pagination::ManyResponse;
@@ -31,7 +34,7 @@ async fn test_episodes_service_get() -> Result<()> {
             .into_connection(),
     );
 
-    let service = DefaultEpisodesService::new(&db);
+    let service = DefaultEpisodesService::new(&db, &DomainEventPublisher::default());
 
     let result = service.get(&episode.id, &false).await?;
 
@@ -47,7 +50,7 @@ async fn test_episodes_service_get() -> Result<()> {
         db.into_transaction_log(),
         vec![Transaction::from_sql_and_values(
             DatabaseBackend::Postgres,
-            r#"SELECT "episodes"."id", "episodes"."created_at", "episodes"."updated_at", "episodes"."title", "episodes"."summary", "episodes"."picture", "episodes"."show_id" FROM "episodes" WHERE "episodes"."id" = $1 LIMIT $2"#,
+            r#"SELECT "episodes"."id", "episodes"."created_at", "episodes"."updated_at", "episodes"."title", "episodes"."summary", "episodes"."picture", "episodes"."picture_thumbnail", "episodes"."media_url", "episodes"."media_type", "episodes"."media_length", "episodes"."duration", "episodes"."season_number", "episodes"."episode_number", "episodes"."explicit", "episodes"."status", "episodes"."publish_at", "episodes"."show_id", "episodes"."deleted_at" FROM "episodes" WHERE "episodes"."id" = $1 AND "episodes"."deleted_at" IS NULL LIMIT $2"#,
             vec![episode.id.into(), 1u64.into()]
         )]
     );
@@ -70,7 +73,7 @@ async fn test_episodes_service_get_with_related() -> Result<()> {
             .into_connection(),
     );
 
-    let service = DefaultEpisodesService::new(&db);
+    let service = DefaultEpisodesService::new(&db, &DomainEventPublisher::default());
 
     let result = service.get(&episode.id, &true).await?;
 
@@ -86,7 +89,7 @@ async fn test_episodes_service_get_with_related() -> Result<()> {
         db.into_transaction_log(),
         vec![Transaction::from_sql_and_values(
             DatabaseBackend::Postgres,
-            r#"SELECT "episodes"."id" AS "A_id", "episodes"."created_at" AS "A_created_at", "episodes"."updated_at" AS "A_updated_at", "episodes"."title" AS "A_title", "episodes"."summary" AS "A_summary", "episodes"."picture" AS "A_picture", "episodes"."show_id" AS "A_show_id", "shows"."id" AS "B_id", "shows"."created_at" AS "B_created_at", "shows"."updated_at" AS "B_updated_at", "shows"."title" AS "B_title", "shows"."summary" AS "B_summary", "shows"."picture" AS "B_picture" FROM "episodes" LEFT JOIN "shows" ON "episodes"."show_id" = "shows"."id" WHERE "episodes"."id" = $1 LIMIT $2"#,
+            r#"SELECT "episodes"."id" AS "A_id", "episodes"."created_at" AS "A_created_at", "episodes"."updated_at" AS "A_updated_at", "episodes"."title" AS "A_title", "episodes"."summary" AS "A_summary", "episodes"."picture" AS "A_picture", "episodes"."picture_thumbnail" AS "A_picture_thumbnail", "episodes"."media_url" AS "A_media_url", "episodes"."media_type" AS "A_media_type", "episodes"."media_length" AS "A_media_length", "episodes"."duration" AS "A_duration", "episodes"."season_number" AS "A_season_number", "episodes"."episode_number" AS "A_episode_number", "episodes"."explicit" AS "A_explicit", "episodes"."status" AS "A_status", "episodes"."publish_at" AS "A_publish_at", "episodes"."show_id" AS "A_show_id", "episodes"."deleted_at" AS "A_deleted_at", "shows"."id" AS "B_id", "shows"."created_at" AS "B_created_at", "shows"."updated_at" AS "B_updated_at", "shows"."title" AS "B_title", "shows"."summary" AS "B_summary", "shows"."picture" AS "B_picture", "shows"."picture_thumbnail" AS "B_picture_thumbnail", "shows"."deleted_at" AS "B_deleted_at" FROM "episodes" LEFT JOIN "shows" ON "episodes"."show_id" = "shows"."id" WHERE "episodes"."id" = $1 AND "episodes"."deleted_at" IS NULL LIMIT $2"#,
             vec![episode.id.into(), 1u64.into()]
         )]
     );
@@ -116,7 +119,7 @@ async fn test_episodes_service_get_many() -> Result<()> {
             .into_connection(),
     );
 
-    let service = DefaultEpisodesService::new(&db);
+    let service = DefaultEpisodesService::new(&db, &DomainEventPublisher::default());
 
     let result = service
         .get_many(
@@ -124,6 +127,7 @@ async fn test_episodes_service_get_many() -> Result<()> {
                 title: Some("Test Episode".to_string()),
                 show_id: None,
                 ids_in: None,
+                ..Default::default()
             }),
             None,
             None,
@@ -153,7 +157,7 @@ async fn test_episodes_service_get_many() -> Result<()> {
         db.into_transaction_log(),
         vec![Transaction::from_sql_and_values(
             DatabaseBackend::Postgres,
-            r#"SELECT "episodes"."id", "episodes"."created_at", "episodes"."updated_at", "episodes"."title", "episodes"."summary", "episodes"."picture", "episodes"."show_id" FROM "episodes" WHERE "episodes"."title" = $1"#,
+            r#"SELECT "episodes"."id", "episodes"."created_at", "episodes"."updated_at", "episodes"."title", "episodes"."summary", "episodes"."picture", "episodes"."picture_thumbnail", "episodes"."media_url", "episodes"."media_type", "episodes"."media_length", "episodes"."duration", "episodes"."season_number", "episodes"."episode_number", "episodes"."explicit", "episodes"."status", "episodes"."publish_at", "episodes"."show_id", "episodes"."deleted_at" FROM "episodes" WHERE "episodes"."deleted_at" IS NULL AND "episodes"."title" = $1"#,
             vec!["Test Episode".into()]
         )]
     );
@@ -161,6 +165,132 @@ async fn test_episodes_service_get_many() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_episodes_service_get_many_by_number() -> Result<()> {
+    let mut episode: Episode = Faker.fake();
+    episode.season_number = Some(2);
+    episode.episode_number = Some(1);
+    episode.show = None;
+
+    let mut other_episode: Episode = Faker.fake();
+    other_episode.season_number = Some(2);
+    other_episode.episode_number = Some(2);
+    other_episode.show = None;
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![other_episode.clone(), episode.clone()]])
+            .into_connection(),
+    );
+
+    let service = DefaultEpisodesService::new(&db, &DomainEventPublisher::default());
+
+    let result = service
+        .get_many(
+            Some(EpisodeCondition {
+                show_id: Some(episode.show_id.clone()),
+                season_number: Some(2),
+                explicit: Some(false),
+                ..Default::default()
+            }),
+            Some(vec![EpisodesOrderBy::EpisodeNumberDesc]),
+            None,
+            None,
+            &false,
+        )
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(
+        result,
+        ManyResponse {
+            data: vec![other_episode, episode.clone()],
+            count: 2,
+            total: 2,
+            page: 1,
+            page_count: 1,
+        }
+    );
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"SELECT "episodes"."id", "episodes"."created_at", "episodes"."updated_at", "episodes"."title", "episodes"."summary", "episodes"."picture", "episodes"."picture_thumbnail", "episodes"."media_url", "episodes"."media_type", "episodes"."media_length", "episodes"."duration", "episodes"."season_number", "episodes"."episode_number", "episodes"."explicit", "episodes"."status", "episodes"."publish_at", "episodes"."show_id", "episodes"."deleted_at" FROM "episodes" WHERE "episodes"."deleted_at" IS NULL AND "episodes"."show_id" = $1 AND "episodes"."season_number" = $2 AND "episodes"."explicit" = $3 ORDER BY "episodes"."episode_number" DESC"#,
+            vec![episode.show_id.into(), 2i32.into(), false.into()]
+        )]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_episodes_service_get_many_granted() -> Result<()> {
+    let mut episode: Episode = Faker.fake();
+    episode.show = None;
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![episode.clone()]])
+            .into_connection(),
+    );
+
+    let service = DefaultEpisodesService::new(&db, &DomainEventPublisher::default());
+
+    let result = service
+        .get_many(
+            Some(EpisodeCondition {
+                permission: Some("episode_chat".to_string()),
+                granted: Some(GrantedRoles {
+                    user_id: "test-user-id".to_string(),
+                    role_keys: vec!["guest".to_string()],
+                }),
+                show_granted: Some(GrantedRoles {
+                    user_id: "test-user-id".to_string(),
+                    role_keys: vec!["guest".to_string(), "manager".to_string()],
+                }),
+                ..Default::default()
+            }),
+            None,
+            None,
+            None,
+            &false,
+        )
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result.data, vec![episode]);
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"SELECT "episodes"."id", "episodes"."created_at", "episodes"."updated_at", "episodes"."title", "episodes"."summary", "episodes"."picture", "episodes"."picture_thumbnail", "episodes"."media_url", "episodes"."media_type", "episodes"."media_length", "episodes"."duration", "episodes"."season_number", "episodes"."episode_number", "episodes"."explicit", "episodes"."status", "episodes"."publish_at", "episodes"."show_id", "episodes"."deleted_at" FROM "episodes" WHERE "episodes"."deleted_at" IS NULL AND ("episodes"."id" IN (SELECT "role_grants"."resource_id" FROM "role_grants" WHERE "role_grants"."user_id" = $1 AND "role_grants"."resource_table" = $2 AND "role_grants"."role_key" IN ($3)) OR "episodes"."show_id" IN (SELECT "role_grants"."resource_id" FROM "role_grants" WHERE "role_grants"."user_id" = $4 AND "role_grants"."resource_table" = $5 AND "role_grants"."role_key" IN ($6, $7)))"#,
+            vec![
+                "test-user-id".into(),
+                "episodes".into(),
+                "guest".into(),
+                "test-user-id".into(),
+                "shows".into(),
+                "guest".into(),
+                "manager".into(),
+            ]
+        )]
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_episodes_service_get_many_with_related() -> Result<()> {
     let mut show: Show = Faker.fake();
@@ -186,7 +316,7 @@ async fn test_episodes_service_get_many_with_related() -> Result<()> {
             .into_connection(),
     );
 
-    let service = DefaultEpisodesService::new(&db);
+    let service = DefaultEpisodesService::new(&db, &DomainEventPublisher::default());
 
     let result = service
         .get_many(
@@ -194,6 +324,7 @@ async fn test_episodes_service_get_many_with_related() -> Result<()> {
                 title: Some("Test Episode".to_string()),
                 show_id: None,
                 ids_in: None,
+                ..Default::default()
             }),
             None,
             None,
@@ -223,7 +354,7 @@ async fn test_episodes_service_get_many_with_related() -> Result<()> {
         db.into_transaction_log(),
         vec![Transaction::from_sql_and_values(
             DatabaseBackend::Postgres,
-            r#"SELECT "episodes"."id" AS "A_id", "episodes"."created_at" AS "A_created_at", "episodes"."updated_at" AS "A_updated_at", "episodes"."title" AS "A_title", "episodes"."summary" AS "A_summary", "episodes"."picture" AS "A_picture", "episodes"."show_id" AS "A_show_id", "shows"."id" AS "B_id", "shows"."created_at" AS "B_created_at", "shows"."updated_at" AS "B_updated_at", "shows"."title" AS "B_title", "shows"."summary" AS "B_summary", "shows"."picture" AS "B_picture" FROM "episodes" LEFT JOIN "shows" ON "episodes"."show_id" = "shows"."id" WHERE "episodes"."title" = $1"#,
+            r#"SELECT "episodes"."id" AS "A_id", "episodes"."created_at" AS "A_created_at", "episodes"."updated_at" AS "A_updated_at", "episodes"."title" AS "A_title", "episodes"."summary" AS "A_summary", "episodes"."picture" AS "A_picture", "episodes"."picture_thumbnail" AS "A_picture_thumbnail", "episodes"."media_url" AS "A_media_url", "episodes"."media_type" AS "A_media_type", "episodes"."media_length" AS "A_media_length", "episodes"."duration" AS "A_duration", "episodes"."season_number" AS "A_season_number", "episodes"."episode_number" AS "A_episode_number", "episodes"."explicit" AS "A_explicit", "episodes"."status" AS "A_status", "episodes"."publish_at" AS "A_publish_at", "episodes"."show_id" AS "A_show_id", "episodes"."deleted_at" AS "A_deleted_at", "shows"."id" AS "B_id", "shows"."created_at" AS "B_created_at", "shows"."updated_at" AS "B_updated_at", "shows"."title" AS "B_title", "shows"."summary" AS "B_summary", "shows"."picture" AS "B_picture", "shows"."picture_thumbnail" AS "B_picture_thumbnail", "shows"."deleted_at" AS "B_deleted_at" FROM "episodes" LEFT JOIN "shows" ON "episodes"."show_id" = "shows"."id" WHERE "episodes"."deleted_at" IS NULL AND "episodes"."title" = $1"#,
             vec!["Test Episode".into()]
         )]
     );
@@ -275,7 +406,7 @@ async fn test_episodes_service_get_many_pagination() -> Result<()> {
             .into_connection(),
     );
 
-    let service = DefaultEpisodesService::new(&db);
+    let service = DefaultEpisodesService::new(&db, &DomainEventPublisher::default());
 
     let result = service
         .get_many(
@@ -309,12 +440,12 @@ async fn test_episodes_service_get_many_pagination() -> Result<()> {
         vec![
             Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT COUNT(*) AS num_items FROM (SELECT "episodes"."id", "episodes"."created_at", "episodes"."updated_at", "episodes"."title", "episodes"."summary", "episodes"."picture", "episodes"."show_id" FROM "episodes" ORDER BY "episodes"."created_at" DESC) AS "sub_query""#,
+                r#"SELECT COUNT(*) AS num_items FROM (SELECT "episodes"."id", "episodes"."created_at", "episodes"."updated_at", "episodes"."title", "episodes"."summary", "episodes"."picture", "episodes"."picture_thumbnail", "episodes"."media_url", "episodes"."media_type", "episodes"."media_length", "episodes"."duration", "episodes"."season_number", "episodes"."episode_number", "episodes"."explicit", "episodes"."status", "episodes"."publish_at", "episodes"."show_id", "episodes"."deleted_at" FROM "episodes" WHERE "episodes"."deleted_at" IS NULL ORDER BY "episodes"."created_at" DESC) AS "sub_query""#,
                 vec![]
             ),
             Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "episodes"."id", "episodes"."created_at", "episodes"."updated_at", "episodes"."title", "episodes"."summary", "episodes"."picture", "episodes"."show_id" FROM "episodes" ORDER BY "episodes"."created_at" DESC LIMIT $1 OFFSET $2"#,
+                r#"SELECT "episodes"."id", "episodes"."created_at", "episodes"."updated_at", "episodes"."title", "episodes"."summary", "episodes"."picture", "episodes"."picture_thumbnail", "episodes"."media_url", "episodes"."media_type", "episodes"."media_length", "episodes"."duration", "episodes"."season_number", "episodes"."episode_number", "episodes"."explicit", "episodes"."status", "episodes"."publish_at", "episodes"."show_id", "episodes"."deleted_at" FROM "episodes" WHERE "episodes"."deleted_at" IS NULL ORDER BY "episodes"."created_at" DESC LIMIT $1 OFFSET $2"#,
                 vec![5u64.into(), 5u64.into()]
             )
         ]
@@ -367,7 +498,7 @@ async fn test_episodes_service_get_many_pagination_with_related() -> Result<()>
             .into_connection(),
     );
 
-    let service = DefaultEpisodesService::new(&db);
+    let service = DefaultEpisodesService::new(&db, &DomainEventPublisher::default());
 
     let result = service
         .get_many(
@@ -404,12 +535,12 @@ async fn test_episodes_service_get_many_pagination_with_related() -> Result<()>
         vec![
             Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT COUNT(*) AS num_items FROM (SELECT "episodes"."id" AS "A_id", "episodes"."created_at" AS "A_created_at", "episodes"."updated_at" AS "A_updated_at", "episodes"."title" AS "A_title", "episodes"."summary" AS "A_summary", "episodes"."picture" AS "A_picture", "episodes"."show_id" AS "A_show_id", "shows"."id" AS "B_id", "shows"."created_at" AS "B_created_at", "shows"."updated_at" AS "B_updated_at", "shows"."title" AS "B_title", "shows"."summary" AS "B_summary", "shows"."picture" AS "B_picture" FROM "episodes" LEFT JOIN "shows" ON "episodes"."show_id" = "shows"."id" ORDER BY "episodes"."created_at" DESC) AS "sub_query""#,
+                r#"SELECT COUNT(*) AS num_items FROM (SELECT "episodes"."id" AS "A_id", "episodes"."created_at" AS "A_created_at", "episodes"."updated_at" AS "A_updated_at", "episodes"."title" AS "A_title", "episodes"."summary" AS "A_summary", "episodes"."picture" AS "A_picture", "episodes"."picture_thumbnail" AS "A_picture_thumbnail", "episodes"."media_url" AS "A_media_url", "episodes"."media_type" AS "A_media_type", "episodes"."media_length" AS "A_media_length", "episodes"."duration" AS "A_duration", "episodes"."season_number" AS "A_season_number", "episodes"."episode_number" AS "A_episode_number", "episodes"."explicit" AS "A_explicit", "episodes"."status" AS "A_status", "episodes"."publish_at" AS "A_publish_at", "episodes"."show_id" AS "A_show_id", "episodes"."deleted_at" AS "A_deleted_at", "shows"."id" AS "B_id", "shows"."created_at" AS "B_created_at", "shows"."updated_at" AS "B_updated_at", "shows"."title" AS "B_title", "shows"."summary" AS "B_summary", "shows"."picture" AS "B_picture", "shows"."picture_thumbnail" AS "B_picture_thumbnail", "shows"."deleted_at" AS "B_deleted_at" FROM "episodes" LEFT JOIN "shows" ON "episodes"."show_id" = "shows"."id" WHERE "episodes"."deleted_at" IS NULL ORDER BY "episodes"."created_at" DESC) AS "sub_query""#,
                 vec![]
             ),
             Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "episodes"."id" AS "A_id", "episodes"."created_at" AS "A_created_at", "episodes"."updated_at" AS "A_updated_at", "episodes"."title" AS "A_title", "episodes"."summary" AS "A_summary", "episodes"."picture" AS "A_picture", "episodes"."show_id" AS "A_show_id", "shows"."id" AS "B_id", "shows"."created_at" AS "B_created_at", "shows"."updated_at" AS "B_updated_at", "shows"."title" AS "B_title", "shows"."summary" AS "B_summary", "shows"."picture" AS "B_picture" FROM "episodes" LEFT JOIN "shows" ON "episodes"."show_id" = "shows"."id" ORDER BY "episodes"."created_at" DESC LIMIT $1 OFFSET $2"#,
+                r#"SELECT "episodes"."id" AS "A_id", "episodes"."created_at" AS "A_created_at", "episodes"."updated_at" AS "A_updated_at", "episodes"."title" AS "A_title", "episodes"."summary" AS "A_summary", "episodes"."picture" AS "A_picture", "episodes"."picture_thumbnail" AS "A_picture_thumbnail", "episodes"."media_url" AS "A_media_url", "episodes"."media_type" AS "A_media_type", "episodes"."media_length" AS "A_media_length", "episodes"."duration" AS "A_duration", "episodes"."season_number" AS "A_season_number", "episodes"."episode_number" AS "A_episode_number", "episodes"."explicit" AS "A_explicit", "episodes"."status" AS "A_status", "episodes"."publish_at" AS "A_publish_at", "episodes"."show_id" AS "A_show_id", "episodes"."deleted_at" AS "A_deleted_at", "shows"."id" AS "B_id", "shows"."created_at" AS "B_created_at", "shows"."updated_at" AS "B_updated_at", "shows"."title" AS "B_title", "shows"."summary" AS "B_summary", "shows"."picture" AS "B_picture", "shows"."picture_thumbnail" AS "B_picture_thumbnail", "shows"."deleted_at" AS "B_deleted_at" FROM "episodes" LEFT JOIN "shows" ON "episodes"."show_id" = "shows"."id" WHERE "episodes"."deleted_at" IS NULL ORDER BY "episodes"."created_at" DESC LIMIT $1 OFFSET $2"#,
                 vec![5u64.into(), 5u64.into()]
             )
         ]
@@ -425,16 +556,21 @@ async fn test_episodes_service_create() -> Result<()> {
 
     let mut episode: Episode = Faker.fake();
     episode.title = "Test Episode".to_string();
+    episode.status = EpisodeStatus::Draft;
     episode.show_id = show.id.clone();
     episode.show = None;
 
     let db = Arc::new(
         MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results(vec![vec![episode.clone()]])
+            .append_exec_results(vec![MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            }])
             .into_connection(),
     );
 
-    let service = DefaultEpisodesService::new(&db);
+    let service = DefaultEpisodesService::new(&db, &DomainEventPublisher::default());
 
     let result = service
         .create(
@@ -442,6 +578,15 @@ async fn test_episodes_service_create() -> Result<()> {
                 title: episode.title.clone(),
                 summary: episode.summary.clone(),
                 picture: episode.picture.clone(),
+                media_url: episode.media_url.clone(),
+                media_type: episode.media_type.clone(),
+                media_length: episode.media_length,
+                duration: episode.duration,
+                season_number: episode.season_number,
+                episode_number: episode.episode_number,
+                explicit: Some(episode.explicit),
+                status: Some(episode.status),
+                publish_at: episode.publish_at,
                 show_id: show.id.clone(),
             },
             &false,
@@ -455,19 +600,40 @@ async fn test_episodes_service_create() -> Result<()> {
 
     assert_eq!(result, episode);
 
+    let show_id = episode.show_id.clone();
+    let payload = serde_json::to_value(&episode)?;
+
     // Check the transaction log
     assert_eq!(
         db.into_transaction_log(),
-        vec![Transaction::from_sql_and_values(
-            DatabaseBackend::Postgres,
-            r#"INSERT INTO "episodes" ("title", "summary", "picture", "show_id") VALUES ($1, $2, $3, $4) RETURNING "id", "created_at", "updated_at", "title", "summary", "picture", "show_id""#,
-            vec![
-                episode.title.into(),
-                episode.summary.into(),
-                episode.picture.into(),
-                episode.show_id.into(),
-            ]
-        )]
+        vec![Transaction::many(vec![
+            Statement::from_string(DatabaseBackend::Postgres, "BEGIN".to_string()),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"INSERT INTO "episodes" ("title", "summary", "picture", "media_url", "media_type", "media_length", "duration", "season_number", "episode_number", "explicit", "status", "publish_at", "show_id") VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13) RETURNING "id", "created_at", "updated_at", "title", "summary", "picture", "picture_thumbnail", "media_url", "media_type", "media_length", "duration", "season_number", "episode_number", "explicit", "status", "publish_at", "show_id", "deleted_at""#,
+                vec![
+                    episode.title.into(),
+                    episode.summary.into(),
+                    episode.picture.into(),
+                    episode.media_url.into(),
+                    episode.media_type.into(),
+                    episode.media_length.into(),
+                    episode.duration.into(),
+                    episode.season_number.into(),
+                    episode.episode_number.into(),
+                    episode.explicit.into(),
+                    episode.status.into(),
+                    episode.publish_at.into(),
+                    episode.show_id.into(),
+                ]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"INSERT INTO "outbox_events" ("show_id", "event_type", "payload") VALUES ($1, $2, $3)"#,
+                vec![show_id.into(), "episode.created".into(), payload.into()]
+            ),
+            Statement::from_string(DatabaseBackend::Postgres, "COMMIT".to_string()),
+        ])]
     );
 
     Ok(())
@@ -480,6 +646,7 @@ async fn test_episodes_service_create_with_related() -> Result<()> {
 
     let mut episode: Episode = Faker.fake();
     episode.title = "Test Episode".to_string();
+    episode.status = EpisodeStatus::Draft;
     episode.show_id = show.id.clone();
     episode.show = Some(show.clone());
 
@@ -487,10 +654,14 @@ async fn test_episodes_service_create_with_related() -> Result<()> {
         MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results(vec![vec![episode.clone()]])
             .append_query_results(vec![vec![show.clone()]])
+            .append_exec_results(vec![MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            }])
             .into_connection(),
     );
 
-    let service = DefaultEpisodesService::new(&db);
+    let service = DefaultEpisodesService::new(&db, &DomainEventPublisher::default());
 
     let result = service
         .create(
@@ -498,6 +669,15 @@ async fn test_episodes_service_create_with_related() -> Result<()> {
                 title: episode.title.clone(),
                 summary: episode.summary.clone(),
                 picture: episode.picture.clone(),
+                media_url: episode.media_url.clone(),
+                media_type: episode.media_type.clone(),
+                media_length: episode.media_length,
+                duration: episode.duration,
+                season_number: episode.season_number,
+                episode_number: episode.episode_number,
+                explicit: Some(episode.explicit),
+                status: Some(episode.status),
+                publish_at: episode.publish_at,
                 show_id: show.id.clone(),
             },
             &true,
@@ -511,23 +691,47 @@ async fn test_episodes_service_create_with_related() -> Result<()> {
 
     assert_eq!(result, episode);
 
+    let show_id = episode.show_id.clone();
+    let payload = serde_json::to_value(&Episode {
+        show: None,
+        ..episode.clone()
+    })?;
+
     // Check the transaction log
     assert_eq!(
         db.into_transaction_log(),
         vec![
+            Transaction::many(vec![
+                Statement::from_string(DatabaseBackend::Postgres, "BEGIN".to_string()),
+                Statement::from_sql_and_values(
+                    DatabaseBackend::Postgres,
+                    r#"INSERT INTO "episodes" ("title", "summary", "picture", "media_url", "media_type", "media_length", "duration", "season_number", "episode_number", "explicit", "status", "publish_at", "show_id") VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13) RETURNING "id", "created_at", "updated_at", "title", "summary", "picture", "picture_thumbnail", "media_url", "media_type", "media_length", "duration", "season_number", "episode_number", "explicit", "status", "publish_at", "show_id", "deleted_at""#,
+                    vec![
+                        episode.title.into(),
+                        episode.summary.into(),
+                        episode.picture.into(),
+                        episode.media_url.into(),
+                        episode.media_type.into(),
+                        episode.media_length.into(),
+                        episode.duration.into(),
+                        episode.season_number.into(),
+                        episode.episode_number.into(),
+                        episode.explicit.into(),
+                        episode.status.into(),
+                        episode.publish_at.into(),
+                        episode.show_id.into(),
+                    ]
+                ),
+                Statement::from_sql_and_values(
+                    DatabaseBackend::Postgres,
+                    r#"INSERT INTO "outbox_events" ("show_id", "event_type", "payload") VALUES ($1, $2, $3)"#,
+                    vec![show_id.into(), "episode.created".into(), payload.into()]
+                ),
+                Statement::from_string(DatabaseBackend::Postgres, "COMMIT".to_string()),
+            ]),
             Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"INSERT INTO "episodes" ("title", "summary", "picture", "show_id") VALUES ($1, $2, $3, $4) RETURNING "id", "created_at", "updated_at", "title", "summary", "picture", "show_id""#,
-                vec![
-                    episode.title.into(),
-                    episode.summary.into(),
-                    episode.picture.into(),
-                    episode.show_id.into(),
-                ]
-            ),
-            Transaction::from_sql_and_values(
-                DatabaseBackend::Postgres,
-                r#"SELECT "shows"."id", "shows"."created_at", "shows"."updated_at", "shows"."title", "shows"."summary", "shows"."picture" FROM "shows" WHERE "shows"."id" = $1 LIMIT $2"#,
+                r#"SELECT "shows"."id", "shows"."created_at", "shows"."updated_at", "shows"."title", "shows"."summary", "shows"."picture", "shows"."picture_thumbnail", "shows"."deleted_at" FROM "shows" WHERE "shows"."id" = $1 LIMIT $2"#,
                 vec![show.id.into(), 1u64.into()]
             )
         ]
@@ -553,10 +757,14 @@ async fn test_episodes_service_update() -> Result<()> {
     let db = Arc::new(
         MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results(vec![vec![episode.clone()], vec![updated.clone()]])
+            .append_exec_results(vec![MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            }])
             .into_connection(),
     );
 
-    let service = DefaultEpisodesService::new(&db);
+    let service = DefaultEpisodesService::new(&db, &DomainEventPublisher::default());
 
     let result = service
         .update(
@@ -565,7 +773,18 @@ async fn test_episodes_service_update() -> Result<()> {
                 title: Some(updated.title.clone()),
                 summary: Undefined,
                 picture: Undefined,
+                picture_thumbnail: Undefined,
+                media_url: Undefined,
+                media_type: Undefined,
+                media_length: Undefined,
+                duration: Undefined,
+                season_number: Undefined,
+                episode_number: Undefined,
+                explicit: None,
+                status: None,
+                publish_at: Undefined,
                 show_id: Some(show.id.clone()),
+                expected_updated_at: None,
             },
             &false,
         )
@@ -578,20 +797,32 @@ async fn test_episodes_service_update() -> Result<()> {
 
     assert_eq!(result, updated.clone());
 
+    let show_id = updated.show_id.clone();
+    let payload = serde_json::to_value(&updated)?;
+
     // Check the transaction log
     assert_eq!(
         db.into_transaction_log(),
         vec![
             Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "episodes"."id", "episodes"."created_at", "episodes"."updated_at", "episodes"."title", "episodes"."summary", "episodes"."picture", "episodes"."show_id" FROM "episodes" WHERE "episodes"."id" = $1 LIMIT $2"#,
+                r#"SELECT "episodes"."id", "episodes"."created_at", "episodes"."updated_at", "episodes"."title", "episodes"."summary", "episodes"."picture", "episodes"."picture_thumbnail", "episodes"."media_url", "episodes"."media_type", "episodes"."media_length", "episodes"."duration", "episodes"."season_number", "episodes"."episode_number", "episodes"."explicit", "episodes"."status", "episodes"."publish_at", "episodes"."show_id", "episodes"."deleted_at" FROM "episodes" WHERE "episodes"."id" = $1 AND "episodes"."deleted_at" IS NULL LIMIT $2"#,
                 vec![show.id.clone().into(), 1u64.into()]
             ),
-            Transaction::from_sql_and_values(
-                DatabaseBackend::Postgres,
-                r#"UPDATE "episodes" SET "title" = $1, "show_id" = $2 WHERE "episodes"."id" = $3 RETURNING "id", "created_at", "updated_at", "title", "summary", "picture", "show_id""#,
-                vec![updated.title.into(), show.id.into(), episode.id.into()]
-            )
+            Transaction::many(vec![
+                Statement::from_string(DatabaseBackend::Postgres, "BEGIN".to_string()),
+                Statement::from_sql_and_values(
+                    DatabaseBackend::Postgres,
+                    r#"UPDATE "episodes" SET "title" = $1, "show_id" = $2 WHERE "episodes"."id" = $3 RETURNING "id", "created_at", "updated_at", "title", "summary", "picture", "picture_thumbnail", "media_url", "media_type", "media_length", "duration", "season_number", "episode_number", "explicit", "status", "publish_at", "show_id", "deleted_at""#,
+                    vec![updated.title.into(), show.id.into(), episode.id.into()]
+                ),
+                Statement::from_sql_and_values(
+                    DatabaseBackend::Postgres,
+                    r#"INSERT INTO "outbox_events" ("show_id", "event_type", "payload") VALUES ($1, $2, $3)"#,
+                    vec![show_id.into(), "episode.updated".into(), payload.into()]
+                ),
+                Statement::from_string(DatabaseBackend::Postgres, "COMMIT".to_string()),
+            ])
         ]
     );
 
@@ -616,10 +847,14 @@ async fn test_episodes_service_update_with_related() -> Result<()> {
         MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results(vec![vec![(episode.clone(), show.clone())]])
             .append_query_results(vec![vec![updated.clone()]])
+            .append_exec_results(vec![MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            }])
             .into_connection(),
     );
 
-    let service = DefaultEpisodesService::new(&db);
+    let service = DefaultEpisodesService::new(&db, &DomainEventPublisher::default());
 
     let result = service
         .update(
@@ -628,7 +863,18 @@ async fn test_episodes_service_update_with_related() -> Result<()> {
                 title: Some(updated.title.clone()),
                 summary: Undefined,
                 picture: Undefined,
+                picture_thumbnail: Undefined,
+                media_url: Undefined,
+                media_type: Undefined,
+                media_length: Undefined,
+                duration: Undefined,
+                season_number: Undefined,
+                episode_number: Undefined,
+                explicit: None,
+                status: None,
+                publish_at: Undefined,
                 show_id: Some(show.id.clone()),
+                expected_updated_at: None,
             },
             &true,
         )
@@ -641,20 +887,35 @@ async fn test_episodes_service_update_with_related() -> Result<()> {
 
     assert_eq!(result, updated.clone());
 
+    let show_id = updated.show_id.clone();
+    let payload = serde_json::to_value(&Episode {
+        show: None,
+        ..updated.clone()
+    })?;
+
     // Check the transaction log
     assert_eq!(
         db.into_transaction_log(),
         vec![
             Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "episodes"."id" AS "A_id", "episodes"."created_at" AS "A_created_at", "episodes"."updated_at" AS "A_updated_at", "episodes"."title" AS "A_title", "episodes"."summary" AS "A_summary", "episodes"."picture" AS "A_picture", "episodes"."show_id" AS "A_show_id", "shows"."id" AS "B_id", "shows"."created_at" AS "B_created_at", "shows"."updated_at" AS "B_updated_at", "shows"."title" AS "B_title", "shows"."summary" AS "B_summary", "shows"."picture" AS "B_picture" FROM "episodes" LEFT JOIN "shows" ON "episodes"."show_id" = "shows"."id" WHERE "episodes"."id" = $1 LIMIT $2"#,
+                r#"SELECT "episodes"."id" AS "A_id", "episodes"."created_at" AS "A_created_at", "episodes"."updated_at" AS "A_updated_at", "episodes"."title" AS "A_title", "episodes"."summary" AS "A_summary", "episodes"."picture" AS "A_picture", "episodes"."picture_thumbnail" AS "A_picture_thumbnail", "episodes"."media_url" AS "A_media_url", "episodes"."media_type" AS "A_media_type", "episodes"."media_length" AS "A_media_length", "episodes"."duration" AS "A_duration", "episodes"."season_number" AS "A_season_number", "episodes"."episode_number" AS "A_episode_number", "episodes"."explicit" AS "A_explicit", "episodes"."status" AS "A_status", "episodes"."publish_at" AS "A_publish_at", "episodes"."show_id" AS "A_show_id", "episodes"."deleted_at" AS "A_deleted_at", "shows"."id" AS "B_id", "shows"."created_at" AS "B_created_at", "shows"."updated_at" AS "B_updated_at", "shows"."title" AS "B_title", "shows"."summary" AS "B_summary", "shows"."picture" AS "B_picture", "shows"."picture_thumbnail" AS "B_picture_thumbnail", "shows"."deleted_at" AS "B_deleted_at" FROM "episodes" LEFT JOIN "shows" ON "episodes"."show_id" = "shows"."id" WHERE "episodes"."id" = $1 AND "episodes"."deleted_at" IS NULL LIMIT $2"#,
                 vec![show.id.clone().into(), 1u64.into()]
             ),
-            Transaction::from_sql_and_values(
-                DatabaseBackend::Postgres,
-                r#"UPDATE "episodes" SET "title" = $1, "show_id" = $2 WHERE "episodes"."id" = $3 RETURNING "id", "created_at", "updated_at", "title", "summary", "picture", "show_id""#,
-                vec![updated.title.into(), show.id.into(), episode.id.into()]
-            )
+            Transaction::many(vec![
+                Statement::from_string(DatabaseBackend::Postgres, "BEGIN".to_string()),
+                Statement::from_sql_and_values(
+                    DatabaseBackend::Postgres,
+                    r#"UPDATE "episodes" SET "title" = $1, "show_id" = $2 WHERE "episodes"."id" = $3 RETURNING "id", "created_at", "updated_at", "title", "summary", "picture", "picture_thumbnail", "media_url", "media_type", "media_length", "duration", "season_number", "episode_number", "explicit", "status", "publish_at", "show_id", "deleted_at""#,
+                    vec![updated.title.into(), show.id.into(), episode.id.into()]
+                ),
+                Statement::from_sql_and_values(
+                    DatabaseBackend::Postgres,
+                    r#"INSERT INTO "outbox_events" ("show_id", "event_type", "payload") VALUES ($1, $2, $3)"#,
+                    vec![show_id.into(), "episode.updated".into(), payload.into()]
+                ),
+                Statement::from_string(DatabaseBackend::Postgres, "COMMIT".to_string()),
+            ])
         ]
     );
 
@@ -670,9 +931,14 @@ async fn test_episodes_service_delete() -> Result<()> {
     episode.title = "Test Episode".to_string();
     episode.show = None;
 
+    let deleted = Episode {
+        deleted_at: Some(Utc::now().naive_utc()),
+        ..episode.clone()
+    };
+
     let db = Arc::new(
         MockDatabase::new(DatabaseBackend::Postgres)
-            .append_query_results(vec![vec![episode.clone()]])
+            .append_query_results(vec![vec![deleted.clone()]])
             .append_exec_results(vec![MockExecResult {
                 last_insert_id: 0,
                 rows_affected: 1,
@@ -680,7 +946,7 @@ async fn test_episodes_service_delete() -> Result<()> {
             .into_connection(),
     );
 
-    let service = DefaultEpisodesService::new(&db);
+    let service = DefaultEpisodesService::new(&db, &DomainEventPublisher::default());
 
     service.delete(&episode.id).await?;
 
@@ -689,21 +955,92 @@ async fn test_episodes_service_delete() -> Result<()> {
 
     let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
 
+    let show_id = deleted.show_id.clone();
+    let payload = serde_json::to_value(&deleted)?;
+
     // Check the transaction log
     assert_eq!(
         db.into_transaction_log(),
-        vec![
-            Transaction::from_sql_and_values(
+        vec![Transaction::many(vec![
+            Statement::from_string(DatabaseBackend::Postgres, "BEGIN".to_string()),
+            Statement::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "episodes"."id", "episodes"."created_at", "episodes"."updated_at", "episodes"."title", "episodes"."summary", "episodes"."picture", "episodes"."show_id" FROM "episodes" WHERE "episodes"."id" = $1 LIMIT $2"#,
-                vec![episode.id.clone().into(), 1u64.into()]
+                r#"UPDATE "episodes" SET "deleted_at" = CURRENT_TIMESTAMP WHERE "episodes"."id" = $1 AND "episodes"."deleted_at" IS NULL RETURNING "id", "created_at", "updated_at", "title", "summary", "picture", "picture_thumbnail", "media_url", "media_type", "media_length", "duration", "season_number", "episode_number", "explicit", "status", "publish_at", "show_id", "deleted_at""#,
+                vec![episode.id.into()]
             ),
-            Transaction::from_sql_and_values(
+            Statement::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"DELETE FROM "episodes" WHERE "episodes"."id" = $1"#,
-                vec![episode.id.into()]
-            )
-        ]
+                r#"INSERT INTO "outbox_events" ("show_id", "event_type", "payload") VALUES ($1, $2, $3)"#,
+                vec![show_id.into(), "episode.deleted".into(), payload.into()]
+            ),
+            Statement::from_string(DatabaseBackend::Postgres, "COMMIT".to_string()),
+        ])]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_episodes_service_publish_scheduled() -> Result<()> {
+    let due_by = Utc::now().naive_utc();
+
+    let mut episode: Episode = Faker.fake();
+    episode.status = EpisodeStatus::Published;
+    episode.publish_at = Some(due_by);
+    episode.show = None;
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![episode.clone()]])
+            .append_exec_results(vec![MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            }])
+            .into_connection(),
+    );
+
+    let events = DomainEventPublisher::default();
+    let mut rx = events.subscribe();
+
+    let service = DefaultEpisodesService::new(&db, &events);
+
+    let result = service.publish_scheduled(due_by).await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, vec![episode.clone()]);
+
+    // Listeners hear about the change once it has been committed
+    assert_eq!(rx.try_recv()?, DomainEvent::EpisodeUpdated(episode.clone()));
+    assert_eq!(
+        rx.try_recv()?,
+        DomainEvent::EpisodePublished(episode.clone())
+    );
+    assert!(rx.try_recv().is_err());
+
+    let show_id = episode.show_id.clone();
+    let payload = serde_json::to_value(&episode)?;
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::many(vec![
+            Statement::from_string(DatabaseBackend::Postgres, "BEGIN".to_string()),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"UPDATE "episodes" SET "status" = $1 WHERE "episodes"."status" = $2 AND "episodes"."publish_at" <= $3 AND "episodes"."deleted_at" IS NULL RETURNING "id", "created_at", "updated_at", "title", "summary", "picture", "picture_thumbnail", "media_url", "media_type", "media_length", "duration", "season_number", "episode_number", "explicit", "status", "publish_at", "show_id", "deleted_at""#,
+                vec!["published".into(), "scheduled".into(), due_by.into()]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"INSERT INTO "outbox_events" ("show_id", "event_type", "payload") VALUES ($1, $2, $3)"#,
+                vec![show_id.into(), "episode.updated".into(), payload.into()]
+            ),
+            Statement::from_string(DatabaseBackend::Postgres, "COMMIT".to_string()),
+        ])]
     );
 
     Ok(())
@@ -0,0 +1,67 @@
+#![allow(missing_docs)]
+
+use chrono::Utc;
+use fake::Dummy;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{profiles::model as profile_model, shows::model as show_model};
+
+/// A Show that a Profile follows
+#[derive(Clone, Debug, Dummy, Eq, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "follows")]
+pub struct Model {
+    /// The id of the Profile following the Show
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Text")]
+    pub profile_id: String,
+
+    /// The id of the followed Show
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Text")]
+    pub show_id: String,
+
+    /// The date the Show was followed
+    pub created_at: DateTime,
+}
+
+/// The Follow type is the same as the database Model
+pub type Follow = Model;
+
+/// Follow entity relationships
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "profile_model::Entity",
+        from = "Column::ProfileId",
+        to = "profile_model::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Profile,
+
+    #[sea_orm(
+        belongs_to = "show_model::Entity",
+        from = "Column::ShowId",
+        to = "show_model::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Show,
+}
+
+impl Related<show_model::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Show.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Default for Model {
+    fn default() -> Self {
+        Self {
+            profile_id: String::default(),
+            show_id: String::default(),
+            created_at: Utc::now().naive_utc(),
+        }
+    }
+}
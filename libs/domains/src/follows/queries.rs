@@ -0,0 +1,41 @@
+use async_graphql::SimpleObject;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::episodes::model::Episode;
+
+/// The position of an `Episode` within a feed, used to request the page that follows it
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct FeedCursor {
+    /// The date the `Episode` was published
+    pub publish_at: NaiveDateTime,
+
+    /// The `Episode` id, to break ties between `Episodes` published at the same time
+    pub id: String,
+}
+
+impl FeedCursor {
+    /// The cursor for an `Episode`, if it has been published
+    pub fn for_episode(episode: &Episode) -> Option<FeedCursor> {
+        episode.publish_at.map(|publish_at| FeedCursor {
+            publish_at,
+            id: episode.id.clone(),
+        })
+    }
+}
+
+/// The `FeedPage` result type
+#[derive(Clone, Eq, PartialEq, SimpleObject)]
+pub struct FeedPage {
+    /// The list of `Episodes` returned for the current page, newest first
+    pub data: Vec<Episode>,
+
+    /// The number of `Episodes` returned for the current page
+    pub count: u64,
+
+    /// Pass this as the `after` argument to request the next page
+    pub end_cursor: Option<String>,
+
+    /// Whether there are more `Episodes` after this page
+    pub has_next_page: bool,
+}
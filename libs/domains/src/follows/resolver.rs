@@ -0,0 +1,153 @@
+use async_graphql::{
+    connection::{CursorType, OpaqueCursor},
+    Context, Object, Result,
+};
+use hyper::StatusCode;
+use std::sync::Arc;
+
+use crate::{
+    follows::{
+        queries::{FeedCursor, FeedPage},
+        service::FollowsService,
+    },
+    profiles::{model::Profile, service::ProfilesService},
+    shows::{model::Show, mutations::MutateShowResult, service::ShowsService},
+    users::model::User,
+};
+use caster_utils::errors::{as_graphql_error, graphql_error};
+
+/// The number of Episodes returned in a feed page when not requested
+const DEFAULT_FEED_SIZE: u64 = 20;
+
+/// The largest number of Episodes that can be requested in a feed page
+const MAX_FEED_SIZE: u64 = 100;
+
+/// The Query segment owned by the Follows library
+#[derive(Default)]
+pub struct FollowsQuery {}
+
+/// The Mutation segment for Follows
+#[derive(Default)]
+pub struct FollowsMutation {}
+
+/// Queries for followed Shows
+#[Object]
+impl FollowsQuery {
+    /// Get the most recently published Episodes from the Shows that the current User follows
+    async fn get_my_feed(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "The number of Episodes to return")] first: Option<u64>,
+        #[graphql(desc = "The endCursor of the previous page")] after: Option<String>,
+    ) -> Result<FeedPage> {
+        let follows = ctx.data_unchecked::<Arc<dyn FollowsService>>();
+
+        let profile = current_profile(ctx).await?;
+
+        let first = first.unwrap_or(DEFAULT_FEED_SIZE).clamp(1, MAX_FEED_SIZE);
+
+        let after = after
+            .map(|after| OpaqueCursor::<FeedCursor>::decode_cursor(&after))
+            .transpose()
+            .map_err(|_err| graphql_error("Invalid cursor", StatusCode::BAD_REQUEST))?
+            .map(|cursor| cursor.0);
+
+        let mut episodes =
+            follows
+                .get_feed(&profile.id, first, after)
+                .await
+                .map_err(as_graphql_error(
+                    "Error while fetching feed",
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                ))?;
+
+        // The service returns an extra Episode if there is another page
+        let has_next_page = episodes.len() as u64 > first;
+        episodes.truncate(first as usize);
+
+        let end_cursor = episodes
+            .last()
+            .and_then(FeedCursor::for_episode)
+            .map(|cursor| OpaqueCursor(cursor).encode_cursor());
+
+        Ok(FeedPage {
+            count: episodes.len().try_into().unwrap_or(0),
+            data: episodes,
+            end_cursor,
+            has_next_page,
+        })
+    }
+}
+
+/// Mutations for followed Shows
+#[Object]
+impl FollowsMutation {
+    /// Follow a Show, adding its Episodes to the current User's feed
+    async fn follow_show(&self, ctx: &Context<'_>, show_id: String) -> Result<MutateShowResult> {
+        let follows = ctx.data_unchecked::<Arc<dyn FollowsService>>();
+
+        let profile = current_profile(ctx).await?;
+        let show = existing_show(ctx, &show_id).await?;
+
+        follows
+            .follow(&profile.id, &show.id)
+            .await
+            .map_err(as_graphql_error(
+                "Error while following Show",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        Ok(MutateShowResult { show: Some(show) })
+    }
+
+    /// Stop following a Show
+    async fn unfollow_show(&self, ctx: &Context<'_>, show_id: String) -> Result<MutateShowResult> {
+        let follows = ctx.data_unchecked::<Arc<dyn FollowsService>>();
+
+        let profile = current_profile(ctx).await?;
+        let show = existing_show(ctx, &show_id).await?;
+
+        follows
+            .unfollow(&profile.id, &show.id)
+            .await
+            .map_err(as_graphql_error(
+                "Error while unfollowing Show",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        Ok(MutateShowResult { show: Some(show) })
+    }
+}
+
+/// Find the Profile of the current User, who must be authenticated
+async fn current_profile(ctx: &Context<'_>) -> Result<Profile> {
+    let user = ctx.data_unchecked::<Option<User>>();
+    let profiles = ctx.data_unchecked::<Arc<dyn ProfilesService>>();
+
+    let user = user
+        .as_ref()
+        .ok_or_else(|| graphql_error("Unauthorized", StatusCode::UNAUTHORIZED))?;
+
+    profiles
+        .get_by_user_id(&user.id, &false)
+        .await
+        .map_err(as_graphql_error(
+            "Error while fetching Profile",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ))?
+        .ok_or_else(|| graphql_error("Unable to find existing Profile", StatusCode::NOT_FOUND))
+}
+
+/// Find a Show that hasn't been deleted
+async fn existing_show(ctx: &Context<'_>, show_id: &str) -> Result<Show> {
+    let shows = ctx.data_unchecked::<Arc<dyn ShowsService>>();
+
+    shows
+        .get(show_id)
+        .await
+        .map_err(as_graphql_error(
+            "Error while fetching Show",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ))?
+        .ok_or_else(|| graphql_error("Unable to find existing Show", StatusCode::NOT_FOUND))
+}
@@ -0,0 +1,213 @@
+use anyhow::Result;
+use async_graphql::{dataloader::Loader, FieldError};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sea_orm::{
+    entity::*,
+    query::*,
+    sea_query::{OnConflict, SelectStatement},
+    DatabaseConnection, EntityTrait, QuerySelect,
+};
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    episodes::model::{self as episode_model, Episode, EpisodeStatus},
+    follows::{model, queries::FeedCursor},
+    profiles::model as profile_model,
+    shows::model::{self as show_model, Show},
+};
+
+/// A FollowsService applies business logic to a dynamic FollowsRepository implementation.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait FollowsService: Sync + Send {
+    /// Follow a `Show` for a `Profile`, returning `false` if it was already followed
+    async fn follow(&self, profile_id: &str, show_id: &str) -> Result<bool>;
+
+    /// Stop following a `Show` for a `Profile`, returning `false` if it wasn't followed
+    async fn unfollow(&self, profile_id: &str, show_id: &str) -> Result<bool>;
+
+    /// Count the followers of each of the given `Show` ids, keyed by `Show` id
+    async fn get_follower_counts(&self, show_ids: Vec<String>) -> Result<HashMap<String, u64>>;
+
+    /// Get the ids of the `Users` whose `Profiles` follow a `Show`
+    async fn get_follower_user_ids(&self, show_id: &str) -> Result<Vec<String>>;
+
+    /// Get the `Shows` that a `Profile` follows
+    async fn get_followed_shows(&self, profile_id: &str) -> Result<Vec<Show>>;
+
+    /// Get the most recently published `Episodes` from the `Shows` that a `Profile` follows,
+    /// starting after the given cursor. One more `Episode` than requested is returned when there
+    /// are more available, so that callers can tell whether there is a next page.
+    async fn get_feed(
+        &self,
+        profile_id: &str,
+        first: u64,
+        after: Option<FeedCursor>,
+    ) -> Result<Vec<Episode>>;
+}
+
+/// The default `FollowsService` struct.
+pub struct DefaultFollowsService {
+    /// The SeaOrm database connection
+    db: Arc<DatabaseConnection>,
+}
+
+/// The default `FollowsService` implementation
+impl DefaultFollowsService {
+    /// Create a new `FollowsService` instance
+    pub fn new(db: &Arc<DatabaseConnection>) -> Self {
+        Self { db: db.clone() }
+    }
+}
+
+#[async_trait]
+impl FollowsService for DefaultFollowsService {
+    async fn follow(&self, profile_id: &str, show_id: &str) -> Result<bool> {
+        let rows = model::Entity::insert(model::ActiveModel {
+            profile_id: Set(profile_id.to_string()),
+            show_id: Set(show_id.to_string()),
+            ..Default::default()
+        })
+        .on_conflict(
+            OnConflict::columns([model::Column::ProfileId, model::Column::ShowId])
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(&*self.db)
+        .await?;
+
+        Ok(rows > 0)
+    }
+
+    async fn unfollow(&self, profile_id: &str, show_id: &str) -> Result<bool> {
+        let result = model::Entity::delete_many()
+            .filter(model::Column::ProfileId.eq(profile_id))
+            .filter(model::Column::ShowId.eq(show_id))
+            .exec(&*self.db)
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn get_follower_counts(&self, show_ids: Vec<String>) -> Result<HashMap<String, u64>> {
+        let counts: Vec<(String, i64)> = model::Entity::find()
+            .select_only()
+            .column(model::Column::ShowId)
+            .column_as(model::Column::ProfileId.count(), "total")
+            .filter(model::Column::ShowId.is_in(show_ids))
+            .group_by(model::Column::ShowId)
+            .into_tuple()
+            .all(&*self.db)
+            .await?;
+
+        Ok(counts
+            .into_iter()
+            .map(|(show_id, count)| (show_id, count.try_into().unwrap_or(0)))
+            .collect())
+    }
+
+    async fn get_follower_user_ids(&self, show_id: &str) -> Result<Vec<String>> {
+        let user_ids: Vec<Option<String>> = profile_model::Entity::find()
+            .select_only()
+            .column(profile_model::Column::UserId)
+            .filter(
+                profile_model::Column::Id.in_subquery(
+                    model::Entity::find()
+                        .select_only()
+                        .column(model::Column::ProfileId)
+                        .filter(model::Column::ShowId.eq(show_id))
+                        .into_query(),
+                ),
+            )
+            .filter(profile_model::Column::DeletedAt.is_null())
+            .into_tuple()
+            .all(&*self.db)
+            .await?;
+
+        Ok(user_ids.into_iter().flatten().collect())
+    }
+
+    async fn get_followed_shows(&self, profile_id: &str) -> Result<Vec<Show>> {
+        let shows = show_model::Entity::find()
+            .filter(show_model::Column::Id.in_subquery(followed_show_ids(profile_id)))
+            .filter(show_model::Column::DeletedAt.is_null())
+            .order_by_asc(show_model::Column::Title)
+            .all(&*self.db)
+            .await?;
+
+        Ok(shows)
+    }
+
+    async fn get_feed(
+        &self,
+        profile_id: &str,
+        first: u64,
+        after: Option<FeedCursor>,
+    ) -> Result<Vec<Episode>> {
+        let mut query = episode_model::Entity::find()
+            .filter(episode_model::Column::ShowId.in_subquery(followed_show_ids(profile_id)))
+            .filter(episode_model::Column::Status.eq(EpisodeStatus::Published))
+            .filter(episode_model::Column::PublishAt.is_not_null())
+            .filter(episode_model::Column::DeletedAt.is_null());
+
+        // Continue with the Episodes published before the cursor, or at the same time with a
+        // lower id
+        if let Some(after) = after {
+            query = query.filter(
+                Condition::any()
+                    .add(episode_model::Column::PublishAt.lt(after.publish_at))
+                    .add(
+                        Condition::all()
+                            .add(episode_model::Column::PublishAt.eq(after.publish_at))
+                            .add(episode_model::Column::Id.lt(after.id)),
+                    ),
+            );
+        }
+
+        let episodes = query
+            .order_by_desc(episode_model::Column::PublishAt)
+            .order_by_desc(episode_model::Column::Id)
+            .limit(first + 1)
+            .all(&*self.db)
+            .await?;
+
+        Ok(episodes)
+    }
+}
+
+/// A subquery selecting the ids of the `Shows` that a `Profile` follows
+fn followed_show_ids(profile_id: &str) -> SelectStatement {
+    model::Entity::find()
+        .select_only()
+        .column(model::Column::ShowId)
+        .filter(model::Column::ProfileId.eq(profile_id))
+        .into_query()
+}
+
+/// A dataloader for the number of `Profiles` following each `Show`, keyed by `Show` id
+pub struct FollowerCountLoader {
+    /// The SeaOrm database connection
+    follows: Arc<dyn FollowsService>,
+}
+
+/// The default implementation for the `FollowerCountLoader`
+impl FollowerCountLoader {
+    /// Create a new instance
+    pub fn new(follows: &Arc<dyn FollowsService>) -> Self {
+        Self {
+            follows: follows.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl Loader<String> for FollowerCountLoader {
+    type Value = u64;
+    type Error = FieldError;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        Ok(self.follows.get_follower_counts(keys.into()).await?)
+    }
+}
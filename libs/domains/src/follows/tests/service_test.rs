@@ -0,0 +1,215 @@
+use anyhow::Result;
+use fake::{Fake, Faker};
+use pretty_assertions::assert_eq;
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Transaction, Value};
+use std::sync::Arc;
+
+use crate::{
+    episodes::model::Episode,
+    follows::{
+        queries::FeedCursor,
+        service::{DefaultFollowsService, FollowsService},
+    },
+};
+
+#[tokio::test]
+async fn test_follows_service_follow() -> Result<()> {
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results(vec![
+                MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 1,
+                },
+                MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 0,
+                },
+            ])
+            .into_connection(),
+    );
+
+    let service = DefaultFollowsService::new(&db);
+
+    let first = service.follow("test-profile", "test-show").await?;
+    let second = service.follow("test-profile", "test-show").await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert!(first);
+    assert!(!second);
+
+    let expected = Transaction::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"INSERT INTO "follows" ("profile_id", "show_id") VALUES ($1, $2) ON CONFLICT ("profile_id", "show_id") DO NOTHING"#,
+        vec!["test-profile".into(), "test-show".into()],
+    );
+
+    // Check the transaction log
+    assert_eq!(db.into_transaction_log(), vec![expected.clone(), expected]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_follows_service_unfollow() -> Result<()> {
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results(vec![MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            }])
+            .into_connection(),
+    );
+
+    let service = DefaultFollowsService::new(&db);
+
+    let result = service.unfollow("test-profile", "test-show").await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert!(result);
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"DELETE FROM "follows" WHERE "follows"."profile_id" = $1 AND "follows"."show_id" = $2"#,
+            vec!["test-profile".into(), "test-show".into()]
+        )]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_follows_service_get_follower_counts() -> Result<()> {
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![maplit::btreemap! {
+                "show_id" => Value::from("test-show"),
+                "total" => Value::from(3i64),
+            }]])
+            .into_connection(),
+    );
+
+    let service = DefaultFollowsService::new(&db);
+
+    let result = service
+        .get_follower_counts(vec!["test-show".to_string(), "other-show".to_string()])
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, maplit::hashmap! { "test-show".to_string() => 3 });
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"SELECT "follows"."show_id", COUNT("follows"."profile_id") AS "total" FROM "follows" WHERE "follows"."show_id" IN ($1, $2) GROUP BY "follows"."show_id""#,
+            vec!["test-show".into(), "other-show".into()]
+        )]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_follows_service_get_feed() -> Result<()> {
+    let mut episode: Episode = Faker.fake();
+    episode.show = None;
+
+    let publish_at = chrono::Utc::now().naive_utc();
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![episode.clone()]])
+            .into_connection(),
+    );
+
+    let service = DefaultFollowsService::new(&db);
+
+    let result = service
+        .get_feed(
+            "test-profile",
+            10,
+            Some(FeedCursor {
+                publish_at,
+                id: "test-episode".to_string(),
+            }),
+        )
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, vec![episode]);
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"SELECT "episodes"."id", "episodes"."created_at", "episodes"."updated_at", "episodes"."title", "episodes"."summary", "episodes"."picture", "episodes"."picture_thumbnail", "episodes"."media_url", "episodes"."media_type", "episodes"."media_length", "episodes"."duration", "episodes"."season_number", "episodes"."episode_number", "episodes"."explicit", "episodes"."status", "episodes"."publish_at", "episodes"."show_id", "episodes"."deleted_at" FROM "episodes" WHERE "episodes"."show_id" IN (SELECT "follows"."show_id" FROM "follows" WHERE "follows"."profile_id" = $1) AND "episodes"."status" = $2 AND "episodes"."publish_at" IS NOT NULL AND "episodes"."deleted_at" IS NULL AND ("episodes"."publish_at" < $3 OR ("episodes"."publish_at" = $4 AND "episodes"."id" < $5)) ORDER BY "episodes"."publish_at" DESC, "episodes"."id" DESC LIMIT $6"#,
+            vec![
+                "test-profile".into(),
+                "published".into(),
+                publish_at.into(),
+                publish_at.into(),
+                "test-episode".into(),
+                11u64.into()
+            ]
+        )]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_follows_service_get_follower_user_ids() -> Result<()> {
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![
+                maplit::btreemap! { "user_id" => Value::from("test-user") },
+                maplit::btreemap! { "user_id" => Value::from(None::<String>) },
+            ]])
+            .into_connection(),
+    );
+
+    let service = DefaultFollowsService::new(&db);
+
+    let result = service.get_follower_user_ids("test-show").await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, vec!["test-user".to_string()]);
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"SELECT "profiles"."user_id" FROM "profiles" WHERE "profiles"."id" IN (SELECT "follows"."profile_id" FROM "follows" WHERE "follows"."show_id" = $1) AND "profiles"."deleted_at" IS NULL"#,
+            vec!["test-show".into()]
+        )]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,17 @@
+//! # Invitations
+
+/// Service
+pub mod service;
+
+/// Model
+pub mod model;
+
+/// GraphQL Mutations
+pub mod mutations;
+
+/// GraphQL Resolver
+pub mod resolver;
+
+/// Tests
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,161 @@
+#![allow(missing_docs)]
+
+use async_graphql::{Enum, SimpleObject};
+use chrono::Utc;
+use fake::Dummy;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{shows::model as show_model, users::model as user_model};
+
+/// The status of an `Invitation`
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Dummy,
+    Eq,
+    PartialEq,
+    EnumIter,
+    DeriveActiveEnum,
+    Deserialize,
+    Serialize,
+    Enum,
+)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+pub enum InvitationStatus {
+    /// Waiting for the invitee to respond
+    #[default]
+    #[sea_orm(string_value = "pending")]
+    Pending,
+
+    /// Accepted by the invitee, who was granted the Role
+    #[sea_orm(string_value = "accepted")]
+    Accepted,
+
+    /// Declined by the invitee
+    #[sea_orm(string_value = "declined")]
+    Declined,
+}
+
+/// The `Invitation` GraphQL and Database Model
+#[derive(
+    Clone, Debug, Dummy, Eq, PartialEq, DeriveEntityModel, Deserialize, Serialize, SimpleObject,
+)]
+#[graphql(name = "Invitation")]
+#[sea_orm(table_name = "invitations")]
+pub struct Model {
+    /// The Invitation id
+    #[sea_orm(primary_key, column_type = "Text")]
+    pub id: String,
+
+    /// The date the Invitation was created
+    pub created_at: DateTime,
+
+    /// The date the Invitation was last updated
+    pub updated_at: DateTime,
+
+    /// The id of the Show that the invitee is invited to
+    #[sea_orm(column_type = "Text")]
+    pub show_id: String,
+
+    /// The key of the Role that is granted when the Invitation is accepted
+    #[sea_orm(column_type = "Text")]
+    pub role_key: String,
+
+    /// The id of the invited User
+    #[sea_orm(column_type = "Text")]
+    pub user_id: String,
+
+    /// The id of the User who sent the Invitation
+    #[sea_orm(column_type = "Text", nullable)]
+    pub inviter_id: Option<String>,
+
+    /// Whether the Invitation is pending, accepted, or declined
+    pub status: InvitationStatus,
+
+    /// The date after which a pending Invitation can no longer be accepted
+    pub expires_at: DateTime,
+}
+
+/// The `Invitation` GraphQL type is the same as the database Model
+pub type Invitation = Model;
+
+impl Model {
+    /// Return true if the Invitation is past its expiration date
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Utc::now().naive_utc()
+    }
+}
+
+/// `Invitation` entity relationships
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "show_model::Entity",
+        from = "Column::ShowId",
+        to = "show_model::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Show,
+
+    #[sea_orm(
+        belongs_to = "user_model::Entity",
+        from = "Column::UserId",
+        to = "user_model::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<show_model::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Show.def()
+    }
+}
+
+impl Related<user_model::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Default for Model {
+    fn default() -> Self {
+        Self {
+            id: String::default(),
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+            show_id: String::default(),
+            role_key: String::default(),
+            user_id: String::default(),
+            inviter_id: Option::default(),
+            status: InvitationStatus::default(),
+            expires_at: Utc::now().naive_utc(),
+        }
+    }
+}
+
+/// The `CreateInvitationInput` type
+#[derive(Clone, Debug, Dummy, Eq, PartialEq)]
+pub struct CreateInvitationInput {
+    /// The id of the Show to invite the User to
+    pub show_id: String,
+
+    /// The key of the Role to grant when the Invitation is accepted
+    pub role_key: String,
+
+    /// The id of the User to invite
+    pub user_id: String,
+
+    /// The id of the User sending the Invitation
+    pub inviter_id: Option<String>,
+
+    /// The date after which the Invitation can no longer be accepted
+    pub expires_at: DateTime,
+}
@@ -0,0 +1,27 @@
+use async_graphql::{InputObject, SimpleObject};
+use fake::Dummy;
+
+use super::model::Invitation;
+
+/// The `InviteToShowInput` input type
+#[derive(Clone, Default, Dummy, Eq, PartialEq, InputObject)]
+pub struct InviteToShowInput {
+    /// The id of the Show to invite the User to
+    pub show_id: String,
+
+    /// The key of the Role to grant when the Invitation is accepted, such as "manager"
+    pub role_key: String,
+
+    /// The username of the User to invite
+    pub username: Option<String>,
+
+    /// The email address on the Profile of the User to invite
+    pub email: Option<String>,
+}
+
+/// The `MutateInvitationResult` type
+#[derive(Clone, Default, Dummy, Eq, PartialEq, SimpleObject)]
+pub struct MutateInvitationResult {
+    /// The Invitation
+    pub invitation: Option<Invitation>,
+}
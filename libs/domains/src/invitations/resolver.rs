@@ -0,0 +1,242 @@
+use async_graphql::{Context, Object, Result};
+use chrono::{Duration, Utc};
+use hyper::StatusCode;
+use std::sync::Arc;
+
+use super::{
+    model::{CreateInvitationInput, Invitation, InvitationStatus},
+    mutations::{InviteToShowInput, MutateInvitationResult},
+    service::InvitationsService,
+};
+use crate::{
+    audit_events::resolver::record_audit_event,
+    authorization::resolver::is_allowed,
+    role_grants::model::RoleGrant,
+    shows::{model::ROLES, service::ShowsService},
+    users::{model::User, resolver::find_user},
+};
+use caster_utils::{
+    config::Config,
+    errors::{as_graphql_error, graphql_error},
+};
+
+/// The Query segment for Invitations
+#[derive(Default)]
+pub struct InvitationsQuery {}
+
+/// The Mutation segment for Invitations
+#[derive(Default)]
+pub struct InvitationsMutation {}
+
+/// Queries for the `Invitation` model
+#[Object]
+impl InvitationsQuery {
+    /// Get the current User's pending Invitations that have not expired
+    async fn get_pending_invitations(&self, ctx: &Context<'_>) -> Result<Vec<Invitation>> {
+        let invitations = ctx.data_unchecked::<Arc<dyn InvitationsService>>();
+        let user = ctx.data_unchecked::<Option<User>>();
+
+        // Check authentication
+        let user = user
+            .as_ref()
+            .ok_or_else(|| graphql_error("Unauthorized", StatusCode::UNAUTHORIZED))?;
+
+        invitations
+            .get_pending_for_user(&user.id)
+            .await
+            .map_err(as_graphql_error(
+                "Error while fetching Invitations",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))
+    }
+}
+
+/// Mutations for the `Invitation` model
+#[Object]
+impl InvitationsMutation {
+    /// Invite a User to a Show with a Role, which they are granted if they accept
+    async fn invite_to_show(
+        &self,
+        ctx: &Context<'_>,
+        input: InviteToShowInput,
+    ) -> Result<MutateInvitationResult> {
+        let invitations = ctx.data_unchecked::<Arc<dyn InvitationsService>>();
+        let shows = ctx.data_unchecked::<Arc<dyn ShowsService>>();
+        let config = ctx.data_unchecked::<&'static Config>();
+        let user = ctx.data_unchecked::<Option<User>>();
+
+        // Check authentication
+        let user = user
+            .as_ref()
+            .ok_or_else(|| graphql_error("Unauthorized", StatusCode::UNAUTHORIZED))?;
+
+        // Retrieve the Show for authorization
+        let show = shows
+            .get(&input.show_id)
+            .await
+            .map_err(as_graphql_error(
+                "Error while fetching Show",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?
+            .ok_or_else(|| graphql_error("Unable to find existing Show", StatusCode::NOT_FOUND))?;
+
+        // Check authorization
+        if !is_allowed(ctx, user, "manage_roles", show)? {
+            return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
+        }
+
+        if !ROLES.contains(&input.role_key.as_str()) {
+            return Err(graphql_error("Unknown role", StatusCode::BAD_REQUEST));
+        }
+
+        let invitee = find_user(ctx, &input.username, &input.email).await?;
+
+        let invitation = invitations
+            .create(&CreateInvitationInput {
+                show_id: input.show_id.clone(),
+                role_key: input.role_key.clone(),
+                user_id: invitee.id.clone(),
+                inviter_id: Some(user.id.clone()),
+                expires_at: Utc::now().naive_utc() + Duration::days(config.invitations.expire_days),
+            })
+            .await
+            .map_err(as_graphql_error(
+                "Error while creating Invitation",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        record_audit_event(
+            ctx,
+            "create",
+            "invitations",
+            &invitation.id,
+            None::<&Invitation>,
+            Some(&invitation),
+        )
+        .await;
+
+        Ok(MutateInvitationResult {
+            invitation: Some(invitation),
+        })
+    }
+
+    /// Accept a pending Invitation, granting the current User the Role
+    async fn accept_invitation(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<MutateInvitationResult> {
+        let invitations = ctx.data_unchecked::<Arc<dyn InvitationsService>>();
+
+        let existing = get_own(ctx, &id).await?;
+
+        let (accepted, grant) = invitations
+            .accept(&id)
+            .await
+            .map_err(as_graphql_error(
+                "Error while accepting Invitation",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?
+            .ok_or_else(|| not_pending(&existing))?;
+
+        record_audit_event(
+            ctx,
+            "update",
+            "invitations",
+            &id,
+            Some(&existing),
+            Some(&accepted),
+        )
+        .await;
+
+        if let Some(grant) = grant {
+            record_audit_event(
+                ctx,
+                "create",
+                "role_grants",
+                &grant.id,
+                None::<&RoleGrant>,
+                Some(&grant),
+            )
+            .await;
+        }
+
+        Ok(MutateInvitationResult {
+            invitation: Some(accepted),
+        })
+    }
+
+    /// Decline a pending Invitation
+    async fn decline_invitation(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<MutateInvitationResult> {
+        let invitations = ctx.data_unchecked::<Arc<dyn InvitationsService>>();
+
+        let existing = get_own(ctx, &id).await?;
+
+        let declined = invitations
+            .decline(&id)
+            .await
+            .map_err(as_graphql_error(
+                "Error while declining Invitation",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?
+            .ok_or_else(|| not_pending(&existing))?;
+
+        record_audit_event(
+            ctx,
+            "update",
+            "invitations",
+            &id,
+            Some(&existing),
+            Some(&declined),
+        )
+        .await;
+
+        Ok(MutateInvitationResult {
+            invitation: Some(declined),
+        })
+    }
+}
+
+/// Retrieve an Invitation addressed to the current User
+async fn get_own(ctx: &Context<'_>, id: &str) -> Result<Invitation> {
+    let invitations = ctx.data_unchecked::<Arc<dyn InvitationsService>>();
+    let user = ctx.data_unchecked::<Option<User>>();
+
+    // Check authentication
+    let user = user
+        .as_ref()
+        .ok_or_else(|| graphql_error("Unauthorized", StatusCode::UNAUTHORIZED))?;
+
+    let invitation = invitations
+        .get(id)
+        .await
+        .map_err(as_graphql_error(
+            "Error while fetching Invitation",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ))?
+        .ok_or_else(|| {
+            graphql_error("Unable to find existing Invitation", StatusCode::NOT_FOUND)
+        })?;
+
+    // Only the invitee may respond
+    if invitation.user_id != user.id {
+        return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
+    }
+
+    Ok(invitation)
+}
+
+/// Explain why the service declined to respond to an Invitation
+fn not_pending(invitation: &Invitation) -> async_graphql::Error {
+    let message = if invitation.status == InvitationStatus::Pending && invitation.is_expired() {
+        "Invitation has expired"
+    } else {
+        "Invitation is no longer pending"
+    };
+
+    graphql_error(message, StatusCode::BAD_REQUEST)
+}
@@ -0,0 +1,175 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+#[cfg(test)]
+use mockall::automock;
+use sea_orm::{
+    entity::*, query::*, sea_query::Expr, DatabaseConnection, EntityTrait, TransactionTrait,
+};
+use std::sync::Arc;
+
+use super::model::{self, CreateInvitationInput, Invitation, InvitationStatus};
+use crate::{
+    domain_events::{model::DomainEvent, publisher::DomainEventPublisher},
+    role_grants::{
+        model::{self as role_grant_model, CreateRoleGrantInput, RoleGrant},
+        service::insert_role_grant,
+    },
+};
+
+/// An InvitationsService applies business logic to a dynamic InvitationsRepository implementation.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait InvitationsService: Sync + Send {
+    /// Get an individual `Invitation` by id
+    async fn get(&self, id: &str) -> Result<Option<Invitation>>;
+
+    /// Get the pending `Invitation` records for a `User` that have not expired, most recent first
+    async fn get_pending_for_user(&self, user_id: &str) -> Result<Vec<Invitation>>;
+
+    /// Create an `Invitation` with the given input
+    async fn create(&self, input: &CreateInvitationInput) -> Result<Invitation>;
+
+    /// Accept a pending `Invitation`, granting the invitee the Role if they don't already hold it.
+    /// Returns `None` if the `Invitation` is no longer pending or has expired.
+    async fn accept(&self, id: &str) -> Result<Option<(Invitation, Option<RoleGrant>)>>;
+
+    /// Decline a pending `Invitation`. Returns `None` if the `Invitation` is no longer pending or
+    /// has expired.
+    async fn decline(&self, id: &str) -> Result<Option<Invitation>>;
+
+    /// Permanently remove pending `Invitation` records that expired before the given date
+    async fn purge_expired(&self, expired_before: NaiveDateTime) -> Result<u64>;
+}
+
+/// The default `InvitationsService` struct.
+pub struct DefaultInvitationsService {
+    /// The SeaOrm database connection
+    db: Arc<DatabaseConnection>,
+
+    /// The publisher to announce granted Roles with
+    events: DomainEventPublisher,
+}
+
+/// The default `InvitationsService` implementation
+impl DefaultInvitationsService {
+    /// Create a new `InvitationsService` instance
+    pub fn new(db: &Arc<DatabaseConnection>, events: &DomainEventPublisher) -> Self {
+        Self {
+            db: db.clone(),
+            events: events.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl InvitationsService for DefaultInvitationsService {
+    async fn get(&self, id: &str) -> Result<Option<Invitation>> {
+        let query = model::Entity::find_by_id(id.to_owned());
+
+        let invitation = query.one(&*self.db).await?;
+
+        Ok(invitation)
+    }
+
+    async fn get_pending_for_user(&self, user_id: &str) -> Result<Vec<Invitation>> {
+        let invitations = model::Entity::find()
+            .filter(model::Column::UserId.eq(user_id))
+            .filter(model::Column::Status.eq(InvitationStatus::Pending))
+            .filter(Expr::col(model::Column::ExpiresAt).gt(Expr::current_timestamp()))
+            .order_by_desc(model::Column::CreatedAt)
+            .all(&*self.db)
+            .await?;
+
+        Ok(invitations)
+    }
+
+    async fn create(&self, input: &CreateInvitationInput) -> Result<Invitation> {
+        let invitation = model::ActiveModel {
+            show_id: Set(input.show_id.clone()),
+            role_key: Set(input.role_key.clone()),
+            user_id: Set(input.user_id.clone()),
+            inviter_id: Set(input.inviter_id.clone()),
+            expires_at: Set(input.expires_at),
+            ..Default::default()
+        }
+        .insert(&*self.db)
+        .await?;
+
+        Ok(invitation)
+    }
+
+    async fn accept(&self, id: &str) -> Result<Option<(Invitation, Option<RoleGrant>)>> {
+        let txn = self.db.begin().await?;
+
+        let Some(invitation) = respond(&txn, id, InvitationStatus::Accepted).await? else {
+            return Ok(None);
+        };
+
+        let existing = role_grant_model::Entity::find()
+            .filter(role_grant_model::Column::UserId.eq(invitation.user_id.clone()))
+            .filter(role_grant_model::Column::RoleKey.eq(invitation.role_key.clone()))
+            .filter(role_grant_model::Column::ResourceTable.eq("shows"))
+            .filter(role_grant_model::Column::ResourceId.eq(invitation.show_id.clone()))
+            .one(&txn)
+            .await?;
+
+        let grant = if existing.is_some() {
+            None
+        } else {
+            Some(
+                insert_role_grant(
+                    &txn,
+                    &CreateRoleGrantInput {
+                        role_key: invitation.role_key.clone(),
+                        user_id: invitation.user_id.clone(),
+                        resource_table: "shows".to_string(),
+                        resource_id: invitation.show_id.clone(),
+                    },
+                )
+                .await?,
+            )
+        };
+
+        txn.commit().await?;
+
+        if let Some(grant) = &grant {
+            self.events.publish(DomainEvent::RoleGranted(grant.clone()));
+        }
+
+        Ok(Some((invitation, grant)))
+    }
+
+    async fn decline(&self, id: &str) -> Result<Option<Invitation>> {
+        respond(&*self.db, id, InvitationStatus::Declined).await
+    }
+
+    async fn purge_expired(&self, expired_before: NaiveDateTime) -> Result<u64> {
+        let result = model::Entity::delete_many()
+            .filter(model::Column::Status.eq(InvitationStatus::Pending))
+            .filter(model::Column::ExpiresAt.lt(expired_before))
+            .exec(&*self.db)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+}
+
+/// Move a pending, unexpired `Invitation` to the given status, returning `None` if it was no
+/// longer pending or had expired
+async fn respond<C: ConnectionTrait>(
+    db: &C,
+    id: &str,
+    status: InvitationStatus,
+) -> Result<Option<Invitation>> {
+    let invitation = model::Entity::update_many()
+        .col_expr(model::Column::Status, Expr::value(status))
+        .filter(model::Column::Id.eq(id.to_owned()))
+        .filter(model::Column::Status.eq(InvitationStatus::Pending))
+        .filter(Expr::col(model::Column::ExpiresAt).gt(Expr::current_timestamp()))
+        .exec_with_returning(db)
+        .await?
+        .pop();
+
+    Ok(invitation)
+}
@@ -0,0 +1,362 @@
+use anyhow::Result;
+use fake::{Fake, Faker};
+use pretty_assertions::assert_eq;
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Statement, Transaction};
+use std::sync::Arc;
+
+use crate::domain_events::{model::DomainEvent, publisher::DomainEventPublisher};
+use crate::invitations::{
+    model::{CreateInvitationInput, Invitation, InvitationStatus},
+    service::{DefaultInvitationsService, InvitationsService},
+};
+use crate::role_grants::model::RoleGrant;
+
+#[tokio::test]
+async fn test_invitations_service_get_pending_for_user() -> Result<()> {
+    let invitation: Invitation = Faker.fake();
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![invitation.clone()]])
+            .into_connection(),
+    );
+
+    let service = DefaultInvitationsService::new(&db, &DomainEventPublisher::default());
+
+    let result = service.get_pending_for_user(&invitation.user_id).await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, vec![invitation.clone()]);
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"SELECT "invitations"."id", "invitations"."created_at", "invitations"."updated_at", "invitations"."show_id", "invitations"."role_key", "invitations"."user_id", "invitations"."inviter_id", "invitations"."status", "invitations"."expires_at" FROM "invitations" WHERE "invitations"."user_id" = $1 AND "invitations"."status" = $2 AND "expires_at" > CURRENT_TIMESTAMP ORDER BY "invitations"."created_at" DESC"#,
+            vec![invitation.user_id.into(), "pending".into()]
+        )]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_invitations_service_create() -> Result<()> {
+    let invitation: Invitation = Faker.fake();
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![invitation.clone()]])
+            .into_connection(),
+    );
+
+    let service = DefaultInvitationsService::new(&db, &DomainEventPublisher::default());
+
+    let result = service
+        .create(&CreateInvitationInput {
+            show_id: invitation.show_id.clone(),
+            role_key: invitation.role_key.clone(),
+            user_id: invitation.user_id.clone(),
+            inviter_id: invitation.inviter_id.clone(),
+            expires_at: invitation.expires_at,
+        })
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, invitation.clone());
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"INSERT INTO "invitations" ("show_id", "role_key", "user_id", "inviter_id", "expires_at") VALUES ($1, $2, $3, $4, $5) RETURNING "id", "created_at", "updated_at", "show_id", "role_key", "user_id", "inviter_id", "status", "expires_at""#,
+            vec![
+                invitation.show_id.into(),
+                invitation.role_key.into(),
+                invitation.user_id.into(),
+                invitation.inviter_id.into(),
+                invitation.expires_at.into()
+            ]
+        )]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_invitations_service_accept() -> Result<()> {
+    let mut invitation: Invitation = Faker.fake();
+    invitation.status = InvitationStatus::Pending;
+
+    let accepted = Invitation {
+        status: InvitationStatus::Accepted,
+        ..invitation.clone()
+    };
+
+    let mut grant: RoleGrant = Faker.fake();
+    grant.role_key = invitation.role_key.clone();
+    grant.user_id = invitation.user_id.clone();
+    grant.resource_table = "shows".to_string();
+    grant.resource_id = invitation.show_id.clone();
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![accepted.clone()]])
+            .append_query_results(vec![Vec::<RoleGrant>::new(), vec![grant.clone()]])
+            .into_connection(),
+    );
+
+    let events = DomainEventPublisher::default();
+    let mut rx = events.subscribe();
+
+    let service = DefaultInvitationsService::new(&db, &events);
+
+    let result = service.accept(&invitation.id).await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, Some((accepted.clone(), Some(grant.clone()))));
+
+    // Listeners hear about the granted Role once it has been committed
+    assert_eq!(rx.try_recv()?, DomainEvent::RoleGranted(grant.clone()));
+    assert!(rx.try_recv().is_err());
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::many(vec![
+            Statement::from_string(DatabaseBackend::Postgres, "BEGIN".to_string()),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"UPDATE "invitations" SET "status" = $1 WHERE "invitations"."id" = $2 AND "invitations"."status" = $3 AND "expires_at" > CURRENT_TIMESTAMP RETURNING "id", "created_at", "updated_at", "show_id", "role_key", "user_id", "inviter_id", "status", "expires_at""#,
+                vec!["accepted".into(), invitation.id.into(), "pending".into()]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"SELECT "role_grants"."id", "role_grants"."created_at", "role_grants"."updated_at", "role_grants"."role_key", "role_grants"."user_id", "role_grants"."resource_table", "role_grants"."resource_id" FROM "role_grants" WHERE "role_grants"."user_id" = $1 AND "role_grants"."role_key" = $2 AND "role_grants"."resource_table" = $3 AND "role_grants"."resource_id" = $4 LIMIT $5"#,
+                vec![
+                    invitation.user_id.clone().into(),
+                    invitation.role_key.clone().into(),
+                    "shows".into(),
+                    invitation.show_id.clone().into(),
+                    1u64.into()
+                ]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"INSERT INTO "role_grants" ("role_key", "user_id", "resource_table", "resource_id") VALUES ($1, $2, $3, $4) RETURNING "id", "created_at", "updated_at", "role_key", "user_id", "resource_table", "resource_id""#,
+                vec![
+                    invitation.role_key.into(),
+                    invitation.user_id.into(),
+                    "shows".into(),
+                    invitation.show_id.into()
+                ]
+            ),
+            Statement::from_string(DatabaseBackend::Postgres, "COMMIT".to_string()),
+        ])]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_invitations_service_accept_existing_role() -> Result<()> {
+    let mut invitation: Invitation = Faker.fake();
+    invitation.status = InvitationStatus::Pending;
+
+    let accepted = Invitation {
+        status: InvitationStatus::Accepted,
+        ..invitation.clone()
+    };
+
+    let mut grant: RoleGrant = Faker.fake();
+    grant.role_key = invitation.role_key.clone();
+    grant.user_id = invitation.user_id.clone();
+    grant.resource_table = "shows".to_string();
+    grant.resource_id = invitation.show_id.clone();
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![accepted.clone()]])
+            .append_query_results(vec![vec![grant.clone()]])
+            .into_connection(),
+    );
+
+    let events = DomainEventPublisher::default();
+    let mut rx = events.subscribe();
+
+    let service = DefaultInvitationsService::new(&db, &events);
+
+    let result = service.accept(&invitation.id).await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    // The Role is already held, so no new grant is made
+    assert_eq!(result, Some((accepted.clone(), None)));
+
+    // Nothing was granted, so there is nothing to announce
+    assert!(rx.try_recv().is_err());
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::many(vec![
+            Statement::from_string(DatabaseBackend::Postgres, "BEGIN".to_string()),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"UPDATE "invitations" SET "status" = $1 WHERE "invitations"."id" = $2 AND "invitations"."status" = $3 AND "expires_at" > CURRENT_TIMESTAMP RETURNING "id", "created_at", "updated_at", "show_id", "role_key", "user_id", "inviter_id", "status", "expires_at""#,
+                vec!["accepted".into(), invitation.id.into(), "pending".into()]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"SELECT "role_grants"."id", "role_grants"."created_at", "role_grants"."updated_at", "role_grants"."role_key", "role_grants"."user_id", "role_grants"."resource_table", "role_grants"."resource_id" FROM "role_grants" WHERE "role_grants"."user_id" = $1 AND "role_grants"."role_key" = $2 AND "role_grants"."resource_table" = $3 AND "role_grants"."resource_id" = $4 LIMIT $5"#,
+                vec![
+                    invitation.user_id.into(),
+                    invitation.role_key.into(),
+                    "shows".into(),
+                    invitation.show_id.into(),
+                    1u64.into()
+                ]
+            ),
+            Statement::from_string(DatabaseBackend::Postgres, "COMMIT".to_string()),
+        ])]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_invitations_service_accept_not_pending() -> Result<()> {
+    let invitation: Invitation = Faker.fake();
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![Vec::<Invitation>::new()])
+            .into_connection(),
+    );
+
+    let events = DomainEventPublisher::default();
+    let mut rx = events.subscribe();
+
+    let service = DefaultInvitationsService::new(&db, &events);
+
+    let result = service.accept(&invitation.id).await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    // The Invitation was already responded to or has expired, so nothing is granted
+    assert_eq!(result, None);
+    assert!(rx.try_recv().is_err());
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::many(vec![
+            Statement::from_string(DatabaseBackend::Postgres, "BEGIN".to_string()),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"UPDATE "invitations" SET "status" = $1 WHERE "invitations"."id" = $2 AND "invitations"."status" = $3 AND "expires_at" > CURRENT_TIMESTAMP RETURNING "id", "created_at", "updated_at", "show_id", "role_key", "user_id", "inviter_id", "status", "expires_at""#,
+                vec!["accepted".into(), invitation.id.into(), "pending".into()]
+            ),
+            Statement::from_string(DatabaseBackend::Postgres, "ROLLBACK".to_string()),
+        ])]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_invitations_service_decline() -> Result<()> {
+    let mut invitation: Invitation = Faker.fake();
+    invitation.status = InvitationStatus::Pending;
+
+    let declined = Invitation {
+        status: InvitationStatus::Declined,
+        ..invitation.clone()
+    };
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![declined.clone()]])
+            .into_connection(),
+    );
+
+    let service = DefaultInvitationsService::new(&db, &DomainEventPublisher::default());
+
+    let result = service.decline(&invitation.id).await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, Some(declined.clone()));
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"UPDATE "invitations" SET "status" = $1 WHERE "invitations"."id" = $2 AND "invitations"."status" = $3 AND "expires_at" > CURRENT_TIMESTAMP RETURNING "id", "created_at", "updated_at", "show_id", "role_key", "user_id", "inviter_id", "status", "expires_at""#,
+            vec!["declined".into(), invitation.id.into(), "pending".into()]
+        )]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_invitations_service_purge_expired() -> Result<()> {
+    let expired_before = chrono::Utc::now().naive_utc();
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results(vec![MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 2,
+            }])
+            .into_connection(),
+    );
+
+    let service = DefaultInvitationsService::new(&db, &DomainEventPublisher::default());
+
+    let result = service.purge_expired(expired_before).await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, 2);
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"DELETE FROM "invitations" WHERE "invitations"."status" = $1 AND "invitations"."expires_at" < $2"#,
+            vec!["pending".into(), expired_before.into()]
+        )]
+    );
+
+    Ok(())
+}
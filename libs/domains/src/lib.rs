@@ -16,6 +16,43 @@ pub mod shows;
 /// Episodes
 pub mod episodes;
 
+/// Categories
+pub mod categories;
+
+/// Tags
+pub mod tags;
+
+/// Follows
+pub mod follows;
+
+/// Messages
+pub mod messages;
+
+/// Notifications
+pub mod notifications;
+
+/// Episode Presence
+pub mod presence;
+
+/// Webhooks
+pub mod webhooks;
+
+/// Audit Events
+pub mod audit_events;
+
+/// Domain Events
+pub mod domain_events;
+
+/// Invitations
+pub mod invitations;
+
+/// Authorization
+pub mod authorization;
+
 /// Error macros
 #[macro_use]
 extern crate anyhow;
+
+/// Logging macros
+#[macro_use]
+extern crate log;
@@ -0,0 +1,35 @@
+//! # Messages
+
+/// Service
+pub mod service;
+
+/// Model
+pub mod model;
+
+/// The previous text of edited Messages
+pub mod message_edit;
+
+/// Profiles muted in an Episode's chat
+pub mod mute;
+
+/// Emoji reactions to Messages
+pub mod reaction;
+
+/// Banned word filtering
+pub mod filter;
+
+/// `@username` mentions
+pub mod mention;
+
+/// GraphQL Queries
+pub mod queries;
+
+/// GraphQL Mutations
+pub mod mutations;
+
+/// GraphQL Resolver
+pub mod resolver;
+
+/// Tests
+#[cfg(test)]
+mod tests;
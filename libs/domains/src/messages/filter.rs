@@ -0,0 +1,52 @@
+use std::{collections::HashSet, iter};
+
+/// Masks banned words in chat Messages before they are saved. Each banned word is matched against
+/// whole words only, ignoring case, and replaced with one asterisk per character.
+#[derive(Clone, Debug, Default)]
+pub struct BannedWords(HashSet<String>);
+
+impl BannedWords {
+    /// Create a new filter for the given list of words
+    pub fn new(words: &[String]) -> Self {
+        Self(
+            words
+                .iter()
+                .map(|word| word.trim().to_lowercase())
+                .filter(|word| !word.is_empty())
+                .collect(),
+        )
+    }
+
+    /// Return the given text with each banned word masked
+    pub fn apply(&self, text: &str) -> String {
+        if self.0.is_empty() {
+            return text.to_string();
+        }
+
+        let mut filtered = String::with_capacity(text.len());
+        let mut word = String::new();
+
+        for c in text.chars() {
+            if c.is_alphanumeric() {
+                word.push(c);
+            } else {
+                self.push_word(&mut filtered, &word);
+                word.clear();
+
+                filtered.push(c);
+            }
+        }
+
+        self.push_word(&mut filtered, &word);
+
+        filtered
+    }
+
+    fn push_word(&self, filtered: &mut String, word: &str) {
+        if self.0.contains(&word.to_lowercase()) {
+            filtered.extend(iter::repeat_n('*', word.chars().count()));
+        } else {
+            filtered.push_str(word);
+        }
+    }
+}
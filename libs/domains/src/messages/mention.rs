@@ -0,0 +1,40 @@
+/// The most Users that a single Message can mention, to keep it from being used to spam
+pub const MAX_MENTIONS: usize = 10;
+
+/// Find the usernames mentioned in a Message's text with an `@username`, in the order they first
+/// appear. An `@` only starts a mention at the beginning of the text or after a character that
+/// can't be part of a username, so email addresses are skipped.
+pub fn mentioned_usernames(text: &str) -> Vec<String> {
+    let mut usernames: Vec<String> = Vec::new();
+    let mut previous: Option<char> = None;
+
+    for (index, c) in text.char_indices() {
+        let starts_mention = c == '@' && !previous.is_some_and(is_username_char);
+
+        previous = Some(c);
+
+        if !starts_mention {
+            continue;
+        }
+
+        let rest = &text[index + c.len_utf8()..];
+        let end = rest.find(|c| !is_username_char(c)).unwrap_or(rest.len());
+
+        // A trailing period ends the sentence rather than the username
+        let username = rest[..end].trim_end_matches('.');
+
+        if !username.is_empty() && !usernames.iter().any(|existing| existing == username) {
+            usernames.push(username.to_string());
+
+            if usernames.len() == MAX_MENTIONS {
+                break;
+            }
+        }
+    }
+
+    usernames
+}
+
+fn is_username_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '|')
+}
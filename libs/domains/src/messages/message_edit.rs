@@ -0,0 +1,67 @@
+#![allow(missing_docs)]
+
+use async_graphql::SimpleObject;
+use chrono::Utc;
+use fake::Dummy;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::messages::model as message_model;
+
+/// The text of a `Message` before it was edited
+#[derive(
+    Clone, Debug, Dummy, Eq, PartialEq, DeriveEntityModel, Deserialize, Serialize, SimpleObject,
+)]
+#[graphql(name = "MessageEdit")]
+#[sea_orm(table_name = "message_edits")]
+pub struct Model {
+    /// The MessageEdit id
+    #[sea_orm(primary_key, column_type = "Text")]
+    pub id: String,
+
+    /// The date the Message was edited
+    pub created_at: DateTime,
+
+    /// The id of the edited Message
+    #[sea_orm(column_type = "Text")]
+    pub message_id: String,
+
+    /// The Message text before the edit
+    #[sea_orm(column_type = "Text")]
+    pub text: String,
+}
+
+/// The `MessageEdit` GraphQL type is the same as the database Model
+pub type MessageEdit = Model;
+
+/// `MessageEdit` entity relationships
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "message_model::Entity",
+        from = "Column::MessageId",
+        to = "message_model::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Message,
+}
+
+impl Related<message_model::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Message.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Default for Model {
+    fn default() -> Self {
+        Self {
+            id: String::default(),
+            created_at: Utc::now().naive_utc(),
+            message_id: String::default(),
+            text: String::default(),
+        }
+    }
+}
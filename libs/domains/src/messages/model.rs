@@ -0,0 +1,96 @@
+#![allow(missing_docs)]
+
+use async_graphql::SimpleObject;
+use chrono::Utc;
+use fake::Dummy;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{episodes::model as episode_model, profiles::model as profile_model};
+
+/// The `Message` GraphQL and Database Model
+#[derive(
+    Clone, Debug, Dummy, Eq, PartialEq, DeriveEntityModel, Deserialize, Serialize, SimpleObject,
+)]
+#[graphql(name = "Message", complex)]
+#[sea_orm(table_name = "messages")]
+pub struct Model {
+    /// The Message id
+    #[sea_orm(primary_key, column_type = "Text")]
+    pub id: String,
+
+    /// The date the Message was posted
+    pub created_at: DateTime,
+
+    /// The date the Message was last updated
+    pub updated_at: DateTime,
+
+    /// The Message text
+    #[sea_orm(column_type = "Text")]
+    pub text: String,
+
+    /// The id of the Profile that posted the Message
+    #[sea_orm(column_type = "Text")]
+    pub profile_id: String,
+
+    /// The id of the Episode that the Message is about
+    #[sea_orm(column_type = "Text")]
+    pub episode_id: String,
+
+    /// The id of the Message that this one replies to, if it's part of a thread
+    #[sea_orm(column_type = "Text", nullable)]
+    pub parent_id: Option<String>,
+}
+
+/// The `Message` GraphQL type is the same as the database Model
+pub type Message = Model;
+
+/// `Message` entity relationships
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "profile_model::Entity",
+        from = "Column::ProfileId",
+        to = "profile_model::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Profile,
+
+    #[sea_orm(
+        belongs_to = "episode_model::Entity",
+        from = "Column::EpisodeId",
+        to = "episode_model::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Episode,
+}
+
+impl Related<profile_model::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Profile.def()
+    }
+}
+
+impl Related<episode_model::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Episode.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Default for Model {
+    fn default() -> Self {
+        Self {
+            id: String::default(),
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+            text: String::default(),
+            profile_id: String::default(),
+            episode_id: String::default(),
+            parent_id: Option::default(),
+        }
+    }
+}
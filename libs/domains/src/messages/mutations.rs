@@ -0,0 +1,51 @@
+use async_graphql::{InputObject, SimpleObject};
+use fake::Dummy;
+
+use super::{model::Message, mute::ChatMute};
+
+/// The `CreateMessageInput` input type
+#[derive(Clone, Default, Dummy, Eq, PartialEq, InputObject)]
+pub struct CreateMessageInput {
+    /// The id of the Episode to chat about
+    pub episode_id: String,
+
+    /// The Message text
+    pub text: String,
+
+    /// The id of the Message to reply to, in the same Episode
+    pub parent_id: Option<String>,
+}
+
+/// The `UpdateMessageInput` input type
+#[derive(Clone, Default, Dummy, Eq, PartialEq, InputObject)]
+pub struct UpdateMessageInput {
+    /// The new Message text
+    pub text: String,
+}
+
+/// The `MuteProfileInput` input type
+#[derive(Clone, Default, Dummy, Eq, PartialEq, InputObject)]
+pub struct MuteProfileInput {
+    /// The id of the Episode that the Profile can't chat about
+    pub episode_id: String,
+
+    /// The id of the Profile to mute
+    pub profile_id: String,
+
+    /// How long the Profile is muted for, in seconds
+    pub duration: i64,
+}
+
+/// The `MutateMessageResult` type
+#[derive(Clone, Default, Dummy, Eq, PartialEq, SimpleObject)]
+pub struct MutateMessageResult {
+    /// The Message
+    pub message: Option<Message>,
+}
+
+/// The `MuteProfileResult` type
+#[derive(Clone, Default, Dummy, Eq, PartialEq, SimpleObject)]
+pub struct MuteProfileResult {
+    /// The ChatMute
+    pub mute: Option<ChatMute>,
+}
@@ -0,0 +1,78 @@
+#![allow(missing_docs)]
+
+use async_graphql::SimpleObject;
+use chrono::Utc;
+use fake::Dummy;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{episodes::model as episode_model, profiles::model as profile_model};
+
+/// A Profile that can't chat about an Episode until a given date
+#[derive(
+    Clone, Debug, Dummy, Eq, PartialEq, DeriveEntityModel, Deserialize, Serialize, SimpleObject,
+)]
+#[graphql(name = "ChatMute")]
+#[sea_orm(table_name = "chat_mutes")]
+pub struct Model {
+    /// The ChatMute id
+    #[sea_orm(primary_key, column_type = "Text")]
+    pub id: String,
+
+    /// The date the Profile was first muted
+    pub created_at: DateTime,
+
+    /// The date the ChatMute was last updated
+    pub updated_at: DateTime,
+
+    /// The id of the Episode that the Profile can't chat about
+    #[sea_orm(column_type = "Text")]
+    pub episode_id: String,
+
+    /// The id of the muted Profile
+    #[sea_orm(column_type = "Text")]
+    pub profile_id: String,
+
+    /// The date the Profile can chat again
+    pub muted_until: DateTime,
+}
+
+/// The `ChatMute` GraphQL type is the same as the database Model
+pub type ChatMute = Model;
+
+/// `ChatMute` entity relationships
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "episode_model::Entity",
+        from = "Column::EpisodeId",
+        to = "episode_model::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Episode,
+
+    #[sea_orm(
+        belongs_to = "profile_model::Entity",
+        from = "Column::ProfileId",
+        to = "profile_model::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Profile,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Default for Model {
+    fn default() -> Self {
+        Self {
+            id: String::default(),
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+            episode_id: String::default(),
+            profile_id: String::default(),
+            muted_until: Utc::now().naive_utc(),
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use async_graphql::SimpleObject;
+
+use super::model::Message;
+
+/// The `MessagesPage` result type
+#[derive(Clone, Eq, PartialEq, SimpleObject)]
+pub struct MessagesPage {
+    /// The list of `Messages` returned for the current page, newest first
+    pub data: Vec<Message>,
+
+    /// The number of `Messages` returned for the current page
+    pub count: u64,
+
+    /// Pass this as the `before` argument to request the page of older `Messages`
+    pub end_cursor: Option<String>,
+
+    /// Whether there are older `Messages` before this page
+    pub has_next_page: bool,
+}
+
+/// The number of Profiles that reacted to a `Message` with an emoji
+#[derive(Clone, Debug, Eq, PartialEq, SimpleObject)]
+pub struct ReactionCount {
+    /// The reaction emoji
+    pub emoji: String,
+
+    /// The number of Profiles that reacted with it
+    pub count: u64,
+}
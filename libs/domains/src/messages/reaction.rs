@@ -0,0 +1,67 @@
+#![allow(missing_docs)]
+
+use chrono::Utc;
+use sea_orm::entity::prelude::*;
+
+use crate::{messages::model as message_model, profiles::model as profile_model};
+
+/// An emoji reaction from a Profile to a Message
+#[derive(Clone, Debug, Eq, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "message_reactions")]
+pub struct Model {
+    /// The id of the Message reacted to
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Text")]
+    pub message_id: String,
+
+    /// The id of the Profile that reacted
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Text")]
+    pub profile_id: String,
+
+    /// The reaction emoji
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Text")]
+    pub emoji: String,
+
+    /// The date of the reaction
+    pub created_at: DateTime,
+}
+
+/// Message Reaction entity relationships
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "message_model::Entity",
+        from = "Column::MessageId",
+        to = "message_model::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Message,
+
+    #[sea_orm(
+        belongs_to = "profile_model::Entity",
+        from = "Column::ProfileId",
+        to = "profile_model::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Profile,
+}
+
+impl Related<message_model::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Message.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Default for Model {
+    fn default() -> Self {
+        Self {
+            message_id: String::default(),
+            profile_id: String::default(),
+            emoji: String::default(),
+            created_at: Utc::now().naive_utc(),
+        }
+    }
+}
@@ -0,0 +1,550 @@
+use async_graphql::{dataloader::DataLoader, ComplexObject, Context, Object, Result};
+use chrono::{Duration, Utc};
+use hyper::StatusCode;
+use std::sync::Arc;
+
+use crate::{
+    audit_events::resolver::record_audit_event,
+    authorization::resolver::is_allowed,
+    episodes::{model::Episode, service::EpisodesService},
+    messages::{
+        message_edit::MessageEdit,
+        model::Message,
+        mutations::{
+            CreateMessageInput, MutateMessageResult, MuteProfileInput, MuteProfileResult,
+            UpdateMessageInput,
+        },
+        mute::ChatMute,
+        queries::{MessagesPage, ReactionCount},
+        service::{MessagesService, ReactionCountsLoader, ReplyCountLoader},
+    },
+    profiles::{model::Profile, service::ProfilesService},
+    users::model::User,
+};
+use caster_utils::errors::{as_graphql_error, graphql_error};
+
+/// The default number of Messages in a page of chat history
+const DEFAULT_PAGE_SIZE: u64 = 50;
+
+/// The maximum number of Messages in a page of chat history
+const MAX_PAGE_SIZE: u64 = 100;
+
+/// The Query segment owned by the Messages library
+#[derive(Default)]
+pub struct MessagesQuery {}
+
+/// The Mutation segment for Messages
+#[derive(Default)]
+pub struct MessagesMutation {}
+
+/// Queries for the `Message` model
+#[Object]
+impl MessagesQuery {
+    /// Get a single chat Message
+    async fn get_message(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "The Message id")] id: String,
+    ) -> Result<Option<Message>> {
+        let messages = ctx.data_unchecked::<Arc<dyn MessagesService>>();
+        let user = current_user(ctx)?;
+
+        let message = messages.get(&id).await.map_err(as_graphql_error(
+            "Error while fetching Message",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ))?;
+
+        if let Some(message) = &message {
+            let episode = get_episode(ctx, &message.episode_id).await?;
+
+            if !is_allowed(ctx, user, "episode_read_chat", episode)? {
+                return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
+            }
+        }
+
+        Ok(message)
+    }
+
+    /// Get the chat history for an Episode, newest first. Only top-level Messages are listed,
+    /// unless a `parentId` is given to list the replies in its thread instead.
+    async fn get_episode_messages(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "The Episode id")] episode_id: String,
+        #[graphql(desc = "The id of the Message to list replies to")] parent_id: Option<String>,
+        #[graphql(desc = "The endCursor of the previous page")] before: Option<String>,
+        #[graphql(desc = "The number of Messages to return")] limit: Option<u64>,
+    ) -> Result<MessagesPage> {
+        let messages = ctx.data_unchecked::<Arc<dyn MessagesService>>();
+
+        check_read_chat(ctx, &episode_id).await?;
+
+        let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+
+        let mut data = messages
+            .get_by_episode_id(&episode_id, parent_id, before, limit)
+            .await
+            .map_err(as_graphql_error(
+                "Error while fetching Messages",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        // The service returns an extra Message if there is another page
+        let has_next_page = data.len() as u64 > limit;
+        data.truncate(limit as usize);
+
+        Ok(MessagesPage {
+            count: data.len().try_into().unwrap_or(0),
+            end_cursor: data.last().map(|message| message.id.clone()),
+            data,
+            has_next_page,
+        })
+    }
+}
+
+/// Mutations for the `Message` model
+#[Object]
+impl MessagesMutation {
+    /// Post a chat Message about an Episode
+    async fn create_message(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateMessageInput,
+    ) -> Result<MutateMessageResult> {
+        let messages = ctx.data_unchecked::<Arc<dyn MessagesService>>();
+
+        let profile = current_profile(ctx).await?;
+
+        check_chat(ctx, &input.episode_id, &profile).await?;
+        check_text(&input.text)?;
+
+        // Replies must stay in the same Episode as the Message they reply to
+        if let Some(parent_id) = &input.parent_id {
+            let parent = messages.get(parent_id).await.map_err(as_graphql_error(
+                "Error while fetching Message",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+            if parent.is_none_or(|parent| parent.episode_id != input.episode_id) {
+                return Err(graphql_error(
+                    "Unable to find the Message to reply to in this Episode",
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+        }
+
+        let message = messages
+            .create(&input, &profile.id)
+            .await
+            .map_err(as_graphql_error(
+                "Error while creating Message",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        Ok(MutateMessageResult {
+            message: Some(message),
+        })
+    }
+
+    /// Edit the text of one of the current User's own chat Messages, keeping the previous text
+    async fn update_message(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        input: UpdateMessageInput,
+    ) -> Result<MutateMessageResult> {
+        let messages = ctx.data_unchecked::<Arc<dyn MessagesService>>();
+
+        let profile = current_profile(ctx).await?;
+        let existing = get_existing(ctx, &id).await?;
+
+        // Only the author can edit a Message
+        if existing.profile_id != profile.id {
+            return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
+        }
+
+        check_chat(ctx, &existing.episode_id, &profile).await?;
+        check_text(&input.text)?;
+
+        let message = messages
+            .update(&id, &input)
+            .await
+            .map_err(as_graphql_error(
+                "Error while updating Message",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        Ok(MutateMessageResult {
+            message: Some(message),
+        })
+    }
+
+    /// Remove a chat Message, which moderators can do for any Message
+    async fn delete_message(&self, ctx: &Context<'_>, id: String) -> Result<bool> {
+        let messages = ctx.data_unchecked::<Arc<dyn MessagesService>>();
+        let profiles = ctx.data_unchecked::<Arc<dyn ProfilesService>>();
+        let user = current_user(ctx)?;
+
+        let existing = get_existing(ctx, &id).await?;
+
+        let is_author = profiles
+            .get_by_user_id(&user.id, &false)
+            .await
+            .map_err(as_graphql_error(
+                "Error while fetching Profile",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?
+            .is_some_and(|profile| profile.id == existing.profile_id);
+
+        if !is_author {
+            let episode = get_episode(ctx, &existing.episode_id).await?;
+
+            if !is_allowed(ctx, user, "episode_moderate_chat", episode)? {
+                return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
+            }
+        }
+
+        messages.delete(&id).await.map_err(as_graphql_error(
+            "Error while deleting Message",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ))?;
+
+        record_audit_event(
+            ctx,
+            "delete",
+            "messages",
+            &id,
+            Some(&existing),
+            None::<&Message>,
+        )
+        .await;
+
+        Ok(true)
+    }
+
+    /// React to a chat Message with an emoji
+    async fn add_reaction(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "The Message id")] message_id: String,
+        #[graphql(desc = "The reaction emoji")] emoji: String,
+    ) -> Result<MutateMessageResult> {
+        let messages = ctx.data_unchecked::<Arc<dyn MessagesService>>();
+
+        let profile = current_profile(ctx).await?;
+        let message = get_existing(ctx, &message_id).await?;
+
+        check_read_chat(ctx, &message.episode_id).await?;
+        check_mute(ctx, &message.episode_id, &profile).await?;
+        check_emoji(&emoji)?;
+
+        messages
+            .add_reaction(&message.id, &profile.id, &emoji)
+            .await
+            .map_err(as_graphql_error(
+                "Error while adding reaction",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        Ok(MutateMessageResult {
+            message: Some(message),
+        })
+    }
+
+    /// Remove the current User's emoji reaction from a chat Message
+    async fn remove_reaction(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "The Message id")] message_id: String,
+        #[graphql(desc = "The reaction emoji")] emoji: String,
+    ) -> Result<MutateMessageResult> {
+        let messages = ctx.data_unchecked::<Arc<dyn MessagesService>>();
+
+        let profile = current_profile(ctx).await?;
+        let message = get_existing(ctx, &message_id).await?;
+
+        check_read_chat(ctx, &message.episode_id).await?;
+
+        messages
+            .remove_reaction(&message.id, &profile.id, &emoji)
+            .await
+            .map_err(as_graphql_error(
+                "Error while removing reaction",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        Ok(MutateMessageResult {
+            message: Some(message),
+        })
+    }
+
+    /// Keep a Profile from chatting about an Episode for the given number of seconds
+    async fn mute_profile(
+        &self,
+        ctx: &Context<'_>,
+        input: MuteProfileInput,
+    ) -> Result<MuteProfileResult> {
+        let messages = ctx.data_unchecked::<Arc<dyn MessagesService>>();
+        let profiles = ctx.data_unchecked::<Arc<dyn ProfilesService>>();
+
+        check_moderator(ctx, &input.episode_id).await?;
+
+        if input.duration <= 0 {
+            return Err(graphql_error(
+                "A mute duration must be a positive number of seconds",
+                StatusCode::BAD_REQUEST,
+            ));
+        }
+
+        let _profile = profiles
+            .get(&input.profile_id, &false)
+            .await
+            .map_err(as_graphql_error(
+                "Error while fetching Profile",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?
+            .ok_or_else(|| {
+                graphql_error("Unable to find existing Profile", StatusCode::NOT_FOUND)
+            })?;
+
+        let muted_until = Duration::try_seconds(input.duration)
+            .and_then(|duration| Utc::now().naive_utc().checked_add_signed(duration))
+            .ok_or_else(|| {
+                graphql_error("The mute duration is too long", StatusCode::BAD_REQUEST)
+            })?;
+
+        let mute = messages
+            .mute(&input.episode_id, &input.profile_id, muted_until)
+            .await
+            .map_err(as_graphql_error(
+                "Error while muting Profile",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        record_audit_event(
+            ctx,
+            "create",
+            "chat_mutes",
+            &mute.id,
+            None::<&ChatMute>,
+            Some(&mute),
+        )
+        .await;
+
+        Ok(MuteProfileResult { mute: Some(mute) })
+    }
+
+    /// Allow a muted Profile to chat about an Episode again
+    async fn unmute_profile(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "The Episode id")] episode_id: String,
+        #[graphql(desc = "The Profile id")] profile_id: String,
+    ) -> Result<bool> {
+        let messages = ctx.data_unchecked::<Arc<dyn MessagesService>>();
+
+        check_moderator(ctx, &episode_id).await?;
+
+        // Retrieve the active mute, if any, for the audit log
+        let existing = messages
+            .get_mute(&episode_id, &profile_id, Utc::now().naive_utc())
+            .await
+            .map_err(as_graphql_error(
+                "Error while checking chat mutes",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        let unmuted = messages
+            .unmute(&episode_id, &profile_id)
+            .await
+            .map_err(as_graphql_error(
+                "Error while unmuting Profile",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        if let Some(existing) = existing {
+            record_audit_event(
+                ctx,
+                "delete",
+                "chat_mutes",
+                &existing.id,
+                Some(&existing),
+                None::<&ChatMute>,
+            )
+            .await;
+        }
+
+        Ok(unmuted)
+    }
+}
+
+#[ComplexObject]
+impl Message {
+    /// The number of replies in the Message's thread
+    #[graphql(name = "replyCount")]
+    async fn resolve_reply_count(&self, ctx: &Context<'_>) -> Result<u64> {
+        let loader = ctx.data_unchecked::<DataLoader<ReplyCountLoader>>();
+        let count = loader.load_one(self.id.clone()).await?;
+
+        Ok(count.unwrap_or_default())
+    }
+
+    /// The number of reactions to the Message with each emoji, in the order they were first used
+    #[graphql(name = "reactions")]
+    async fn resolve_reactions(&self, ctx: &Context<'_>) -> Result<Vec<ReactionCount>> {
+        let loader = ctx.data_unchecked::<DataLoader<ReactionCountsLoader>>();
+        let reactions = loader.load_one(self.id.clone()).await?;
+
+        Ok(reactions.unwrap_or_default())
+    }
+
+    /// The previous text of the Message, oldest first
+    #[graphql(name = "edits")]
+    async fn resolve_edits(&self, ctx: &Context<'_>) -> Result<Vec<MessageEdit>> {
+        let messages = ctx.data_unchecked::<Arc<dyn MessagesService>>();
+
+        messages.get_edits(&self.id).await.map_err(as_graphql_error(
+            "Error while fetching Message edits",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ))
+    }
+}
+
+/// Require an authenticated User
+fn current_user<'a>(ctx: &Context<'a>) -> Result<&'a User> {
+    let user = ctx.data_unchecked::<Option<User>>();
+
+    user.as_ref()
+        .ok_or_else(|| graphql_error("Unauthorized", StatusCode::UNAUTHORIZED))
+}
+
+/// Find the Profile of the current User, who must be authenticated
+async fn current_profile(ctx: &Context<'_>) -> Result<Profile> {
+    let profiles = ctx.data_unchecked::<Arc<dyn ProfilesService>>();
+    let user = current_user(ctx)?;
+
+    profiles
+        .get_by_user_id(&user.id, &false)
+        .await
+        .map_err(as_graphql_error(
+            "Error while fetching Profile",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ))?
+        .ok_or_else(|| graphql_error("Unable to find existing Profile", StatusCode::NOT_FOUND))
+}
+
+/// Retrieve an existing Message, or fail with a not found error
+async fn get_existing(ctx: &Context<'_>, id: &str) -> Result<Message> {
+    let messages = ctx.data_unchecked::<Arc<dyn MessagesService>>();
+
+    messages
+        .get(id)
+        .await
+        .map_err(as_graphql_error(
+            "Error while fetching Message",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ))?
+        .ok_or_else(|| graphql_error("Unable to find existing Message", StatusCode::NOT_FOUND))
+}
+
+/// Retrieve an Episode along with its Show, so that inherited permissions apply
+async fn get_episode(ctx: &Context<'_>, id: &str) -> Result<Episode> {
+    let episodes = ctx.data_unchecked::<Arc<dyn EpisodesService>>();
+
+    episodes
+        .get(id, &true)
+        .await
+        .map_err(as_graphql_error(
+            "Error while fetching Episode",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ))?
+        .ok_or_else(|| graphql_error("Unable to find existing Episode", StatusCode::NOT_FOUND))
+}
+
+/// Require a User who is allowed to read the chat about the Episode
+async fn check_read_chat(ctx: &Context<'_>, episode_id: &str) -> Result<()> {
+    let user = current_user(ctx)?;
+
+    let episode = get_episode(ctx, episode_id).await?;
+
+    if !is_allowed(ctx, user, "episode_read_chat", episode)? {
+        return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
+    }
+
+    Ok(())
+}
+
+/// Require that the Profile is allowed to chat about the Episode, and isn't muted
+async fn check_chat(ctx: &Context<'_>, episode_id: &str, profile: &Profile) -> Result<()> {
+    let user = current_user(ctx)?;
+
+    let episode = get_episode(ctx, episode_id).await?;
+
+    if !is_allowed(ctx, user, "episode_chat", episode)? {
+        return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
+    }
+
+    check_mute(ctx, episode_id, profile).await
+}
+
+/// Require that the Profile isn't muted in the Episode's chat
+async fn check_mute(ctx: &Context<'_>, episode_id: &str, profile: &Profile) -> Result<()> {
+    let messages = ctx.data_unchecked::<Arc<dyn MessagesService>>();
+
+    let mute = messages
+        .get_mute(episode_id, &profile.id, Utc::now().naive_utc())
+        .await
+        .map_err(as_graphql_error(
+            "Error while checking chat mutes",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ))?;
+
+    if mute.is_some() {
+        return Err(graphql_error("Muted in this chat", StatusCode::FORBIDDEN));
+    }
+
+    Ok(())
+}
+
+/// Require a User who is allowed to moderate the chat about the Episode
+async fn check_moderator(ctx: &Context<'_>, episode_id: &str) -> Result<()> {
+    let user = current_user(ctx)?;
+
+    let episode = get_episode(ctx, episode_id).await?;
+
+    if !is_allowed(ctx, user, "episode_moderate_chat", episode)? {
+        return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
+    }
+
+    Ok(())
+}
+
+/// Require some text to post
+fn check_text(text: &str) -> Result<()> {
+    if text.trim().is_empty() {
+        return Err(graphql_error(
+            "A Message must have some text",
+            StatusCode::BAD_REQUEST,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Require a single emoji, rather than arbitrary text
+fn check_emoji(emoji: &str) -> Result<()> {
+    let valid = !emoji.is_empty()
+        && emoji.len() <= 32
+        && emoji
+            .chars()
+            .all(|c| !c.is_ascii() && !c.is_whitespace() && !c.is_alphanumeric());
+
+    if !valid {
+        return Err(graphql_error(
+            "A reaction must be a single emoji",
+            StatusCode::BAD_REQUEST,
+        ));
+    }
+
+    Ok(())
+}
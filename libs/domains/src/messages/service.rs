@@ -0,0 +1,432 @@
+use anyhow::Result;
+use async_graphql::{dataloader::Loader, FieldError};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sea_orm::{
+    entity::*,
+    prelude::DateTime,
+    query::*,
+    sea_query::{Expr, OnConflict},
+    DatabaseConnection, EntityTrait, TransactionTrait,
+};
+use std::{collections::HashMap, sync::Arc};
+
+use super::{
+    filter::BannedWords,
+    message_edit::{self, MessageEdit},
+    model::{self, Message},
+    mutations::{CreateMessageInput, UpdateMessageInput},
+    mute::{self, ChatMute},
+    queries::ReactionCount,
+    reaction,
+};
+use crate::domain_events::{model::DomainEvent, publisher::DomainEventPublisher};
+
+/// A MessagesService applies business logic to the chat Messages posted about Episodes
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait MessagesService: Sync + Send {
+    /// Get an individual `Message` by id
+    async fn get(&self, id: &str) -> Result<Option<Message>>;
+
+    /// Get up to `limit + 1` `Messages` about an `Episode`, newest first. Only top-level
+    /// `Messages` are included, unless a parent id is given to list the replies to it instead. The
+    /// extra `Message` is returned if there is another page.
+    async fn get_by_episode_id(
+        &self,
+        episode_id: &str,
+        parent_id: Option<String>,
+        before: Option<String>,
+        limit: u64,
+    ) -> Result<Vec<Message>>;
+
+    /// Count the replies to each of the given `Messages`
+    async fn get_reply_counts(&self, message_ids: Vec<String>) -> Result<HashMap<String, u64>>;
+
+    /// Count the reactions to each of the given `Messages`, grouped by emoji
+    async fn get_reaction_counts(
+        &self,
+        message_ids: Vec<String>,
+    ) -> Result<HashMap<String, Vec<ReactionCount>>>;
+
+    /// React to a `Message` with an emoji, which has no further effect if already done
+    async fn add_reaction(&self, message_id: &str, profile_id: &str, emoji: &str) -> Result<()>;
+
+    /// Remove a reaction from a `Message`, returning false if there wasn't one
+    async fn remove_reaction(
+        &self,
+        message_id: &str,
+        profile_id: &str,
+        emoji: &str,
+    ) -> Result<bool>;
+
+    /// Get the previous text of a `Message`, oldest first
+    async fn get_edits(&self, message_id: &str) -> Result<Vec<MessageEdit>>;
+
+    /// Post a `Message` from a `Profile`, masking any banned words
+    async fn create(&self, input: &CreateMessageInput, profile_id: &str) -> Result<Message>;
+
+    /// Update the text of an existing `Message`, keeping the previous text as an edit
+    async fn update(&self, id: &str, input: &UpdateMessageInput) -> Result<Message>;
+
+    /// Delete an existing `Message`
+    async fn delete(&self, id: &str) -> Result<()>;
+
+    /// Get the `ChatMute` keeping a `Profile` from chatting about an `Episode`, if it hasn't
+    /// expired by the given date
+    async fn get_mute(
+        &self,
+        episode_id: &str,
+        profile_id: &str,
+        now: DateTime,
+    ) -> Result<Option<ChatMute>>;
+
+    /// Mute a `Profile` in an `Episode's` chat until the given date, replacing any existing mute
+    async fn mute(
+        &self,
+        episode_id: &str,
+        profile_id: &str,
+        muted_until: DateTime,
+    ) -> Result<ChatMute>;
+
+    /// Unmute a `Profile` in an `Episode's` chat, returning false if it wasn't muted
+    async fn unmute(&self, episode_id: &str, profile_id: &str) -> Result<bool>;
+}
+
+/// The default `MessagesService` struct
+pub struct DefaultMessagesService {
+    /// The SeaOrm database connection
+    db: Arc<DatabaseConnection>,
+
+    /// Publishes the changes made by the service
+    events: DomainEventPublisher,
+
+    /// Masks banned words before Messages are saved
+    banned_words: BannedWords,
+}
+
+/// The default `MessagesService` implementation
+impl DefaultMessagesService {
+    /// Create a new `MessagesService` instance
+    pub fn new(
+        db: &Arc<DatabaseConnection>,
+        events: &DomainEventPublisher,
+        banned_words: BannedWords,
+    ) -> Self {
+        Self {
+            db: db.clone(),
+            events: events.clone(),
+            banned_words,
+        }
+    }
+}
+
+#[async_trait]
+impl MessagesService for DefaultMessagesService {
+    async fn get(&self, id: &str) -> Result<Option<Message>> {
+        let message = model::Entity::find_by_id(id.to_owned())
+            .one(&*self.db)
+            .await?;
+
+        Ok(message)
+    }
+
+    async fn get_by_episode_id(
+        &self,
+        episode_id: &str,
+        parent_id: Option<String>,
+        before: Option<String>,
+        limit: u64,
+    ) -> Result<Vec<Message>> {
+        let mut query = model::Entity::find().filter(model::Column::EpisodeId.eq(episode_id));
+
+        query = match parent_id {
+            Some(parent_id) => query.filter(model::Column::ParentId.eq(parent_id)),
+            None => query.filter(model::Column::ParentId.is_null()),
+        };
+
+        // Ids are ULIDs, so they sort in the order the Messages were posted
+        if let Some(before) = before {
+            query = query.filter(model::Column::Id.lt(before));
+        }
+
+        let messages = query
+            .order_by_desc(model::Column::Id)
+            .limit(limit + 1)
+            .all(&*self.db)
+            .await?;
+
+        Ok(messages)
+    }
+
+    async fn get_reply_counts(&self, message_ids: Vec<String>) -> Result<HashMap<String, u64>> {
+        let counts: Vec<(String, i64)> = model::Entity::find()
+            .select_only()
+            .column(model::Column::ParentId)
+            .column_as(model::Column::Id.count(), "total")
+            .filter(model::Column::ParentId.is_in(message_ids))
+            .group_by(model::Column::ParentId)
+            .into_tuple()
+            .all(&*self.db)
+            .await?;
+
+        Ok(counts
+            .into_iter()
+            .map(|(message_id, count)| (message_id, count.try_into().unwrap_or(0)))
+            .collect())
+    }
+
+    async fn get_reaction_counts(
+        &self,
+        message_ids: Vec<String>,
+    ) -> Result<HashMap<String, Vec<ReactionCount>>> {
+        let counts: Vec<(String, String, i64)> = reaction::Entity::find()
+            .select_only()
+            .column(reaction::Column::MessageId)
+            .column(reaction::Column::Emoji)
+            .column_as(reaction::Column::ProfileId.count(), "total")
+            .filter(reaction::Column::MessageId.is_in(message_ids))
+            .group_by(reaction::Column::MessageId)
+            .group_by(reaction::Column::Emoji)
+            .order_by_asc(reaction::Column::MessageId)
+            .order_by_asc(Expr::col(reaction::Column::CreatedAt).min())
+            .into_tuple()
+            .all(&*self.db)
+            .await?;
+
+        let mut reactions: HashMap<String, Vec<ReactionCount>> = HashMap::new();
+
+        for (message_id, emoji, count) in counts {
+            reactions
+                .entry(message_id)
+                .or_default()
+                .push(ReactionCount {
+                    emoji,
+                    count: count.try_into().unwrap_or(0),
+                });
+        }
+
+        Ok(reactions)
+    }
+
+    async fn add_reaction(&self, message_id: &str, profile_id: &str, emoji: &str) -> Result<()> {
+        reaction::Entity::insert(reaction::ActiveModel {
+            message_id: Set(message_id.to_string()),
+            profile_id: Set(profile_id.to_string()),
+            emoji: Set(emoji.to_string()),
+            ..Default::default()
+        })
+        .on_conflict(
+            OnConflict::columns([
+                reaction::Column::MessageId,
+                reaction::Column::ProfileId,
+                reaction::Column::Emoji,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .exec_without_returning(&*self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn remove_reaction(
+        &self,
+        message_id: &str,
+        profile_id: &str,
+        emoji: &str,
+    ) -> Result<bool> {
+        let result = reaction::Entity::delete_many()
+            .filter(reaction::Column::MessageId.eq(message_id))
+            .filter(reaction::Column::ProfileId.eq(profile_id))
+            .filter(reaction::Column::Emoji.eq(emoji))
+            .exec(&*self.db)
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn get_edits(&self, message_id: &str) -> Result<Vec<MessageEdit>> {
+        let edits = message_edit::Entity::find()
+            .filter(message_edit::Column::MessageId.eq(message_id))
+            .order_by_asc(message_edit::Column::CreatedAt)
+            .order_by_asc(message_edit::Column::Id)
+            .all(&*self.db)
+            .await?;
+
+        Ok(edits)
+    }
+
+    async fn create(&self, input: &CreateMessageInput, profile_id: &str) -> Result<Message> {
+        let message = model::ActiveModel {
+            episode_id: Set(input.episode_id.clone()),
+            profile_id: Set(profile_id.to_string()),
+            text: Set(self.banned_words.apply(&input.text)),
+            parent_id: Set(input.parent_id.clone()),
+            ..Default::default()
+        }
+        .insert(&*self.db)
+        .await?;
+
+        self.events
+            .publish(DomainEvent::MessagePosted(message.clone()));
+
+        Ok(message)
+    }
+
+    async fn update(&self, id: &str, input: &UpdateMessageInput) -> Result<Message> {
+        let txn = self.db.begin().await?;
+
+        // Retrieve the existing Message
+        let message = model::Entity::find_by_id(id.to_owned())
+            .one(&txn)
+            .await?
+            .ok_or_else(|| anyhow!("Unable to find Message with id: {}", id))?;
+
+        let text = self.banned_words.apply(&input.text);
+
+        // Nothing changed, so there is nothing to keep
+        if text == message.text {
+            txn.commit().await?;
+
+            return Ok(message);
+        }
+
+        message_edit::ActiveModel {
+            message_id: Set(message.id.clone()),
+            text: Set(message.text.clone()),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        let mut message: model::ActiveModel = message.into();
+        message.text = Set(text);
+
+        let updated: Message = message.update(&txn).await?;
+
+        txn.commit().await?;
+
+        self.events
+            .publish(DomainEvent::MessageUpdated(updated.clone()));
+
+        Ok(updated)
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        let message = model::Entity::find_by_id(id.to_owned())
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| anyhow!("Unable to find Message with id: {}", id))?;
+
+        // Edits are removed along with it by the foreign key
+        let _result = message.clone().delete(&*self.db).await?;
+
+        self.events.publish(DomainEvent::MessageDeleted(message));
+
+        Ok(())
+    }
+
+    async fn get_mute(
+        &self,
+        episode_id: &str,
+        profile_id: &str,
+        now: DateTime,
+    ) -> Result<Option<ChatMute>> {
+        let mute = mute::Entity::find()
+            .filter(mute::Column::EpisodeId.eq(episode_id))
+            .filter(mute::Column::ProfileId.eq(profile_id))
+            .filter(mute::Column::MutedUntil.gt(now))
+            .one(&*self.db)
+            .await?;
+
+        Ok(mute)
+    }
+
+    async fn mute(
+        &self,
+        episode_id: &str,
+        profile_id: &str,
+        muted_until: DateTime,
+    ) -> Result<ChatMute> {
+        let mute = mute::Entity::insert(mute::ActiveModel {
+            episode_id: Set(episode_id.to_string()),
+            profile_id: Set(profile_id.to_string()),
+            muted_until: Set(muted_until),
+            ..Default::default()
+        })
+        .on_conflict(
+            OnConflict::columns([mute::Column::EpisodeId, mute::Column::ProfileId])
+                .update_column(mute::Column::MutedUntil)
+                .to_owned(),
+        )
+        .exec_with_returning(&*self.db)
+        .await?;
+
+        Ok(mute)
+    }
+
+    async fn unmute(&self, episode_id: &str, profile_id: &str) -> Result<bool> {
+        let result = mute::Entity::delete_many()
+            .filter(mute::Column::EpisodeId.eq(episode_id))
+            .filter(mute::Column::ProfileId.eq(profile_id))
+            .exec(&*self.db)
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+}
+
+/// A dataloader for the number of replies to each `Message`, keyed by `Message` id
+pub struct ReplyCountLoader {
+    /// The SeaOrm database connection
+    messages: Arc<dyn MessagesService>,
+}
+
+/// The default implementation for the `ReplyCountLoader`
+impl ReplyCountLoader {
+    /// Create a new instance
+    pub fn new(messages: &Arc<dyn MessagesService>) -> Self {
+        Self {
+            messages: messages.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl Loader<String> for ReplyCountLoader {
+    type Value = u64;
+    type Error = FieldError;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        Ok(self.messages.get_reply_counts(keys.into()).await?)
+    }
+}
+
+/// A dataloader for the reactions to each `Message` grouped by emoji, keyed by `Message` id
+pub struct ReactionCountsLoader {
+    /// The SeaOrm database connection
+    messages: Arc<dyn MessagesService>,
+}
+
+/// The default implementation for the `ReactionCountsLoader`
+impl ReactionCountsLoader {
+    /// Create a new instance
+    pub fn new(messages: &Arc<dyn MessagesService>) -> Self {
+        Self {
+            messages: messages.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl Loader<String> for ReactionCountsLoader {
+    type Value = Vec<ReactionCount>;
+    type Error = FieldError;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        Ok(self.messages.get_reaction_counts(keys.into()).await?)
+    }
+}
@@ -0,0 +1,3 @@
+mod filter_test;
+mod mention_test;
+mod service_test;
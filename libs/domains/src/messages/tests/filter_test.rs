@@ -0,0 +1,23 @@
+use pretty_assertions::assert_eq;
+
+use crate::messages::filter::BannedWords;
+
+#[test]
+fn test_banned_words_apply() {
+    let filter = BannedWords::new(&["heck".to_string(), " Darn ".to_string(), "".to_string()]);
+
+    assert_eq!(
+        filter.apply("What the HECK, darn it!"),
+        "What the ****, **** it!"
+    );
+
+    // Only whole words are matched
+    assert_eq!(filter.apply("Checkered darning"), "Checkered darning");
+}
+
+#[test]
+fn test_banned_words_empty() {
+    let filter = BannedWords::default();
+
+    assert_eq!(filter.apply("What the heck"), "What the heck");
+}
@@ -0,0 +1,33 @@
+use pretty_assertions::assert_eq;
+
+use crate::messages::mention::{mentioned_usernames, MAX_MENTIONS};
+
+#[test]
+fn test_mentioned_usernames() {
+    assert_eq!(
+        mentioned_usernames("@alice, have you met @bob.smith? Ask @alice about @carol."),
+        vec!["alice", "bob.smith", "carol"]
+    );
+
+    // Usernames from an identity provider may include a separator
+    assert_eq!(mentioned_usernames("(@auth0|1234)"), vec!["auth0|1234"]);
+}
+
+#[test]
+fn test_mentioned_usernames_ignored() {
+    // Email addresses and lone @ signs aren't mentions
+    assert_eq!(
+        mentioned_usernames("Write to alice@example.com @ noon, or @."),
+        Vec::<String>::new()
+    );
+}
+
+#[test]
+fn test_mentioned_usernames_limit() {
+    let text = (0..MAX_MENTIONS + 5)
+        .map(|i| format!("@user{}", i))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    assert_eq!(mentioned_usernames(&text).len(), MAX_MENTIONS);
+}
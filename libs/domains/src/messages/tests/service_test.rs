@@ -0,0 +1,236 @@
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use fake::{Fake, Faker};
+use pretty_assertions::assert_eq;
+use sea_orm::{DatabaseBackend, MockDatabase, Statement, Transaction};
+use std::sync::Arc;
+
+use crate::{
+    domain_events::{model::DomainEvent, publisher::DomainEventPublisher},
+    messages::{
+        filter::BannedWords,
+        message_edit::MessageEdit,
+        model::Message,
+        mutations::{CreateMessageInput, UpdateMessageInput},
+        mute::ChatMute,
+        service::{DefaultMessagesService, MessagesService},
+    },
+};
+
+#[tokio::test]
+async fn test_messages_service_create() -> Result<()> {
+    let mut message: Message = Faker.fake();
+    message.episode_id = "test-episode".to_string();
+    message.profile_id = "test-profile".to_string();
+    message.text = "What the ****".to_string();
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![message.clone()]])
+            .into_connection(),
+    );
+
+    let events = DomainEventPublisher::default();
+    let mut rx = events.subscribe();
+
+    let service =
+        DefaultMessagesService::new(&db, &events, BannedWords::new(&["heck".to_string()]));
+
+    let result = service
+        .create(
+            &CreateMessageInput {
+                episode_id: "test-episode".to_string(),
+                text: "What the heck".to_string(),
+                parent_id: None,
+            },
+            "test-profile",
+        )
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, message);
+    assert_eq!(rx.try_recv()?, DomainEvent::MessagePosted(message));
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"INSERT INTO "messages" ("text", "profile_id", "episode_id", "parent_id") VALUES ($1, $2, $3, $4) RETURNING "id", "created_at", "updated_at", "text", "profile_id", "episode_id", "parent_id""#,
+            vec![
+                "What the ****".into(),
+                "test-profile".into(),
+                "test-episode".into(),
+                None::<String>.into()
+            ]
+        )]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_messages_service_get_by_episode_id() -> Result<()> {
+    let mut message: Message = Faker.fake();
+    message.episode_id = "test-episode".to_string();
+    message.parent_id = None;
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![message.clone()]])
+            .into_connection(),
+    );
+
+    let service = DefaultMessagesService::new(
+        &db,
+        &DomainEventPublisher::default(),
+        BannedWords::default(),
+    );
+
+    let result = service
+        .get_by_episode_id("test-episode", None, Some("test-cursor".to_string()), 20)
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, vec![message]);
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"SELECT "messages"."id", "messages"."created_at", "messages"."updated_at", "messages"."text", "messages"."profile_id", "messages"."episode_id", "messages"."parent_id" FROM "messages" WHERE "messages"."episode_id" = $1 AND "messages"."parent_id" IS NULL AND "messages"."id" < $2 ORDER BY "messages"."id" DESC LIMIT $3"#,
+            vec!["test-episode".into(), "test-cursor".into(), 21u64.into()]
+        )]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_messages_service_update() -> Result<()> {
+    let mut message: Message = Faker.fake();
+    message.text = "Original text".to_string();
+
+    let mut edit: MessageEdit = Faker.fake();
+    edit.message_id = message.id.clone();
+    edit.text = message.text.clone();
+
+    let updated = Message {
+        text: "Updated text".to_string(),
+        ..message.clone()
+    };
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![message.clone()]])
+            .append_query_results(vec![vec![edit.clone()]])
+            .append_query_results(vec![vec![updated.clone()]])
+            .into_connection(),
+    );
+
+    let service = DefaultMessagesService::new(
+        &db,
+        &DomainEventPublisher::default(),
+        BannedWords::default(),
+    );
+
+    let result = service
+        .update(
+            &message.id,
+            &UpdateMessageInput {
+                text: "Updated text".to_string(),
+            },
+        )
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, updated);
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::many(vec![
+            Statement::from_string(DatabaseBackend::Postgres, "BEGIN".to_string()),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"SELECT "messages"."id", "messages"."created_at", "messages"."updated_at", "messages"."text", "messages"."profile_id", "messages"."episode_id", "messages"."parent_id" FROM "messages" WHERE "messages"."id" = $1 LIMIT $2"#,
+                vec![message.id.clone().into(), 1u64.into()]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"INSERT INTO "message_edits" ("message_id", "text") VALUES ($1, $2) RETURNING "id", "created_at", "message_id", "text""#,
+                vec![message.id.clone().into(), "Original text".into()]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"UPDATE "messages" SET "text" = $1 WHERE "messages"."id" = $2 RETURNING "id", "created_at", "updated_at", "text", "profile_id", "episode_id", "parent_id""#,
+                vec!["Updated text".into(), message.id.into()]
+            ),
+            Statement::from_string(DatabaseBackend::Postgres, "COMMIT".to_string()),
+        ])]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_messages_service_mute() -> Result<()> {
+    let muted_until = Utc::now().naive_utc() + Duration::minutes(10);
+
+    let mut mute: ChatMute = Faker.fake();
+    mute.episode_id = "test-episode".to_string();
+    mute.profile_id = "test-profile".to_string();
+    mute.muted_until = muted_until;
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![mute.clone()]])
+            .into_connection(),
+    );
+
+    let service = DefaultMessagesService::new(
+        &db,
+        &DomainEventPublisher::default(),
+        BannedWords::default(),
+    );
+
+    let result = service
+        .mute("test-episode", "test-profile", muted_until)
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, mute);
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"INSERT INTO "chat_mutes" ("episode_id", "profile_id", "muted_until") VALUES ($1, $2, $3) ON CONFLICT ("episode_id", "profile_id") DO UPDATE SET "muted_until" = "excluded"."muted_until" RETURNING "id", "created_at", "updated_at", "episode_id", "profile_id", "muted_until""#,
+            vec![
+                "test-episode".into(),
+                "test-profile".into(),
+                muted_until.into()
+            ]
+        )]
+    );
+
+    Ok(())
+}
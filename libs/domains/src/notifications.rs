@@ -0,0 +1,20 @@
+//! # Notifications
+
+/// Service
+pub mod service;
+
+/// Model
+pub mod model;
+
+/// GraphQL Queries
+pub mod queries;
+
+/// GraphQL Resolver
+pub mod resolver;
+
+/// New Notification broadcasts
+pub mod publisher;
+
+/// Tests
+#[cfg(test)]
+mod tests;
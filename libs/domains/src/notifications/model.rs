@@ -0,0 +1,166 @@
+#![allow(missing_docs)]
+
+use async_graphql::{Enum, SimpleObject};
+use chrono::Utc;
+use fake::Dummy;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    episodes::model::Episode, messages::model::Message, role_grants::model::RoleGrant,
+    users::model as user_model,
+};
+
+/// The kinds of things a User can be notified about
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Dummy,
+    Eq,
+    PartialEq,
+    EnumIter,
+    DeriveActiveEnum,
+    Deserialize,
+    Serialize,
+    Enum,
+)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+pub enum NotificationKind {
+    /// A new Episode was published on a followed Show
+    #[default]
+    #[sea_orm(string_value = "episode_published")]
+    EpisodePublished,
+
+    /// The User was granted a Role
+    #[sea_orm(string_value = "role_granted")]
+    RoleGranted,
+
+    /// The User was mentioned in a chat Message
+    #[sea_orm(string_value = "mentioned")]
+    Mentioned,
+}
+
+/// The `Notification` GraphQL and Database Model
+#[derive(
+    Clone, Debug, Dummy, Eq, PartialEq, DeriveEntityModel, Deserialize, Serialize, SimpleObject,
+)]
+#[graphql(name = "Notification")]
+#[sea_orm(table_name = "notifications")]
+pub struct Model {
+    /// The Notification id
+    #[sea_orm(primary_key, column_type = "Text")]
+    pub id: String,
+
+    /// The date the Notification was created
+    pub created_at: DateTime,
+
+    /// The id of the User being notified
+    #[sea_orm(column_type = "Text")]
+    pub user_id: String,
+
+    /// What the Notification is about
+    pub kind: NotificationKind,
+
+    /// The details needed to display the Notification, which depend on its kind
+    #[sea_orm(column_type = "JsonBinary")]
+    #[dummy(default)]
+    pub payload: Json,
+
+    /// The date the User read the Notification
+    pub read_at: Option<DateTime>,
+}
+
+/// The `Notification` GraphQL type is the same as the database Model
+pub type Notification = Model;
+
+/// `Notification` entity relationships
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "user_model::Entity",
+        from = "Column::UserId",
+        to = "user_model::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<user_model::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Default for Model {
+    fn default() -> Self {
+        Self {
+            id: String::default(),
+            created_at: Utc::now().naive_utc(),
+            user_id: String::default(),
+            kind: NotificationKind::default(),
+            payload: Json::Null,
+            read_at: Option::default(),
+        }
+    }
+}
+
+/// The `CreateNotificationInput` type
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CreateNotificationInput {
+    /// The id of the User being notified
+    pub user_id: String,
+
+    /// What the Notification is about
+    pub kind: NotificationKind,
+
+    /// The details needed to display the Notification
+    pub payload: Json,
+}
+
+impl CreateNotificationInput {
+    /// Notify a User that an Episode was published on a Show they follow
+    pub fn episode_published(user_id: &str, episode: &Episode) -> Self {
+        Self {
+            user_id: user_id.to_string(),
+            kind: NotificationKind::EpisodePublished,
+            payload: json!({
+                "showId": episode.show_id,
+                "episodeId": episode.id,
+                "title": episode.title,
+            }),
+        }
+    }
+
+    /// Notify a User that they were granted a Role
+    pub fn role_granted(grant: &RoleGrant) -> Self {
+        Self {
+            user_id: grant.user_id.clone(),
+            kind: NotificationKind::RoleGranted,
+            payload: json!({
+                "roleGrantId": grant.id,
+                "roleKey": grant.role_key,
+                "resourceTable": grant.resource_table,
+                "resourceId": grant.resource_id,
+            }),
+        }
+    }
+
+    /// Notify a User that they were mentioned in a chat Message
+    pub fn mentioned(user_id: &str, message: &Message) -> Self {
+        Self {
+            user_id: user_id.to_string(),
+            kind: NotificationKind::Mentioned,
+            payload: json!({
+                "messageId": message.id,
+                "episodeId": message.episode_id,
+                "profileId": message.profile_id,
+            }),
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use tokio::sync::broadcast;
+
+use super::model::Notification;
+
+/// The number of new `Notifications` that can be waiting for slow listeners before they miss some
+const CAPACITY: usize = 100;
+
+/// Broadcasts `Notifications` to listeners as they are created
+#[derive(Clone)]
+pub struct NotificationPublisher(broadcast::Sender<Notification>);
+
+impl Default for NotificationPublisher {
+    fn default() -> Self {
+        let (tx, _rx) = broadcast::channel(CAPACITY);
+
+        Self(tx)
+    }
+}
+
+impl NotificationPublisher {
+    /// Notify listeners that a `Notification` has been created
+    pub fn notify(&self, notification: &Notification) {
+        // Sending only fails when nobody is listening
+        let _ = self.0.send(notification.clone());
+    }
+
+    /// Listen for new `Notifications`
+    pub fn subscribe(&self) -> broadcast::Receiver<Notification> {
+        self.0.subscribe()
+    }
+}
@@ -0,0 +1,35 @@
+use async_graphql::SimpleObject;
+
+use crate::notifications::model::Notification;
+use caster_utils::pagination::ManyResponse;
+
+/// The `NotificationsPage` result type
+#[derive(Clone, Eq, PartialEq, SimpleObject)]
+pub struct NotificationsPage {
+    /// The list of `Notifications` returned for the current page
+    data: Vec<Notification>,
+
+    /// The number of `Notifications` returned for the current page
+    count: u64,
+
+    /// Tne total number of `Notifications` available
+    total: u64,
+
+    /// The current page
+    page: u64,
+
+    /// The number of pages available
+    page_count: u64,
+}
+
+impl From<ManyResponse<Notification>> for NotificationsPage {
+    fn from(resp: ManyResponse<Notification>) -> NotificationsPage {
+        NotificationsPage {
+            data: resp.data,
+            count: resp.count,
+            total: resp.total,
+            page: resp.page,
+            page_count: resp.page_count,
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use async_graphql::{Context, Object, Result};
+use hyper::StatusCode;
+use std::sync::Arc;
+
+use super::{queries::NotificationsPage, service::NotificationsService};
+use crate::users::model::User;
+use caster_utils::errors::{as_graphql_error, graphql_error};
+
+/// The Query segment owned by the Notifications library
+#[derive(Default)]
+pub struct NotificationsQuery {}
+
+/// The Mutation segment for Notifications
+#[derive(Default)]
+pub struct NotificationsMutation {}
+
+/// Queries for the `Notification` model
+#[Object]
+impl NotificationsQuery {
+    /// Get the Notifications sent to the current User, most recent first
+    async fn get_my_notifications(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Only include Notifications that haven't been read")] unread: Option<bool>,
+        page: Option<u64>,
+        page_size: Option<u64>,
+    ) -> Result<NotificationsPage> {
+        let notifications = ctx.data_unchecked::<Arc<dyn NotificationsService>>();
+        let user = current_user(ctx)?;
+
+        let response = notifications
+            .get_by_user_id(&user.id, unread.unwrap_or(false), page, page_size)
+            .await
+            .map_err(as_graphql_error(
+                "Error while listing Notifications",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        Ok(response.into())
+    }
+}
+
+/// Mutations for the `Notification` model
+#[Object]
+impl NotificationsMutation {
+    /// Mark the current User's Notifications as read, returning the number that were unread
+    async fn mark_notifications_read(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "The Notifications to mark, or all of them if not provided")] ids: Option<
+            Vec<String>,
+        >,
+    ) -> Result<u64> {
+        let notifications = ctx.data_unchecked::<Arc<dyn NotificationsService>>();
+        let user = current_user(ctx)?;
+
+        let marked = notifications
+            .mark_read(&user.id, &ids)
+            .await
+            .map_err(as_graphql_error(
+                "Error while updating Notifications",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        Ok(marked)
+    }
+}
+
+/// Require an authenticated User
+fn current_user<'a>(ctx: &'a Context<'_>) -> Result<&'a User> {
+    let user = ctx.data_unchecked::<Option<User>>();
+
+    user.as_ref()
+        .ok_or_else(|| graphql_error("Unauthorized", StatusCode::UNAUTHORIZED))
+}
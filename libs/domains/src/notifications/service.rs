@@ -0,0 +1,124 @@
+use anyhow::Result;
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sea_orm::{entity::*, query::*, sea_query::Expr, DatabaseConnection, EntityTrait};
+use std::sync::Arc;
+
+use super::model::{self, CreateNotificationInput, Notification};
+use caster_utils::pagination::ManyResponse;
+
+/// A NotificationsService records and retrieves the Notifications sent to Users
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait NotificationsService: Sync + Send {
+    /// Get the `Notification` records for a `User`, most recent first
+    async fn get_by_user_id(
+        &self,
+        user_id: &str,
+        unread_only: bool,
+        page: Option<u64>,
+        page_size: Option<u64>,
+    ) -> Result<ManyResponse<Notification>>;
+
+    /// Create a `Notification` for each of the given inputs together
+    async fn create_many(&self, inputs: &[CreateNotificationInput]) -> Result<Vec<Notification>>;
+
+    /// Mark a `User's` unread `Notifications` as read, limited to the given ids if provided, and
+    /// return the number marked
+    async fn mark_read(&self, user_id: &str, ids: &Option<Vec<String>>) -> Result<u64>;
+}
+
+/// The default `NotificationsService` struct
+pub struct DefaultNotificationsService {
+    /// The SeaOrm database connection
+    db: Arc<DatabaseConnection>,
+}
+
+/// The default `NotificationsService` implementation
+impl DefaultNotificationsService {
+    /// Create a new `NotificationsService` instance
+    pub fn new(db: &Arc<DatabaseConnection>) -> Self {
+        Self { db: db.clone() }
+    }
+}
+
+#[async_trait]
+impl NotificationsService for DefaultNotificationsService {
+    async fn get_by_user_id(
+        &self,
+        user_id: &str,
+        unread_only: bool,
+        page: Option<u64>,
+        page_size: Option<u64>,
+    ) -> Result<ManyResponse<Notification>> {
+        let page_num = page.unwrap_or(1);
+
+        let mut query = model::Entity::find().filter(model::Column::UserId.eq(user_id));
+
+        if unread_only {
+            query = query.filter(model::Column::ReadAt.is_null());
+        }
+
+        let query = query
+            .order_by_desc(model::Column::CreatedAt)
+            .order_by_desc(model::Column::Id);
+
+        let (data, total) = if let Some(page_size) = page_size {
+            let paginator = query.paginate(&*self.db, page_size);
+            let total = paginator.num_items().await?;
+            let data: Vec<Notification> = paginator.fetch_page(page_num - 1).await?;
+
+            (data, total)
+        } else {
+            let data: Vec<Notification> = query.all(&*self.db).await?;
+            let total = data.len().try_into().unwrap_or(0);
+
+            (data, total)
+        };
+
+        Ok(ManyResponse::new(data, total, page_num, page_size))
+    }
+
+    async fn create_many(&self, inputs: &[CreateNotificationInput]) -> Result<Vec<Notification>> {
+        if inputs.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let txn = self.db.begin().await?;
+
+        let mut notifications = Vec::with_capacity(inputs.len());
+
+        for input in inputs {
+            let notification = model::ActiveModel {
+                user_id: Set(input.user_id.clone()),
+                kind: Set(input.kind),
+                payload: Set(input.payload.clone()),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?;
+
+            notifications.push(notification);
+        }
+
+        txn.commit().await?;
+
+        Ok(notifications)
+    }
+
+    async fn mark_read(&self, user_id: &str, ids: &Option<Vec<String>>) -> Result<u64> {
+        let mut query = model::Entity::update_many()
+            .col_expr(model::Column::ReadAt, Expr::current_timestamp().into())
+            .filter(model::Column::UserId.eq(user_id))
+            .filter(model::Column::ReadAt.is_null());
+
+        if let Some(ids) = ids {
+            query = query.filter(model::Column::Id.is_in(ids.clone()));
+        }
+
+        let result = query.exec(&*self.db).await?;
+
+        Ok(result.rows_affected)
+    }
+}
@@ -0,0 +1,146 @@
+use anyhow::Result;
+use fake::{Fake, Faker};
+use pretty_assertions::assert_eq;
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Statement, Transaction};
+use serde_json::json;
+use std::sync::Arc;
+
+use crate::notifications::{
+    model::{CreateNotificationInput, Notification, NotificationKind},
+    service::{DefaultNotificationsService, NotificationsService},
+};
+use caster_utils::pagination::ManyResponse;
+
+#[tokio::test]
+async fn test_notifications_service_get_by_user_id() -> Result<()> {
+    let mut notification: Notification = Faker.fake();
+    notification.user_id = "test-user".to_string();
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![notification.clone()]])
+            .into_connection(),
+    );
+
+    let service = DefaultNotificationsService::new(&db);
+
+    let result = service
+        .get_by_user_id("test-user", true, None, None)
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(
+        result,
+        ManyResponse {
+            data: vec![notification],
+            count: 1,
+            total: 1,
+            page: 1,
+            page_count: 1,
+        }
+    );
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"SELECT "notifications"."id", "notifications"."created_at", "notifications"."user_id", "notifications"."kind", "notifications"."payload", "notifications"."read_at" FROM "notifications" WHERE "notifications"."user_id" = $1 AND "notifications"."read_at" IS NULL ORDER BY "notifications"."created_at" DESC, "notifications"."id" DESC"#,
+            vec!["test-user".into()]
+        )]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_notifications_service_create_many() -> Result<()> {
+    let mut notification: Notification = Faker.fake();
+    notification.user_id = "test-user".to_string();
+    notification.kind = NotificationKind::RoleGranted;
+    notification.payload = json!({ "roleKey": "admin" });
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![notification.clone()]])
+            .into_connection(),
+    );
+
+    let service = DefaultNotificationsService::new(&db);
+
+    let result = service
+        .create_many(&[CreateNotificationInput {
+            user_id: "test-user".to_string(),
+            kind: NotificationKind::RoleGranted,
+            payload: json!({ "roleKey": "admin" }),
+        }])
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, vec![notification]);
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::many(vec![
+            Statement::from_string(DatabaseBackend::Postgres, "BEGIN".to_string()),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"INSERT INTO "notifications" ("user_id", "kind", "payload") VALUES ($1, $2, $3) RETURNING "id", "created_at", "user_id", "kind", "payload", "read_at""#,
+                vec![
+                    "test-user".into(),
+                    "role_granted".into(),
+                    json!({ "roleKey": "admin" }).into()
+                ]
+            ),
+            Statement::from_string(DatabaseBackend::Postgres, "COMMIT".to_string()),
+        ])]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_notifications_service_mark_read() -> Result<()> {
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results(vec![MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 2,
+            }])
+            .into_connection(),
+    );
+
+    let service = DefaultNotificationsService::new(&db);
+
+    let result = service
+        .mark_read("test-user", &Some(vec!["test-notification".to_string()]))
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, 2);
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"UPDATE "notifications" SET "read_at" = CURRENT_TIMESTAMP WHERE "notifications"."user_id" = $1 AND "notifications"."read_at" IS NULL AND "notifications"."id" IN ($2)"#,
+            vec!["test-user".into(), "test-notification".into()]
+        )]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,17 @@
+//! # Presence
+
+/// Service
+pub mod service;
+
+/// Model
+pub mod model;
+
+/// GraphQL Queries
+pub mod queries;
+
+/// GraphQL Resolver
+pub mod resolver;
+
+/// Tests
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// A `WebSocket` connection that is watching an Episode
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct Viewer {
+    /// The connection id
+    pub connection_id: String,
+
+    /// The Profile of the User watching, if the connection is authenticated
+    pub profile_id: Option<String>,
+}
+
+/// Count the distinct viewers among a set of connections. A Profile watching from more than one
+/// connection is only counted once, while each anonymous connection counts on its own.
+pub fn viewer_count(viewers: &[Viewer]) -> u64 {
+    let mut profiles = HashSet::new();
+    let mut anonymous = 0;
+
+    for viewer in viewers {
+        match &viewer.profile_id {
+            Some(profile_id) => {
+                profiles.insert(profile_id.as_str());
+            }
+            None => anonymous += 1,
+        }
+    }
+
+    profiles.len() as u64 + anonymous
+}
@@ -0,0 +1,16 @@
+use async_graphql::SimpleObject;
+
+use crate::profiles::model::Profile;
+
+/// The `EpisodePresence` result type
+#[derive(Clone, Eq, PartialEq, SimpleObject)]
+pub struct EpisodePresence {
+    /// The Episode id
+    pub episode_id: String,
+
+    /// The number of distinct viewers, including anonymous ones
+    pub viewer_count: u64,
+
+    /// The Profiles of the authenticated Users watching
+    pub profiles: Vec<Profile>,
+}
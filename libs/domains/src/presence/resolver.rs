@@ -0,0 +1,75 @@
+use async_graphql::{dataloader::DataLoader, Context, Object, Result};
+use hyper::StatusCode;
+use std::{collections::BTreeSet, sync::Arc};
+
+use super::{model::viewer_count, queries::EpisodePresence, service::PresenceService};
+use crate::{
+    episodes::{resolver::can_manage, service::EpisodesService},
+    profiles::service::ProfileLoader,
+    users::model::User,
+};
+use caster_utils::errors::{as_graphql_error, graphql_error};
+
+/// The Query segment for Presence
+#[derive(Default)]
+pub struct PresenceQuery {}
+
+/// Queries for Episode presence
+#[Object]
+impl PresenceQuery {
+    /// Get who is currently watching an Episode
+    async fn get_episode_presence(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "The Episode id")] episode_id: String,
+    ) -> Result<EpisodePresence> {
+        let user = ctx.data_unchecked::<Option<User>>();
+        let episodes = ctx.data_unchecked::<Arc<dyn EpisodesService>>();
+        let presence = ctx.data_unchecked::<Arc<dyn PresenceService>>();
+        let loader = ctx.data_unchecked::<DataLoader<ProfileLoader>>();
+
+        let not_found = || graphql_error("Unable to find existing Episode", StatusCode::NOT_FOUND);
+
+        let episode = episodes
+            .get(&episode_id, &false)
+            .await
+            .map_err(as_graphql_error(
+                "Error while fetching Episode",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?
+            .ok_or_else(not_found)?;
+
+        // Unpublished Episodes are hidden from those who can't manage them
+        if !episode.is_published() && !can_manage(ctx, &episode).await? {
+            return Err(not_found());
+        }
+
+        let viewers = presence
+            .get_viewers(&episode_id)
+            .await
+            .map_err(as_graphql_error(
+                "Error while fetching viewers",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        let profile_ids: BTreeSet<String> = viewers
+            .iter()
+            .filter_map(|viewer| viewer.profile_id.clone())
+            .collect();
+
+        let mut profiles = loader.load_many(profile_ids.iter().cloned()).await?;
+
+        // Use the request User to decide if each Profile should be censored
+        let user_id = user.as_ref().map(|user| user.id.clone());
+
+        Ok(EpisodePresence {
+            episode_id,
+            viewer_count: viewer_count(&viewers),
+            profiles: profile_ids
+                .iter()
+                .filter_map(|profile_id| profiles.remove(profile_id))
+                .map(|profile| profile.censor(&user_id))
+                .collect(),
+        })
+    }
+}
@@ -0,0 +1,375 @@
+use anyhow::Result;
+use async_graphql::{dataloader::Loader, FieldError};
+use async_trait::async_trait;
+use chrono::Utc;
+#[cfg(test)]
+use mockall::automock;
+use redis::{aio::ConnectionManager, AsyncCommands, Script};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::Mutex;
+
+use super::model::{viewer_count, Viewer};
+use caster_utils::config::{Config, PresenceBackend};
+
+/// A `PresenceService` tracks which connections are watching each Episode. Entries expire unless
+/// they are refreshed with a heartbeat, so that viewers on a server that went away are dropped.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait PresenceService: Sync + Send {
+    /// Mark a connection as watching an Episode, as the given Profile if authenticated. Joining
+    /// again refreshes the entry.
+    async fn join(
+        &self,
+        episode_id: &str,
+        connection_id: &str,
+        profile_id: Option<String>,
+    ) -> Result<()>;
+
+    /// Mark a connection as no longer watching an Episode
+    async fn leave(&self, episode_id: &str, connection_id: &str) -> Result<()>;
+
+    /// Keep a connection's entries for the given Episodes from expiring
+    async fn heartbeat(&self, episode_ids: &[String], connection_id: &str) -> Result<()>;
+
+    /// Get the connections currently watching an Episode
+    async fn get_viewers(&self, episode_id: &str) -> Result<Vec<Viewer>>;
+
+    /// Get the number of distinct viewers for each of the given Episodes
+    async fn get_viewer_counts(&self, episode_ids: Vec<String>) -> Result<HashMap<String, u64>>;
+}
+
+/// Create a `PresenceService` based on the configured backend. Entries expire after the
+/// `WebSocket` heartbeat timeout.
+pub async fn init_presence(config: &'static Config) -> Result<Arc<dyn PresenceService>> {
+    let ttl = Duration::from_secs(config.events.heartbeat_timeout);
+
+    match config.presence.backend {
+        PresenceBackend::Memory => Ok(Arc::new(MemoryPresenceService::new(ttl))),
+        PresenceBackend::Redis => Ok(Arc::new(
+            RedisPresenceService::new(&config.redis.url, ttl).await?,
+        )),
+    }
+}
+
+/// A viewer entry for the in-memory backend
+struct Entry {
+    profile_id: Option<String>,
+    expires: Instant,
+}
+
+/// A `PresenceService` that keeps viewers in memory, local to this process
+pub struct MemoryPresenceService {
+    ttl: Duration,
+
+    /// - Key is an Episode id
+    /// - Value is the map of connection ids watching it to their entries
+    episodes: Mutex<HashMap<String, HashMap<String, Entry>>>,
+}
+
+impl MemoryPresenceService {
+    /// Create a new `MemoryPresenceService` instance
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            episodes: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl PresenceService for MemoryPresenceService {
+    async fn join(
+        &self,
+        episode_id: &str,
+        connection_id: &str,
+        profile_id: Option<String>,
+    ) -> Result<()> {
+        self.episodes
+            .lock()
+            .await
+            .entry(episode_id.to_string())
+            .or_default()
+            .insert(
+                connection_id.to_string(),
+                Entry {
+                    profile_id,
+                    expires: Instant::now() + self.ttl,
+                },
+            );
+
+        Ok(())
+    }
+
+    async fn leave(&self, episode_id: &str, connection_id: &str) -> Result<()> {
+        let mut episodes = self.episodes.lock().await;
+
+        if let Some(entries) = episodes.get_mut(episode_id) {
+            entries.remove(connection_id);
+
+            if entries.is_empty() {
+                episodes.remove(episode_id);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn heartbeat(&self, episode_ids: &[String], connection_id: &str) -> Result<()> {
+        let mut episodes = self.episodes.lock().await;
+        let expires = Instant::now() + self.ttl;
+
+        for episode_id in episode_ids {
+            if let Some(entry) = episodes
+                .get_mut(episode_id)
+                .and_then(|entries| entries.get_mut(connection_id))
+            {
+                entry.expires = expires;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn get_viewers(&self, episode_id: &str) -> Result<Vec<Viewer>> {
+        let mut episodes = self.episodes.lock().await;
+        let now = Instant::now();
+
+        let Some(entries) = episodes.get_mut(episode_id) else {
+            return Ok(vec![]);
+        };
+
+        // Drop expired entries so that abandoned connections don't accumulate
+        entries.retain(|_, entry| entry.expires > now);
+
+        let viewers = entries
+            .iter()
+            .map(|(connection_id, entry)| Viewer {
+                connection_id: connection_id.clone(),
+                profile_id: entry.profile_id.clone(),
+            })
+            .collect();
+
+        if entries.is_empty() {
+            episodes.remove(episode_id);
+        }
+
+        Ok(viewers)
+    }
+
+    async fn get_viewer_counts(&self, episode_ids: Vec<String>) -> Result<HashMap<String, u64>> {
+        let mut counts = HashMap::with_capacity(episode_ids.len());
+
+        for episode_id in episode_ids {
+            let viewers = self.get_viewers(&episode_id).await?;
+
+            counts.insert(episode_id, viewer_count(&viewers));
+        }
+
+        Ok(counts)
+    }
+}
+
+/// A viewer entry for the Redis backend, stored as JSON in a hash field named after the
+/// connection id
+#[derive(Deserialize, Serialize)]
+struct RedisEntry {
+    profile_id: Option<String>,
+    /// A Unix timestamp, in seconds
+    expires_at: i64,
+}
+
+/// Refresh a viewer's entry and the hash it belongs to, but only if the entry still exists, all
+/// in one step so that a connection leaving at the same time isn't added back. Returns 1 if the
+/// entry was refreshed.
+const HEARTBEAT_SCRIPT: &str = r"
+local value = redis.call('HGET', KEYS[1], ARGV[1])
+
+if not value then
+    return 0
+end
+
+local entry = cjson.decode(value)
+entry['expires_at'] = tonumber(ARGV[2])
+
+redis.call('HSET', KEYS[1], ARGV[1], cjson.encode(entry))
+redis.call('EXPIRE', KEYS[1], ARGV[3])
+
+return 1
+";
+
+/// A `PresenceService` that keeps viewers in Redis, shared between processes
+pub struct RedisPresenceService {
+    ttl: Duration,
+    redis: ConnectionManager,
+    heartbeat_script: Script,
+}
+
+impl RedisPresenceService {
+    /// Create a new `RedisPresenceService` instance connected to the given url
+    pub async fn new(url: &str, ttl: Duration) -> Result<Self> {
+        // The Redis url is configured without a scheme by default
+        let url = if url.contains("://") {
+            url.to_string()
+        } else {
+            format!("redis://{}", url)
+        };
+
+        let client = redis::Client::open(url)?;
+        let redis = ConnectionManager::new(client).await?;
+
+        Ok(Self {
+            ttl,
+            redis,
+            heartbeat_script: Script::new(HEARTBEAT_SCRIPT),
+        })
+    }
+
+    /// The Unix timestamp that an entry refreshed now expires at
+    fn expires_at(&self) -> i64 {
+        Utc::now().timestamp() + self.ttl.as_secs() as i64
+    }
+
+    /// The key of the hash of viewers for an Episode
+    fn key(episode_id: &str) -> String {
+        format!("presence:{}", episode_id)
+    }
+
+    /// Write an entry, and keep the whole hash around for as long as its newest entry
+    async fn set_entry(
+        &self,
+        episode_id: &str,
+        connection_id: &str,
+        profile_id: Option<String>,
+    ) -> Result<()> {
+        let key = Self::key(episode_id);
+        let mut redis = self.redis.clone();
+
+        let entry = RedisEntry {
+            profile_id,
+            expires_at: self.expires_at(),
+        };
+
+        let _: () = redis::pipe()
+            .atomic()
+            .hset(&key, connection_id, serde_json::to_string(&entry)?)
+            .ignore()
+            .expire(&key, self.ttl.as_secs() as usize)
+            .ignore()
+            .query_async(&mut redis)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PresenceService for RedisPresenceService {
+    async fn join(
+        &self,
+        episode_id: &str,
+        connection_id: &str,
+        profile_id: Option<String>,
+    ) -> Result<()> {
+        self.set_entry(episode_id, connection_id, profile_id).await
+    }
+
+    async fn leave(&self, episode_id: &str, connection_id: &str) -> Result<()> {
+        let mut redis = self.redis.clone();
+
+        let _: () = redis.hdel(Self::key(episode_id), connection_id).await?;
+
+        Ok(())
+    }
+
+    async fn heartbeat(&self, episode_ids: &[String], connection_id: &str) -> Result<()> {
+        let mut redis = self.redis.clone();
+
+        // Entries that have already been removed stay removed
+        for episode_id in episode_ids {
+            let _: i64 = self
+                .heartbeat_script
+                .key(Self::key(episode_id))
+                .arg(connection_id)
+                .arg(self.expires_at())
+                .arg(self.ttl.as_secs())
+                .invoke_async(&mut redis)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn get_viewers(&self, episode_id: &str) -> Result<Vec<Viewer>> {
+        let key = Self::key(episode_id);
+        let mut redis = self.redis.clone();
+        let now = Utc::now().timestamp();
+
+        let values: HashMap<String, String> = redis.hgetall(&key).await?;
+
+        let mut viewers = Vec::with_capacity(values.len());
+        let mut expired = Vec::new();
+
+        for (connection_id, value) in values {
+            let entry: RedisEntry = serde_json::from_str(&value)?;
+
+            if entry.expires_at > now {
+                viewers.push(Viewer {
+                    connection_id,
+                    profile_id: entry.profile_id,
+                });
+            } else {
+                expired.push(connection_id);
+            }
+        }
+
+        // Drop entries left behind by servers that went away
+        if !expired.is_empty() {
+            let _: () = redis.hdel(&key, expired).await?;
+        }
+
+        Ok(viewers)
+    }
+
+    async fn get_viewer_counts(&self, episode_ids: Vec<String>) -> Result<HashMap<String, u64>> {
+        let mut counts = HashMap::with_capacity(episode_ids.len());
+
+        for episode_id in episode_ids {
+            let viewers = self.get_viewers(&episode_id).await?;
+
+            counts.insert(episode_id, viewer_count(&viewers));
+        }
+
+        Ok(counts)
+    }
+}
+
+/// A dataloader for the number of distinct viewers watching each `Episode`, keyed by `Episode` id
+pub struct ViewerCountLoader {
+    /// The Presence service
+    presence: Arc<dyn PresenceService>,
+}
+
+/// The default implementation for the `ViewerCountLoader`
+impl ViewerCountLoader {
+    /// Create a new instance
+    pub fn new(presence: &Arc<dyn PresenceService>) -> Self {
+        Self {
+            presence: presence.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl Loader<String> for ViewerCountLoader {
+    type Value = u64;
+    type Error = FieldError;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        Ok(self.presence.get_viewer_counts(keys.into()).await?)
+    }
+}
@@ -0,0 +1,2 @@
+mod model_test;
+mod service_test;
@@ -0,0 +1,25 @@
+use pretty_assertions::assert_eq;
+
+use crate::presence::model::{viewer_count, Viewer};
+
+fn viewer(connection_id: &str, profile_id: Option<&str>) -> Viewer {
+    Viewer {
+        connection_id: connection_id.to_string(),
+        profile_id: profile_id.map(|id| id.to_string()),
+    }
+}
+
+#[test]
+fn test_viewer_count() {
+    let viewers = vec![
+        viewer("first", Some("test-profile")),
+        viewer("second", Some("test-profile")),
+        viewer("third", Some("other-profile")),
+        viewer("fourth", None),
+        viewer("fifth", None),
+    ];
+
+    // Each Profile counts once, and each anonymous connection counts on its own
+    assert_eq!(viewer_count(&viewers), 4);
+    assert_eq!(viewer_count(&[]), 0);
+}
@@ -0,0 +1,97 @@
+use anyhow::Result;
+use maplit::hashmap;
+use pretty_assertions::assert_eq;
+use std::time::Duration;
+
+use crate::presence::{
+    model::Viewer,
+    service::{MemoryPresenceService, PresenceService},
+};
+
+#[tokio::test]
+async fn test_memory_presence_join_and_leave() -> Result<()> {
+    let service = MemoryPresenceService::new(Duration::from_secs(60));
+
+    service
+        .join("test-episode", "first", Some("test-profile".to_string()))
+        .await?;
+    service.join("test-episode", "second", None).await?;
+
+    // Joining again replaces the entry
+    service
+        .join("test-episode", "first", Some("test-profile".to_string()))
+        .await?;
+
+    let mut viewers = service.get_viewers("test-episode").await?;
+    viewers.sort_by(|a, b| a.connection_id.cmp(&b.connection_id));
+
+    assert_eq!(
+        viewers,
+        vec![
+            Viewer {
+                connection_id: "first".to_string(),
+                profile_id: Some("test-profile".to_string()),
+            },
+            Viewer {
+                connection_id: "second".to_string(),
+                profile_id: None,
+            },
+        ]
+    );
+
+    service.leave("test-episode", "first").await?;
+
+    let counts = service
+        .get_viewer_counts(vec![
+            "test-episode".to_string(),
+            "other-episode".to_string(),
+        ])
+        .await?;
+
+    assert_eq!(
+        counts,
+        hashmap! {
+            "test-episode".to_string() => 1,
+            "other-episode".to_string() => 0,
+        }
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_memory_presence_expiry() -> Result<()> {
+    let service = MemoryPresenceService::new(Duration::from_millis(100));
+
+    service.join("test-episode", "stale", None).await?;
+    service.join("test-episode", "live", None).await?;
+
+    tokio::time::sleep(Duration::from_millis(60)).await;
+
+    // Only the live connection keeps its entry fresh
+    service
+        .heartbeat(&["test-episode".to_string()], "live")
+        .await?;
+
+    tokio::time::sleep(Duration::from_millis(60)).await;
+
+    let viewers = service.get_viewers("test-episode").await?;
+
+    assert_eq!(
+        viewers,
+        vec![Viewer {
+            connection_id: "live".to_string(),
+            profile_id: None,
+        }]
+    );
+
+    // Heartbeats don't bring back entries that have gone away
+    service.leave("test-episode", "live").await?;
+    service
+        .heartbeat(&["test-episode".to_string()], "live")
+        .await?;
+
+    assert_eq!(service.get_viewers("test-episode").await?, vec![]);
+
+    Ok(())
+}
@@ -8,6 +8,7 @@ use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::users::model::{self as user_model, User};
+use caster_utils::errors::ConflictRecord;
 
 /// The `Profile` GraphQL model
 #[derive(Debug, Dummy, Clone, Eq, PartialEq, Deserialize, PolarClass, Serialize, SimpleObject)]
@@ -35,6 +36,9 @@ pub struct Profile {
     /// The `Profile`'s picture
     pub picture: Option<String>,
 
+    /// A thumbnail of the `Profile`'s picture, if it was uploaded
+    pub picture_thumbnail: Option<String>,
+
     /// The `Profile`'s city
     pub city: Option<String>,
 
@@ -50,6 +54,10 @@ pub struct Profile {
     pub user: Option<User>,
 }
 
+impl ConflictRecord for Profile {
+    const HIDDEN_FIELDS: &'static [&'static str] = &["user"];
+}
+
 impl Profile {
     /// If not authorized, censor the `Profile` `email` and `user_id`
     pub fn censor(&self, current_user_id: &Option<String>) -> Self {
@@ -95,6 +103,9 @@ pub struct Model {
     #[sea_orm(column_type = "Text", nullable)]
     pub picture: Option<String>,
 
+    #[sea_orm(column_type = "Text", nullable)]
+    pub picture_thumbnail: Option<String>,
+
     #[sea_orm(column_type = "Text", nullable)]
     pub city: Option<String>,
 
@@ -103,6 +114,8 @@ pub struct Model {
 
     #[sea_orm(column_type = "Text", nullable)]
     pub user_id: Option<String>,
+
+    pub deleted_at: Option<DateTime>,
 }
 
 impl Model {
@@ -114,6 +127,7 @@ impl Model {
             email: Some(self.email),
             display_name: self.display_name,
             picture: self.picture,
+            picture_thumbnail: self.picture_thumbnail,
             city: self.city,
             state_province: self.state_province,
             user_id: Some(user.id.clone()),
@@ -131,9 +145,11 @@ impl Default for Model {
             email: String::default(),
             display_name: Option::default(),
             picture: Option::default(),
+            picture_thumbnail: Option::default(),
             city: Option::default(),
             state_province: Option::default(),
             user_id: Option::default(),
+            deleted_at: Option::default(),
         }
     }
 }
@@ -166,6 +182,7 @@ impl From<Model> for Profile {
             email: Some(model.email),
             display_name: model.display_name,
             picture: model.picture,
+            picture_thumbnail: model.picture_thumbnail,
             city: model.city,
             state_province: model.state_province,
             user_id: model.user_id,
@@ -1,4 +1,5 @@
 use async_graphql::{InputObject, MaybeUndefined, SimpleObject};
+use chrono::NaiveDateTime;
 use fake::{faker::internet::en::FreeEmail, Dummy, Fake, Faker};
 use rand::Rng;
 
@@ -40,6 +41,10 @@ pub struct UpdateProfileInput {
     /// The Profile's picture
     pub picture: MaybeUndefined<String>,
 
+    /// The thumbnail generated for an uploaded picture, which can't be set directly
+    #[graphql(skip)]
+    pub picture_thumbnail: MaybeUndefined<String>,
+
     /// The Profile's city
     pub city: MaybeUndefined<String>,
 
@@ -48,6 +53,9 @@ pub struct UpdateProfileInput {
 
     /// The Profile's User id
     pub user_id: Option<String>,
+
+    /// Reject the update with a Conflict error if the Profile has been updated since this date
+    pub expected_updated_at: Option<NaiveDateTime>,
 }
 
 impl Dummy<Faker> for UpdateProfileInput {
@@ -56,9 +64,11 @@ impl Dummy<Faker> for UpdateProfileInput {
             email: FreeEmail().fake(),
             display_name: dummy_maybe_undef(config, rng),
             picture: dummy_maybe_undef(config, rng),
+            picture_thumbnail: MaybeUndefined::Undefined,
             city: dummy_maybe_undef(config, rng),
             state_province: dummy_maybe_undef(config, rng),
             user_id: Faker.fake(),
+            expected_updated_at: None,
         }
     }
 }
@@ -1,4 +1,6 @@
-use async_graphql::{dataloader::DataLoader, ComplexObject, Context, Object, Result};
+use async_graphql::{
+    dataloader::DataLoader, ComplexObject, Context, MaybeUndefined, Object, Result, Upload,
+};
 use hyper::StatusCode;
 use std::sync::Arc;
 
@@ -8,8 +10,15 @@ use super::{
     queries::{ProfileCondition, ProfilesOrderBy, ProfilesPage},
     service::ProfilesService,
 };
-use crate::users::{model::User, service::UserLoader};
-use caster_utils::errors::{as_graphql_error, graphql_error};
+use crate::{
+    audit_events::resolver::record_audit_event,
+    authorization::resolver::is_allowed,
+    follows::service::FollowsService,
+    shows::model::Show,
+    users::{model::User, service::UserLoader},
+};
+use caster_storage::uploads::{remove_picture, upload_picture};
+use caster_utils::errors::{as_graphql_error, as_update_error, graphql_error};
 
 /// The Query segment for Profiles
 #[derive(Default)]
@@ -121,6 +130,16 @@ impl ProfilesMutation {
                 StatusCode::INTERNAL_SERVER_ERROR,
             ))?;
 
+        record_audit_event(
+            ctx,
+            "create",
+            "profiles",
+            &profile.id,
+            None::<&Profile>,
+            Some(&profile),
+        )
+        .await;
+
         Ok(MutateProfileResult {
             profile: Some(profile),
         })
@@ -148,12 +167,9 @@ impl ProfilesMutation {
                 graphql_error("Unable to find existing Profile", StatusCode::NOT_FOUND)
             })?;
 
-        // Retrieve the current request User id for authorization
-        let user_id = user.clone().map(|u| u.id);
-
-        if let Some(user_id) = user_id {
-            // Make sure the current request User id matches the existing user
-            if existing.user.as_ref().map(|u| u.id.clone()) != Some(user_id) {
+        // Check authentication and authorization
+        if let Some(user) = user {
+            if !is_allowed(ctx, user, "update", existing.clone())? {
                 return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
             }
         } else {
@@ -168,11 +184,97 @@ impl ProfilesMutation {
         let profile = profiles
             .update(&existing.id, &input, &with_user)
             .await
+            .map_err(as_update_error::<Profile>(
+                "Error while updating Profile",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        record_audit_event(
+            ctx,
+            "update",
+            "profiles",
+            &id,
+            Some(&existing),
+            Some(&profile),
+        )
+        .await;
+
+        Ok(MutateProfileResult {
+            profile: Some(profile),
+        })
+    }
+
+    /// Upload a new picture for an existing Profile, along with a generated thumbnail
+    async fn upload_profile_picture(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        picture: Upload,
+    ) -> Result<MutateProfileResult> {
+        let user = ctx.data_unchecked::<Option<User>>();
+        let profiles = ctx.data_unchecked::<Arc<dyn ProfilesService>>();
+
+        // Retrieve the existing Profile for authorization
+        let existing = profiles
+            .get(&id, &true)
+            .await
             .map_err(as_graphql_error(
+                "Error while fetching Profile",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?
+            .ok_or_else(|| {
+                graphql_error("Unable to find existing Profile", StatusCode::NOT_FOUND)
+            })?;
+
+        // Check authentication and authorization
+        if let Some(user) = user {
+            if !is_allowed(ctx, user, "update", existing.clone())? {
+                return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
+            }
+        } else {
+            // If there is no request User, return a 401
+            return Err(graphql_error("Unauthorized", StatusCode::UNAUTHORIZED));
+        }
+
+        let prefix = format!("profiles/{}", id);
+        let stored = upload_picture(ctx, &picture, &prefix).await?;
+
+        let input = UpdateProfileInput {
+            picture: MaybeUndefined::Value(stored.url),
+            picture_thumbnail: MaybeUndefined::Value(stored.thumbnail_url),
+            ..Default::default()
+        };
+
+        // Check to see if the associated User is selected
+        let with_user = ctx.look_ahead().field("profile").field("user").exists();
+
+        let profile = profiles
+            .update(&existing.id, &input, &with_user)
+            .await
+            .map_err(as_update_error::<Profile>(
                 "Error while updating Profile",
                 StatusCode::INTERNAL_SERVER_ERROR,
             ))?;
 
+        record_audit_event(
+            ctx,
+            "update",
+            "profiles",
+            &id,
+            Some(&existing),
+            Some(&profile),
+        )
+        .await;
+
+        // Clean up the files for the picture that was replaced
+        remove_picture(
+            ctx,
+            &prefix,
+            existing.picture.as_deref(),
+            existing.picture_thumbnail.as_deref(),
+        )
+        .await;
+
         Ok(MutateProfileResult {
             profile: Some(profile),
         })
@@ -195,12 +297,9 @@ impl ProfilesMutation {
                 graphql_error("Unable to find existing Profile", StatusCode::NOT_FOUND)
             })?;
 
-        // Retrieve the current request User id for authorization
-        let user_id = user.clone().map(|u| u.id);
-
-        if let Some(user_id) = user_id {
-            // Make sure the current request User id matches the existing user
-            if existing.user.as_ref().map(|u| u.id.clone()) != Some(user_id) {
+        // Check authentication and authorization
+        if let Some(user) = user {
+            if !is_allowed(ctx, user, "delete", existing.clone())? {
                 return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
             }
         } else {
@@ -213,6 +312,16 @@ impl ProfilesMutation {
             StatusCode::INTERNAL_SERVER_ERROR,
         ))?;
 
+        record_audit_event(
+            ctx,
+            "delete",
+            "profiles",
+            &id,
+            Some(&existing),
+            None::<&Profile>,
+        )
+        .await;
+
         Ok(true)
     }
 }
@@ -234,4 +343,30 @@ impl Profile {
 
         Ok(None)
     }
+
+    /// The Shows that the Profile follows, only visible to the Profile's own User
+    #[graphql(name = "followedShows")]
+    async fn resolve_followed_shows(&self, ctx: &Context<'_>) -> Result<Option<Vec<Show>>> {
+        let user = ctx.data_unchecked::<Option<User>>();
+        let follows = ctx.data_unchecked::<Arc<dyn FollowsService>>();
+
+        let same_user = match (user, &self.user_id) {
+            (Some(user), Some(user_id)) => &user.id == user_id,
+            _ => false,
+        };
+
+        if !same_user {
+            return Ok(None);
+        }
+
+        let shows = follows
+            .get_followed_shows(&self.id)
+            .await
+            .map_err(as_graphql_error(
+                "Error while fetching followed Shows",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        Ok(Some(shows))
+    }
 }
@@ -5,9 +5,12 @@ use async_graphql::{
     MaybeUndefined::{Null, Undefined, Value},
 };
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 #[cfg(test)]
 use mockall::automock;
-use sea_orm::{entity::*, query::*, DatabaseConnection, EntityTrait};
+use sea_orm::{
+    entity::*, query::*, sea_query::Expr, ConnectionTrait, DatabaseConnection, EntityTrait,
+};
 use std::{collections::HashMap, sync::Arc};
 
 use super::{
@@ -16,7 +19,9 @@ use super::{
     queries::{ProfileCondition, ProfilesOrderBy},
 };
 use crate::users::model as user_model;
-use caster_utils::{ordering::Ordering, pagination::ManyResponse};
+use caster_utils::{
+    errors::Conflict, ordering::Ordering, pagination::ManyResponse, updates::update_if_unchanged,
+};
 
 /// A ProfilesService applies business logic to a dynamic ProfilesRepository implementation.
 #[cfg_attr(test, automock)]
@@ -60,8 +65,11 @@ pub trait ProfilesService: Sync + Send {
         with_user: &bool,
     ) -> Result<Profile>;
 
-    /// Delete an existing `Profile`
+    /// Delete an existing `Profile`, keeping it around until it is purged
     async fn delete(&self, id: &str) -> Result<()>;
+
+    /// Permanently remove `Profile` records that were deleted before the given date
+    async fn purge(&self, deleted_before: NaiveDateTime) -> Result<u64>;
 }
 
 /// The default `ProfilesService` struct
@@ -81,7 +89,8 @@ impl DefaultProfilesService {
 #[async_trait]
 impl ProfilesService for DefaultProfilesService {
     async fn get(&self, id: &str, with_user: &bool) -> Result<Option<Profile>> {
-        let query = model::Entity::find_by_id(id.to_owned());
+        let query =
+            model::Entity::find_by_id(id.to_owned()).filter(model::Column::DeletedAt.is_null());
 
         let profile = if *with_user {
             query
@@ -106,6 +115,7 @@ impl ProfilesService for DefaultProfilesService {
 
         let profiles = model::Entity::find()
             .filter(condition)
+            .filter(model::Column::DeletedAt.is_null())
             .all(&*self.db)
             .await?;
 
@@ -124,7 +134,7 @@ impl ProfilesService for DefaultProfilesService {
     ) -> Result<ManyResponse<Profile>> {
         let page_num = page.unwrap_or(1);
 
-        let mut query = model::Entity::find();
+        let mut query = model::Entity::find().filter(model::Column::DeletedAt.is_null());
 
         if let Some(condition) = condition {
             if let Some(email) = condition.email {
@@ -214,7 +224,9 @@ impl ProfilesService for DefaultProfilesService {
     }
 
     async fn get_by_user_id(&self, user_id: &str, with_user: &bool) -> Result<Option<Profile>> {
-        let query = model::Entity::find().filter(model::Column::UserId.eq(user_id.to_owned()));
+        let query = model::Entity::find()
+            .filter(model::Column::UserId.eq(user_id.to_owned()))
+            .filter(model::Column::DeletedAt.is_null());
 
         let profile: ProfileOption = match with_user {
             true => query
@@ -229,19 +241,7 @@ impl ProfilesService for DefaultProfilesService {
     }
 
     async fn create(&self, input: &CreateProfileInput, with_user: &bool) -> Result<Profile> {
-        let profile = model::ActiveModel {
-            email: Set(input.email.clone()),
-            display_name: Set(input.display_name.clone()),
-            picture: Set(input.picture.clone()),
-            city: Set(input.city.clone()),
-            state_province: Set(input.state_province.clone()),
-            user_id: Set(Some(input.user_id.clone())),
-            ..Default::default()
-        }
-        .insert(&*self.db)
-        .await?;
-
-        let mut created: Profile = profile.into();
+        let mut created = insert_profile(&*self.db, input).await?;
 
         if !with_user {
             return Ok(created);
@@ -277,7 +277,8 @@ impl ProfilesService for DefaultProfilesService {
         input: &UpdateProfileInput,
         with_user: &bool,
     ) -> Result<Profile> {
-        let query = model::Entity::find_by_id(id.to_owned());
+        let query =
+            model::Entity::find_by_id(id.to_owned()).filter(model::Column::DeletedAt.is_null());
 
         // Pull out the `Profile` and the related `User`, if selected
         let (profile, user) = if *with_user {
@@ -291,6 +292,19 @@ impl ProfilesService for DefaultProfilesService {
         }
         .ok_or_else(|| anyhow!("Unable to find Profile with id: {}", id))?;
 
+        // Reject the update if the Profile has changed since the client last retrieved it
+        if let Some(expected) = input.expected_updated_at {
+            if profile.updated_at != expected {
+                return Err(Conflict {
+                    current: Profile {
+                        user,
+                        ..profile.into()
+                    },
+                }
+                .into());
+            }
+        }
+
         let mut profile: model::ActiveModel = profile.into();
 
         if let Some(email) = &input.email {
@@ -309,6 +323,13 @@ impl ProfilesService for DefaultProfilesService {
             Value(value) => profile.picture = Set(Some(value.clone())),
         }
 
+        // A new picture replaces the old thumbnail, which is only kept if one was generated for it
+        match &input.picture_thumbnail {
+            Undefined if input.picture.is_undefined() => (),
+            Undefined | Null => profile.picture_thumbnail = Set(None),
+            Value(value) => profile.picture_thumbnail = Set(Some(value.clone())),
+        }
+
         match &input.city {
             Undefined => (),
             Null => profile.city = Set(None),
@@ -325,7 +346,21 @@ impl ProfilesService for DefaultProfilesService {
             profile.user_id = Set(Some(user_id.clone()));
         }
 
-        let mut updated: Profile = profile.update(&*self.db).await?.into();
+        // Make the write conditional as well, in case of a concurrent update since the read
+        let mut updated: Profile = update_if_unchanged(
+            &*self.db,
+            profile,
+            model::Column::Id.eq(id.to_owned()),
+            model::Column::UpdatedAt,
+            input.expected_updated_at,
+            || async {
+                self.get(id, with_user)
+                    .await?
+                    .ok_or_else(|| anyhow!("Unable to find Profile with id: {}", id))
+            },
+        )
+        .await?
+        .into();
 
         // Add back the User from above
         updated.user = user;
@@ -334,15 +369,49 @@ impl ProfilesService for DefaultProfilesService {
     }
 
     async fn delete(&self, id: &str) -> Result<()> {
-        let profile = model::Entity::find_by_id(id.to_owned())
-            .one(&*self.db)
-            .await?
-            .ok_or_else(|| anyhow!("Unable to find Profile with id: {}", id))?;
+        let result = model::Entity::update_many()
+            .col_expr(model::Column::DeletedAt, Expr::current_timestamp().into())
+            .filter(model::Column::Id.eq(id.to_owned()))
+            .filter(model::Column::DeletedAt.is_null())
+            .exec(&*self.db)
+            .await?;
 
-        let _result = profile.delete(&*self.db).await?;
+        if result.rows_affected == 0 {
+            return Err(anyhow!("Unable to find Profile with id: {}", id));
+        }
 
         Ok(())
     }
+
+    async fn purge(&self, deleted_before: NaiveDateTime) -> Result<u64> {
+        let result = model::Entity::delete_many()
+            .filter(model::Column::DeletedAt.lt(deleted_before))
+            .exec(&*self.db)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+}
+
+/// Insert a `Profile` using the given connection, so that it can be part of a larger
+/// transaction in other services
+pub async fn insert_profile<C: ConnectionTrait>(
+    db: &C,
+    input: &CreateProfileInput,
+) -> Result<Profile> {
+    let profile = model::ActiveModel {
+        email: Set(input.email.clone()),
+        display_name: Set(input.display_name.clone()),
+        picture: Set(input.picture.clone()),
+        city: Set(input.city.clone()),
+        state_province: Set(input.state_province.clone()),
+        user_id: Set(Some(input.user_id.clone())),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    Ok(profile.into())
 }
 
 /// A dataloader for `Profile` instances
@@ -48,7 +48,7 @@ async fn test_profiles_service_get() -> Result<()> {
         db.into_transaction_log(),
         vec![Transaction::from_sql_and_values(
             DatabaseBackend::Postgres,
-            r#"SELECT "profiles"."id", "profiles"."created_at", "profiles"."updated_at", "profiles"."email", "profiles"."display_name", "profiles"."picture", "profiles"."city", "profiles"."state_province", "profiles"."user_id" FROM "profiles" WHERE "profiles"."id" = $1 LIMIT $2"#,
+            r#"SELECT "profiles"."id", "profiles"."created_at", "profiles"."updated_at", "profiles"."email", "profiles"."display_name", "profiles"."picture", "profiles"."picture_thumbnail", "profiles"."city", "profiles"."state_province", "profiles"."user_id", "profiles"."deleted_at" FROM "profiles" WHERE "profiles"."id" = $1 AND "profiles"."deleted_at" IS NULL LIMIT $2"#,
             vec![profile.id.into(), 1u64.into()]
         )]
     );
@@ -88,7 +88,7 @@ async fn test_profiles_service_get_with_related() -> Result<()> {
         db.into_transaction_log(),
         vec![Transaction::from_sql_and_values(
             DatabaseBackend::Postgres,
-            r#"SELECT "profiles"."id" AS "A_id", "profiles"."created_at" AS "A_created_at", "profiles"."updated_at" AS "A_updated_at", "profiles"."email" AS "A_email", "profiles"."display_name" AS "A_display_name", "profiles"."picture" AS "A_picture", "profiles"."city" AS "A_city", "profiles"."state_province" AS "A_state_province", "profiles"."user_id" AS "A_user_id", "users"."id" AS "B_id", "users"."created_at" AS "B_created_at", "users"."updated_at" AS "B_updated_at", "users"."username" AS "B_username", "users"."is_active" AS "B_is_active" FROM "profiles" LEFT JOIN "users" ON "profiles"."user_id" = "users"."id" WHERE "profiles"."id" = $1 LIMIT $2"#,
+            r#"SELECT "profiles"."id" AS "A_id", "profiles"."created_at" AS "A_created_at", "profiles"."updated_at" AS "A_updated_at", "profiles"."email" AS "A_email", "profiles"."display_name" AS "A_display_name", "profiles"."picture" AS "A_picture", "profiles"."picture_thumbnail" AS "A_picture_thumbnail", "profiles"."city" AS "A_city", "profiles"."state_province" AS "A_state_province", "profiles"."user_id" AS "A_user_id", "profiles"."deleted_at" AS "A_deleted_at", "users"."id" AS "B_id", "users"."created_at" AS "B_created_at", "users"."updated_at" AS "B_updated_at", "users"."username" AS "B_username", "users"."is_active" AS "B_is_active" FROM "profiles" LEFT JOIN "users" ON "profiles"."user_id" = "users"."id" WHERE "profiles"."id" = $1 AND "profiles"."deleted_at" IS NULL LIMIT $2"#,
             vec![profile.id.into(), 1u64.into()]
         )]
     );
@@ -160,7 +160,7 @@ async fn test_profiles_service_get_many() -> Result<()> {
         db.into_transaction_log(),
         vec![Transaction::from_sql_and_values(
             DatabaseBackend::Postgres,
-            r#"SELECT "profiles"."id", "profiles"."created_at", "profiles"."updated_at", "profiles"."email", "profiles"."display_name", "profiles"."picture", "profiles"."city", "profiles"."state_province", "profiles"."user_id" FROM "profiles" WHERE "profiles"."email" = $1"#,
+            r#"SELECT "profiles"."id", "profiles"."created_at", "profiles"."updated_at", "profiles"."email", "profiles"."display_name", "profiles"."picture", "profiles"."picture_thumbnail", "profiles"."city", "profiles"."state_province", "profiles"."user_id", "profiles"."deleted_at" FROM "profiles" WHERE "profiles"."deleted_at" IS NULL AND "profiles"."email" = $1"#,
             vec!["test@profile.com".into()]
         )]
     );
@@ -238,7 +238,7 @@ async fn test_profiles_service_get_many_with_related() -> Result<()> {
         db.into_transaction_log(),
         vec![Transaction::from_sql_and_values(
             DatabaseBackend::Postgres,
-            r#"SELECT "profiles"."id" AS "A_id", "profiles"."created_at" AS "A_created_at", "profiles"."updated_at" AS "A_updated_at", "profiles"."email" AS "A_email", "profiles"."display_name" AS "A_display_name", "profiles"."picture" AS "A_picture", "profiles"."city" AS "A_city", "profiles"."state_province" AS "A_state_province", "profiles"."user_id" AS "A_user_id", "users"."id" AS "B_id", "users"."created_at" AS "B_created_at", "users"."updated_at" AS "B_updated_at", "users"."username" AS "B_username", "users"."is_active" AS "B_is_active" FROM "profiles" LEFT JOIN "users" ON "profiles"."user_id" = "users"."id" WHERE "profiles"."email" = $1"#,
+            r#"SELECT "profiles"."id" AS "A_id", "profiles"."created_at" AS "A_created_at", "profiles"."updated_at" AS "A_updated_at", "profiles"."email" AS "A_email", "profiles"."display_name" AS "A_display_name", "profiles"."picture" AS "A_picture", "profiles"."picture_thumbnail" AS "A_picture_thumbnail", "profiles"."city" AS "A_city", "profiles"."state_province" AS "A_state_province", "profiles"."user_id" AS "A_user_id", "profiles"."deleted_at" AS "A_deleted_at", "users"."id" AS "B_id", "users"."created_at" AS "B_created_at", "users"."updated_at" AS "B_updated_at", "users"."username" AS "B_username", "users"."is_active" AS "B_is_active" FROM "profiles" LEFT JOIN "users" ON "profiles"."user_id" = "users"."id" WHERE "profiles"."deleted_at" IS NULL AND "profiles"."email" = $1"#,
             vec!["test@profile.com".into()]
         )]
     );
@@ -331,12 +331,12 @@ async fn test_profiles_service_get_many_pagination() -> Result<()> {
         vec![
             Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT COUNT(*) AS num_items FROM (SELECT "profiles"."id", "profiles"."created_at", "profiles"."updated_at", "profiles"."email", "profiles"."display_name", "profiles"."picture", "profiles"."city", "profiles"."state_province", "profiles"."user_id" FROM "profiles" ORDER BY "profiles"."created_at" DESC) AS "sub_query""#,
+                r#"SELECT COUNT(*) AS num_items FROM (SELECT "profiles"."id", "profiles"."created_at", "profiles"."updated_at", "profiles"."email", "profiles"."display_name", "profiles"."picture", "profiles"."picture_thumbnail", "profiles"."city", "profiles"."state_province", "profiles"."user_id", "profiles"."deleted_at" FROM "profiles" WHERE "profiles"."deleted_at" IS NULL ORDER BY "profiles"."created_at" DESC) AS "sub_query""#,
                 vec![]
             ),
             Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "profiles"."id", "profiles"."created_at", "profiles"."updated_at", "profiles"."email", "profiles"."display_name", "profiles"."picture", "profiles"."city", "profiles"."state_province", "profiles"."user_id" FROM "profiles" ORDER BY "profiles"."created_at" DESC LIMIT $1 OFFSET $2"#,
+                r#"SELECT "profiles"."id", "profiles"."created_at", "profiles"."updated_at", "profiles"."email", "profiles"."display_name", "profiles"."picture", "profiles"."picture_thumbnail", "profiles"."city", "profiles"."state_province", "profiles"."user_id", "profiles"."deleted_at" FROM "profiles" WHERE "profiles"."deleted_at" IS NULL ORDER BY "profiles"."created_at" DESC LIMIT $1 OFFSET $2"#,
                 vec![5u64.into(), 5u64.into()]
             )
         ]
@@ -431,12 +431,12 @@ async fn test_profiles_service_get_many_pagination_with_related() -> Result<()>
         vec![
             Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT COUNT(*) AS num_items FROM (SELECT "profiles"."id" AS "A_id", "profiles"."created_at" AS "A_created_at", "profiles"."updated_at" AS "A_updated_at", "profiles"."email" AS "A_email", "profiles"."display_name" AS "A_display_name", "profiles"."picture" AS "A_picture", "profiles"."city" AS "A_city", "profiles"."state_province" AS "A_state_province", "profiles"."user_id" AS "A_user_id", "users"."id" AS "B_id", "users"."created_at" AS "B_created_at", "users"."updated_at" AS "B_updated_at", "users"."username" AS "B_username", "users"."is_active" AS "B_is_active" FROM "profiles" LEFT JOIN "users" ON "profiles"."user_id" = "users"."id" ORDER BY "profiles"."created_at" DESC) AS "sub_query""#,
+                r#"SELECT COUNT(*) AS num_items FROM (SELECT "profiles"."id" AS "A_id", "profiles"."created_at" AS "A_created_at", "profiles"."updated_at" AS "A_updated_at", "profiles"."email" AS "A_email", "profiles"."display_name" AS "A_display_name", "profiles"."picture" AS "A_picture", "profiles"."picture_thumbnail" AS "A_picture_thumbnail", "profiles"."city" AS "A_city", "profiles"."state_province" AS "A_state_province", "profiles"."user_id" AS "A_user_id", "profiles"."deleted_at" AS "A_deleted_at", "users"."id" AS "B_id", "users"."created_at" AS "B_created_at", "users"."updated_at" AS "B_updated_at", "users"."username" AS "B_username", "users"."is_active" AS "B_is_active" FROM "profiles" LEFT JOIN "users" ON "profiles"."user_id" = "users"."id" WHERE "profiles"."deleted_at" IS NULL ORDER BY "profiles"."created_at" DESC) AS "sub_query""#,
                 vec![]
             ),
             Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "profiles"."id" AS "A_id", "profiles"."created_at" AS "A_created_at", "profiles"."updated_at" AS "A_updated_at", "profiles"."email" AS "A_email", "profiles"."display_name" AS "A_display_name", "profiles"."picture" AS "A_picture", "profiles"."city" AS "A_city", "profiles"."state_province" AS "A_state_province", "profiles"."user_id" AS "A_user_id", "users"."id" AS "B_id", "users"."created_at" AS "B_created_at", "users"."updated_at" AS "B_updated_at", "users"."username" AS "B_username", "users"."is_active" AS "B_is_active" FROM "profiles" LEFT JOIN "users" ON "profiles"."user_id" = "users"."id" ORDER BY "profiles"."created_at" DESC LIMIT $1 OFFSET $2"#,
+                r#"SELECT "profiles"."id" AS "A_id", "profiles"."created_at" AS "A_created_at", "profiles"."updated_at" AS "A_updated_at", "profiles"."email" AS "A_email", "profiles"."display_name" AS "A_display_name", "profiles"."picture" AS "A_picture", "profiles"."picture_thumbnail" AS "A_picture_thumbnail", "profiles"."city" AS "A_city", "profiles"."state_province" AS "A_state_province", "profiles"."user_id" AS "A_user_id", "profiles"."deleted_at" AS "A_deleted_at", "users"."id" AS "B_id", "users"."created_at" AS "B_created_at", "users"."updated_at" AS "B_updated_at", "users"."username" AS "B_username", "users"."is_active" AS "B_is_active" FROM "profiles" LEFT JOIN "users" ON "profiles"."user_id" = "users"."id" WHERE "profiles"."deleted_at" IS NULL ORDER BY "profiles"."created_at" DESC LIMIT $1 OFFSET $2"#,
                 vec![5u64.into(), 5u64.into()]
             )
         ]
@@ -489,7 +489,7 @@ async fn test_profiles_service_create() -> Result<()> {
         db.into_transaction_log(),
         vec![Transaction::from_sql_and_values(
             DatabaseBackend::Postgres,
-            r#"INSERT INTO "profiles" ("email", "display_name", "picture", "city", "state_province", "user_id") VALUES ($1, $2, $3, $4, $5, $6) RETURNING "id", "created_at", "updated_at", "email", "display_name", "picture", "city", "state_province", "user_id""#,
+            r#"INSERT INTO "profiles" ("email", "display_name", "picture", "city", "state_province", "user_id") VALUES ($1, $2, $3, $4, $5, $6) RETURNING "id", "created_at", "updated_at", "email", "display_name", "picture", "picture_thumbnail", "city", "state_province", "user_id", "deleted_at""#,
             vec![
                 profile.email.into(),
                 profile.display_name.into(),
@@ -550,7 +550,7 @@ async fn test_profiles_service_create_with_related() -> Result<()> {
         vec![
             Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"INSERT INTO "profiles" ("email", "display_name", "picture", "city", "state_province", "user_id") VALUES ($1, $2, $3, $4, $5, $6) RETURNING "id", "created_at", "updated_at", "email", "display_name", "picture", "city", "state_province", "user_id""#,
+                r#"INSERT INTO "profiles" ("email", "display_name", "picture", "city", "state_province", "user_id") VALUES ($1, $2, $3, $4, $5, $6) RETURNING "id", "created_at", "updated_at", "email", "display_name", "picture", "picture_thumbnail", "city", "state_province", "user_id", "deleted_at""#,
                 vec![
                     profile.email.into(),
                     profile.display_name.into(),
@@ -601,9 +601,11 @@ async fn test_profiles_service_update() -> Result<()> {
                 email: Some(updated.email.clone()),
                 display_name: Undefined,
                 picture: Undefined,
+                picture_thumbnail: Undefined,
                 city: Undefined,
                 state_province: Undefined,
                 user_id: Some(user.id.clone()),
+                expected_updated_at: None,
             },
             &false,
         )
@@ -622,12 +624,12 @@ async fn test_profiles_service_update() -> Result<()> {
         vec![
             Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "profiles"."id", "profiles"."created_at", "profiles"."updated_at", "profiles"."email", "profiles"."display_name", "profiles"."picture", "profiles"."city", "profiles"."state_province", "profiles"."user_id" FROM "profiles" WHERE "profiles"."id" = $1 LIMIT $2"#,
+                r#"SELECT "profiles"."id", "profiles"."created_at", "profiles"."updated_at", "profiles"."email", "profiles"."display_name", "profiles"."picture", "profiles"."picture_thumbnail", "profiles"."city", "profiles"."state_province", "profiles"."user_id", "profiles"."deleted_at" FROM "profiles" WHERE "profiles"."id" = $1 AND "profiles"."deleted_at" IS NULL LIMIT $2"#,
                 vec![user.id.clone().into(), 1u64.into()]
             ),
             Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"UPDATE "profiles" SET "email" = $1, "user_id" = $2 WHERE "profiles"."id" = $3 RETURNING "id", "created_at", "updated_at", "email", "display_name", "picture", "city", "state_province", "user_id""#,
+                r#"UPDATE "profiles" SET "email" = $1, "user_id" = $2 WHERE "profiles"."id" = $3 RETURNING "id", "created_at", "updated_at", "email", "display_name", "picture", "picture_thumbnail", "city", "state_province", "user_id", "deleted_at""#,
                 vec![updated.email.into(), user.id.into(), profile.id.into()]
             )
         ]
@@ -667,9 +669,11 @@ async fn test_profiles_service_update_with_related() -> Result<()> {
                 email: Some(updated.email.clone()),
                 display_name: Undefined,
                 picture: Undefined,
+                picture_thumbnail: Undefined,
                 city: Undefined,
                 state_province: Undefined,
                 user_id: Some(user.id.clone()),
+                expected_updated_at: None,
             },
             &true,
         )
@@ -688,12 +692,12 @@ async fn test_profiles_service_update_with_related() -> Result<()> {
         vec![
             Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "profiles"."id" AS "A_id", "profiles"."created_at" AS "A_created_at", "profiles"."updated_at" AS "A_updated_at", "profiles"."email" AS "A_email", "profiles"."display_name" AS "A_display_name", "profiles"."picture" AS "A_picture", "profiles"."city" AS "A_city", "profiles"."state_province" AS "A_state_province", "profiles"."user_id" AS "A_user_id", "users"."id" AS "B_id", "users"."created_at" AS "B_created_at", "users"."updated_at" AS "B_updated_at", "users"."username" AS "B_username", "users"."is_active" AS "B_is_active" FROM "profiles" LEFT JOIN "users" ON "profiles"."user_id" = "users"."id" WHERE "profiles"."id" = $1 LIMIT $2"#,
+                r#"SELECT "profiles"."id" AS "A_id", "profiles"."created_at" AS "A_created_at", "profiles"."updated_at" AS "A_updated_at", "profiles"."email" AS "A_email", "profiles"."display_name" AS "A_display_name", "profiles"."picture" AS "A_picture", "profiles"."picture_thumbnail" AS "A_picture_thumbnail", "profiles"."city" AS "A_city", "profiles"."state_province" AS "A_state_province", "profiles"."user_id" AS "A_user_id", "profiles"."deleted_at" AS "A_deleted_at", "users"."id" AS "B_id", "users"."created_at" AS "B_created_at", "users"."updated_at" AS "B_updated_at", "users"."username" AS "B_username", "users"."is_active" AS "B_is_active" FROM "profiles" LEFT JOIN "users" ON "profiles"."user_id" = "users"."id" WHERE "profiles"."id" = $1 AND "profiles"."deleted_at" IS NULL LIMIT $2"#,
                 vec![user.id.clone().into(), 1u64.into()]
             ),
             Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"UPDATE "profiles" SET "email" = $1, "user_id" = $2 WHERE "profiles"."id" = $3 RETURNING "id", "created_at", "updated_at", "email", "display_name", "picture", "city", "state_province", "user_id""#,
+                r#"UPDATE "profiles" SET "email" = $1, "user_id" = $2 WHERE "profiles"."id" = $3 RETURNING "id", "created_at", "updated_at", "email", "display_name", "picture", "picture_thumbnail", "city", "state_province", "user_id", "deleted_at""#,
                 vec![updated.email.into(), user.id.into(), profile.id.into()]
             )
         ]
@@ -714,7 +718,6 @@ async fn test_profiles_service_delete() -> Result<()> {
 
     let db = Arc::new(
         MockDatabase::new(DatabaseBackend::Postgres)
-            .append_query_results(vec![vec![profile.clone()]])
             .append_exec_results(vec![MockExecResult {
                 last_insert_id: 0,
                 rows_affected: 1,
@@ -734,18 +737,11 @@ async fn test_profiles_service_delete() -> Result<()> {
     // Check the transaction log
     assert_eq!(
         db.into_transaction_log(),
-        vec![
-            Transaction::from_sql_and_values(
-                DatabaseBackend::Postgres,
-                r#"SELECT "profiles"."id", "profiles"."created_at", "profiles"."updated_at", "profiles"."email", "profiles"."display_name", "profiles"."picture", "profiles"."city", "profiles"."state_province", "profiles"."user_id" FROM "profiles" WHERE "profiles"."id" = $1 LIMIT $2"#,
-                vec![profile.id.clone().into(), 1u64.into()]
-            ),
-            Transaction::from_sql_and_values(
-                DatabaseBackend::Postgres,
-                r#"DELETE FROM "profiles" WHERE "profiles"."id" = $1"#,
-                vec![profile.id.into()]
-            )
-        ]
+        vec![Transaction::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"UPDATE "profiles" SET "deleted_at" = CURRENT_TIMESTAMP WHERE "profiles"."id" = $1 AND "profiles"."deleted_at" IS NULL"#,
+            vec![profile.id.into()]
+        )]
     );
 
     Ok(())
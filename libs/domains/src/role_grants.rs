@@ -5,3 +5,6 @@ pub mod service;
 
 /// Model
 pub mod model;
+
+/// Listing filters
+pub mod queries;
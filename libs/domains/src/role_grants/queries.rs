@@ -0,0 +1,65 @@
+use anyhow::Result;
+use oso::{Oso, ToPolar};
+use sea_orm::{entity::*, query::*, sea_query::SelectStatement};
+
+use super::model;
+use crate::users::model::User;
+
+/// Restricts a listing to resources where a `User` holds one of the given roles
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GrantedRoles {
+    /// The `User` id holding the roles
+    pub user_id: String,
+
+    /// The role keys that grant the requested permission
+    pub role_keys: Vec<String>,
+}
+
+impl GrantedRoles {
+    /// Determine which of the `User`'s roles for the given table grant a permission. Each role is
+    /// checked on its own against a stand-in resource, built from the granted resource id.
+    pub fn for_permission<R, F>(
+        oso: &Oso,
+        user: &User,
+        permission: &str,
+        resource_table: &str,
+        resource: F,
+    ) -> Result<Self>
+    where
+        R: ToPolar,
+        F: Fn(&str) -> R,
+    {
+        let mut role_keys: Vec<String> = vec![];
+
+        for role in &user.roles {
+            if role.resource_table != resource_table || role_keys.contains(&role.role_key) {
+                continue;
+            }
+
+            let actor = User {
+                roles: vec![role.clone()],
+                ..user.clone()
+            };
+
+            if oso.is_allowed(actor, permission.to_string(), resource(&role.resource_id))? {
+                role_keys.push(role.role_key.clone());
+            }
+        }
+
+        Ok(Self {
+            user_id: user.id.clone(),
+            role_keys,
+        })
+    }
+
+    /// Select the ids of the resources in the given table that these roles are granted for
+    pub fn resource_ids(&self, resource_table: &str) -> SelectStatement {
+        model::Entity::find()
+            .select_only()
+            .column(model::Column::ResourceId)
+            .filter(model::Column::UserId.eq(self.user_id.clone()))
+            .filter(model::Column::ResourceTable.eq(resource_table))
+            .filter(model::Column::RoleKey.is_in(self.role_keys.clone()))
+            .into_query()
+    }
+}
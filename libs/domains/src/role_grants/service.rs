@@ -3,10 +3,11 @@ use async_graphql::{dataloader::Loader, FieldError};
 use async_trait::async_trait;
 #[cfg(test)]
 use mockall::automock;
-use sea_orm::{entity::*, query::*, Condition, DatabaseConnection, EntityTrait};
+use sea_orm::{entity::*, query::*, Condition, ConnectionTrait, DatabaseConnection, EntityTrait};
 use std::{collections::HashMap, sync::Arc};
 
 use super::model::{self, CreateRoleGrantInput, RoleGrant};
+use crate::domain_events::{model::DomainEvent, publisher::DomainEventPublisher};
 
 /// A RoleGrantsService appliies business logic to a dynamic RoleGrantsRepository implementation.
 #[cfg_attr(test, automock)]
@@ -29,13 +30,19 @@ pub trait RoleGrantsService: Sync + Send {
 pub struct DefaultRoleGrantsService {
     /// The SeaOrm database connection
     db: Arc<DatabaseConnection>,
+
+    /// Publishes the changes made by the service
+    events: DomainEventPublisher,
 }
 
 /// The default `RoleGrantsService` implementation
 impl DefaultRoleGrantsService {
     /// Create a new `RoleGrantsService` instance
-    pub fn new(db: &Arc<DatabaseConnection>) -> Self {
-        Self { db: db.clone() }
+    pub fn new(db: &Arc<DatabaseConnection>, events: &DomainEventPublisher) -> Self {
+        Self {
+            db: db.clone(),
+            events: events.clone(),
+        }
     }
 }
 
@@ -65,19 +72,12 @@ impl RoleGrantsService for DefaultRoleGrantsService {
     }
 
     async fn create(&self, input: &CreateRoleGrantInput) -> Result<RoleGrant> {
-        let role_grant = model::ActiveModel {
-            role_key: Set(input.role_key.clone()),
-            user_id: Set(input.user_id.clone()),
-            resource_table: Set(input.resource_table.clone()),
-            resource_id: Set(input.resource_id.clone()),
-            ..Default::default()
-        }
-        .insert(&*self.db)
-        .await?;
+        let role_grant = insert_role_grant(&*self.db, input).await?;
 
-        let created: RoleGrant = role_grant;
+        self.events
+            .publish(DomainEvent::RoleGranted(role_grant.clone()));
 
-        return Ok(created);
+        Ok(role_grant)
     }
 
     async fn delete(&self, id: &str) -> Result<()> {
@@ -86,12 +86,33 @@ impl RoleGrantsService for DefaultRoleGrantsService {
             .await?
             .ok_or_else(|| anyhow!("Unable to find RoleGrant with id: {}", id))?;
 
-        let _result = role_grant.delete(&*self.db).await?;
+        let _result = role_grant.clone().delete(&*self.db).await?;
+
+        self.events.publish(DomainEvent::RoleRevoked(role_grant));
 
         Ok(())
     }
 }
 
+/// Insert a `RoleGrant` using the given connection, so that it can be part of a larger
+/// transaction in other services
+pub async fn insert_role_grant<C: ConnectionTrait>(
+    db: &C,
+    input: &CreateRoleGrantInput,
+) -> Result<RoleGrant> {
+    let role_grant = model::ActiveModel {
+        role_key: Set(input.role_key.clone()),
+        user_id: Set(input.user_id.clone()),
+        resource_table: Set(input.resource_table.clone()),
+        resource_id: Set(input.resource_id.clone()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    Ok(role_grant)
+}
+
 /// A dataloader for `RoleGrant` instances
 pub struct RoleGrantLoader {
     /// The SeaOrm database connection
@@ -7,6 +7,14 @@ use oso::PolarClass;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use caster_utils::errors::ConflictRecord;
+
+/// The Role that fully controls a Show
+pub const ADMIN_ROLE: &str = "admin";
+
+/// The Roles that can be granted for a Show
+pub const ROLES: [&str; 3] = ["guest", "manager", ADMIN_ROLE];
+
 /// The Show GraphQL and Database Model
 #[derive(
     Clone,
@@ -20,7 +28,7 @@ use serde::{Deserialize, Serialize};
     SimpleObject,
     PolarClass,
 )]
-#[graphql(name = "Show")]
+#[graphql(name = "Show", complex)]
 #[sea_orm(table_name = "shows")]
 pub struct Model {
     /// The Show id
@@ -45,6 +53,14 @@ pub struct Model {
     /// An optional Show image
     #[sea_orm(column_type = "Text", nullable)]
     pub picture: Option<String>,
+
+    /// A thumbnail of the Show image, if it was uploaded
+    #[sea_orm(column_type = "Text", nullable)]
+    pub picture_thumbnail: Option<String>,
+
+    /// The date the Show was deleted, if it is awaiting purge
+    #[graphql(skip)]
+    pub deleted_at: Option<DateTime>,
 }
 
 /// The Show GraphQL type is the same as the database Model
@@ -56,6 +72,10 @@ pub enum Relation {}
 
 impl ActiveModelBehavior for ActiveModel {}
 
+impl ConflictRecord for Model {
+    const HIDDEN_FIELDS: &'static [&'static str] = &["deleted_at"];
+}
+
 impl Default for Model {
     fn default() -> Self {
         Self {
@@ -65,6 +85,8 @@ impl Default for Model {
             title: String::default(),
             summary: Option::default(),
             picture: Option::default(),
+            picture_thumbnail: Option::default(),
+            deleted_at: Option::default(),
         }
     }
 }
@@ -1,5 +1,6 @@
 use async_graphql::{InputObject, MaybeUndefined, SimpleObject};
 use caster_utils::graphql::dummy_maybe_undef;
+use chrono::NaiveDateTime;
 use fake::{Dummy, Faker};
 use rand::Rng;
 
@@ -16,6 +17,14 @@ pub struct CreateShowInput {
 
     /// The Show's picture
     pub picture: Option<String>,
+
+    /// The ids of the Categories to list the Show under
+    #[dummy(default)]
+    pub category_ids: Option<Vec<String>>,
+
+    /// Free-form Tags to label the Show with
+    #[dummy(default)]
+    pub tags: Option<Vec<String>>,
 }
 
 /// The `UpdateShowInput` input type
@@ -29,6 +38,19 @@ pub struct UpdateShowInput {
 
     /// The Show's picture
     pub picture: MaybeUndefined<String>,
+
+    /// The thumbnail generated for an uploaded picture, which can't be set directly
+    #[graphql(skip)]
+    pub picture_thumbnail: MaybeUndefined<String>,
+
+    /// Replace the Categories that the Show is listed under
+    pub category_ids: Option<Vec<String>>,
+
+    /// Replace the free-form Tags that the Show is labeled with
+    pub tags: Option<Vec<String>>,
+
+    /// Reject the update with a Conflict error if the Show has been updated since this date
+    pub expected_updated_at: Option<NaiveDateTime>,
 }
 
 impl Dummy<Faker> for UpdateShowInput {
@@ -37,10 +59,30 @@ impl Dummy<Faker> for UpdateShowInput {
             title: dummy_maybe_undef(config, rng),
             summary: dummy_maybe_undef(config, rng),
             picture: dummy_maybe_undef(config, rng),
+            picture_thumbnail: MaybeUndefined::Undefined,
+            category_ids: None,
+            tags: None,
+            expected_updated_at: None,
         }
     }
 }
 
+/// The `TransferShowOwnershipInput` input type
+#[derive(Clone, Default, Dummy, Eq, PartialEq, InputObject)]
+pub struct TransferShowOwnershipInput {
+    /// The id of the Show to transfer
+    pub show_id: String,
+
+    /// The username of the User to grant the admin Role to
+    pub username: Option<String>,
+
+    /// The email address on the Profile of the User to grant the admin Role to
+    pub email: Option<String>,
+
+    /// Revoke the current User's own admin Role for the Show
+    pub remove_own_admin: Option<bool>,
+}
+
 /// The `MutateShowResult` type
 #[derive(Clone, Default, Dummy, Eq, PartialEq, SimpleObject)]
 pub struct MutateShowResult {
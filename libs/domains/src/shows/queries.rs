@@ -1,6 +1,9 @@
 use async_graphql::{Enum, InputObject, SimpleObject};
 
-use crate::shows::model::{self, Show};
+use crate::{
+    role_grants::queries::GrantedRoles,
+    shows::model::{self, Show},
+};
 use caster_utils::{
     ordering::Ordering::{self, Asc, Desc},
     pagination::ManyResponse,
@@ -42,13 +45,27 @@ impl From<ManyResponse<Show>> for ShowsPage {
 }
 
 /// Conditions to filter Show listings by
-#[derive(Clone, Eq, PartialEq, InputObject)]
+#[derive(Clone, Default, Eq, PartialEq, InputObject)]
 pub struct ShowCondition {
     /// The `Show`'s title
     pub title: Option<String>,
 
     /// Filter by IDs
     pub ids_in: Option<Vec<String>>,
+
+    /// Only include `Shows` listed under any of these `Categories`, or the `Categories` nested
+    /// beneath them
+    pub category_ids: Option<Vec<String>>,
+
+    /// Only include `Shows` labeled with any of these `Tags`
+    pub tags: Option<Vec<String>>,
+
+    /// Only include `Shows` that the current `User` has this permission for
+    pub permission: Option<String>,
+
+    /// The current `User`'s roles that grant the requested permission
+    #[graphql(skip)]
+    pub granted: Option<GrantedRoles>,
 }
 
 /// The available ordering values
@@ -1,19 +1,32 @@
-use async_graphql::{Context, Object, Result};
+use async_graphql::{
+    dataloader::DataLoader, ComplexObject, Context, MaybeUndefined, Object, Result, Upload,
+};
 use hyper::StatusCode;
 use oso::Oso;
 use std::sync::Arc;
 
 use crate::{
-    role_grants::{model::CreateRoleGrantInput, service::RoleGrantsService},
+    audit_events::resolver::record_audit_event,
+    authorization::resolver::is_allowed,
+    categories::{
+        model::Category,
+        service::{CategoriesService, ShowCategoriesLoader},
+    },
+    follows::service::FollowerCountLoader,
+    role_grants::{model::RoleGrant, queries::GrantedRoles},
     shows::{
         model::Show,
-        mutations::{CreateShowInput, MutateShowResult, UpdateShowInput},
+        mutations::{
+            CreateShowInput, MutateShowResult, TransferShowOwnershipInput, UpdateShowInput,
+        },
         queries::{ShowCondition, ShowsOrderBy, ShowsPage},
         service::ShowsService,
     },
-    users::model::User,
+    tags::service::ShowTagsLoader,
+    users::{model::User, resolver::find_user},
 };
-use caster_utils::errors::{as_graphql_error, graphql_error};
+use caster_storage::uploads::{remove_picture, upload_picture};
+use caster_utils::errors::{as_graphql_error, as_update_error, graphql_error};
 
 /// The Query segment owned by the Shows library
 #[derive(Default)]
@@ -46,9 +59,37 @@ impl ShowsQuery {
         page_size: Option<u64>,
     ) -> Result<ShowsPage> {
         let shows = ctx.data_unchecked::<Arc<dyn ShowsService>>();
+        let user = ctx.data_unchecked::<Option<User>>();
+        let oso = ctx.data_unchecked::<Oso>();
+
+        let mut condition = r#where;
+
+        // Resolve a requested permission to the roles that grant it, so it can be filtered in SQL
+        if let Some(condition) = condition.as_mut() {
+            if let Some(permission) = &condition.permission {
+                let user = user
+                    .as_ref()
+                    .ok_or_else(|| graphql_error("Unauthorized", StatusCode::UNAUTHORIZED))?;
+
+                // Permissions that apply to any Show, such as those of site admins, need no filter
+                if !oso.is_allowed(user.clone(), permission.clone(), Show::default())? {
+                    let granted =
+                        GrantedRoles::for_permission(oso, user, permission, "shows", |id| Show {
+                            id: id.to_string(),
+                            ..Default::default()
+                        })
+                        .map_err(as_graphql_error(
+                            "Error while checking permissions",
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                        ))?;
+
+                    condition.granted = Some(granted);
+                }
+            }
+        }
 
         let response = shows
-            .get_many(r#where, order_by, page, page_size)
+            .get_many(condition, order_by, page, page_size)
             .await
             .map_err(as_graphql_error(
                 "Error while listing Shows",
@@ -69,29 +110,35 @@ impl ShowsMutation {
         input: CreateShowInput,
     ) -> Result<MutateShowResult> {
         let shows = ctx.data_unchecked::<Arc<dyn ShowsService>>();
-        let role_grants = ctx.data_unchecked::<Arc<dyn RoleGrantsService>>();
         let user = ctx.data_unchecked::<Option<User>>();
 
         // Check authorization
         if let Some(user) = user {
-            let show = shows.create(&input).await.map_err(as_graphql_error(
-                "Error while creating Show",
-                StatusCode::INTERNAL_SERVER_ERROR,
-            ))?;
+            if let Some(category_ids) = &input.category_ids {
+                check_categories(ctx, category_ids).await?;
+            }
+
+            // Grant the Admin role to the creator along with the new Show
+            let (show, grant) =
+                shows
+                    .create_with_admin(&input, &user.id)
+                    .await
+                    .map_err(as_graphql_error(
+                        "Error while creating Show",
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                    ))?;
 
-            // Grant the Admin role to the creator
-            role_grants
-                .create(&CreateRoleGrantInput {
-                    role_key: "admin".to_string(),
-                    user_id: user.id.clone(),
-                    resource_table: "shows".to_string(),
-                    resource_id: show.id.clone(),
-                })
-                .await
-                .map_err(as_graphql_error(
-                    "Error while granting the admin role for a Show",
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                ))?;
+            record_audit_event(ctx, "create", "shows", &show.id, None::<&Show>, Some(&show)).await;
+
+            record_audit_event(
+                ctx,
+                "create",
+                "role_grants",
+                &grant.id,
+                None::<&RoleGrant>,
+                Some(&grant),
+            )
+            .await;
 
             Ok(MutateShowResult { show: Some(show) })
         } else {
@@ -99,6 +146,86 @@ impl ShowsMutation {
         }
     }
 
+    /// Grant the admin Role for a Show to another User, optionally revoking the current User's own
+    async fn transfer_show_ownership(
+        &self,
+        ctx: &Context<'_>,
+        input: TransferShowOwnershipInput,
+    ) -> Result<MutateShowResult> {
+        let shows = ctx.data_unchecked::<Arc<dyn ShowsService>>();
+        let user = ctx.data_unchecked::<Option<User>>();
+
+        // Check authentication
+        let user = user
+            .as_ref()
+            .ok_or_else(|| graphql_error("Unauthorized", StatusCode::UNAUTHORIZED))?;
+
+        // Retrieve the existing Show for authorization
+        let existing = shows
+            .get(&input.show_id)
+            .await
+            .map_err(as_graphql_error(
+                "Error while fetching Show",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?
+            .ok_or_else(|| graphql_error("Unable to find existing Show", StatusCode::NOT_FOUND))?;
+
+        // Check authorization
+        if !is_allowed(ctx, user, "transfer", existing.clone())? {
+            return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
+        }
+
+        let owner = find_user(ctx, &input.username, &input.email).await?;
+
+        if owner.id == user.id {
+            return Err(graphql_error(
+                "Unable to transfer a Show to the current User",
+                StatusCode::BAD_REQUEST,
+            ));
+        }
+
+        let from_user_id = input
+            .remove_own_admin
+            .unwrap_or(false)
+            .then(|| user.id.clone());
+
+        let (granted, revoked) = shows
+            .transfer_ownership(&existing.id, &owner.id, &from_user_id)
+            .await
+            .map_err(as_graphql_error(
+                "Error while transferring Show",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        if let Some(grant) = granted {
+            record_audit_event(
+                ctx,
+                "create",
+                "role_grants",
+                &grant.id,
+                None::<&RoleGrant>,
+                Some(&grant),
+            )
+            .await;
+        }
+
+        for grant in revoked {
+            record_audit_event(
+                ctx,
+                "delete",
+                "role_grants",
+                &grant.id,
+                Some(&grant),
+                None::<&RoleGrant>,
+            )
+            .await;
+        }
+
+        Ok(MutateShowResult {
+            show: Some(existing),
+        })
+    }
+
     /// Update an existing Show
     async fn update_show(
         &self,
@@ -108,7 +235,6 @@ impl ShowsMutation {
     ) -> Result<MutateShowResult> {
         let shows = ctx.data_unchecked::<Arc<dyn ShowsService>>();
         let user = ctx.data_unchecked::<Option<User>>();
-        let oso = ctx.data_unchecked::<Oso>();
 
         // Retrieve the existing Show for authorization
         let existing = shows
@@ -122,17 +248,86 @@ impl ShowsMutation {
 
         // Check authentication and authorization
         if let Some(user) = user {
-            if !oso.is_allowed(user.clone(), "update", existing)? {
+            if !is_allowed(ctx, user, "update", existing.clone())? {
                 return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
             }
         } else {
             return Err(graphql_error("Unauthorized", StatusCode::UNAUTHORIZED));
         }
 
-        let show = shows.update(&id, &input).await.map_err(as_graphql_error(
-            "Error while updating Show",
-            StatusCode::INTERNAL_SERVER_ERROR,
-        ))?;
+        if let Some(category_ids) = &input.category_ids {
+            check_categories(ctx, category_ids).await?;
+        }
+
+        let show = shows
+            .update(&id, &input)
+            .await
+            .map_err(as_update_error::<Show>(
+                "Error while updating Show",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        record_audit_event(ctx, "update", "shows", &id, Some(&existing), Some(&show)).await;
+
+        Ok(MutateShowResult { show: Some(show) })
+    }
+
+    /// Upload a new picture for an existing Show, along with a generated thumbnail
+    async fn upload_show_picture(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        picture: Upload,
+    ) -> Result<MutateShowResult> {
+        let shows = ctx.data_unchecked::<Arc<dyn ShowsService>>();
+        let user = ctx.data_unchecked::<Option<User>>();
+
+        // Retrieve the existing Show for authorization
+        let existing = shows
+            .get(&id)
+            .await
+            .map_err(as_graphql_error(
+                "Error while fetching Show",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?
+            .ok_or_else(|| graphql_error("Unable to find existing Show", StatusCode::NOT_FOUND))?;
+
+        // Check authentication and authorization
+        if let Some(user) = user {
+            if !is_allowed(ctx, user, "update", existing.clone())? {
+                return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
+            }
+        } else {
+            return Err(graphql_error("Unauthorized", StatusCode::UNAUTHORIZED));
+        }
+
+        let prefix = format!("shows/{}", id);
+        let stored = upload_picture(ctx, &picture, &prefix).await?;
+
+        let input = UpdateShowInput {
+            picture: MaybeUndefined::Value(stored.url),
+            picture_thumbnail: MaybeUndefined::Value(stored.thumbnail_url),
+            ..Default::default()
+        };
+
+        let show = shows
+            .update(&id, &input)
+            .await
+            .map_err(as_update_error::<Show>(
+                "Error while updating Show",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        record_audit_event(ctx, "update", "shows", &id, Some(&existing), Some(&show)).await;
+
+        // Clean up the files for the picture that was replaced
+        remove_picture(
+            ctx,
+            &prefix,
+            existing.picture.as_deref(),
+            existing.picture_thumbnail.as_deref(),
+        )
+        .await;
 
         Ok(MutateShowResult { show: Some(show) })
     }
@@ -141,7 +336,6 @@ impl ShowsMutation {
     async fn delete_show(&self, ctx: &Context<'_>, id: String) -> Result<bool> {
         let shows = ctx.data_unchecked::<Arc<dyn ShowsService>>();
         let user = ctx.data_unchecked::<Option<User>>();
-        let oso = ctx.data_unchecked::<Oso>();
 
         // Retrieve the existing Show for authorization
         let existing = shows
@@ -155,7 +349,7 @@ impl ShowsMutation {
 
         // Check authentication and authorization
         if let Some(user) = user {
-            if !oso.is_allowed(user.clone(), "delete", existing)? {
+            if !is_allowed(ctx, user, "delete", existing.clone())? {
                 return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
             }
         } else {
@@ -167,6 +361,97 @@ impl ShowsMutation {
             StatusCode::INTERNAL_SERVER_ERROR,
         ))?;
 
+        record_audit_event(ctx, "delete", "shows", &id, Some(&existing), None::<&Show>).await;
+
         Ok(true)
     }
+
+    /// Restore a deleted Show, along with the Episodes that were deleted with it
+    async fn restore_show(&self, ctx: &Context<'_>, id: String) -> Result<MutateShowResult> {
+        let shows = ctx.data_unchecked::<Arc<dyn ShowsService>>();
+        let user = ctx.data_unchecked::<Option<User>>();
+
+        // Retrieve the deleted Show for authorization
+        let existing = shows
+            .get_deleted(&id)
+            .await
+            .map_err(as_graphql_error(
+                "Error while fetching Show",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?
+            .ok_or_else(|| graphql_error("Unable to find deleted Show", StatusCode::NOT_FOUND))?;
+
+        // Check authentication and authorization
+        if let Some(user) = user {
+            if !is_allowed(ctx, user, "restore", existing.clone())? {
+                return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
+            }
+        } else {
+            return Err(graphql_error("Unauthorized", StatusCode::UNAUTHORIZED));
+        }
+
+        let show = shows.restore(&id).await.map_err(as_graphql_error(
+            "Error while restoring Show",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ))?;
+
+        record_audit_event(ctx, "restore", "shows", &id, Some(&existing), Some(&show)).await;
+
+        Ok(MutateShowResult { show: Some(show) })
+    }
+}
+
+/// Require every requested Category to exist, so that a Show can't be listed under an unknown one
+async fn check_categories(ctx: &Context<'_>, category_ids: &[String]) -> Result<()> {
+    let categories = ctx.data_unchecked::<Arc<dyn CategoriesService>>();
+
+    let found = categories
+        .get_by_ids(category_ids.to_vec())
+        .await
+        .map_err(as_graphql_error(
+            "Error while fetching Categories",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ))?;
+
+    if category_ids
+        .iter()
+        .any(|id| !found.iter().any(|category| &category.id == id))
+    {
+        return Err(graphql_error(
+            "Unable to find Category",
+            StatusCode::BAD_REQUEST,
+        ));
+    }
+
+    Ok(())
+}
+
+#[ComplexObject]
+impl Show {
+    /// The Categories that the Show is listed under
+    #[graphql(name = "categories")]
+    async fn resolve_categories(&self, ctx: &Context<'_>) -> Result<Vec<Category>> {
+        let loader = ctx.data_unchecked::<DataLoader<ShowCategoriesLoader>>();
+        let categories = loader.load_one(self.id.clone()).await?;
+
+        Ok(categories.unwrap_or_default())
+    }
+
+    /// The free-form Tags that the Show is labeled with
+    #[graphql(name = "tags")]
+    async fn resolve_tags(&self, ctx: &Context<'_>) -> Result<Vec<String>> {
+        let loader = ctx.data_unchecked::<DataLoader<ShowTagsLoader>>();
+        let tags = loader.load_one(self.id.clone()).await?;
+
+        Ok(tags.unwrap_or_default())
+    }
+
+    /// The number of Profiles following the Show
+    #[graphql(name = "followerCount")]
+    async fn resolve_follower_count(&self, ctx: &Context<'_>) -> Result<u64> {
+        let loader = ctx.data_unchecked::<DataLoader<FollowerCountLoader>>();
+        let count = loader.load_one(self.id.clone()).await?;
+
+        Ok(count.unwrap_or_default())
+    }
 }
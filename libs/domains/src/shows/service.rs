@@ -5,17 +5,38 @@ use async_graphql::{
     MaybeUndefined::{Null, Undefined, Value},
 };
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 #[cfg(test)]
 use mockall::automock;
-use sea_orm::{entity::*, query::*, DatabaseConnection, EntityTrait};
+use sea_orm::{
+    entity::*, query::*, sea_query::Expr, ConnectionTrait, DatabaseConnection, EntityTrait,
+    TransactionTrait,
+};
 use std::{collections::HashMap, sync::Arc};
 
+use crate::categories::{
+    service::{category_tree_ids, set_show_categories},
+    show_category,
+};
+use crate::domain_events::{model::DomainEvent, publisher::DomainEventPublisher};
+use crate::role_grants::{
+    model::{self as role_grant_model, CreateRoleGrantInput, RoleGrant},
+    service::insert_role_grant,
+};
 use crate::shows::{
     model::{self, Show},
     mutations::{CreateShowInput, UpdateShowInput},
     queries::{ShowCondition, ShowsOrderBy},
 };
-use caster_utils::{ordering::Ordering, pagination::ManyResponse};
+use crate::tags::{
+    model as tag_model,
+    service::{normalize_tags, set_show_tags},
+    show_tag,
+};
+use crate::webhooks::{model::WebhookEventType, outbox::record_event};
+use caster_utils::{
+    errors::Conflict, ordering::Ordering, pagination::ManyResponse, updates::update_if_unchanged,
+};
 
 /// A ShowsService applies business logic to a dynamic ShowsRepository implementation.
 #[cfg_attr(test, automock)]
@@ -39,31 +60,65 @@ pub trait ShowsService: Sync + Send {
     /// Create a `Show` with the given input
     async fn create(&self, input: &CreateShowInput) -> Result<Show>;
 
+    /// Create a `Show` and grant the admin role for it to the given `User` together, so that
+    /// no `Show` is left without anyone able to administer it
+    async fn create_with_admin(
+        &self,
+        input: &CreateShowInput,
+        user_id: &str,
+    ) -> Result<(Show, RoleGrant)>;
+
+    /// Grant the admin role for a `Show` to a `User`, and optionally revoke it from another `User`
+    /// in the same transaction. Returns the new grant, if the `User` didn't already hold it, and
+    /// any revoked grants.
+    async fn transfer_ownership(
+        &self,
+        id: &str,
+        to_user_id: &str,
+        from_user_id: &Option<String>,
+    ) -> Result<(Option<RoleGrant>, Vec<RoleGrant>)>;
+
     /// Update an existing `Show` by id
     async fn update(&self, id: &str, input: &UpdateShowInput) -> Result<Show>;
 
-    /// Delete an existing `Show`
+    /// Delete an existing `Show`, keeping it around to be restored until it is purged
     async fn delete(&self, id: &str) -> Result<()>;
+
+    /// Get an individual deleted `Show` by id
+    async fn get_deleted(&self, id: &str) -> Result<Option<Show>>;
+
+    /// Restore a deleted `Show`
+    async fn restore(&self, id: &str) -> Result<Show>;
+
+    /// Permanently remove `Show` records that were deleted before the given date
+    async fn purge(&self, deleted_before: NaiveDateTime) -> Result<u64>;
 }
 
 /// The default `ShowsService` struct.
 pub struct DefaultShowsService {
     /// The SeaOrm database connection
     db: Arc<DatabaseConnection>,
+
+    /// Publishes the changes made by the service
+    events: DomainEventPublisher,
 }
 
 /// The default `ShowsService` implementation
 impl DefaultShowsService {
     /// Create a new `ShowsService` instance
-    pub fn new(db: &Arc<DatabaseConnection>) -> Self {
-        Self { db: db.clone() }
+    pub fn new(db: &Arc<DatabaseConnection>, events: &DomainEventPublisher) -> Self {
+        Self {
+            db: db.clone(),
+            events: events.clone(),
+        }
     }
 }
 
 #[async_trait]
 impl ShowsService for DefaultShowsService {
     async fn get(&self, id: &str) -> Result<Option<model::Model>> {
-        let query = model::Entity::find_by_id(id.to_owned());
+        let query =
+            model::Entity::find_by_id(id.to_owned()).filter(model::Column::DeletedAt.is_null());
 
         let show = query.one(&*self.db).await?;
 
@@ -79,6 +134,7 @@ impl ShowsService for DefaultShowsService {
 
         let shows = model::Entity::find()
             .filter(condition)
+            .filter(model::Column::DeletedAt.is_null())
             .all(&*self.db)
             .await?;
 
@@ -94,7 +150,7 @@ impl ShowsService for DefaultShowsService {
     ) -> Result<ManyResponse<Show>> {
         let page_num = page.unwrap_or(1);
 
-        let mut query = model::Entity::find();
+        let mut query = model::Entity::find().filter(model::Column::DeletedAt.is_null());
 
         if let Some(condition) = condition {
             if let Some(title) = condition.title {
@@ -110,6 +166,38 @@ impl ShowsService for DefaultShowsService {
 
                 query = query.filter(condition);
             }
+
+            if let Some(category_ids) = condition.category_ids {
+                // Shows listed under a nested Category also belong to its ancestors
+                let category_ids = category_tree_ids(&*self.db, category_ids).await?;
+
+                query = query.filter(
+                    model::Column::Id.in_subquery(
+                        show_category::Entity::find()
+                            .select_only()
+                            .column(show_category::Column::ShowId)
+                            .filter(show_category::Column::CategoryId.is_in(category_ids))
+                            .into_query(),
+                    ),
+                );
+            }
+
+            if let Some(tags) = condition.tags {
+                query = query.filter(
+                    model::Column::Id.in_subquery(
+                        show_tag::Entity::find()
+                            .select_only()
+                            .column(show_tag::Column::ShowId)
+                            .inner_join(tag_model::Entity)
+                            .filter(tag_model::Column::Name.is_in(normalize_tags(&tags)))
+                            .into_query(),
+                    ),
+                );
+            }
+
+            if let Some(granted) = condition.granted {
+                query = query.filter(model::Column::Id.in_subquery(granted.resource_ids("shows")));
+            }
         }
 
         if let Some(order_by) = order_by {
@@ -144,29 +232,162 @@ impl ShowsService for DefaultShowsService {
     }
 
     async fn create(&self, input: &CreateShowInput) -> Result<Show> {
+        let txn = self.db.begin().await?;
+
         let show = model::ActiveModel {
             title: Set(input.title.clone()),
             summary: Set(input.summary.clone()),
             picture: Set(input.picture.clone()),
             ..Default::default()
         }
-        .insert(&*self.db)
+        .insert(&txn)
         .await?;
 
+        set_taxonomy(&txn, &show.id, &input.category_ids, &input.tags).await?;
+
+        record_event(&txn, &show.id, WebhookEventType::ShowCreated, &show).await?;
+
+        txn.commit().await?;
+
+        self.events.publish(DomainEvent::ShowCreated(show.clone()));
+
         let created: Show = show;
 
         return Ok(created);
     }
 
+    async fn create_with_admin(
+        &self,
+        input: &CreateShowInput,
+        user_id: &str,
+    ) -> Result<(Show, RoleGrant)> {
+        let txn = self.db.begin().await?;
+
+        let show = model::ActiveModel {
+            title: Set(input.title.clone()),
+            summary: Set(input.summary.clone()),
+            picture: Set(input.picture.clone()),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        set_taxonomy(&txn, &show.id, &input.category_ids, &input.tags).await?;
+
+        let grant = insert_role_grant(
+            &txn,
+            &CreateRoleGrantInput {
+                role_key: model::ADMIN_ROLE.to_string(),
+                user_id: user_id.to_string(),
+                resource_table: "shows".to_string(),
+                resource_id: show.id.clone(),
+            },
+        )
+        .await?;
+
+        record_event(&txn, &show.id, WebhookEventType::ShowCreated, &show).await?;
+
+        txn.commit().await?;
+
+        self.events.publish_all([
+            DomainEvent::ShowCreated(show.clone()),
+            DomainEvent::RoleGranted(grant.clone()),
+        ]);
+
+        Ok((show, grant))
+    }
+
+    async fn transfer_ownership(
+        &self,
+        id: &str,
+        to_user_id: &str,
+        from_user_id: &Option<String>,
+    ) -> Result<(Option<RoleGrant>, Vec<RoleGrant>)> {
+        let admin_grants = || {
+            role_grant_model::Entity::find()
+                .filter(role_grant_model::Column::RoleKey.eq(model::ADMIN_ROLE))
+                .filter(role_grant_model::Column::ResourceTable.eq("shows"))
+                .filter(role_grant_model::Column::ResourceId.eq(id))
+        };
+
+        let txn = self.db.begin().await?;
+
+        let existing = admin_grants()
+            .filter(role_grant_model::Column::UserId.eq(to_user_id))
+            .one(&txn)
+            .await?;
+
+        let granted = if existing.is_some() {
+            None
+        } else {
+            Some(
+                insert_role_grant(
+                    &txn,
+                    &CreateRoleGrantInput {
+                        role_key: model::ADMIN_ROLE.to_string(),
+                        user_id: to_user_id.to_string(),
+                        resource_table: "shows".to_string(),
+                        resource_id: id.to_string(),
+                    },
+                )
+                .await?,
+            )
+        };
+
+        let revoked = if let Some(from_user_id) = from_user_id {
+            let revoked = admin_grants()
+                .filter(role_grant_model::Column::UserId.eq(from_user_id))
+                .all(&txn)
+                .await?;
+
+            if !revoked.is_empty() {
+                role_grant_model::Entity::delete_many()
+                    .filter(
+                        role_grant_model::Column::Id
+                            .is_in(revoked.iter().map(|grant| grant.id.clone())),
+                    )
+                    .exec(&txn)
+                    .await?;
+            }
+
+            revoked
+        } else {
+            vec![]
+        };
+
+        txn.commit().await?;
+
+        self.events.publish_all(
+            granted
+                .iter()
+                .cloned()
+                .map(DomainEvent::RoleGranted)
+                .chain(revoked.iter().cloned().map(DomainEvent::RoleRevoked)),
+        );
+
+        Ok((granted, revoked))
+    }
+
     async fn update(&self, id: &str, input: &UpdateShowInput) -> Result<Show> {
-        let query = model::Entity::find_by_id(id.to_owned());
+        let query =
+            model::Entity::find_by_id(id.to_owned()).filter(model::Column::DeletedAt.is_null());
+
+        let txn = self.db.begin().await?;
 
         // Retrieve the existing Show
         let show = query
-            .one(&*self.db)
+            .clone()
+            .one(&txn)
             .await?
             .ok_or_else(|| anyhow!("Unable to find Show with id: {}", id))?;
 
+        // Reject the update if the Show has changed since the client last retrieved it
+        if let Some(expected) = input.expected_updated_at {
+            if show.updated_at != expected {
+                return Err(Conflict { current: show }.into());
+            }
+        }
+
         let mut show: model::ActiveModel = show.into();
 
         match &input.title {
@@ -186,21 +407,123 @@ impl ShowsService for DefaultShowsService {
             Value(value) => show.picture = Set(Some(value.clone())),
         }
 
-        let updated: Show = show.update(&*self.db).await?;
+        // A new picture replaces the old thumbnail, which is only kept if one was generated for it
+        match &input.picture_thumbnail {
+            Undefined if input.picture.is_undefined() => (),
+            Undefined | Null => show.picture_thumbnail = Set(None),
+            Value(value) => show.picture_thumbnail = Set(Some(value.clone())),
+        }
+
+        // Make the write conditional as well, in case of a concurrent update since the read
+        let updated: Show = update_if_unchanged(
+            &txn,
+            show,
+            model::Column::Id.eq(id.to_owned()),
+            model::Column::UpdatedAt,
+            input.expected_updated_at,
+            || async {
+                query
+                    .one(&txn)
+                    .await?
+                    .ok_or_else(|| anyhow!("Unable to find Show with id: {}", id))
+            },
+        )
+        .await?;
+
+        set_taxonomy(&txn, id, &input.category_ids, &input.tags).await?;
+
+        record_event(&txn, id, WebhookEventType::ShowUpdated, &updated).await?;
+
+        txn.commit().await?;
+
+        self.events
+            .publish(DomainEvent::ShowUpdated(updated.clone()));
 
         Ok(updated)
     }
 
     async fn delete(&self, id: &str) -> Result<()> {
-        let show = model::Entity::find_by_id(id.to_owned())
-            .one(&*self.db)
+        let txn = self.db.begin().await?;
+
+        // The Episodes for the Show are soft-deleted along with it by a database trigger
+        let deleted = model::Entity::update_many()
+            .col_expr(model::Column::DeletedAt, Expr::current_timestamp().into())
+            .filter(model::Column::Id.eq(id.to_owned()))
+            .filter(model::Column::DeletedAt.is_null())
+            .exec_with_returning(&txn)
             .await?
+            .pop()
             .ok_or_else(|| anyhow!("Unable to find Show with id: {}", id))?;
 
-        let _result = show.delete(&*self.db).await?;
+        record_event(&txn, id, WebhookEventType::ShowDeleted, &deleted).await?;
+
+        txn.commit().await?;
+
+        self.events.publish(DomainEvent::ShowDeleted(deleted));
 
         Ok(())
     }
+
+    async fn get_deleted(&self, id: &str) -> Result<Option<Show>> {
+        let show = model::Entity::find_by_id(id.to_owned())
+            .filter(model::Column::DeletedAt.is_not_null())
+            .one(&*self.db)
+            .await?;
+
+        Ok(show)
+    }
+
+    async fn restore(&self, id: &str) -> Result<Show> {
+        let show = model::Entity::find_by_id(id.to_owned())
+            .filter(model::Column::DeletedAt.is_not_null())
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| anyhow!("Unable to find deleted Show with id: {}", id))?;
+
+        let mut show: model::ActiveModel = show.into();
+
+        show.deleted_at = Set(None);
+
+        let txn = self.db.begin().await?;
+
+        let restored: Show = show.update(&txn).await?;
+
+        record_event(&txn, id, WebhookEventType::ShowUpdated, &restored).await?;
+
+        txn.commit().await?;
+
+        self.events
+            .publish(DomainEvent::ShowUpdated(restored.clone()));
+
+        Ok(restored)
+    }
+
+    async fn purge(&self, deleted_before: NaiveDateTime) -> Result<u64> {
+        let result = model::Entity::delete_many()
+            .filter(model::Column::DeletedAt.lt(deleted_before))
+            .exec(&*self.db)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+}
+
+/// Replace the Categories and Tags for a Show, if they were provided
+async fn set_taxonomy<C: ConnectionTrait>(
+    db: &C,
+    show_id: &str,
+    category_ids: &Option<Vec<String>>,
+    tags: &Option<Vec<String>>,
+) -> Result<()> {
+    if let Some(category_ids) = category_ids {
+        set_show_categories(db, show_id, category_ids).await?;
+    }
+
+    if let Some(tags) = tags {
+        set_show_tags(db, show_id, tags).await?;
+    }
+
+    Ok(())
 }
 
 /// A dataloader for `Show` instances
@@ -1,17 +1,20 @@
 use anyhow::Result;
 use async_graphql::MaybeUndefined;
+use chrono::Duration;
 use fake::{Fake, Faker};
 use pretty_assertions::assert_eq;
-use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Transaction, Value};
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Statement, Transaction, Value};
 use std::sync::Arc;
 
+use crate::domain_events::publisher::DomainEventPublisher;
+use crate::role_grants::{model::RoleGrant, queries::GrantedRoles};
 use crate::shows::{
     model::Show,
     mutations::{CreateShowInput, UpdateShowInput},
     queries::{ShowCondition, ShowsOrderBy},
     service::{DefaultShowsService, ShowsService},
 };
-use caster_utils::pagination::ManyResponse;
+use caster_utils::{errors::Conflict, pagination::ManyResponse};
 
 #[tokio::test]
 async fn test_shows_service_get() -> Result<()> {
@@ -24,7 +27,7 @@ async fn test_shows_service_get() -> Result<()> {
             .into_connection(),
     );
 
-    let service = DefaultShowsService::new(&db);
+    let service = DefaultShowsService::new(&db, &DomainEventPublisher::default());
 
     let result = service.get(&show.id).await?;
 
@@ -40,7 +43,7 @@ async fn test_shows_service_get() -> Result<()> {
         db.into_transaction_log(),
         vec![Transaction::from_sql_and_values(
             DatabaseBackend::Postgres,
-            r#"SELECT "shows"."id", "shows"."created_at", "shows"."updated_at", "shows"."title", "shows"."summary", "shows"."picture" FROM "shows" WHERE "shows"."id" = $1 LIMIT $2"#,
+            r#"SELECT "shows"."id", "shows"."created_at", "shows"."updated_at", "shows"."title", "shows"."summary", "shows"."picture", "shows"."picture_thumbnail", "shows"."deleted_at" FROM "shows" WHERE "shows"."id" = $1 AND "shows"."deleted_at" IS NULL LIMIT $2"#,
             vec![show.id.into(), 1u64.into()]
         )]
     );
@@ -62,13 +65,14 @@ async fn test_shows_service_get_many() -> Result<()> {
             .into_connection(),
     );
 
-    let service = DefaultShowsService::new(&db);
+    let service = DefaultShowsService::new(&db, &DomainEventPublisher::default());
 
     let result = service
         .get_many(
             Some(ShowCondition {
                 title: Some("Test Show".to_string()),
                 ids_in: None,
+                ..Default::default()
             }),
             None,
             None,
@@ -97,7 +101,7 @@ async fn test_shows_service_get_many() -> Result<()> {
         db.into_transaction_log(),
         vec![Transaction::from_sql_and_values(
             DatabaseBackend::Postgres,
-            r#"SELECT "shows"."id", "shows"."created_at", "shows"."updated_at", "shows"."title", "shows"."summary", "shows"."picture" FROM "shows" WHERE "shows"."title" = $1"#,
+            r#"SELECT "shows"."id", "shows"."created_at", "shows"."updated_at", "shows"."title", "shows"."summary", "shows"."picture", "shows"."picture_thumbnail", "shows"."deleted_at" FROM "shows" WHERE "shows"."deleted_at" IS NULL AND "shows"."title" = $1"#,
             vec!["Test Show".into()]
         )]
     );
@@ -105,6 +109,59 @@ async fn test_shows_service_get_many() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_shows_service_get_many_granted() -> Result<()> {
+    let show: Show = Faker.fake();
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![show.clone()]])
+            .into_connection(),
+    );
+
+    let service = DefaultShowsService::new(&db, &DomainEventPublisher::default());
+
+    let result = service
+        .get_many(
+            Some(ShowCondition {
+                permission: Some("update".to_string()),
+                granted: Some(GrantedRoles {
+                    user_id: "test-user-id".to_string(),
+                    role_keys: vec!["manager".to_string(), "admin".to_string()],
+                }),
+                ..Default::default()
+            }),
+            None,
+            None,
+            None,
+        )
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result.data, vec![show]);
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"SELECT "shows"."id", "shows"."created_at", "shows"."updated_at", "shows"."title", "shows"."summary", "shows"."picture", "shows"."picture_thumbnail", "shows"."deleted_at" FROM "shows" WHERE "shows"."deleted_at" IS NULL AND "shows"."id" IN (SELECT "role_grants"."resource_id" FROM "role_grants" WHERE "role_grants"."user_id" = $1 AND "role_grants"."resource_table" = $2 AND "role_grants"."role_key" IN ($3, $4))"#,
+            vec![
+                "test-user-id".into(),
+                "shows".into(),
+                "manager".into(),
+                "admin".into()
+            ]
+        )]
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_shows_service_get_many_pagination() -> Result<()> {
     let mut show1: Show = Faker.fake();
@@ -137,7 +194,7 @@ async fn test_shows_service_get_many_pagination() -> Result<()> {
             .into_connection(),
     );
 
-    let service = DefaultShowsService::new(&db);
+    let service = DefaultShowsService::new(&db, &DomainEventPublisher::default());
 
     let result = service
         .get_many(
@@ -170,12 +227,12 @@ async fn test_shows_service_get_many_pagination() -> Result<()> {
         vec![
             Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT COUNT(*) AS num_items FROM (SELECT "shows"."id", "shows"."created_at", "shows"."updated_at", "shows"."title", "shows"."summary", "shows"."picture" FROM "shows" ORDER BY "shows"."created_at" DESC) AS "sub_query""#,
+                r#"SELECT COUNT(*) AS num_items FROM (SELECT "shows"."id", "shows"."created_at", "shows"."updated_at", "shows"."title", "shows"."summary", "shows"."picture", "shows"."picture_thumbnail", "shows"."deleted_at" FROM "shows" WHERE "shows"."deleted_at" IS NULL ORDER BY "shows"."created_at" DESC) AS "sub_query""#,
                 vec![]
             ),
             Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "shows"."id", "shows"."created_at", "shows"."updated_at", "shows"."title", "shows"."summary", "shows"."picture" FROM "shows" ORDER BY "shows"."created_at" DESC LIMIT $1 OFFSET $2"#,
+                r#"SELECT "shows"."id", "shows"."created_at", "shows"."updated_at", "shows"."title", "shows"."summary", "shows"."picture", "shows"."picture_thumbnail", "shows"."deleted_at" FROM "shows" WHERE "shows"."deleted_at" IS NULL ORDER BY "shows"."created_at" DESC LIMIT $1 OFFSET $2"#,
                 vec![5u64.into(), 5u64.into()]
             )
         ]
@@ -192,16 +249,21 @@ async fn test_shows_service_create() -> Result<()> {
     let db = Arc::new(
         MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results(vec![vec![show.clone()]])
+            .append_exec_results(vec![MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            }])
             .into_connection(),
     );
 
-    let service = DefaultShowsService::new(&db);
+    let service = DefaultShowsService::new(&db, &DomainEventPublisher::default());
 
     let result = service
         .create(&CreateShowInput {
             title: show.title.clone(),
             summary: show.summary.clone(),
             picture: show.picture.clone(),
+            ..Default::default()
         })
         .await?;
 
@@ -212,14 +274,192 @@ async fn test_shows_service_create() -> Result<()> {
 
     assert_eq!(result, show);
 
+    let show_id = show.id.clone();
+    let payload = serde_json::to_value(&show)?;
+
     // Check the transaction log
     assert_eq!(
         db.into_transaction_log(),
-        vec![Transaction::from_sql_and_values(
-            DatabaseBackend::Postgres,
-            r#"INSERT INTO "shows" ("title", "summary", "picture") VALUES ($1, $2, $3) RETURNING "id", "created_at", "updated_at", "title", "summary", "picture""#,
-            vec![show.title.into(), show.summary.into(), show.picture.into(),]
-        )]
+        vec![Transaction::many(vec![
+            Statement::from_string(DatabaseBackend::Postgres, "BEGIN".to_string()),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"INSERT INTO "shows" ("title", "summary", "picture") VALUES ($1, $2, $3) RETURNING "id", "created_at", "updated_at", "title", "summary", "picture", "picture_thumbnail", "deleted_at""#,
+                vec![show.title.into(), show.summary.into(), show.picture.into()]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"INSERT INTO "outbox_events" ("show_id", "event_type", "payload") VALUES ($1, $2, $3)"#,
+                vec![show_id.into(), "show.created".into(), payload.into()]
+            ),
+            Statement::from_string(DatabaseBackend::Postgres, "COMMIT".to_string()),
+        ])]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shows_service_create_with_admin() -> Result<()> {
+    let mut show: Show = Faker.fake();
+    show.title = "Test Show".to_string();
+
+    let mut grant: RoleGrant = Faker.fake();
+    grant.role_key = "admin".to_string();
+    grant.resource_table = "shows".to_string();
+    grant.resource_id = show.id.clone();
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![show.clone()]])
+            .append_query_results(vec![vec![grant.clone()]])
+            .append_exec_results(vec![MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            }])
+            .into_connection(),
+    );
+
+    let service = DefaultShowsService::new(&db, &DomainEventPublisher::default());
+
+    let result = service
+        .create_with_admin(
+            &CreateShowInput {
+                title: show.title.clone(),
+                summary: show.summary.clone(),
+                picture: show.picture.clone(),
+                ..Default::default()
+            },
+            &grant.user_id,
+        )
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, (show.clone(), grant.clone()));
+
+    let show_id = show.id.clone();
+    let payload = serde_json::to_value(&show)?;
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::many(vec![
+            Statement::from_string(DatabaseBackend::Postgres, "BEGIN".to_string()),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"INSERT INTO "shows" ("title", "summary", "picture") VALUES ($1, $2, $3) RETURNING "id", "created_at", "updated_at", "title", "summary", "picture", "picture_thumbnail", "deleted_at""#,
+                vec![show.title.into(), show.summary.into(), show.picture.into()]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"INSERT INTO "role_grants" ("role_key", "user_id", "resource_table", "resource_id") VALUES ($1, $2, $3, $4) RETURNING "id", "created_at", "updated_at", "role_key", "user_id", "resource_table", "resource_id""#,
+                vec![
+                    "admin".into(),
+                    grant.user_id.into(),
+                    "shows".into(),
+                    show.id.into()
+                ]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"INSERT INTO "outbox_events" ("show_id", "event_type", "payload") VALUES ($1, $2, $3)"#,
+                vec![show_id.into(), "show.created".into(), payload.into()]
+            ),
+            Statement::from_string(DatabaseBackend::Postgres, "COMMIT".to_string()),
+        ])]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shows_service_transfer_ownership() -> Result<()> {
+    let show: Show = Faker.fake();
+
+    let mut grant: RoleGrant = Faker.fake();
+    grant.role_key = "admin".to_string();
+    grant.resource_table = "shows".to_string();
+    grant.resource_id = show.id.clone();
+
+    let mut revoked: RoleGrant = Faker.fake();
+    revoked.role_key = "admin".to_string();
+    revoked.resource_table = "shows".to_string();
+    revoked.resource_id = show.id.clone();
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![
+                Vec::<RoleGrant>::new(),
+                vec![grant.clone()],
+                vec![revoked.clone()],
+            ])
+            .append_exec_results(vec![MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            }])
+            .into_connection(),
+    );
+
+    let service = DefaultShowsService::new(&db, &DomainEventPublisher::default());
+
+    let result = service
+        .transfer_ownership(&show.id, &grant.user_id, &Some(revoked.user_id.clone()))
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, (Some(grant.clone()), vec![revoked.clone()]));
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::many(vec![
+            Statement::from_string(DatabaseBackend::Postgres, "BEGIN".to_string()),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"SELECT "role_grants"."id", "role_grants"."created_at", "role_grants"."updated_at", "role_grants"."role_key", "role_grants"."user_id", "role_grants"."resource_table", "role_grants"."resource_id" FROM "role_grants" WHERE "role_grants"."role_key" = $1 AND "role_grants"."resource_table" = $2 AND "role_grants"."resource_id" = $3 AND "role_grants"."user_id" = $4 LIMIT $5"#,
+                vec![
+                    "admin".into(),
+                    "shows".into(),
+                    show.id.clone().into(),
+                    grant.user_id.clone().into(),
+                    1u64.into()
+                ]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"INSERT INTO "role_grants" ("role_key", "user_id", "resource_table", "resource_id") VALUES ($1, $2, $3, $4) RETURNING "id", "created_at", "updated_at", "role_key", "user_id", "resource_table", "resource_id""#,
+                vec![
+                    "admin".into(),
+                    grant.user_id.into(),
+                    "shows".into(),
+                    show.id.clone().into()
+                ]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"SELECT "role_grants"."id", "role_grants"."created_at", "role_grants"."updated_at", "role_grants"."role_key", "role_grants"."user_id", "role_grants"."resource_table", "role_grants"."resource_id" FROM "role_grants" WHERE "role_grants"."role_key" = $1 AND "role_grants"."resource_table" = $2 AND "role_grants"."resource_id" = $3 AND "role_grants"."user_id" = $4"#,
+                vec![
+                    "admin".into(),
+                    "shows".into(),
+                    show.id.into(),
+                    revoked.user_id.into()
+                ]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"DELETE FROM "role_grants" WHERE "role_grants"."id" IN ($1)"#,
+                vec![revoked.id.into()]
+            ),
+            Statement::from_string(DatabaseBackend::Postgres, "COMMIT".to_string()),
+        ])]
     );
 
     Ok(())
@@ -238,10 +478,14 @@ async fn test_shows_service_update() -> Result<()> {
     let db = Arc::new(
         MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results(vec![vec![show.clone()], vec![updated.clone()]])
+            .append_exec_results(vec![MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            }])
             .into_connection(),
     );
 
-    let service = DefaultShowsService::new(&db);
+    let service = DefaultShowsService::new(&db, &DomainEventPublisher::default());
 
     let result = service
         .update(
@@ -250,6 +494,10 @@ async fn test_shows_service_update() -> Result<()> {
                 title: MaybeUndefined::Value(updated.title.clone()),
                 summary: MaybeUndefined::Undefined,
                 picture: MaybeUndefined::Undefined,
+                picture_thumbnail: MaybeUndefined::Undefined,
+                category_ids: None,
+                tags: None,
+                expected_updated_at: None,
             },
         )
         .await?;
@@ -261,34 +509,179 @@ async fn test_shows_service_update() -> Result<()> {
 
     assert_eq!(result, updated.clone());
 
+    let show_id = show.id.clone();
+    let payload = serde_json::to_value(&updated)?;
+
     // Check the transaction log
     assert_eq!(
         db.into_transaction_log(),
-        vec![
-            Transaction::from_sql_and_values(
+        vec![Transaction::many(vec![
+            Statement::from_string(DatabaseBackend::Postgres, "BEGIN".to_string()),
+            Statement::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "shows"."id", "shows"."created_at", "shows"."updated_at", "shows"."title", "shows"."summary", "shows"."picture" FROM "shows" WHERE "shows"."id" = $1 LIMIT $2"#,
+                r#"SELECT "shows"."id", "shows"."created_at", "shows"."updated_at", "shows"."title", "shows"."summary", "shows"."picture", "shows"."picture_thumbnail", "shows"."deleted_at" FROM "shows" WHERE "shows"."id" = $1 AND "shows"."deleted_at" IS NULL LIMIT $2"#,
                 vec![show.id.clone().into(), 1u64.into()]
             ),
-            Transaction::from_sql_and_values(
+            Statement::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"UPDATE "shows" SET "title" = $1 WHERE "shows"."id" = $2 RETURNING "id", "created_at", "updated_at", "title", "summary", "picture""#,
+                r#"UPDATE "shows" SET "title" = $1 WHERE "shows"."id" = $2 RETURNING "id", "created_at", "updated_at", "title", "summary", "picture", "picture_thumbnail", "deleted_at""#,
                 vec![updated.title.into(), show.id.into()]
-            )
-        ]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"INSERT INTO "outbox_events" ("show_id", "event_type", "payload") VALUES ($1, $2, $3)"#,
+                vec![show_id.into(), "show.updated".into(), payload.into()]
+            ),
+            Statement::from_string(DatabaseBackend::Postgres, "COMMIT".to_string()),
+        ])]
     );
 
     Ok(())
 }
 
 #[tokio::test]
-async fn test_shows_service_delete() -> Result<()> {
+async fn test_shows_service_update_expected_updated_at() -> Result<()> {
+    let mut show: Show = Faker.fake();
+    show.title = "Test Show".to_string();
+
+    let updated = Show {
+        title: "Updated Show".to_string(),
+        ..show.clone()
+    };
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![show.clone()], vec![updated.clone()]])
+            .append_exec_results(vec![MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            }])
+            .into_connection(),
+    );
+
+    let service = DefaultShowsService::new(&db, &DomainEventPublisher::default());
+
+    let result = service
+        .update(
+            &show.id,
+            &UpdateShowInput {
+                title: MaybeUndefined::Value(updated.title.clone()),
+                summary: MaybeUndefined::Undefined,
+                picture: MaybeUndefined::Undefined,
+                picture_thumbnail: MaybeUndefined::Undefined,
+                category_ids: None,
+                tags: None,
+                expected_updated_at: Some(show.updated_at),
+            },
+        )
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, updated.clone());
+
+    let show_id = show.id.clone();
+    let payload = serde_json::to_value(&updated)?;
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::many(vec![
+            Statement::from_string(DatabaseBackend::Postgres, "BEGIN".to_string()),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"SELECT "shows"."id", "shows"."created_at", "shows"."updated_at", "shows"."title", "shows"."summary", "shows"."picture", "shows"."picture_thumbnail", "shows"."deleted_at" FROM "shows" WHERE "shows"."id" = $1 AND "shows"."deleted_at" IS NULL LIMIT $2"#,
+                vec![show.id.clone().into(), 1u64.into()]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"UPDATE "shows" SET "title" = $1, "updated_at" = CURRENT_TIMESTAMP WHERE "shows"."id" = $2 AND "shows"."updated_at" = $3 RETURNING "id", "created_at", "updated_at", "title", "summary", "picture", "picture_thumbnail", "deleted_at""#,
+                vec![updated.title.into(), show.id.into(), show.updated_at.into()]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"INSERT INTO "outbox_events" ("show_id", "event_type", "payload") VALUES ($1, $2, $3)"#,
+                vec![show_id.into(), "show.updated".into(), payload.into()]
+            ),
+            Statement::from_string(DatabaseBackend::Postgres, "COMMIT".to_string()),
+        ])]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shows_service_update_conflict() -> Result<()> {
     let mut show: Show = Faker.fake();
     show.title = "Test Show".to_string();
 
     let db = Arc::new(
         MockDatabase::new(DatabaseBackend::Postgres)
             .append_query_results(vec![vec![show.clone()]])
+            .into_connection(),
+    );
+
+    let service = DefaultShowsService::new(&db, &DomainEventPublisher::default());
+
+    let result = service
+        .update(
+            &show.id,
+            &UpdateShowInput {
+                title: MaybeUndefined::Value("Updated Show".to_string()),
+                summary: MaybeUndefined::Undefined,
+                picture: MaybeUndefined::Undefined,
+                picture_thumbnail: MaybeUndefined::Undefined,
+                category_ids: None,
+                tags: None,
+                expected_updated_at: Some(show.updated_at - Duration::seconds(1)),
+            },
+        )
+        .await;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    let conflict = result
+        .expect_err("Expected a Conflict")
+        .downcast::<Conflict<Show>>()?;
+
+    assert_eq!(conflict.current, show.clone());
+
+    // The Show is left untouched
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::many(vec![
+            Statement::from_string(DatabaseBackend::Postgres, "BEGIN".to_string()),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"SELECT "shows"."id", "shows"."created_at", "shows"."updated_at", "shows"."title", "shows"."summary", "shows"."picture", "shows"."picture_thumbnail", "shows"."deleted_at" FROM "shows" WHERE "shows"."id" = $1 AND "shows"."deleted_at" IS NULL LIMIT $2"#,
+                vec![show.id.into(), 1u64.into()]
+            ),
+            Statement::from_string(DatabaseBackend::Postgres, "ROLLBACK".to_string()),
+        ])]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shows_service_delete() -> Result<()> {
+    let mut show: Show = Faker.fake();
+    show.title = "Test Show".to_string();
+
+    let deleted = Show {
+        deleted_at: Some(chrono::Utc::now().naive_utc()),
+        ..show.clone()
+    };
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![deleted.clone()]])
             .append_exec_results(vec![MockExecResult {
                 last_insert_id: 0,
                 rows_affected: 1,
@@ -296,7 +689,7 @@ async fn test_shows_service_delete() -> Result<()> {
             .into_connection(),
     );
 
-    let service = DefaultShowsService::new(&db);
+    let service = DefaultShowsService::new(&db, &DomainEventPublisher::default());
 
     service.delete(&show.id).await?;
 
@@ -305,20 +698,281 @@ async fn test_shows_service_delete() -> Result<()> {
 
     let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
 
+    let show_id = show.id.clone();
+    let payload = serde_json::to_value(&deleted)?;
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::many(vec![
+            Statement::from_string(DatabaseBackend::Postgres, "BEGIN".to_string()),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"UPDATE "shows" SET "deleted_at" = CURRENT_TIMESTAMP WHERE "shows"."id" = $1 AND "shows"."deleted_at" IS NULL RETURNING "id", "created_at", "updated_at", "title", "summary", "picture", "picture_thumbnail", "deleted_at""#,
+                vec![show.id.into()]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"INSERT INTO "outbox_events" ("show_id", "event_type", "payload") VALUES ($1, $2, $3)"#,
+                vec![show_id.into(), "show.deleted".into(), payload.into()]
+            ),
+            Statement::from_string(DatabaseBackend::Postgres, "COMMIT".to_string()),
+        ])]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shows_service_restore() -> Result<()> {
+    let mut show: Show = Faker.fake();
+    show.title = "Test Show".to_string();
+    show.deleted_at = Some(chrono::Utc::now().naive_utc());
+
+    let restored = Show {
+        deleted_at: None,
+        ..show.clone()
+    };
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![show.clone()], vec![restored.clone()]])
+            .append_exec_results(vec![MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            }])
+            .into_connection(),
+    );
+
+    let service = DefaultShowsService::new(&db, &DomainEventPublisher::default());
+
+    let result = service.restore(&show.id).await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, restored);
+
+    let show_id = show.id.clone();
+    let payload = serde_json::to_value(&restored)?;
+
     // Check the transaction log
     assert_eq!(
         db.into_transaction_log(),
         vec![
             Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "shows"."id", "shows"."created_at", "shows"."updated_at", "shows"."title", "shows"."summary", "shows"."picture" FROM "shows" WHERE "shows"."id" = $1 LIMIT $2"#,
+                r#"SELECT "shows"."id", "shows"."created_at", "shows"."updated_at", "shows"."title", "shows"."summary", "shows"."picture", "shows"."picture_thumbnail", "shows"."deleted_at" FROM "shows" WHERE "shows"."id" = $1 AND "shows"."deleted_at" IS NOT NULL LIMIT $2"#,
                 vec![show.id.clone().into(), 1u64.into()]
             ),
+            Transaction::many(vec![
+                Statement::from_string(DatabaseBackend::Postgres, "BEGIN".to_string()),
+                Statement::from_sql_and_values(
+                    DatabaseBackend::Postgres,
+                    r#"UPDATE "shows" SET "deleted_at" = $1 WHERE "shows"."id" = $2 RETURNING "id", "created_at", "updated_at", "title", "summary", "picture", "picture_thumbnail", "deleted_at""#,
+                    vec![Value::ChronoDateTime(None), show.id.into()]
+                ),
+                Statement::from_sql_and_values(
+                    DatabaseBackend::Postgres,
+                    r#"INSERT INTO "outbox_events" ("show_id", "event_type", "payload") VALUES ($1, $2, $3)"#,
+                    vec![show_id.into(), "show.updated".into(), payload.into()]
+                ),
+                Statement::from_string(DatabaseBackend::Postgres, "COMMIT".to_string()),
+            ])
+        ]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shows_service_create_with_taxonomy() -> Result<()> {
+    let mut show: Show = Faker.fake();
+    show.title = "Test Show".to_string();
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![show.clone()]])
+            .append_query_results(vec![vec![
+                maplit::btreemap! { "id" => Value::from("tag-1") },
+                maplit::btreemap! { "id" => Value::from("tag-2") },
+            ]])
+            .append_exec_results(vec![
+                MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 0,
+                },
+                MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 1,
+                },
+                MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 0,
+                },
+                MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 2,
+                },
+                MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 2,
+                },
+                MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 1,
+                },
+            ])
+            .into_connection(),
+    );
+
+    let service = DefaultShowsService::new(&db, &DomainEventPublisher::default());
+
+    let result = service
+        .create(&CreateShowInput {
+            title: show.title.clone(),
+            summary: show.summary.clone(),
+            picture: show.picture.clone(),
+            category_ids: Some(vec!["category-1".to_string()]),
+            tags: Some(vec![
+                " Comedy".to_string(),
+                "comedy".to_string(),
+                "Games ".to_string(),
+                "".to_string(),
+            ]),
+        })
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, show);
+
+    let show_id = show.id.clone();
+    let payload = serde_json::to_value(&show)?;
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::many(vec![
+            Statement::from_string(DatabaseBackend::Postgres, "BEGIN".to_string()),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"INSERT INTO "shows" ("title", "summary", "picture") VALUES ($1, $2, $3) RETURNING "id", "created_at", "updated_at", "title", "summary", "picture", "picture_thumbnail", "deleted_at""#,
+                vec![show.title.into(), show.summary.into(), show.picture.into()]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"DELETE FROM "shows_categories" WHERE "shows_categories"."show_id" = $1"#,
+                vec![show.id.clone().into()]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"INSERT INTO "shows_categories" ("show_id", "category_id") VALUES ($1, $2)"#,
+                vec![show.id.clone().into(), "category-1".into()]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"DELETE FROM "shows_tags" WHERE "shows_tags"."show_id" = $1"#,
+                vec![show.id.clone().into()]
+            ),
+            // Tags are normalized before they are stored
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"INSERT INTO "tags" ("name") VALUES ($1), ($2) ON CONFLICT ("name") DO NOTHING"#,
+                vec!["comedy".into(), "games".into()]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"SELECT "tags"."id" FROM "tags" WHERE "tags"."name" IN ($1, $2)"#,
+                vec!["comedy".into(), "games".into()]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"INSERT INTO "shows_tags" ("show_id", "tag_id") VALUES ($1, $2), ($3, $4)"#,
+                vec![
+                    show.id.clone().into(),
+                    "tag-1".into(),
+                    show.id.into(),
+                    "tag-2".into()
+                ]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"INSERT INTO "outbox_events" ("show_id", "event_type", "payload") VALUES ($1, $2, $3)"#,
+                vec![show_id.into(), "show.created".into(), payload.into()]
+            ),
+            Statement::from_string(DatabaseBackend::Postgres, "COMMIT".to_string()),
+        ])]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shows_service_get_many_taxonomy() -> Result<()> {
+    let show: Show = Faker.fake();
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![
+                vec![maplit::btreemap! { "id" => Value::from("category-2") }],
+                vec![],
+            ])
+            .append_query_results(vec![vec![show.clone()]])
+            .into_connection(),
+    );
+
+    let service = DefaultShowsService::new(&db, &DomainEventPublisher::default());
+
+    let result = service
+        .get_many(
+            Some(ShowCondition {
+                category_ids: Some(vec!["category-1".to_string()]),
+                tags: Some(vec!["Comedy".to_string()]),
+                ..Default::default()
+            }),
+            None,
+            None,
+            None,
+        )
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result.data, vec![show]);
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![
+            // Nested Categories are found one level at a time
             Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"DELETE FROM "shows" WHERE "shows"."id" = $1"#,
-                vec![show.id.into()]
-            )
+                r#"SELECT "categories"."id" FROM "categories" WHERE "categories"."parent_id" IN ($1) AND "categories"."id" NOT IN ($2)"#,
+                vec!["category-1".into(), "category-1".into()]
+            ),
+            Transaction::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"SELECT "categories"."id" FROM "categories" WHERE "categories"."parent_id" IN ($1) AND "categories"."id" NOT IN ($2, $3)"#,
+                vec![
+                    "category-2".into(),
+                    "category-1".into(),
+                    "category-2".into()
+                ]
+            ),
+            Transaction::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"SELECT "shows"."id", "shows"."created_at", "shows"."updated_at", "shows"."title", "shows"."summary", "shows"."picture", "shows"."picture_thumbnail", "shows"."deleted_at" FROM "shows" WHERE "shows"."deleted_at" IS NULL AND "shows"."id" IN (SELECT "shows_categories"."show_id" FROM "shows_categories" WHERE "shows_categories"."category_id" IN ($1, $2)) AND "shows"."id" IN (SELECT "shows_tags"."show_id" FROM "shows_tags" INNER JOIN "tags" ON "shows_tags"."tag_id" = "tags"."id" WHERE "tags"."name" IN ($3))"#,
+                vec!["category-1".into(), "category-2".into(), "comedy".into()]
+            ),
         ]
     );
 
@@ -0,0 +1,14 @@
+//! # Tags
+
+/// Service
+pub mod service;
+
+/// Model
+pub mod model;
+
+/// The join table between Shows and Tags
+pub mod show_tag;
+
+/// Tests
+#[cfg(test)]
+mod tests;
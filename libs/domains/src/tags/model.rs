@@ -0,0 +1,30 @@
+#![allow(missing_docs)]
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The Tag Database Model. Tags are exposed in GraphQL as plain strings on the `Shows` they
+/// label.
+#[derive(Clone, Debug, Eq, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "tags")]
+pub struct Model {
+    /// The Tag id
+    #[sea_orm(primary_key, column_type = "Text")]
+    pub id: String,
+
+    /// The date the Tag was first used
+    pub created_at: DateTime,
+
+    /// The normalized Tag name
+    #[sea_orm(column_type = "Text")]
+    pub name: String,
+}
+
+/// The Tag type is the same as the database Model
+pub type Tag = Model;
+
+/// Tag entity relationships
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,142 @@
+use anyhow::Result;
+use async_graphql::{dataloader::Loader, FieldError};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sea_orm::{
+    entity::*, query::*, sea_query::OnConflict, ConnectionTrait, DatabaseConnection, EntityTrait,
+};
+use std::{collections::HashMap, sync::Arc};
+
+use crate::tags::{model, show_tag};
+
+/// A TagsService applies business logic to a dynamic TagsRepository implementation.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait TagsService: Sync + Send {
+    /// Get the names of the `Tags` for each of the given `Show` ids, keyed by `Show` id
+    async fn get_by_show_ids(&self, show_ids: Vec<String>) -> Result<HashMap<String, Vec<String>>>;
+}
+
+/// The default `TagsService` struct.
+pub struct DefaultTagsService {
+    /// The SeaOrm database connection
+    db: Arc<DatabaseConnection>,
+}
+
+/// The default `TagsService` implementation
+impl DefaultTagsService {
+    /// Create a new `TagsService` instance
+    pub fn new(db: &Arc<DatabaseConnection>) -> Self {
+        Self { db: db.clone() }
+    }
+}
+
+#[async_trait]
+impl TagsService for DefaultTagsService {
+    async fn get_by_show_ids(&self, show_ids: Vec<String>) -> Result<HashMap<String, Vec<String>>> {
+        let rows = show_tag::Entity::find()
+            .find_also_related(model::Entity)
+            .filter(show_tag::Column::ShowId.is_in(show_ids))
+            .order_by_asc(model::Column::Name)
+            .all(&*self.db)
+            .await?;
+
+        let mut tags: HashMap<String, Vec<String>> = HashMap::new();
+
+        for (show_tag, tag) in rows {
+            if let Some(tag) = tag {
+                tags.entry(show_tag.show_id).or_default().push(tag.name);
+            }
+        }
+
+        Ok(tags)
+    }
+}
+
+/// Normalize free-form Tags by trimming and lowercasing them, dropping any that are blank or
+/// repeated
+pub fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = tags
+        .iter()
+        .map(|tag| tag.trim().to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+
+    normalized.sort();
+    normalized.dedup();
+
+    normalized
+}
+
+/// Replace the `Tags` that a `Show` is labeled with, creating any `Tags` that haven't been used
+/// before
+pub async fn set_show_tags<C: ConnectionTrait>(
+    db: &C,
+    show_id: &str,
+    tags: &[String],
+) -> Result<()> {
+    show_tag::Entity::delete_many()
+        .filter(show_tag::Column::ShowId.eq(show_id))
+        .exec(db)
+        .await?;
+
+    let names = normalize_tags(tags);
+
+    if names.is_empty() {
+        return Ok(());
+    }
+
+    model::Entity::insert_many(names.iter().map(|name| model::ActiveModel {
+        name: Set(name.clone()),
+        ..Default::default()
+    }))
+    .on_conflict(
+        OnConflict::column(model::Column::Name)
+            .do_nothing()
+            .to_owned(),
+    )
+    .exec_without_returning(db)
+    .await?;
+
+    let tag_ids: Vec<String> = model::Entity::find()
+        .select_only()
+        .column(model::Column::Id)
+        .filter(model::Column::Name.is_in(names))
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    show_tag::Entity::insert_many(tag_ids.into_iter().map(|tag_id| show_tag::ActiveModel {
+        show_id: Set(show_id.to_string()),
+        tag_id: Set(tag_id),
+    }))
+    .exec_without_returning(db)
+    .await?;
+
+    Ok(())
+}
+
+/// A dataloader for the names of the `Tags` that each `Show` is labeled with, keyed by `Show` id
+pub struct ShowTagsLoader {
+    /// The SeaOrm database connection
+    tags: Arc<dyn TagsService>,
+}
+
+/// The default implementation for the `ShowTagsLoader`
+impl ShowTagsLoader {
+    /// Create a new instance
+    pub fn new(tags: &Arc<dyn TagsService>) -> Self {
+        Self { tags: tags.clone() }
+    }
+}
+
+#[async_trait]
+impl Loader<String> for ShowTagsLoader {
+    type Value = Vec<String>;
+    type Error = FieldError;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        Ok(self.tags.get_by_show_ids(keys.into()).await?)
+    }
+}
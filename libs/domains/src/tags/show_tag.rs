@@ -0,0 +1,48 @@
+#![allow(missing_docs)]
+
+use sea_orm::entity::prelude::*;
+
+use crate::{shows::model as show_model, tags::model as tag_model};
+
+/// A Tag that a Show is labeled with
+#[derive(Clone, Debug, Eq, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "shows_tags")]
+pub struct Model {
+    /// The Show id
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Text")]
+    pub show_id: String,
+
+    /// The Tag id
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Text")]
+    pub tag_id: String,
+}
+
+/// Show Tag entity relationships
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "show_model::Entity",
+        from = "Column::ShowId",
+        to = "show_model::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Show,
+
+    #[sea_orm(
+        belongs_to = "tag_model::Entity",
+        from = "Column::TagId",
+        to = "tag_model::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Tag,
+}
+
+impl Related<tag_model::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Tag.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,82 @@
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use sea_orm::{DatabaseBackend, MockDatabase, Transaction, Value};
+use std::sync::Arc;
+
+use crate::tags::service::{normalize_tags, DefaultTagsService, TagsService};
+
+#[test]
+fn test_normalize_tags() {
+    let tags = vec![
+        " Comedy".to_string(),
+        "comedy".to_string(),
+        "True Crime ".to_string(),
+        "   ".to_string(),
+        "GAMES".to_string(),
+    ];
+
+    assert_eq!(
+        normalize_tags(&tags),
+        vec![
+            "comedy".to_string(),
+            "games".to_string(),
+            "true crime".to_string()
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_tags_service_get_by_show_ids() -> Result<()> {
+    let created_at = chrono::Utc::now().naive_utc();
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![
+                maplit::btreemap! {
+                    "A_show_id" => Value::from("test-show"),
+                    "A_tag_id" => Value::from("tag-1"),
+                    "B_id" => Value::from("tag-1"),
+                    "B_created_at" => Value::from(created_at),
+                    "B_name" => Value::from("comedy"),
+                },
+                maplit::btreemap! {
+                    "A_show_id" => Value::from("test-show"),
+                    "A_tag_id" => Value::from("tag-2"),
+                    "B_id" => Value::from("tag-2"),
+                    "B_created_at" => Value::from(created_at),
+                    "B_name" => Value::from("games"),
+                },
+            ]])
+            .into_connection(),
+    );
+
+    let service = DefaultTagsService::new(&db);
+
+    let result = service
+        .get_by_show_ids(vec!["test-show".to_string()])
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(
+        result,
+        maplit::hashmap! {
+            "test-show".to_string() => vec!["comedy".to_string(), "games".to_string()]
+        }
+    );
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"SELECT "shows_tags"."show_id" AS "A_show_id", "shows_tags"."tag_id" AS "A_tag_id", "tags"."id" AS "B_id", "tags"."created_at" AS "B_created_at", "tags"."name" AS "B_name" FROM "shows_tags" LEFT JOIN "tags" ON "shows_tags"."tag_id" = "tags"."id" WHERE "shows_tags"."show_id" IN ($1) ORDER BY "tags"."name" ASC"#,
+            vec!["test-show".into()]
+        )]
+    );
+
+    Ok(())
+}
@@ -8,6 +8,13 @@ use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::role_grants::model::{self as role_grant_model, RoleGrant};
+use caster_utils::errors::ConflictRecord;
+
+/// The role key granted to site admins
+pub const SITE_ADMIN_ROLE: &str = "admin";
+
+/// The resource id used for site-wide RoleGrants on the "users" table
+pub const ALL_USERS: &str = "*";
 
 /// The User GraphQL and Database Model
 #[derive(
@@ -69,6 +76,10 @@ impl Related<role_grant_model::Entity> for Entity {
 
 impl ActiveModelBehavior for ActiveModel {}
 
+impl ConflictRecord for Model {
+    const HIDDEN_FIELDS: &'static [&'static str] = &[];
+}
+
 impl Default for Model {
     fn default() -> Self {
         Self {
@@ -1,4 +1,5 @@
-use async_graphql::{InputObject, SimpleObject};
+use async_graphql::{InputObject, MaybeUndefined, SimpleObject};
+use chrono::NaiveDateTime;
 
 use super::model::User;
 
@@ -28,14 +29,46 @@ pub struct CreateUserInput {
     pub profile: Option<CreateUserProfileInput>,
 }
 
-/// The `UpdateUserInput` input type
+/// The `UpdateUserProfileInput` input type
 #[derive(Clone, Default, Eq, PartialEq, InputObject)]
+pub struct UpdateUserProfileInput {
+    /// The Profile's email address
+    pub email: Option<String>,
+
+    /// The Profile's display name
+    pub display_name: MaybeUndefined<String>,
+
+    /// The Profile's picture
+    pub picture: MaybeUndefined<String>,
+
+    /// The Profile's city
+    pub city: MaybeUndefined<String>,
+
+    /// The Profile's state or province
+    pub state_province: MaybeUndefined<String>,
+
+    /// Reject the update with a Conflict error if the Profile has been updated since this date
+    pub expected_updated_at: Option<NaiveDateTime>,
+}
+
+/// The `UpdateCurrentUserInput` input type, limited to the details a User may change themselves
+#[derive(Clone, Default, Eq, PartialEq, InputObject)]
+pub struct UpdateCurrentUserInput {
+    /// The User's profile
+    pub profile: Option<UpdateUserProfileInput>,
+}
+
+/// The `UpdateUserInput` type, used by site admins to change account details
+#[derive(Clone, Default, Eq, PartialEq)]
 pub struct UpdateUserInput {
     /// The User's subscriber id
     pub username: Option<String>,
 
     /// Whether the User is active or disabled
     pub is_active: Option<bool>,
+
+    /// Reject the update with a Conflict error if the User has been updated since this date
+    pub expected_updated_at: Option<NaiveDateTime>,
 }
 
 /// The `MutateUserResult` input type
@@ -1,15 +1,22 @@
-use async_graphql::{Context, Object, Result};
+use async_graphql::{Context, MaybeUndefined, Object, Result};
 use hyper::StatusCode;
 use std::sync::Arc;
 
 use super::{
     model::User,
-    mutations::{CreateUserInput, MutateUserResult, UpdateUserInput},
+    mutations::{CreateUserInput, MutateUserResult, UpdateCurrentUserInput, UpdateUserInput},
     service::UsersServiceTrait,
 };
-use crate::profiles::{mutations::CreateProfileInput, service::ProfilesService};
+use crate::{
+    audit_events::resolver::record_audit_event,
+    authorization::resolver::is_allowed,
+    profiles::{
+        model::Profile, mutations::UpdateProfileInput, queries::ProfileCondition,
+        service::ProfilesService,
+    },
+};
 use caster_auth::authenticate::Subject;
-use caster_utils::errors::{as_graphql_error, graphql_error};
+use caster_utils::errors::{as_graphql_error, as_update_error, graphql_error};
 
 /// The Query segment for Users
 #[derive(Default)]
@@ -41,7 +48,6 @@ impl UsersMutation {
     ) -> Result<MutateUserResult> {
         let user = ctx.data_unchecked::<Option<User>>();
         let users = ctx.data_unchecked::<Arc<dyn UsersServiceTrait>>();
-        let profiles = ctx.data_unchecked::<Arc<dyn ProfilesService>>();
         let subject = ctx.data_unchecked::<Subject>();
 
         // If the User exists in the GraphQL context, simply return it
@@ -57,58 +63,243 @@ impl UsersMutation {
             _ => Err(graphql_error("Unauthorized", StatusCode::UNAUTHORIZED)),
         }?;
 
-        let user = users.create(username).await.map_err(as_graphql_error(
-            "Eror while creating User",
-            StatusCode::INTERNAL_SERVER_ERROR,
-        ))?;
+        let (user, profile) = users
+            .create_with_profile(username, &input.profile)
+            .await
+            .map_err(as_graphql_error(
+                "Eror while creating User",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        record_audit_event(ctx, "create", "users", &user.id, None::<&User>, Some(&user)).await;
+
+        if let Some(profile) = profile {
+            record_audit_event(
+                ctx,
+                "create",
+                "profiles",
+                &profile.id,
+                None::<&Profile>,
+                Some(&profile),
+            )
+            .await;
+        }
+
+        Ok(MutateUserResult { user: Some(user) })
+    }
+
+    /// Update the current User's Profile based on the current token username (the "sub" claim)
+    async fn update_current_user(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateCurrentUserInput,
+    ) -> Result<MutateUserResult> {
+        let user = ctx.data_unchecked::<Option<User>>();
+        let profiles = ctx.data_unchecked::<Arc<dyn ProfilesService>>();
+
+        let user = user
+            .as_ref()
+            .ok_or_else(|| graphql_error("Unauthorized", StatusCode::UNAUTHORIZED))?;
 
         if let Some(profile) = input.profile {
-            profiles
-                .get_or_create(
-                    &user.id,
-                    &CreateProfileInput {
+            let existing = profiles
+                .get_by_user_id(&user.id, &false)
+                .await
+                .map_err(as_graphql_error(
+                    "Error while fetching Profile",
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                ))?
+                .ok_or_else(|| {
+                    graphql_error("Unable to find existing Profile", StatusCode::NOT_FOUND)
+                })?;
+
+            let updated = profiles
+                .update(
+                    &existing.id,
+                    &UpdateProfileInput {
                         email: profile.email,
                         display_name: profile.display_name,
                         picture: profile.picture,
+                        picture_thumbnail: MaybeUndefined::Undefined,
                         city: profile.city,
                         state_province: profile.state_province,
-                        user_id: user.id.clone(),
+                        user_id: None,
+                        expected_updated_at: profile.expected_updated_at,
                     },
                     &false,
                 )
-                .await?;
+                .await
+                .map_err(as_update_error::<Profile>(
+                    "Error while updating Profile",
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                ))?;
+
+            record_audit_event(
+                ctx,
+                "update",
+                "profiles",
+                &existing.id,
+                Some(&existing),
+                Some(&updated),
+            )
+            .await;
         }
 
-        Ok(MutateUserResult { user: Some(user) })
+        Ok(MutateUserResult {
+            user: Some(user.clone()),
+        })
     }
 
-    /// Update the current User based on the current token username (the "sub" claim)
-    async fn update_current_user(
+    /// Activate or deactivate a User account, as a site admin
+    async fn set_user_active(
         &self,
         ctx: &Context<'_>,
-        input: UpdateUserInput,
+        id: String,
+        is_active: bool,
     ) -> Result<MutateUserResult> {
         let user = ctx.data_unchecked::<Option<User>>();
-        let users = ctx.data_unchecked::<Arc<dyn UsersServiceTrait>>();
 
-        // Check to see if the associated Profile is selected
-        let with_roles = ctx.look_ahead().field("user").field("roles").exists();
+        // Keep site admins from locking themselves out
+        if !is_active && user.as_ref().map(|u| &u.id) == Some(&id) {
+            return Err(graphql_error(
+                "Unable to deactivate the current User",
+                StatusCode::BAD_REQUEST,
+            ));
+        }
 
-        if let Some(user) = user {
-            let updated =
-                users
-                    .update(&user.id, &input, &with_roles)
-                    .await
-                    .map_err(as_graphql_error(
-                        "Error while updating User",
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                    ))?;
+        manage_account(
+            ctx,
+            &id,
+            &UpdateUserInput {
+                is_active: Some(is_active),
+                ..Default::default()
+            },
+        )
+        .await
+    }
 
-            return Ok(MutateUserResult {
-                user: Some(updated),
-            });
-        }
+    /// Change the subscriber id for a User account, as a site admin
+    async fn rename_user(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        username: String,
+    ) -> Result<MutateUserResult> {
+        manage_account(
+            ctx,
+            &id,
+            &UpdateUserInput {
+                username: Some(username),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+}
+
+/// Apply an account change to the given User, if the current User is a site admin
+async fn manage_account(
+    ctx: &Context<'_>,
+    id: &str,
+    input: &UpdateUserInput,
+) -> Result<MutateUserResult> {
+    let user = ctx.data_unchecked::<Option<User>>();
+    let users = ctx.data_unchecked::<Arc<dyn UsersServiceTrait>>();
 
-        Err(graphql_error("Unauthorized", StatusCode::UNAUTHORIZED))
+    // Retrieve the existing User for authorization
+    let existing = users
+        .get(id)
+        .await
+        .map_err(as_graphql_error(
+            "Error while fetching User",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ))?
+        .ok_or_else(|| graphql_error("Unable to find existing User", StatusCode::NOT_FOUND))?;
+
+    // Check authentication and authorization
+    if let Some(user) = user {
+        if !is_allowed(ctx, user, "manage_accounts", existing.clone())? {
+            return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
+        }
+    } else {
+        return Err(graphql_error("Unauthorized", StatusCode::UNAUTHORIZED));
     }
+
+    // Check to see if the associated RoleGrants are selected
+    let with_roles = ctx.look_ahead().field("user").field("roles").exists();
+
+    let updated = users
+        .update(id, input, &with_roles)
+        .await
+        .map_err(as_update_error::<User>(
+            "Error while updating User",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ))?;
+
+    record_audit_event(ctx, "update", "users", id, Some(&existing), Some(&updated)).await;
+
+    Ok(MutateUserResult {
+        user: Some(updated),
+    })
+}
+
+/// Find an existing User by username, or by the email address on their Profile. Exactly one of
+/// the two must be given.
+pub async fn find_user(
+    ctx: &Context<'_>,
+    username: &Option<String>,
+    email: &Option<String>,
+) -> Result<User> {
+    let users = ctx.data_unchecked::<Arc<dyn UsersServiceTrait>>();
+    let profiles = ctx.data_unchecked::<Arc<dyn ProfilesService>>();
+
+    let fetch_error = || {
+        as_graphql_error(
+            "Error while fetching User",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )
+    };
+
+    let user = match (username, email) {
+        (Some(username), None) => users
+            .get_by_username(username, &false)
+            .await
+            .map_err(fetch_error())?,
+        (None, Some(email)) => {
+            let profiles = profiles
+                .get_many(
+                    Some(ProfileCondition {
+                        email: Some(email.clone()),
+                        display_name: None,
+                        city: None,
+                        state_province: None,
+                        user_id: None,
+                        ids_in: None,
+                    }),
+                    None,
+                    None,
+                    None,
+                    &false,
+                )
+                .await
+                .map_err(fetch_error())?;
+
+            match profiles
+                .data
+                .into_iter()
+                .find_map(|profile| profile.user_id)
+            {
+                Some(user_id) => users.get(&user_id).await.map_err(fetch_error())?,
+                None => None,
+            }
+        }
+        _ => {
+            return Err(graphql_error(
+                "Provide either a username or an email",
+                StatusCode::BAD_REQUEST,
+            ))
+        }
+    };
+
+    user.ok_or_else(|| graphql_error("Unable to find User", StatusCode::NOT_FOUND))
 }
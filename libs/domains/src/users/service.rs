@@ -3,14 +3,18 @@ use async_graphql::{dataloader::Loader, FieldError};
 use async_trait::async_trait;
 #[cfg(test)]
 use mockall::automock;
-use sea_orm::{entity::*, query::*, DatabaseConnection, EntityTrait};
+use sea_orm::{entity::*, query::*, DatabaseConnection, EntityTrait, TransactionTrait};
 use std::{collections::HashMap, sync::Arc};
 
 use super::{
     model::{self, User, UserOption},
-    mutations::UpdateUserInput,
+    mutations::{CreateUserProfileInput, UpdateUserInput},
 };
-use crate::role_grants::model as role_grant_model;
+use crate::{
+    profiles::{model::Profile, mutations::CreateProfileInput, service::insert_profile},
+    role_grants::model as role_grant_model,
+};
+use caster_utils::{errors::Conflict, updates::update_if_unchanged};
 
 /// A UsersService appliies business logic to a dynamic UsersRepository implementation.
 #[cfg_attr(test, automock)]
@@ -28,6 +32,14 @@ pub trait UsersServiceTrait: Sync + Send {
     /// Create a `User` with the given username
     async fn create(&self, username: &str) -> Result<User>;
 
+    /// Create a `User` with the given username, along with an optional `Profile`. Either both
+    /// are created or neither is.
+    async fn create_with_profile(
+        &self,
+        username: &str,
+        profile: &Option<CreateUserProfileInput>,
+    ) -> Result<(User, Option<Profile>)>;
+
     /// Get the `User` with the given username, creating one if none are found
     async fn get_or_create(&self, username: &str) -> Result<User>;
 
@@ -106,6 +118,44 @@ impl UsersServiceTrait for UsersService {
         Ok(user)
     }
 
+    async fn create_with_profile(
+        &self,
+        username: &str,
+        profile: &Option<CreateUserProfileInput>,
+    ) -> Result<(User, Option<Profile>)> {
+        let txn = self.db.begin().await?;
+
+        let user = model::ActiveModel {
+            username: Set(username.to_owned()),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        let profile = if let Some(profile) = profile {
+            Some(
+                insert_profile(
+                    &txn,
+                    &CreateProfileInput {
+                        email: profile.email.clone(),
+                        display_name: profile.display_name.clone(),
+                        picture: profile.picture.clone(),
+                        city: profile.city.clone(),
+                        state_province: profile.state_province.clone(),
+                        user_id: user.id.clone(),
+                    },
+                )
+                .await?,
+            )
+        } else {
+            None
+        };
+
+        txn.commit().await?;
+
+        Ok((user, profile))
+    }
+
     async fn get_or_create(&self, username: &str) -> Result<User> {
         match self.get_by_username(username, &false).await? {
             Some(user) => Ok(user),
@@ -130,6 +180,16 @@ impl UsersServiceTrait for UsersService {
         }
         .ok_or_else(|| anyhow!("Unable to find User with id: {}", id))?;
 
+        // Reject the update if the User has changed since the client last retrieved it
+        if let Some(expected) = input.expected_updated_at {
+            if user.updated_at != expected {
+                return Err(Conflict {
+                    current: User { roles, ..user },
+                }
+                .into());
+            }
+        }
+
         let mut user: model::ActiveModel = user.into();
 
         if let Some(username) = &input.username {
@@ -140,7 +200,20 @@ impl UsersServiceTrait for UsersService {
             user.is_active = Set(is_active.to_owned());
         }
 
-        let mut updated = user.update(&*self.db).await?;
+        // Make the write conditional as well, in case of a concurrent update since the read
+        let mut updated = update_if_unchanged(
+            &*self.db,
+            user,
+            model::Column::Id.eq(id.to_owned()),
+            model::Column::UpdatedAt,
+            input.expected_updated_at,
+            || async {
+                self.get(id)
+                    .await?
+                    .ok_or_else(|| anyhow!("Unable to find User with id: {}", id))
+            },
+        )
+        .await?;
 
         // Add back the RoleGrants from above
         updated.roles = roles;
@@ -1,12 +1,13 @@
 use anyhow::Result;
 use fake::{Fake, Faker};
 use pretty_assertions::assert_eq;
-use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Transaction};
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Statement, Transaction};
 use std::sync::Arc;
 
+use crate::profiles::model::Model as ProfileModel;
 use crate::users::{
     model::User,
-    mutations::UpdateUserInput,
+    mutations::{CreateUserProfileInput, UpdateUserInput},
     service::{UsersService, UsersServiceTrait},
 };
 
@@ -120,6 +121,75 @@ async fn test_users_service_create() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_users_service_create_with_profile() -> Result<()> {
+    let mut user: User = Faker.fake();
+    user.roles = vec![];
+    user.username = "test-username".to_string();
+
+    let mut profile: ProfileModel = Faker.fake();
+    profile.user_id = Some(user.id.clone());
+    profile.deleted_at = None;
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![user.clone()]])
+            .append_query_results(vec![vec![profile.clone()]])
+            .into_connection(),
+    );
+
+    let service = UsersService::new(&db);
+
+    let (result, result_profile) = service
+        .create_with_profile(
+            &user.username,
+            &Some(CreateUserProfileInput {
+                email: profile.email.clone(),
+                display_name: profile.display_name.clone(),
+                picture: profile.picture.clone(),
+                city: profile.city.clone(),
+                state_province: profile.state_province.clone(),
+            }),
+        )
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, user);
+    assert_eq!(result_profile, Some(profile.clone().into()));
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::many(vec![
+            Statement::from_string(DatabaseBackend::Postgres, "BEGIN".to_string()),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"INSERT INTO "users" ("username") VALUES ($1) RETURNING "id", "created_at", "updated_at", "username", "is_active""#,
+                vec![user.username.into()]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"INSERT INTO "profiles" ("email", "display_name", "picture", "city", "state_province", "user_id") VALUES ($1, $2, $3, $4, $5, $6) RETURNING "id", "created_at", "updated_at", "email", "display_name", "picture", "picture_thumbnail", "city", "state_province", "user_id", "deleted_at""#,
+                vec![
+                    profile.email.into(),
+                    profile.display_name.into(),
+                    profile.picture.into(),
+                    profile.city.into(),
+                    profile.state_province.into(),
+                    user.id.into(),
+                ]
+            ),
+            Statement::from_string(DatabaseBackend::Postgres, "COMMIT".to_string()),
+        ])]
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_users_service_update() -> Result<()> {
     let mut user: User = Faker.fake();
@@ -145,6 +215,7 @@ async fn test_users_service_update() -> Result<()> {
             &UpdateUserInput {
                 username: Some(updated.username.clone()),
                 is_active: None,
+                expected_updated_at: None,
             },
             &false,
         )
@@ -0,0 +1,26 @@
+//! # Webhooks
+
+/// Service
+pub mod service;
+
+/// Model
+pub mod model;
+
+/// Webhook delivery log
+pub mod delivery;
+
+/// Transactional outbox of Show and Episode changes
+pub mod outbox;
+
+/// GraphQL Queries
+pub mod queries;
+
+/// GraphQL Mutations
+pub mod mutations;
+
+/// GraphQL Resolver
+pub mod resolver;
+
+/// Tests
+#[cfg(test)]
+mod tests;
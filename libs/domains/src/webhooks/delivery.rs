@@ -0,0 +1,172 @@
+#![allow(missing_docs)]
+
+use async_graphql::{Enum, SimpleObject};
+use chrono::Utc;
+use fake::Dummy;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{model as webhook_model, model::WebhookEventType, outbox};
+
+/// The progress of a webhook delivery
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Dummy,
+    Eq,
+    PartialEq,
+    EnumIter,
+    DeriveActiveEnum,
+    Deserialize,
+    Serialize,
+    Enum,
+)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+pub enum DeliveryStatus {
+    /// Waiting for the next attempt
+    #[default]
+    #[sea_orm(string_value = "pending")]
+    Pending,
+
+    /// The receiver accepted the event
+    #[sea_orm(string_value = "succeeded")]
+    Succeeded,
+
+    /// Every attempt failed, so no more will be made
+    #[sea_orm(string_value = "failed")]
+    Failed,
+}
+
+/// The `WebhookDelivery` GraphQL and Database Model
+#[derive(
+    Clone, Debug, Dummy, Eq, PartialEq, DeriveEntityModel, Deserialize, Serialize, SimpleObject,
+)]
+#[graphql(name = "WebhookDelivery")]
+#[sea_orm(table_name = "webhook_deliveries")]
+pub struct Model {
+    /// The WebhookDelivery id
+    #[sea_orm(primary_key, column_type = "Text")]
+    pub id: String,
+
+    /// The date the delivery was queued
+    pub created_at: DateTime,
+
+    /// The date the delivery was last updated
+    pub updated_at: DateTime,
+
+    /// The id of the Webhook being delivered to
+    #[sea_orm(column_type = "Text")]
+    pub webhook_id: String,
+
+    /// The id of the event being delivered
+    #[sea_orm(column_type = "Text")]
+    pub event_id: String,
+
+    /// The kind of event being delivered
+    pub event_type: WebhookEventType,
+
+    /// The progress of the delivery
+    pub status: DeliveryStatus,
+
+    /// The number of attempts made so far
+    pub attempts: i32,
+
+    /// The date of the next attempt, while the delivery is pending
+    pub next_attempt_at: Option<DateTime>,
+
+    /// The HTTP status returned by the receiver on the last attempt
+    pub response_status: Option<i32>,
+
+    /// Why the last attempt failed
+    #[sea_orm(column_type = "Text", nullable)]
+    pub last_error: Option<String>,
+
+    /// The date the receiver accepted the event
+    pub delivered_at: Option<DateTime>,
+}
+
+/// The `WebhookDelivery` GraphQL type is the same as the database Model
+pub type WebhookDelivery = Model;
+
+/// `WebhookDelivery` entity relationships
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "webhook_model::Entity",
+        from = "Column::WebhookId",
+        to = "webhook_model::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Webhook,
+
+    #[sea_orm(
+        belongs_to = "outbox::Entity",
+        from = "Column::EventId",
+        to = "outbox::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Event,
+}
+
+impl Related<webhook_model::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Webhook.def()
+    }
+}
+
+impl Related<outbox::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Event.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Default for Model {
+    fn default() -> Self {
+        Self {
+            id: String::default(),
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+            webhook_id: String::default(),
+            event_id: String::default(),
+            event_type: WebhookEventType::default(),
+            status: DeliveryStatus::default(),
+            attempts: 0,
+            next_attempt_at: Option::default(),
+            response_status: Option::default(),
+            last_error: Option::default(),
+            delivered_at: Option::default(),
+        }
+    }
+}
+
+/// The outcome of an attempt to deliver an event to a Webhook
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DeliveryAttempt {
+    /// The HTTP status returned by the receiver, if it responded
+    pub response_status: Option<i32>,
+
+    /// Why the attempt failed, if it did
+    pub error: Option<String>,
+
+    /// When to try again if the attempt failed, or `None` to give up
+    pub retry_at: Option<DateTime>,
+}
+
+/// A delivery that is due, along with what is needed to make it
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingDelivery {
+    /// The delivery being attempted
+    pub delivery: WebhookDelivery,
+
+    /// The Webhook being delivered to
+    pub webhook: webhook_model::Webhook,
+
+    /// The event being delivered
+    pub event: outbox::OutboxEvent,
+}
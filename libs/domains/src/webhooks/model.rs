@@ -0,0 +1,146 @@
+#![allow(missing_docs)]
+
+use async_graphql::{Enum, SimpleObject};
+use chrono::Utc;
+use fake::Dummy;
+use sea_orm::{entity::prelude::*, FromJsonQueryResult};
+use serde::{Deserialize, Serialize};
+
+use crate::shows::model as show_model;
+
+/// The kinds of changes that webhooks can be notified about
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Dummy,
+    Eq,
+    PartialEq,
+    EnumIter,
+    DeriveActiveEnum,
+    Deserialize,
+    Serialize,
+    Enum,
+)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+pub enum WebhookEventType {
+    /// A Show was created
+    #[default]
+    #[sea_orm(string_value = "show.created")]
+    #[serde(rename = "show.created")]
+    ShowCreated,
+
+    /// A Show was updated or restored
+    #[sea_orm(string_value = "show.updated")]
+    #[serde(rename = "show.updated")]
+    ShowUpdated,
+
+    /// A Show was deleted
+    #[sea_orm(string_value = "show.deleted")]
+    #[serde(rename = "show.deleted")]
+    ShowDeleted,
+
+    /// An Episode was created
+    #[sea_orm(string_value = "episode.created")]
+    #[serde(rename = "episode.created")]
+    EpisodeCreated,
+
+    /// An Episode was updated, published, or restored
+    #[sea_orm(string_value = "episode.updated")]
+    #[serde(rename = "episode.updated")]
+    EpisodeUpdated,
+
+    /// An Episode was deleted
+    #[sea_orm(string_value = "episode.deleted")]
+    #[serde(rename = "episode.deleted")]
+    EpisodeDeleted,
+}
+
+/// The list of event types that a webhook subscribes to, stored as JSON
+#[derive(
+    Clone, Debug, Default, Dummy, Eq, PartialEq, Deserialize, Serialize, FromJsonQueryResult,
+)]
+pub struct WebhookEventTypes(pub Vec<WebhookEventType>);
+
+/// The `Webhook` GraphQL and Database Model
+#[derive(
+    Clone, Debug, Dummy, Eq, PartialEq, DeriveEntityModel, Deserialize, Serialize, SimpleObject,
+)]
+#[graphql(name = "Webhook", complex)]
+#[sea_orm(table_name = "webhooks")]
+pub struct Model {
+    /// The Webhook id
+    #[sea_orm(primary_key, column_type = "Text")]
+    pub id: String,
+
+    /// The date the Webhook was created
+    pub created_at: DateTime,
+
+    /// The date the Webhook was last updated
+    pub updated_at: DateTime,
+
+    /// The id of the Show that the Webhook is notified about
+    #[sea_orm(column_type = "Text")]
+    pub show_id: String,
+
+    /// The url that events are posted to
+    #[sea_orm(column_type = "Text")]
+    pub url: String,
+
+    /// The key used to sign each delivery, which is never revealed once set
+    #[sea_orm(column_type = "Text")]
+    #[graphql(skip)]
+    #[serde(skip_serializing)]
+    pub secret: String,
+
+    /// The kinds of events that the Webhook is notified about
+    #[sea_orm(column_type = "JsonBinary")]
+    #[graphql(skip)]
+    pub event_types: WebhookEventTypes,
+}
+
+/// The `Webhook` GraphQL type is the same as the database Model
+pub type Webhook = Model;
+
+/// `Webhook` entity relationships
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "show_model::Entity",
+        from = "Column::ShowId",
+        to = "show_model::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Show,
+}
+
+impl Related<show_model::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Show.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Default for Model {
+    fn default() -> Self {
+        Self {
+            id: String::default(),
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+            show_id: String::default(),
+            url: String::default(),
+            secret: String::default(),
+            event_types: WebhookEventTypes::default(),
+        }
+    }
+}
+
+impl Model {
+    /// Whether the Webhook should be notified about the given kind of event
+    pub fn subscribes_to(&self, event_type: WebhookEventType) -> bool {
+        self.event_types.0.contains(&event_type)
+    }
+}
@@ -0,0 +1,40 @@
+use async_graphql::{InputObject, SimpleObject};
+use fake::Dummy;
+
+use super::model::{Webhook, WebhookEventType};
+
+/// The `CreateWebhookInput` input type
+#[derive(Clone, Default, Dummy, Eq, PartialEq, InputObject)]
+pub struct CreateWebhookInput {
+    /// The id of the Show to be notified about
+    pub show_id: String,
+
+    /// The http or https url to post events to
+    pub url: String,
+
+    /// The key used to sign each delivery, so that the receiver can verify it
+    pub secret: String,
+
+    /// The kinds of events to be notified about
+    pub event_types: Vec<WebhookEventType>,
+}
+
+/// The `UpdateWebhookInput` input type
+#[derive(Clone, Default, Dummy, Eq, PartialEq, InputObject)]
+pub struct UpdateWebhookInput {
+    /// The http or https url to post events to
+    pub url: Option<String>,
+
+    /// The key used to sign each delivery
+    pub secret: Option<String>,
+
+    /// The kinds of events to be notified about
+    pub event_types: Option<Vec<WebhookEventType>>,
+}
+
+/// The `MutateWebhookResult` type
+#[derive(Clone, Default, Dummy, Eq, PartialEq, SimpleObject)]
+pub struct MutateWebhookResult {
+    /// The Webhook
+    pub webhook: Option<Webhook>,
+}
@@ -0,0 +1,94 @@
+#![allow(missing_docs)]
+
+use anyhow::Result;
+use chrono::Utc;
+use fake::Dummy;
+use sea_orm::{entity::prelude::*, ConnectionTrait, Set};
+use serde::{Deserialize, Serialize};
+
+use super::model::WebhookEventType;
+use crate::shows::model as show_model;
+
+/// A change to a Show or one of its Episodes, waiting to be dispatched to webhooks
+#[derive(Clone, Debug, Dummy, Eq, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "outbox_events")]
+pub struct Model {
+    /// The OutboxEvent id
+    #[sea_orm(primary_key, column_type = "Text")]
+    pub id: String,
+
+    /// The date the change was made
+    pub created_at: DateTime,
+
+    /// The id of the Show that changed, or whose Episode changed
+    #[sea_orm(column_type = "Text")]
+    pub show_id: String,
+
+    /// The kind of change that was made
+    pub event_type: WebhookEventType,
+
+    /// A snapshot of the changed Show or Episode
+    #[sea_orm(column_type = "JsonBinary")]
+    #[dummy(default)]
+    pub payload: Json,
+
+    /// The date that deliveries were queued for each subscribed webhook
+    pub dispatched_at: Option<DateTime>,
+}
+
+/// The OutboxEvent type is the same as the database Model
+pub type OutboxEvent = Model;
+
+/// OutboxEvent entity relationships
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "show_model::Entity",
+        from = "Column::ShowId",
+        to = "show_model::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Show,
+}
+
+impl Related<show_model::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Show.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Default for Model {
+    fn default() -> Self {
+        Self {
+            id: String::default(),
+            created_at: Utc::now().naive_utc(),
+            show_id: String::default(),
+            event_type: WebhookEventType::default(),
+            payload: Json::Null,
+            dispatched_at: Option::default(),
+        }
+    }
+}
+
+/// Record a change to a Show or Episode in the outbox. Pass the transaction that makes the change,
+/// so that the event is only recorded if the change is.
+pub async fn record_event<C: ConnectionTrait, T: Serialize + Sync>(
+    db: &C,
+    show_id: &str,
+    event_type: WebhookEventType,
+    data: &T,
+) -> Result<()> {
+    Entity::insert(ActiveModel {
+        show_id: Set(show_id.to_string()),
+        event_type: Set(event_type),
+        payload: Set(serde_json::to_value(data)?),
+        ..Default::default()
+    })
+    .exec_without_returning(db)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use async_graphql::SimpleObject;
+
+use crate::webhooks::delivery::WebhookDelivery;
+use caster_utils::pagination::ManyResponse;
+
+/// The `WebhookDeliveriesPage` result type
+#[derive(Clone, Eq, PartialEq, SimpleObject)]
+pub struct WebhookDeliveriesPage {
+    /// The list of `WebhookDeliveries` returned for the current page
+    data: Vec<WebhookDelivery>,
+
+    /// The number of `WebhookDeliveries` returned for the current page
+    count: u64,
+
+    /// Tne total number of `WebhookDeliveries` available
+    total: u64,
+
+    /// The current page
+    page: u64,
+
+    /// The number of pages available
+    page_count: u64,
+}
+
+impl From<ManyResponse<WebhookDelivery>> for WebhookDeliveriesPage {
+    fn from(resp: ManyResponse<WebhookDelivery>) -> WebhookDeliveriesPage {
+        WebhookDeliveriesPage {
+            data: resp.data,
+            count: resp.count,
+            total: resp.total,
+            page: resp.page,
+            page_count: resp.page_count,
+        }
+    }
+}
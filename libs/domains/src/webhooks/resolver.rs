@@ -0,0 +1,274 @@
+use async_graphql::{ComplexObject, Context, Object, Result};
+use hyper::{StatusCode, Uri};
+use std::sync::Arc;
+
+use crate::{
+    audit_events::resolver::record_audit_event,
+    authorization::resolver::is_allowed,
+    shows::service::ShowsService,
+    users::model::User,
+    webhooks::{
+        model::{Webhook, WebhookEventType},
+        mutations::{CreateWebhookInput, MutateWebhookResult, UpdateWebhookInput},
+        queries::WebhookDeliveriesPage,
+        service::WebhooksService,
+    },
+};
+use caster_utils::errors::{as_graphql_error, graphql_error};
+
+/// The Query segment owned by the Webhooks library
+#[derive(Default)]
+pub struct WebhooksQuery {}
+
+/// The Mutation segment for Webhooks
+#[derive(Default)]
+pub struct WebhooksMutation {}
+
+/// Queries for the `Webhook` model
+#[Object]
+impl WebhooksQuery {
+    /// Get the Webhooks notified about changes to a Show
+    async fn get_show_webhooks(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "The Show id")] show_id: String,
+    ) -> Result<Vec<Webhook>> {
+        let webhooks = ctx.data_unchecked::<Arc<dyn WebhooksService>>();
+
+        check_show(ctx, &show_id).await?;
+
+        webhooks
+            .get_by_show_id(&show_id)
+            .await
+            .map_err(as_graphql_error(
+                "Error while listing Webhooks",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))
+    }
+
+    /// Get the log of deliveries made to a Webhook, most recent first
+    async fn get_webhook_deliveries(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "The Webhook id")] webhook_id: String,
+        page: Option<u64>,
+        page_size: Option<u64>,
+    ) -> Result<WebhookDeliveriesPage> {
+        let webhooks = ctx.data_unchecked::<Arc<dyn WebhooksService>>();
+
+        let existing = get_existing(ctx, &webhook_id).await?;
+
+        check_show(ctx, &existing.show_id).await?;
+
+        let response = webhooks
+            .get_deliveries(&webhook_id, page, page_size)
+            .await
+            .map_err(as_graphql_error(
+                "Error while listing Webhook deliveries",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        Ok(response.into())
+    }
+}
+
+/// Mutations for the Webhook model
+#[Object]
+impl WebhooksMutation {
+    /// Create a new Webhook for a Show
+    async fn create_webhook(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateWebhookInput,
+    ) -> Result<MutateWebhookResult> {
+        let webhooks = ctx.data_unchecked::<Arc<dyn WebhooksService>>();
+
+        check_show(ctx, &input.show_id).await?;
+        check_url(&input.url)?;
+        check_secret(&input.secret)?;
+        check_event_types(&input.event_types)?;
+
+        let webhook = webhooks.create(&input).await.map_err(as_graphql_error(
+            "Error while creating Webhook",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ))?;
+
+        record_audit_event(
+            ctx,
+            "create",
+            "webhooks",
+            &webhook.id,
+            None::<&Webhook>,
+            Some(&webhook),
+        )
+        .await;
+
+        Ok(MutateWebhookResult {
+            webhook: Some(webhook),
+        })
+    }
+
+    /// Update an existing Webhook
+    async fn update_webhook(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        input: UpdateWebhookInput,
+    ) -> Result<MutateWebhookResult> {
+        let webhooks = ctx.data_unchecked::<Arc<dyn WebhooksService>>();
+
+        let existing = get_existing(ctx, &id).await?;
+
+        check_show(ctx, &existing.show_id).await?;
+
+        if let Some(url) = &input.url {
+            check_url(url)?;
+        }
+
+        if let Some(secret) = &input.secret {
+            check_secret(secret)?;
+        }
+
+        if let Some(event_types) = &input.event_types {
+            check_event_types(event_types)?;
+        }
+
+        let webhook = webhooks
+            .update(&id, &input)
+            .await
+            .map_err(as_graphql_error(
+                "Error while updating Webhook",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))?;
+
+        record_audit_event(
+            ctx,
+            "update",
+            "webhooks",
+            &id,
+            Some(&existing),
+            Some(&webhook),
+        )
+        .await;
+
+        Ok(MutateWebhookResult {
+            webhook: Some(webhook),
+        })
+    }
+
+    /// Remove an existing Webhook, along with its delivery log
+    async fn delete_webhook(&self, ctx: &Context<'_>, id: String) -> Result<bool> {
+        let webhooks = ctx.data_unchecked::<Arc<dyn WebhooksService>>();
+
+        let existing = get_existing(ctx, &id).await?;
+
+        check_show(ctx, &existing.show_id).await?;
+
+        webhooks.delete(&id).await.map_err(as_graphql_error(
+            "Error while deleting Webhook",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ))?;
+
+        record_audit_event(
+            ctx,
+            "delete",
+            "webhooks",
+            &id,
+            Some(&existing),
+            None::<&Webhook>,
+        )
+        .await;
+
+        Ok(true)
+    }
+}
+
+/// Retrieve an existing Webhook, or fail with a not found error
+async fn get_existing(ctx: &Context<'_>, id: &str) -> Result<Webhook> {
+    let webhooks = ctx.data_unchecked::<Arc<dyn WebhooksService>>();
+
+    webhooks
+        .get(id)
+        .await
+        .map_err(as_graphql_error(
+            "Error while fetching Webhook",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ))?
+        .ok_or_else(|| graphql_error("Unable to find existing Webhook", StatusCode::NOT_FOUND))
+}
+
+/// Require a User who is allowed to manage the webhooks for the given Show
+async fn check_show(ctx: &Context<'_>, show_id: &str) -> Result<()> {
+    let shows = ctx.data_unchecked::<Arc<dyn ShowsService>>();
+    let user = ctx.data_unchecked::<Option<User>>();
+
+    // Check authentication
+    let user = user
+        .as_ref()
+        .ok_or_else(|| graphql_error("Unauthorized", StatusCode::UNAUTHORIZED))?;
+
+    let show = shows
+        .get(show_id)
+        .await
+        .map_err(as_graphql_error(
+            "Error while fetching Show",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ))?
+        .ok_or_else(|| graphql_error("Unable to find existing Show", StatusCode::NOT_FOUND))?;
+
+    // Check authorization
+    if !is_allowed(ctx, user, "manage_webhooks", show)? {
+        return Err(graphql_error("Forbidden", StatusCode::FORBIDDEN));
+    }
+
+    Ok(())
+}
+
+/// Require an absolute http or https url
+fn check_url(url: &str) -> Result<()> {
+    let valid = url.parse::<Uri>().is_ok_and(|uri| {
+        matches!(uri.scheme_str(), Some("http" | "https")) && uri.host().is_some()
+    });
+
+    if !valid {
+        return Err(graphql_error(
+            "A Webhook url must be an absolute http or https url",
+            StatusCode::BAD_REQUEST,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Require a secret to sign deliveries with
+fn check_secret(secret: &str) -> Result<()> {
+    if secret.trim().is_empty() {
+        return Err(graphql_error(
+            "A Webhook secret is required",
+            StatusCode::BAD_REQUEST,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Require at least one kind of event to be notified about
+fn check_event_types(event_types: &[WebhookEventType]) -> Result<()> {
+    if event_types.is_empty() {
+        return Err(graphql_error(
+            "A Webhook must subscribe to at least one event type",
+            StatusCode::BAD_REQUEST,
+        ));
+    }
+
+    Ok(())
+}
+
+#[ComplexObject]
+impl Webhook {
+    /// The kinds of events that the Webhook is notified about
+    #[graphql(name = "eventTypes")]
+    async fn resolve_event_types(&self) -> Vec<WebhookEventType> {
+        self.event_types.0.clone()
+    }
+}
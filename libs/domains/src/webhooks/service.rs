@@ -0,0 +1,392 @@
+use anyhow::Result;
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sea_orm::{
+    entity::*,
+    prelude::DateTime,
+    query::*,
+    sea_query::{Expr, LockBehavior, LockType, OnConflict},
+    DatabaseConnection, EntityTrait,
+};
+use std::{collections::HashMap, sync::Arc};
+
+use super::{
+    delivery::{self, DeliveryAttempt, DeliveryStatus, PendingDelivery, WebhookDelivery},
+    model::{self, Webhook, WebhookEventTypes},
+    mutations::{CreateWebhookInput, UpdateWebhookInput},
+    outbox,
+};
+use caster_utils::pagination::ManyResponse;
+
+/// A WebhooksService manages webhook subscriptions and tracks the delivery of outbox events to
+/// them
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait WebhooksService: Sync + Send {
+    /// Get an individual `Webhook` by id
+    async fn get(&self, id: &str) -> Result<Option<Webhook>>;
+
+    /// Get the `Webhooks` for a `Show`, oldest first
+    async fn get_by_show_id(&self, show_id: &str) -> Result<Vec<Webhook>>;
+
+    /// Create a `Webhook` with the given input
+    async fn create(&self, input: &CreateWebhookInput) -> Result<Webhook>;
+
+    /// Update an existing `Webhook` by id
+    async fn update(&self, id: &str, input: &UpdateWebhookInput) -> Result<Webhook>;
+
+    /// Delete an existing `Webhook`, along with its delivery log
+    async fn delete(&self, id: &str) -> Result<()>;
+
+    /// Get the deliveries made to a `Webhook`, most recent first
+    async fn get_deliveries(
+        &self,
+        webhook_id: &str,
+        page: Option<u64>,
+        page_size: Option<u64>,
+    ) -> Result<ManyResponse<WebhookDelivery>>;
+
+    /// Queue a delivery to each subscribed `Webhook` for up to `limit` undispatched outbox events,
+    /// and return the number of events dispatched
+    async fn dispatch_events(&self, limit: u64) -> Result<u64>;
+
+    /// Claim up to `limit` pending deliveries that are due at `now`, holding them until
+    /// `lease_until` so that no other worker attempts them in the meantime
+    async fn claim_due(
+        &self,
+        now: DateTime,
+        lease_until: DateTime,
+        limit: u64,
+    ) -> Result<Vec<PendingDelivery>>;
+
+    /// Record the outcome of an attempt to make a delivery
+    async fn record_attempt(&self, id: &str, attempt: &DeliveryAttempt) -> Result<()>;
+}
+
+/// The default `WebhooksService` struct
+pub struct DefaultWebhooksService {
+    /// The SeaOrm database connection
+    db: Arc<DatabaseConnection>,
+}
+
+/// The default `WebhooksService` implementation
+impl DefaultWebhooksService {
+    /// Create a new `WebhooksService` instance
+    pub fn new(db: &Arc<DatabaseConnection>) -> Self {
+        Self { db: db.clone() }
+    }
+}
+
+#[async_trait]
+impl WebhooksService for DefaultWebhooksService {
+    async fn get(&self, id: &str) -> Result<Option<Webhook>> {
+        let webhook = model::Entity::find_by_id(id.to_owned())
+            .one(&*self.db)
+            .await?;
+
+        Ok(webhook)
+    }
+
+    async fn get_by_show_id(&self, show_id: &str) -> Result<Vec<Webhook>> {
+        let webhooks = model::Entity::find()
+            .filter(model::Column::ShowId.eq(show_id))
+            .order_by_asc(model::Column::CreatedAt)
+            .order_by_asc(model::Column::Id)
+            .all(&*self.db)
+            .await?;
+
+        Ok(webhooks)
+    }
+
+    async fn create(&self, input: &CreateWebhookInput) -> Result<Webhook> {
+        let webhook = model::ActiveModel {
+            show_id: Set(input.show_id.clone()),
+            url: Set(input.url.clone()),
+            secret: Set(input.secret.clone()),
+            event_types: Set(event_types(&input.event_types)),
+            ..Default::default()
+        }
+        .insert(&*self.db)
+        .await?;
+
+        Ok(webhook)
+    }
+
+    async fn update(&self, id: &str, input: &UpdateWebhookInput) -> Result<Webhook> {
+        // Retrieve the existing Webhook
+        let webhook = model::Entity::find_by_id(id.to_owned())
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| anyhow!("Unable to find Webhook with id: {}", id))?;
+
+        let mut webhook: model::ActiveModel = webhook.into();
+
+        if let Some(url) = &input.url {
+            webhook.url = Set(url.clone());
+        }
+
+        if let Some(secret) = &input.secret {
+            webhook.secret = Set(secret.clone());
+        }
+
+        if let Some(types) = &input.event_types {
+            webhook.event_types = Set(event_types(types));
+        }
+
+        let updated: Webhook = webhook.update(&*self.db).await?;
+
+        Ok(updated)
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        let webhook = model::Entity::find_by_id(id.to_owned())
+            .one(&*self.db)
+            .await?
+            .ok_or_else(|| anyhow!("Unable to find Webhook with id: {}", id))?;
+
+        // Deliveries are removed along with it by the foreign key
+        let _result = webhook.delete(&*self.db).await?;
+
+        Ok(())
+    }
+
+    async fn get_deliveries(
+        &self,
+        webhook_id: &str,
+        page: Option<u64>,
+        page_size: Option<u64>,
+    ) -> Result<ManyResponse<WebhookDelivery>> {
+        let page_num = page.unwrap_or(1);
+
+        let query = delivery::Entity::find()
+            .filter(delivery::Column::WebhookId.eq(webhook_id))
+            .order_by_desc(delivery::Column::CreatedAt)
+            .order_by_desc(delivery::Column::Id);
+
+        let (data, total) = if let Some(page_size) = page_size {
+            let paginator = query.paginate(&*self.db, page_size);
+            let total = paginator.num_items().await?;
+            let data: Vec<WebhookDelivery> = paginator.fetch_page(page_num - 1).await?;
+
+            (data, total)
+        } else {
+            let data: Vec<WebhookDelivery> = query.all(&*self.db).await?;
+            let total = data.len().try_into().unwrap_or(0);
+
+            (data, total)
+        };
+
+        Ok(ManyResponse::new(data, total, page_num, page_size))
+    }
+
+    async fn dispatch_events(&self, limit: u64) -> Result<u64> {
+        let txn = self.db.begin().await?;
+
+        // Skip events that another worker is already dispatching
+        let mut query = outbox::Entity::find()
+            .filter(outbox::Column::DispatchedAt.is_null())
+            .order_by_asc(outbox::Column::Id)
+            .limit(limit);
+
+        QuerySelect::query(&mut query)
+            .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked);
+
+        let events = query.all(&txn).await?;
+
+        if events.is_empty() {
+            txn.commit().await?;
+
+            return Ok(0);
+        }
+
+        let mut show_ids: Vec<String> = events.iter().map(|event| event.show_id.clone()).collect();
+        show_ids.sort();
+        show_ids.dedup();
+
+        let webhooks = model::Entity::find()
+            .filter(model::Column::ShowId.is_in(show_ids))
+            .all(&txn)
+            .await?;
+
+        let deliveries: Vec<delivery::ActiveModel> = events
+            .iter()
+            .flat_map(|event| {
+                webhooks
+                    .iter()
+                    .filter(|webhook| {
+                        webhook.show_id == event.show_id && webhook.subscribes_to(event.event_type)
+                    })
+                    .map(|webhook| delivery::ActiveModel {
+                        webhook_id: Set(webhook.id.clone()),
+                        event_id: Set(event.id.clone()),
+                        event_type: Set(event.event_type),
+                        ..Default::default()
+                    })
+            })
+            .collect();
+
+        if !deliveries.is_empty() {
+            delivery::Entity::insert_many(deliveries)
+                .on_conflict(
+                    OnConflict::columns([delivery::Column::WebhookId, delivery::Column::EventId])
+                        .do_nothing()
+                        .to_owned(),
+                )
+                .exec_without_returning(&txn)
+                .await?;
+        }
+
+        let event_ids: Vec<String> = events.iter().map(|event| event.id.clone()).collect();
+
+        outbox::Entity::update_many()
+            .col_expr(
+                outbox::Column::DispatchedAt,
+                Expr::current_timestamp().into(),
+            )
+            .filter(outbox::Column::Id.is_in(event_ids))
+            .exec(&txn)
+            .await?;
+
+        txn.commit().await?;
+
+        Ok(events.len().try_into().unwrap_or(0))
+    }
+
+    async fn claim_due(
+        &self,
+        now: DateTime,
+        lease_until: DateTime,
+        limit: u64,
+    ) -> Result<Vec<PendingDelivery>> {
+        let txn = self.db.begin().await?;
+
+        // Skip deliveries that another worker has already claimed
+        let mut query = delivery::Entity::find()
+            .filter(delivery::Column::Status.eq(DeliveryStatus::Pending))
+            .filter(
+                Condition::any()
+                    .add(delivery::Column::NextAttemptAt.is_null())
+                    .add(delivery::Column::NextAttemptAt.lte(now)),
+            )
+            .order_by_asc(delivery::Column::Id)
+            .limit(limit);
+
+        QuerySelect::query(&mut query)
+            .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked);
+
+        let deliveries = query.all(&txn).await?;
+
+        if deliveries.is_empty() {
+            txn.commit().await?;
+
+            return Ok(vec![]);
+        }
+
+        let ids: Vec<String> = deliveries.iter().map(|d| d.id.clone()).collect();
+
+        delivery::Entity::update_many()
+            .col_expr(delivery::Column::NextAttemptAt, Expr::value(lease_until))
+            .filter(delivery::Column::Id.is_in(ids))
+            .exec(&txn)
+            .await?;
+
+        let mut webhook_ids: Vec<String> =
+            deliveries.iter().map(|d| d.webhook_id.clone()).collect();
+        webhook_ids.sort();
+        webhook_ids.dedup();
+
+        let webhooks: HashMap<String, Webhook> = model::Entity::find()
+            .filter(model::Column::Id.is_in(webhook_ids))
+            .all(&txn)
+            .await?
+            .into_iter()
+            .map(|webhook| (webhook.id.clone(), webhook))
+            .collect();
+
+        let mut event_ids: Vec<String> = deliveries.iter().map(|d| d.event_id.clone()).collect();
+        event_ids.sort();
+        event_ids.dedup();
+
+        let events: HashMap<String, outbox::OutboxEvent> = outbox::Entity::find()
+            .filter(outbox::Column::Id.is_in(event_ids))
+            .all(&txn)
+            .await?
+            .into_iter()
+            .map(|event| (event.id.clone(), event))
+            .collect();
+
+        txn.commit().await?;
+
+        Ok(deliveries
+            .into_iter()
+            .filter_map(|delivery| {
+                let webhook = webhooks.get(&delivery.webhook_id)?.clone();
+                let event = events.get(&delivery.event_id)?.clone();
+
+                Some(PendingDelivery {
+                    delivery: WebhookDelivery {
+                        next_attempt_at: Some(lease_until),
+                        ..delivery
+                    },
+                    webhook,
+                    event,
+                })
+            })
+            .collect())
+    }
+
+    async fn record_attempt(&self, id: &str, attempt: &DeliveryAttempt) -> Result<()> {
+        let (status, next_attempt_at) = match (&attempt.error, attempt.retry_at) {
+            (None, _) => (DeliveryStatus::Succeeded, None),
+            (Some(_), Some(retry_at)) => (DeliveryStatus::Pending, Some(retry_at)),
+            (Some(_), None) => (DeliveryStatus::Failed, None),
+        };
+
+        let mut query = delivery::Entity::update_many()
+            .col_expr(
+                delivery::Column::Attempts,
+                Expr::col(delivery::Column::Attempts).add(1),
+            )
+            .col_expr(delivery::Column::Status, Expr::value(status))
+            .col_expr(
+                delivery::Column::NextAttemptAt,
+                Expr::value(next_attempt_at),
+            )
+            .col_expr(
+                delivery::Column::ResponseStatus,
+                Expr::value(attempt.response_status),
+            )
+            .col_expr(
+                delivery::Column::LastError,
+                Expr::value(attempt.error.clone()),
+            );
+
+        if status == DeliveryStatus::Succeeded {
+            query = query.col_expr(
+                delivery::Column::DeliveredAt,
+                Expr::current_timestamp().into(),
+            );
+        }
+
+        query
+            .filter(delivery::Column::Id.eq(id))
+            .exec(&*self.db)
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Remove any duplicates from a list of event types, keeping the first of each
+fn event_types(types: &[model::WebhookEventType]) -> WebhookEventTypes {
+    let mut unique = Vec::with_capacity(types.len());
+
+    for event_type in types {
+        if !unique.contains(event_type) {
+            unique.push(*event_type);
+        }
+    }
+
+    WebhookEventTypes(unique)
+}
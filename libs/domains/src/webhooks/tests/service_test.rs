@@ -0,0 +1,194 @@
+use anyhow::Result;
+use fake::{Fake, Faker};
+use pretty_assertions::assert_eq;
+use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Statement, Transaction};
+use serde_json::json;
+use std::sync::Arc;
+
+use crate::webhooks::{
+    delivery::DeliveryAttempt,
+    model::{Webhook, WebhookEventType, WebhookEventTypes},
+    mutations::CreateWebhookInput,
+    outbox::OutboxEvent,
+    service::{DefaultWebhooksService, WebhooksService},
+};
+
+#[tokio::test]
+async fn test_webhooks_service_create() -> Result<()> {
+    let mut webhook: Webhook = Faker.fake();
+    webhook.show_id = "test-show".to_string();
+    webhook.url = "https://example.com/hooks".to_string();
+    webhook.secret = "test-secret".to_string();
+    webhook.event_types = WebhookEventTypes(vec![WebhookEventType::ShowUpdated]);
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![webhook.clone()]])
+            .into_connection(),
+    );
+
+    let service = DefaultWebhooksService::new(&db);
+
+    let result = service
+        .create(&CreateWebhookInput {
+            show_id: "test-show".to_string(),
+            url: "https://example.com/hooks".to_string(),
+            secret: "test-secret".to_string(),
+            // Duplicates are removed
+            event_types: vec![WebhookEventType::ShowUpdated, WebhookEventType::ShowUpdated],
+        })
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, webhook);
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"INSERT INTO "webhooks" ("show_id", "url", "secret", "event_types") VALUES ($1, $2, $3, $4) RETURNING "id", "created_at", "updated_at", "show_id", "url", "secret", "event_types""#,
+            vec![
+                "test-show".into(),
+                "https://example.com/hooks".into(),
+                "test-secret".into(),
+                json!(["show.updated"]).into()
+            ]
+        )]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_webhooks_service_dispatch_events() -> Result<()> {
+    let mut event: OutboxEvent = Faker.fake();
+    event.id = "test-event".to_string();
+    event.show_id = "test-show".to_string();
+    event.event_type = WebhookEventType::ShowUpdated;
+
+    let mut subscribed: Webhook = Faker.fake();
+    subscribed.id = "test-webhook".to_string();
+    subscribed.show_id = "test-show".to_string();
+    subscribed.event_types = WebhookEventTypes(vec![WebhookEventType::ShowUpdated]);
+
+    let mut unsubscribed: Webhook = Faker.fake();
+    unsubscribed.show_id = "test-show".to_string();
+    unsubscribed.event_types = WebhookEventTypes(vec![WebhookEventType::EpisodeCreated]);
+
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(vec![vec![event.clone()]])
+            .append_query_results(vec![vec![subscribed.clone(), unsubscribed.clone()]])
+            .append_exec_results(vec![
+                MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 1,
+                },
+                MockExecResult {
+                    last_insert_id: 0,
+                    rows_affected: 1,
+                },
+            ])
+            .into_connection(),
+    );
+
+    let service = DefaultWebhooksService::new(&db);
+
+    let result = service.dispatch_events(10).await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    assert_eq!(result, 1);
+
+    // Check the transaction log
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::many(vec![
+            Statement::from_string(DatabaseBackend::Postgres, "BEGIN".to_string()),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"SELECT "outbox_events"."id", "outbox_events"."created_at", "outbox_events"."show_id", "outbox_events"."event_type", "outbox_events"."payload", "outbox_events"."dispatched_at" FROM "outbox_events" WHERE "outbox_events"."dispatched_at" IS NULL ORDER BY "outbox_events"."id" ASC LIMIT $1 FOR UPDATE SKIP LOCKED"#,
+                vec![10u64.into()]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"SELECT "webhooks"."id", "webhooks"."created_at", "webhooks"."updated_at", "webhooks"."show_id", "webhooks"."url", "webhooks"."secret", "webhooks"."event_types" FROM "webhooks" WHERE "webhooks"."show_id" IN ($1)"#,
+                vec!["test-show".into()]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"INSERT INTO "webhook_deliveries" ("webhook_id", "event_id", "event_type") VALUES ($1, $2, $3) ON CONFLICT ("webhook_id", "event_id") DO NOTHING"#,
+                vec![
+                    "test-webhook".into(),
+                    "test-event".into(),
+                    "show.updated".into()
+                ]
+            ),
+            Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"UPDATE "outbox_events" SET "dispatched_at" = CURRENT_TIMESTAMP WHERE "outbox_events"."id" IN ($1)"#,
+                vec!["test-event".into()]
+            ),
+            Statement::from_string(DatabaseBackend::Postgres, "COMMIT".to_string()),
+        ])]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_webhooks_service_record_attempt() -> Result<()> {
+    let db = Arc::new(
+        MockDatabase::new(DatabaseBackend::Postgres)
+            .append_exec_results(vec![MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 1,
+            }])
+            .into_connection(),
+    );
+
+    let service = DefaultWebhooksService::new(&db);
+
+    service
+        .record_attempt(
+            "test-delivery",
+            &DeliveryAttempt {
+                response_status: Some(500),
+                error: Some("Unexpected response status: 500".to_string()),
+                retry_at: None,
+            },
+        )
+        .await?;
+
+    // Destroy the service to clean up the reference count
+    drop(service);
+
+    let db = Arc::try_unwrap(db).expect("Unable to unwrap the DatabaseConnection");
+
+    // Without a retry, the delivery is given up on
+    assert_eq!(
+        db.into_transaction_log(),
+        vec![Transaction::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            r#"UPDATE "webhook_deliveries" SET "attempts" = "attempts" + $1, "status" = $2, "next_attempt_at" = $3, "response_status" = $4, "last_error" = $5 WHERE "webhook_deliveries"."id" = $6"#,
+            vec![
+                1i32.into(),
+                "failed".into(),
+                None::<sea_orm::prelude::DateTime>.into(),
+                Some(500i32).into(),
+                Some("Unexpected response status: 500".to_string()).into(),
+                "test-delivery".into()
+            ]
+        )]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use hyper::StatusCode;
+use thiserror::Error;
+
+/// Expected Error Cases
+#[derive(Error, Debug)]
+pub enum UploadError {
+    /// The uploaded file is larger than the configured limit
+    #[error("The file is larger than the {0} byte limit")]
+    TooLarge(usize),
+
+    /// The uploaded file is not an image type that pictures can use
+    #[error("The file is not a supported image type")]
+    UnsupportedType,
+
+    /// The uploaded file looks like an image, but could not be decoded
+    #[error("The image could not be read: {0}")]
+    InvalidImage(image::ImageError),
+
+    /// An error occurred while reading the upload or writing to storage
+    #[error("Unable to store the file: {0}")]
+    Storage(#[from] anyhow::Error),
+}
+
+impl UploadError {
+    /// The HTTP status code that best describes the error
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            UploadError::TooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            UploadError::UnsupportedType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            UploadError::InvalidImage(_) => StatusCode::BAD_REQUEST,
+            UploadError::Storage(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use bytes::Bytes;
+use image::{ImageFormat, ImageOutputFormat};
+use std::io::Cursor;
+
+use crate::errors::UploadError;
+
+/// An uploaded image that has been checked and thumbnailed, ready to be stored
+#[derive(Clone, Debug)]
+pub struct ProcessedImage {
+    /// The detected format of the original image
+    pub format: ImageFormat,
+    /// The original image data
+    pub data: Bytes,
+    /// The format of the thumbnail
+    pub thumbnail_format: ImageFormat,
+    /// The thumbnail image data
+    pub thumbnail: Bytes,
+}
+
+/// The MIME type for a supported image format
+pub fn content_type(format: ImageFormat) -> &'static str {
+    match format {
+        ImageFormat::Jpeg => "image/jpeg",
+        ImageFormat::Gif => "image/gif",
+        ImageFormat::WebP => "image/webp",
+        _ => "image/png",
+    }
+}
+
+/// The file extension for a supported image format
+pub fn extension(format: ImageFormat) -> &'static str {
+    match format {
+        ImageFormat::Jpeg => "jpg",
+        ImageFormat::Gif => "gif",
+        ImageFormat::WebP => "webp",
+        _ => "png",
+    }
+}
+
+/// Identify the image format from the file's contents rather than trusting the name or
+/// content type the client sent, rejecting anything that isn't a supported picture format
+pub fn sniff_format(data: &[u8]) -> Result<ImageFormat, UploadError> {
+    match image::guess_format(data) {
+        Ok(
+            format @ (ImageFormat::Jpeg | ImageFormat::Png | ImageFormat::Gif | ImageFormat::WebP),
+        ) => Ok(format),
+        _ => Err(UploadError::UnsupportedType),
+    }
+}
+
+/// Check an uploaded image and generate a thumbnail that fits within `thumbnail_size` pixels on
+/// each side. Decoding is CPU-bound, so this should be called from a blocking task.
+pub fn process_image(
+    data: Vec<u8>,
+    max_size: usize,
+    thumbnail_size: u32,
+) -> Result<ProcessedImage, UploadError> {
+    if data.len() > max_size {
+        return Err(UploadError::TooLarge(max_size));
+    }
+
+    let format = sniff_format(&data)?;
+
+    let image =
+        image::load_from_memory_with_format(&data, format).map_err(UploadError::InvalidImage)?;
+
+    // Photos stay JPEG, while everything else becomes a PNG to keep any transparency
+    let (thumbnail_format, output_format) = match format {
+        ImageFormat::Jpeg => (ImageFormat::Jpeg, ImageOutputFormat::Jpeg(85)),
+        _ => (ImageFormat::Png, ImageOutputFormat::Png),
+    };
+
+    let mut thumbnail = Cursor::new(Vec::new());
+
+    image
+        .thumbnail(thumbnail_size, thumbnail_size)
+        .write_to(&mut thumbnail, output_format)
+        .map_err(UploadError::InvalidImage)?;
+
+    Ok(ProcessedImage {
+        format,
+        data: Bytes::from(data),
+        thumbnail_format,
+        thumbnail: Bytes::from(thumbnail.into_inner()),
+    })
+}
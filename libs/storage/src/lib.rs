@@ -0,0 +1,20 @@
+//! Storage Library
+
+/// Error Cases
+pub mod errors;
+
+/// Storage backends for uploaded files
+pub mod storage;
+
+/// Image processing for uploaded pictures
+pub mod images;
+
+/// GraphQL file uploads
+pub mod uploads;
+
+/// Tests
+#[cfg(test)]
+mod tests;
+
+#[macro_use]
+extern crate log;
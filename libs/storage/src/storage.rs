@@ -0,0 +1,169 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use bytes::Bytes;
+#[cfg(test)]
+use mockall::automock;
+use object_store::{
+    aws::{AmazonS3, AmazonS3Builder},
+    path::Path as ObjectPath,
+    Attribute, Attributes, ObjectStore, PutOptions, PutPayload,
+};
+use std::{
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+use tokio::fs;
+
+use caster_utils::config::{Config, StorageBackend, S3};
+
+/// A Storage backend keeps uploaded files and makes them available at a public url
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait Storage: Sync + Send {
+    /// Store a file under the given key, returning the url it can be retrieved from
+    async fn put(&self, key: &str, content_type: &str, data: Bytes) -> Result<String>;
+
+    /// Remove the file stored under the given key, if it exists
+    async fn delete(&self, key: &str) -> Result<()>;
+}
+
+/// Create a `Storage` based on the configured backend
+pub fn init_storage(config: &'static Config) -> Result<Arc<dyn Storage>> {
+    let storage = &config.storage;
+
+    match storage.backend {
+        StorageBackend::Local => Ok(Arc::new(LocalStorage::new(
+            &storage.path,
+            &storage.base_url,
+        ))),
+        StorageBackend::S3 => Ok(Arc::new(S3Storage::new(&storage.s3, &storage.base_url)?)),
+    }
+}
+
+/// Join a key onto a base url
+fn url_for(base_url: &str, key: &str) -> String {
+    format!("{}/{}", base_url.trim_end_matches('/'), key)
+}
+
+/// Find the key for a url under the base url, if it was issued by this storage
+pub fn key_for<'a>(base_url: &str, url: &'a str) -> Option<&'a str> {
+    url.strip_prefix(base_url.trim_end_matches('/'))?
+        .strip_prefix('/')
+        .filter(|key| !key.is_empty())
+}
+
+/// A `Storage` that writes files to a directory on the local filesystem
+pub struct LocalStorage {
+    path: PathBuf,
+    base_url: String,
+}
+
+impl LocalStorage {
+    /// Create a new `LocalStorage` writing to the given directory
+    pub fn new(path: &str, base_url: &str) -> Self {
+        Self {
+            path: PathBuf::from(path),
+            base_url: base_url.to_string(),
+        }
+    }
+
+    /// Resolve a key within the storage directory, refusing keys that would escape it
+    fn resolve(&self, key: &str) -> Result<PathBuf> {
+        let relative = Path::new(key);
+
+        if !relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return Err(anyhow!("Invalid storage key: {}", key));
+        }
+
+        Ok(self.path.join(relative))
+    }
+}
+
+#[async_trait]
+impl Storage for LocalStorage {
+    async fn put(&self, key: &str, _content_type: &str, data: Bytes) -> Result<String> {
+        let path = self.resolve(key)?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        fs::write(&path, data).await?;
+
+        Ok(url_for(&self.base_url, key))
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        match fs::remove_file(self.resolve(key)?).await {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+/// A `Storage` that writes files to an S3-compatible bucket
+pub struct S3Storage {
+    store: AmazonS3,
+    base_url: String,
+}
+
+impl S3Storage {
+    /// Create a new `S3Storage` for the configured bucket
+    pub fn new(config: &S3, base_url: &str) -> Result<Self> {
+        // Fall back to the standard AWS environment variables for anything not configured
+        let mut builder = AmazonS3Builder::from_env()
+            .with_bucket_name(&config.bucket)
+            .with_region(&config.region);
+
+        if let Some(endpoint) = &config.endpoint {
+            builder = builder
+                .with_endpoint(endpoint)
+                .with_allow_http(endpoint.starts_with("http://"));
+        }
+
+        if let Some(access_key_id) = &config.access_key_id {
+            builder = builder.with_access_key_id(access_key_id);
+        }
+
+        if let Some(secret_access_key) = &config.secret_access_key {
+            builder = builder.with_secret_access_key(secret_access_key);
+        }
+
+        Ok(Self {
+            store: builder.build()?,
+            base_url: base_url.to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl Storage for S3Storage {
+    async fn put(&self, key: &str, content_type: &str, data: Bytes) -> Result<String> {
+        let mut attributes = Attributes::new();
+        attributes.insert(Attribute::ContentType, content_type.to_string().into());
+
+        self.store
+            .put_opts(
+                &ObjectPath::parse(key)?,
+                PutPayload::from(data),
+                PutOptions {
+                    attributes,
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        Ok(url_for(&self.base_url, key))
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        match self.store.delete(&ObjectPath::parse(key)?).await {
+            Ok(()) | Err(object_store::Error::NotFound { .. }) => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
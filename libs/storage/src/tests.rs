@@ -0,0 +1,3 @@
+mod images_test;
+mod storage_test;
+mod uploads_test;
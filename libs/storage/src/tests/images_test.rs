@@ -0,0 +1,93 @@
+use anyhow::Result;
+use image::{DynamicImage, GenericImageView, ImageFormat, ImageOutputFormat, RgbaImage};
+use pretty_assertions::assert_eq;
+use std::io::Cursor;
+
+use crate::{errors::UploadError, images::process_image};
+
+/// Encode a blank image of the given size
+pub fn encode_image(width: u32, height: u32, format: ImageOutputFormat) -> Vec<u8> {
+    let image = DynamicImage::ImageRgba8(RgbaImage::new(width, height));
+    let image = match format {
+        // JPEG has no alpha channel
+        ImageOutputFormat::Jpeg(_) => DynamicImage::ImageRgb8(image.to_rgb8()),
+        _ => image,
+    };
+
+    let mut data = Cursor::new(Vec::new());
+    image
+        .write_to(&mut data, format)
+        .expect("Unable to encode image");
+
+    data.into_inner()
+}
+
+#[test]
+fn test_images_process_png() -> Result<()> {
+    let data = encode_image(800, 400, ImageOutputFormat::Png);
+
+    let result = process_image(data.clone(), 1_000_000, 256)?;
+
+    assert_eq!(result.format, ImageFormat::Png);
+    assert_eq!(result.data.to_vec(), data);
+    assert_eq!(result.thumbnail_format, ImageFormat::Png);
+
+    // The thumbnail keeps the aspect ratio
+    let thumbnail = image::load_from_memory(&result.thumbnail)?;
+    assert_eq!(thumbnail.dimensions(), (256, 128));
+
+    Ok(())
+}
+
+#[test]
+fn test_images_process_jpeg() -> Result<()> {
+    let data = encode_image(300, 600, ImageOutputFormat::Jpeg(90));
+
+    let result = process_image(data, 1_000_000, 100)?;
+
+    assert_eq!(result.format, ImageFormat::Jpeg);
+    assert_eq!(result.thumbnail_format, ImageFormat::Jpeg);
+    assert_eq!(image::guess_format(&result.thumbnail)?, ImageFormat::Jpeg);
+
+    let thumbnail = image::load_from_memory(&result.thumbnail)?;
+    assert_eq!(thumbnail.dimensions(), (50, 100));
+
+    Ok(())
+}
+
+#[test]
+fn test_images_process_too_large() -> Result<()> {
+    let data = encode_image(100, 100, ImageOutputFormat::Png);
+    let max_size = data.len() - 1;
+
+    let result = process_image(data, max_size, 256);
+
+    assert!(matches!(result, Err(UploadError::TooLarge(size)) if size == max_size));
+
+    Ok(())
+}
+
+#[test]
+fn test_images_process_unsupported() -> Result<()> {
+    // A client-supplied content type or name makes no difference
+    let data = b"<svg xmlns=\"http://www.w3.org/2000/svg\"></svg>".to_vec();
+
+    let result = process_image(data, 1_000_000, 256);
+
+    assert!(matches!(result, Err(UploadError::UnsupportedType)));
+
+    Ok(())
+}
+
+#[test]
+fn test_images_process_invalid() -> Result<()> {
+    // A valid PNG signature followed by garbage
+    let mut data = encode_image(10, 10, ImageOutputFormat::Png)[..8].to_vec();
+    data.extend_from_slice(b"not really a png");
+
+    let result = process_image(data, 1_000_000, 256);
+
+    assert!(matches!(result, Err(UploadError::InvalidImage(_))));
+
+    Ok(())
+}
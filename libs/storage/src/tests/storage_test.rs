@@ -0,0 +1,101 @@
+use anyhow::Result;
+use bytes::Bytes;
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+use crate::storage::{key_for, LocalStorage, Storage};
+
+#[tokio::test]
+async fn test_local_storage_put() -> Result<()> {
+    let dir = tempdir()?;
+
+    let storage = LocalStorage::new(
+        dir.path().to_str().unwrap(),
+        "http://localhost:3000/uploads/",
+    );
+
+    let url = storage
+        .put(
+            "shows/test-show/picture.png",
+            "image/png",
+            Bytes::from("data"),
+        )
+        .await?;
+
+    assert_eq!(
+        url,
+        "http://localhost:3000/uploads/shows/test-show/picture.png"
+    );
+    assert_eq!(
+        std::fs::read(dir.path().join("shows/test-show/picture.png"))?,
+        b"data"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_local_storage_delete() -> Result<()> {
+    let dir = tempdir()?;
+
+    let storage = LocalStorage::new(dir.path().to_str().unwrap(), "/uploads");
+
+    storage
+        .put(
+            "shows/test-show/picture.png",
+            "image/png",
+            Bytes::from("data"),
+        )
+        .await?;
+
+    storage.delete("shows/test-show/picture.png").await?;
+
+    assert!(!dir.path().join("shows/test-show/picture.png").exists());
+
+    // Deleting a missing file is not an error
+    storage.delete("shows/test-show/picture.png").await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_local_storage_invalid_key() -> Result<()> {
+    let dir = tempdir()?;
+
+    let storage = LocalStorage::new(dir.path().join("uploads").to_str().unwrap(), "/uploads");
+
+    for key in [
+        "../escaped.png",
+        "/etc/escaped.png",
+        "shows/../../escaped.png",
+    ] {
+        let result = storage.put(key, "image/png", Bytes::from("data")).await;
+
+        assert!(result.is_err(), "Expected {} to be rejected", key);
+    }
+
+    assert!(!dir.path().join("escaped.png").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_storage_key_for() {
+    let base_url = "http://localhost:3000/uploads/";
+
+    assert_eq!(
+        key_for(
+            base_url,
+            "http://localhost:3000/uploads/shows/1/picture.png"
+        ),
+        Some("shows/1/picture.png")
+    );
+
+    // Urls from elsewhere don't belong to this storage
+    assert_eq!(key_for(base_url, "https://example.com/picture.png"), None);
+    assert_eq!(
+        key_for(base_url, "http://localhost:3000/uploads-old/picture.png"),
+        None
+    );
+    assert_eq!(key_for(base_url, "http://localhost:3000/uploads/"), None);
+}
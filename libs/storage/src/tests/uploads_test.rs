@@ -0,0 +1,163 @@
+use anyhow::{anyhow, Result};
+use image::ImageOutputFormat;
+use mockall::predicate::{always, eq, function};
+use pretty_assertions::assert_eq;
+
+use super::images_test::encode_image;
+use crate::{
+    errors::UploadError,
+    storage::MockStorage,
+    uploads::{remove_pictures, store_picture, StoredPicture},
+};
+use caster_utils::config::{self, StorageBackend, S3};
+
+fn storage_config() -> config::Storage {
+    config::Storage {
+        backend: StorageBackend::Local,
+        path: "uploads".to_string(),
+        base_url: "http://localhost:3000/uploads".to_string(),
+        max_size: 1_000_000,
+        thumbnail_size: 64,
+        s3: S3 {
+            bucket: "caster".to_string(),
+            region: "us-east-1".to_string(),
+            endpoint: None,
+            access_key_id: None,
+            secret_access_key: None,
+        },
+    }
+}
+
+#[tokio::test]
+async fn test_uploads_store_picture() -> Result<()> {
+    let data = encode_image(128, 128, ImageOutputFormat::Png);
+
+    let mut storage = MockStorage::new();
+
+    storage
+        .expect_put()
+        .with(
+            function(|key: &str| key.starts_with("shows/test-show/") && key.ends_with(".png")),
+            eq("image/png"),
+            eq(bytes::Bytes::from(data.clone())),
+        )
+        .times(1)
+        .returning(|key, _, _| Ok(format!("http://localhost:3000/uploads/{}", key)));
+
+    storage
+        .expect_put()
+        .with(
+            function(|key: &str| key.ends_with("-thumbnail.png")),
+            eq("image/png"),
+            always(),
+        )
+        .times(1)
+        .returning(|key, _, _| Ok(format!("http://localhost:3000/uploads/{}", key)));
+
+    let result = store_picture(&storage, &storage_config(), "shows/test-show", data).await?;
+
+    let StoredPicture { url, thumbnail_url } = result;
+
+    assert!(url.starts_with("http://localhost:3000/uploads/shows/test-show/"));
+    assert_eq!(thumbnail_url, url.replace(".png", "-thumbnail.png"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_uploads_store_picture_thumbnail_error() -> Result<()> {
+    let data = encode_image(128, 128, ImageOutputFormat::Png);
+
+    let mut storage = MockStorage::new();
+
+    storage
+        .expect_put()
+        .with(
+            function(|key: &str| !key.contains("-thumbnail")),
+            always(),
+            always(),
+        )
+        .times(1)
+        .returning(|key, _, _| Ok(format!("http://localhost:3000/uploads/{}", key)));
+
+    storage
+        .expect_put()
+        .with(
+            function(|key: &str| key.contains("-thumbnail")),
+            always(),
+            always(),
+        )
+        .times(1)
+        .returning(|_, _, _| Err(anyhow!("Storage is unavailable")));
+
+    // The original is cleaned up when the thumbnail can't be stored
+    storage
+        .expect_delete()
+        .with(function(|key: &str| {
+            key.starts_with("shows/test-show/") && !key.contains("-thumbnail")
+        }))
+        .times(1)
+        .returning(|_| Ok(()));
+
+    let result = store_picture(&storage, &storage_config(), "shows/test-show", data).await;
+
+    assert!(matches!(result, Err(UploadError::Storage(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_uploads_store_picture_unsupported() -> Result<()> {
+    // Nothing is stored for an invalid upload
+    let storage = MockStorage::new();
+
+    let result = store_picture(
+        &storage,
+        &storage_config(),
+        "shows/test-show",
+        b"just some text".to_vec(),
+    )
+    .await;
+
+    assert!(matches!(result, Err(UploadError::UnsupportedType)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_uploads_remove_pictures() -> Result<()> {
+    let mut storage = MockStorage::new();
+
+    storage
+        .expect_delete()
+        .with(eq("shows/test-show/picture.png"))
+        .times(1)
+        .returning(|_| Ok(()));
+
+    // A failure is logged, and doesn't stop the rest from being removed
+    storage
+        .expect_delete()
+        .with(eq("shows/test-show/picture-thumbnail.png"))
+        .times(1)
+        .returning(|_| Err(anyhow!("Storage is unavailable")));
+
+    remove_pictures(
+        &storage,
+        &storage_config(),
+        "shows/test-show",
+        &[
+            "http://localhost:3000/uploads/shows/test-show/picture.png",
+            "http://localhost:3000/uploads/shows/test-show/picture-thumbnail.png",
+            // Pictures hosted elsewhere are left alone
+            "https://example.com/picture.png",
+            // So are pictures stored for other records, or outside the record's own prefix
+            "http://localhost:3000/uploads/shows/other-show/picture.png",
+            "http://localhost:3000/uploads/shows/test-show-2/picture.png",
+            "http://localhost:3000/uploads/shows/test-show/../other-show/picture.png",
+            "http://localhost:3000/uploads/shows/test-show/",
+        ],
+    )
+    .await;
+
+    Ok(())
+}
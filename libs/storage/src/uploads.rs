@@ -0,0 +1,174 @@
+use async_graphql::{Context, Error, Upload, UploadValue};
+use std::{io::Read, sync::Arc};
+use tokio::task::spawn_blocking;
+use ulid::Ulid;
+
+use crate::{
+    errors::UploadError,
+    images::{content_type, extension, process_image},
+    storage::{key_for, Storage},
+};
+use caster_utils::{
+    config::{self, Config},
+    errors::as_graphql_error,
+};
+
+/// The urls of a stored picture and its thumbnail
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StoredPicture {
+    /// The url of the original picture
+    pub url: String,
+    /// The url of the generated thumbnail
+    pub thumbnail_url: String,
+}
+
+/// Check, thumbnail, and store picture data under the given key prefix. The thumbnail is stored
+/// alongside the original, with a "-thumbnail" suffix on the file name.
+pub async fn store_picture(
+    storage: &dyn Storage,
+    config: &config::Storage,
+    prefix: &str,
+    data: Vec<u8>,
+) -> Result<StoredPicture, UploadError> {
+    let (max_size, thumbnail_size) = (config.max_size, config.thumbnail_size);
+
+    let image = spawn_blocking(move || process_image(data, max_size, thumbnail_size))
+        .await
+        .map_err(|err| UploadError::Storage(err.into()))??;
+
+    let name = Ulid::new().to_string().to_lowercase();
+    let key = format!("{}/{}.{}", prefix, name, extension(image.format));
+    let thumbnail_key = format!(
+        "{}/{}-thumbnail.{}",
+        prefix,
+        name,
+        extension(image.thumbnail_format)
+    );
+
+    let url = storage
+        .put(&key, content_type(image.format), image.data)
+        .await?;
+
+    let thumbnail_url = match storage
+        .put(
+            &thumbnail_key,
+            content_type(image.thumbnail_format),
+            image.thumbnail,
+        )
+        .await
+    {
+        Ok(thumbnail_url) => thumbnail_url,
+        Err(err) => {
+            // Don't leave the original behind without its thumbnail
+            if let Err(err) = storage.delete(&key).await {
+                warn!("Unable to remove picture {}: {}", key, err);
+            }
+
+            return Err(err.into());
+        }
+    };
+
+    Ok(StoredPicture { url, thumbnail_url })
+}
+
+/// Remove previously stored pictures, skipping any urls that weren't issued by this storage for
+/// a picture directly under the given key prefix, so that a url copied from another record can't
+/// be used to remove that record's files. Failures are logged rather than returned, because the
+/// replacement has already been saved.
+pub async fn remove_pictures(
+    storage: &dyn Storage,
+    config: &config::Storage,
+    prefix: &str,
+    urls: &[&str],
+) {
+    let keys = urls
+        .iter()
+        .filter_map(|url| key_for(&config.base_url, url))
+        .filter(|key| is_under(prefix, key));
+
+    for key in keys {
+        if let Err(err) = storage.delete(key).await {
+            warn!("Unable to remove picture {}: {}", key, err);
+        }
+    }
+}
+
+/// Read a GraphQL upload and store it as a picture under the given key prefix, returning the urls
+/// of the stored picture and its thumbnail
+pub async fn upload_picture(
+    ctx: &Context<'_>,
+    upload: &Upload,
+    prefix: &str,
+) -> async_graphql::Result<StoredPicture> {
+    let storage = ctx.data_unchecked::<Arc<dyn Storage>>();
+    let config = ctx.data_unchecked::<&'static Config>();
+
+    let max_size = config.storage.max_size;
+    let value = upload.value(ctx)?;
+
+    // Check the size before reading the whole file into memory
+    let data = match spawn_blocking(move || read_upload(value, max_size)).await {
+        Ok(result) => result,
+        Err(err) => Err(UploadError::Storage(err.into())),
+    }
+    .map_err(as_upload_error)?;
+
+    store_picture(storage.as_ref(), &config.storage, prefix, data)
+        .await
+        .map_err(as_upload_error)
+}
+
+/// Remove the stored files for a picture that has been replaced, as long as they were stored
+/// under the given key prefix
+pub async fn remove_picture(
+    ctx: &Context<'_>,
+    prefix: &str,
+    url: Option<&str>,
+    thumbnail_url: Option<&str>,
+) {
+    let storage = ctx.data_unchecked::<Arc<dyn Storage>>();
+    let config = ctx.data_unchecked::<&'static Config>();
+
+    let urls: Vec<&str> = url.into_iter().chain(thumbnail_url).collect();
+
+    remove_pictures(storage.as_ref(), &config.storage, prefix, &urls).await;
+}
+
+/// Check whether a key names a file stored directly under the prefix by `store_picture`
+fn is_under(prefix: &str, key: &str) -> bool {
+    key.strip_prefix(prefix)
+        .and_then(|rest| rest.strip_prefix('/'))
+        .is_some_and(|name| !name.is_empty() && !name.contains('/'))
+}
+
+/// Read an uploaded file into memory, as long as it is within the size limit
+fn read_upload(value: UploadValue, max_size: usize) -> Result<Vec<u8>, UploadError> {
+    let size = value.size().map_err(anyhow::Error::from)?;
+
+    if size > max_size as u64 {
+        return Err(UploadError::TooLarge(max_size));
+    }
+
+    let mut data = Vec::new();
+
+    value
+        .into_read()
+        .read_to_end(&mut data)
+        .map_err(anyhow::Error::from)?;
+
+    Ok(data)
+}
+
+/// Convert an `UploadError` into a GraphQL error with an appropriate status code
+fn as_upload_error(err: UploadError) -> Error {
+    let message = match err {
+        UploadError::TooLarge(_) => "Picture is too large",
+        UploadError::UnsupportedType => "Picture must be a JPEG, PNG, GIF, or WebP image",
+        UploadError::InvalidImage(_) => "Picture could not be read",
+        UploadError::Storage(_) => "Error while storing picture",
+    };
+
+    let code = err.status_code();
+
+    as_graphql_error(message, code)(err.into())
+}
@@ -0,0 +1,52 @@
+use anyhow::Result;
+use bytes::Bytes;
+use hyper::{body::to_bytes, Client, StatusCode};
+use pretty_assertions::assert_eq;
+use ulid::Ulid;
+
+use caster_storage::storage::{S3Storage, Storage};
+use caster_utils::config::get_config;
+
+/// It stores and removes files in an S3-compatible bucket, like the `MinIO` server from
+/// docker-compose
+#[tokio::test]
+#[ignore]
+async fn test_s3_storage_put_and_delete() -> Result<()> {
+    let s3 = &get_config().storage.s3;
+
+    let endpoint = s3
+        .endpoint
+        .as_ref()
+        .expect("An S3 endpoint is required, like a local MinIO server");
+
+    // The docker-compose bucket allows anonymous downloads
+    let base_url = format!("{}/{}", endpoint.trim_end_matches('/'), s3.bucket);
+
+    let storage = S3Storage::new(s3, &base_url)?;
+
+    let key = format!("tests/{}.png", Ulid::new().to_string().to_lowercase());
+    let data = Bytes::from_static(b"not really a png");
+
+    let url = storage.put(&key, "image/png", data.clone()).await?;
+
+    assert_eq!(url, format!("{}/{}", base_url, key));
+
+    let client = Client::new();
+
+    let resp = client.get(url.parse()?).await?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()["content-type"], "image/png");
+    assert_eq!(to_bytes(resp.into_body()).await?, data);
+
+    storage.delete(&key).await?;
+
+    let resp = client.get(url.parse()?).await?;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    // Removing a file that doesn't exist is not an error
+    storage.delete(&key).await?;
+
+    Ok(())
+}
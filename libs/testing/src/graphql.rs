@@ -30,4 +30,70 @@ impl GraphQL {
 
         req.body(Body::from(body)).map_err(|err| err.into())
     }
+
+    /// Create a GraphQL multipart request for Hyper that uploads a single file as the given
+    /// variable, with an optional auth token
+    pub fn upload(
+        &self,
+        query: &str,
+        mut variables: Value,
+        file: Upload<'_>,
+        token: Option<&str>,
+    ) -> Result<Request<Body>> {
+        let boundary = "caster-upload-boundary";
+
+        let mut req = Request::builder()
+            .method(Method::POST)
+            .uri(&self.url)
+            .header(
+                "Content-Type",
+                format!("multipart/form-data; boundary={}", boundary),
+            );
+
+        if let Some(token) = token {
+            req = req.header("Authorization", format!("Bearer {}", token));
+        }
+
+        // The file variable is null in the operation, and mapped to the file part
+        variables[file.variable] = Value::Null;
+
+        let operations = serde_json::to_string(&json!({ "query": query, "variables": variables }))?;
+        let map = serde_json::to_string(&json!({ "0": [format!("variables.{}", file.variable)] }))?;
+
+        let mut body = Vec::new();
+
+        for (name, value) in [("operations", operations), ("map", map)] {
+            body.extend_from_slice(
+                format!(
+                    "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+                    boundary, name, value
+                )
+                .as_bytes(),
+            );
+        }
+
+        body.extend_from_slice(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"0\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+                boundary, file.filename, file.content_type
+            )
+            .as_bytes(),
+        );
+        body.extend_from_slice(file.data);
+        body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+
+        req.body(Body::from(body)).map_err(|err| err.into())
+    }
+}
+
+/// A file to upload with a GraphQL multipart request
+pub struct Upload<'a> {
+    /// The name of the variable the file is passed as
+    pub variable: &'a str,
+    /// The file name
+    pub filename: &'a str,
+    /// The content type reported by the client
+    pub content_type: &'a str,
+    /// The file contents
+    pub data: &'a [u8],
 }
@@ -66,6 +66,175 @@ pub struct Auth {
     pub audience: String,
     /// Auth client config
     pub client: AuthClient,
+    /// Log the User's roles and allowed actions for denied authorization decisions, honored in
+    /// development only. This is not a Polar trace; set `POLAR_LOG=trace` for that.
+    #[serde(default, alias = "trace_denied")]
+    pub log_denied: bool,
+}
+
+/// Rate limiting backends
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RateLimitBackend {
+    /// Counters are kept in memory, local to this process
+    Memory,
+    /// Counters are kept in Redis, shared between processes
+    Redis,
+}
+
+/// Rate limiting config
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RateLimit {
+    /// Whether rate limiting is enabled
+    pub enabled: bool,
+    /// Where the request counters are stored
+    pub backend: RateLimitBackend,
+    /// The length of each rate limiting window, in seconds
+    pub window: u64,
+    /// The number of GraphQL queries allowed per window
+    pub queries: u64,
+    /// The number of GraphQL mutations allowed per window
+    pub mutations: u64,
+    /// The number of `WebSocket` messages allowed per window
+    pub messages: u64,
+}
+
+/// Episode presence backends
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PresenceBackend {
+    /// Viewers are kept in memory, local to this process
+    Memory,
+    /// Viewers are kept in Redis, shared between processes
+    Redis,
+}
+
+/// Episode presence config
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Presence {
+    /// Where the current viewers of each Episode are stored. This is chosen explicitly, like the
+    /// rate limit backend, because `redis.url` always has a default and so can't show whether
+    /// Redis is actually available.
+    pub backend: PresenceBackend,
+}
+
+/// Purge config for deleted records
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Purge {
+    /// The number of days that deleted records are kept around to be restored
+    pub retention_days: i64,
+    /// How often to check for deleted records to purge, in seconds
+    pub interval: u64,
+}
+
+/// Scheduled Episode publishing config
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Publishing {
+    /// How often to check for scheduled Episodes that are due, in seconds
+    pub interval: u64,
+}
+
+/// Outbound webhook delivery config
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Webhooks {
+    /// How often to check for webhook deliveries that are due, in seconds
+    pub interval: u64,
+    /// How long to wait for a webhook receiver to respond, in seconds
+    pub timeout: u64,
+    /// How long to wait before the first retry of a failed delivery, in seconds. Each retry
+    /// after that waits twice as long as the one before.
+    pub retry_delay: u64,
+    /// The number of attempts to make before giving up on a delivery
+    pub max_attempts: u32,
+    /// Allow deliveries to loopback, private, and link-local addresses. These are refused by
+    /// default, so that a webhook can't be used to reach internal services.
+    pub allow_private_addresses: bool,
+}
+
+/// Episode chat config
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Chat {
+    /// Words that are masked with asterisks in chat Messages before they are saved. Each one is
+    /// matched against whole words only, ignoring case.
+    pub banned_words: Vec<String>,
+}
+
+/// `WebSocket` events config
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Events {
+    /// How often the server pings each connection, in seconds
+    pub heartbeat_interval: u64,
+    /// How long a connection can go without sending anything, including Pongs, before it is
+    /// evicted, in seconds
+    pub heartbeat_timeout: u64,
+    /// The number of outgoing messages that can be queued for a connection. Connections that
+    /// fall this far behind are evicted.
+    pub send_queue: usize,
+}
+
+/// Show invitation config
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Invitations {
+    /// The number of days that a pending Invitation can be accepted
+    pub expire_days: i64,
+}
+
+/// Podcast feed config
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Feeds {
+    /// The public site url that feed links point to
+    pub base_url: String,
+}
+
+/// Storage backends for uploaded files
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    /// Files are written to a directory on the local filesystem and served by the API
+    Local,
+    /// Files are written to an S3-compatible bucket
+    S3,
+}
+
+/// S3-compatible bucket config
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct S3 {
+    /// The bucket name
+    pub bucket: String,
+    /// The bucket region
+    pub region: String,
+    /// A custom endpoint, for S3-compatible services like `MinIO`
+    pub endpoint: Option<String>,
+    /// The access key id, read from the environment if not set
+    pub access_key_id: Option<String>,
+    /// The secret access key, read from the environment if not set
+    pub secret_access_key: Option<String>,
+}
+
+/// Uploaded file storage config
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Storage {
+    /// Where uploaded files are stored
+    pub backend: StorageBackend,
+    /// The directory that the local backend writes files to
+    pub path: String,
+    /// The public url that stored files are served from
+    pub base_url: String,
+    /// The maximum size of an uploaded image, in bytes
+    pub max_size: usize,
+    /// The maximum width and height of generated thumbnails, in pixels
+    pub thumbnail_size: u32,
+    /// S3-compatible bucket config
+    pub s3: S3,
+}
+
+/// Authorization policy config
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Policies {
+    /// A directory of `.polar` files to load instead of the built-in policies
+    pub dir: Option<String>,
+    /// How often to check the directory for changes, in seconds
+    pub watch_interval: u64,
 }
 
 /// Application Config
@@ -81,6 +250,28 @@ pub struct Config {
     pub redis: Redis,
     /// Auth config
     pub auth: Auth,
+    /// Rate limiting config
+    pub rate_limit: RateLimit,
+    /// Purge config for deleted records
+    pub purge: Purge,
+    /// Authorization policy config
+    pub policies: Policies,
+    /// Show invitation config
+    pub invitations: Invitations,
+    /// Podcast feed config
+    pub feeds: Feeds,
+    /// Scheduled Episode publishing config
+    pub publishing: Publishing,
+    /// Outbound webhook delivery config
+    pub webhooks: Webhooks,
+    /// Episode chat config
+    pub chat: Chat,
+    /// `WebSocket` events config
+    pub events: Events,
+    /// Episode presence config
+    pub presence: Presence,
+    /// Uploaded file storage config
+    pub storage: Storage,
 }
 
 impl Config {
@@ -108,6 +299,29 @@ impl Config {
                     .map(|key| key.as_str().replace("DATABASE_", "DATABASE.").into())
                     // Split the Redis variables
                     .map(|key| key.as_str().replace("REDIS_", "REDIS.").into())
+                    // Split the Rate Limit variables
+                    .map(|key| key.as_str().replace("RATE_LIMIT_", "RATE_LIMIT.").into())
+                    // Split the Purge variables
+                    .map(|key| key.as_str().replace("PURGE_", "PURGE.").into())
+                    // Split the Invitations variables
+                    .map(|key| key.as_str().replace("INVITATIONS_", "INVITATIONS.").into())
+                    // Split the Publishing variables
+                    .map(|key| key.as_str().replace("PUBLISHING_", "PUBLISHING.").into())
+                    // Split the Webhooks variables
+                    .map(|key| key.as_str().replace("WEBHOOKS_", "WEBHOOKS.").into())
+                    // Split the Chat variables
+                    .map(|key| key.as_str().replace("CHAT_", "CHAT.").into())
+                    // Split the Events variables
+                    .map(|key| key.as_str().replace("EVENTS_", "EVENTS.").into())
+                    // Split the Presence variables
+                    .map(|key| key.as_str().replace("PRESENCE_", "PRESENCE.").into())
+                    // Split the Feeds variables
+                    .map(|key| key.as_str().replace("FEEDS_", "FEEDS.").into())
+                    // Split the Storage variables
+                    .map(|key| key.as_str().replace("STORAGE_S3_", "STORAGE.S3.").into())
+                    .map(|key| key.as_str().replace("STORAGE_", "STORAGE.").into())
+                    // Split the Policies variables
+                    .map(|key| key.as_str().replace("POLICIES_", "POLICIES.").into())
                     // Split the Auth variables
                     .map(|key| key.as_str().replace("AUTH_CLIENT_", "AUTH.CLIENT.").into())
                     .map(|key| key.as_str().replace("AUTH_", "AUTH.").into()),
@@ -1,5 +1,7 @@
-use async_graphql::{Error, ErrorExtensions};
+use async_graphql::{Error, ErrorExtensions, Value};
 use hyper::StatusCode;
+use serde::Serialize;
+use std::fmt;
 
 /// A convenience function to create a GraphQL error with predictable extension props
 pub fn graphql_error(message: &'static str, code: StatusCode) -> Error {
@@ -23,3 +25,92 @@ pub fn as_graphql_error(
         })
     })
 }
+
+/// An error for an update that was rejected because the record has changed since the client
+/// last retrieved it
+#[derive(Debug)]
+pub struct Conflict<T> {
+    /// The current state of the record
+    pub current: T,
+}
+
+impl<T> fmt::Display for Conflict<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The record has changed since it was last retrieved")
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for Conflict<T> {}
+
+/// A record that can be returned in the "current" extension of a Conflict error
+pub trait ConflictRecord: Serialize {
+    /// The serialized fields that are hidden from the GraphQL schema, which are left out of the
+    /// extension so that it only shows clients what they could query themselves
+    const HIDDEN_FIELDS: &'static [&'static str];
+}
+
+/// Create a 409 GraphQL error carrying the current state of a record in the "current" extension
+pub fn conflict_error<T: ConflictRecord>(current: &T) -> Error {
+    let current = serde_json::to_value(current)
+        .ok()
+        .map(|mut value| {
+            if let serde_json::Value::Object(fields) = &mut value {
+                for field in T::HIDDEN_FIELDS {
+                    fields.remove(*field);
+                }
+            }
+
+            value
+        })
+        .and_then(|value| Value::from_json(camel_case_keys(value)).ok())
+        .unwrap_or(Value::Null);
+
+    graphql_error("Conflict", StatusCode::CONFLICT).extend_with(move |_err, e| {
+        e.set("current", current.clone());
+    })
+}
+
+/// Like `as_graphql_error`, but turns a `Conflict` for the given type into a 409 error with the
+/// current state of the record
+pub fn as_update_error<T>(
+    message: &'static str,
+    code: StatusCode,
+) -> Box<dyn Fn(anyhow::Error) -> Error>
+where
+    T: ConflictRecord + fmt::Debug + Send + Sync + 'static,
+{
+    Box::new(move |err| match err.downcast::<Conflict<T>>() {
+        Ok(conflict) => conflict_error(&conflict.current),
+        Err(err) => as_graphql_error(message, code)(err),
+    })
+}
+
+/// Rename object keys to match the camelCase field names used in the GraphQL schema
+fn camel_case_keys(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(fields) => serde_json::Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| {
+                    let mut words = key.split('_');
+                    let mut renamed = words.next().unwrap_or_default().to_string();
+
+                    for word in words {
+                        let mut chars = word.chars();
+
+                        if let Some(first) = chars.next() {
+                            renamed.extend(first.to_uppercase());
+                            renamed.push_str(chars.as_str());
+                        }
+                    }
+
+                    (renamed, camel_case_keys(value))
+                })
+                .collect(),
+        ),
+        serde_json::Value::Array(values) => {
+            serde_json::Value::Array(values.into_iter().map(camel_case_keys).collect())
+        }
+        value => value,
+    }
+}
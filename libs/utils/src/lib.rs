@@ -13,11 +13,17 @@ pub mod http;
 /// Error helpers for GraphQL
 pub mod errors;
 
+/// Conditional update helpers
+pub mod updates;
+
 /// Pagination utils
 pub mod pagination;
 
 /// Ordering utils
 pub mod ordering;
 
+/// Request context utils
+pub mod request;
+
 #[macro_use]
 extern crate anyhow;
@@ -0,0 +1,6 @@
+/// The header used to read and echo the id of a request
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// The id of the current request, used to correlate audit events and logs
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestId(pub String);
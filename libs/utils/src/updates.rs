@@ -0,0 +1,52 @@
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use sea_orm::{
+    sea_query::{Expr, SimpleExpr},
+    ActiveModelBehavior, ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait,
+    IntoActiveModel, QueryFilter,
+};
+use std::{fmt, future::Future};
+
+use crate::errors::Conflict;
+
+/// Save changes to a record. When an `expected` date is given, the write only happens if the
+/// record's `updated_at` column still matches it, so that concurrent updates aren't silently
+/// overwritten. Otherwise, the current state of the record is retrieved with `current` and
+/// returned in a `Conflict` error.
+pub async fn update_if_unchanged<A, C, T, F, Fut>(
+    db: &C,
+    record: A,
+    filter: SimpleExpr,
+    updated_at: <A::Entity as EntityTrait>::Column,
+    expected: Option<NaiveDateTime>,
+    current: F,
+) -> Result<<A::Entity as EntityTrait>::Model>
+where
+    A: ActiveModelTrait + ActiveModelBehavior + Send,
+    <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+    C: ConnectionTrait,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<T>>,
+    T: fmt::Debug + Send + Sync + 'static,
+{
+    let Some(expected) = expected else {
+        return Ok(record.update(db).await?);
+    };
+
+    let updated = A::Entity::update_many()
+        .set(record)
+        .col_expr(updated_at, Expr::current_timestamp().into())
+        .filter(filter)
+        .filter(updated_at.eq(expected))
+        .exec_with_returning(db)
+        .await?
+        .pop();
+
+    match updated {
+        Some(updated) => Ok(updated),
+        None => Err(Conflict {
+            current: current().await?,
+        }
+        .into()),
+    }
+}
@@ -0,0 +1,44 @@
+-- Shows
+alter table shows add column deleted_at timestamp(3);
+
+create index shows__deleted_at__index on shows (deleted_at);
+
+-- Episodes
+alter table episodes add column deleted_at timestamp(3);
+
+create index episodes__deleted_at__index on episodes (deleted_at);
+
+-- Profiles
+alter table profiles add column deleted_at timestamp(3);
+
+create index profiles__deleted_at__index on profiles (deleted_at);
+
+-- Allow a User to create a new Profile while a deleted one is awaiting purge
+drop index profiles__user_id__unique;
+
+create unique index profiles__user_id__unique on profiles (user_id) where deleted_at is null;
+
+-- Soft-delete or restore the Episodes of a Show along with it. Episodes that were deleted on
+-- their own beforehand keep their own deleted_at, so they stay deleted when the Show is restored.
+create or replace function on_soft_delete_show ()
+    returns trigger
+    as $$
+begin
+    if old.deleted_at is null and new.deleted_at is not null then
+        update episodes set deleted_at = new.deleted_at
+        where show_id = new.id
+            and deleted_at is null;
+    elsif old.deleted_at is not null and new.deleted_at is null then
+        update episodes set deleted_at = null
+        where show_id = new.id
+            and deleted_at = old.deleted_at;
+    end if;
+    return new;
+end;
+$$
+language plpgsql;
+
+-- Whenever a Show is soft-deleted or restored, do the same for its Episodes
+create trigger on_soft_delete_show
+    after update of deleted_at on shows for each row
+    execute procedure on_soft_delete_show ();
@@ -0,0 +1,20 @@
+create table audit_events
+(
+    id             text         default gen_random_ulid() not null
+        primary key,
+    created_at     timestamp(3) default CURRENT_TIMESTAMP not null,
+
+    actor_id       text
+        references users
+            on update cascade on delete set null,
+    action         text                                   not null,
+    resource_table text                                   not null,
+    resource_id    text                                   not null,
+    before         jsonb,
+    after          jsonb,
+    request_id     text
+);
+
+create index audit_events__resource__index on audit_events (resource_table, resource_id, created_at);
+
+create index audit_events__actor_id__index on audit_events (actor_id);
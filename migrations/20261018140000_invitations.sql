@@ -0,0 +1,26 @@
+-- Invitations to join a Show with a Role
+create table invitations (
+    id text default gen_random_ulid () not null primary key,
+    created_at timestamp(3) default current_timestamp not null,
+    updated_at timestamp(3) default current_timestamp not null,
+    show_id text not null
+        references shows
+            on update cascade on delete cascade,
+    role_key text not null,
+    user_id text not null
+        references users
+            on update cascade on delete cascade,
+    inviter_id text
+        references users
+            on update cascade on delete set null,
+    status text default 'pending' not null,
+    expires_at timestamp(3) not null
+);
+
+create index invitations__user_id__index on invitations (user_id, status);
+
+create index invitations__show_id__index on invitations (show_id);
+
+create trigger sync_invitations_updated_at
+    before update on invitations for each row
+    execute procedure sync_updated_at ();
@@ -0,0 +1,11 @@
+-- Episode media enclosures and audio metadata
+alter table episodes
+    add column media_url text,
+    add column media_type text,
+    add column media_length bigint check (media_length >= 0),
+    add column duration integer check (duration >= 0),
+    add column season_number integer check (season_number >= 0),
+    add column episode_number integer check (episode_number >= 0),
+    add column explicit boolean default false not null;
+
+create index episodes__episode_number__index on episodes (show_id, season_number, episode_number);
@@ -0,0 +1,11 @@
+-- Episode publishing workflow
+alter table episodes
+    add column status text default 'published' not null,
+    add column publish_at timestamp(3);
+
+-- Existing Episodes stay visible, and new ones start out as drafts
+update episodes set publish_at = created_at;
+
+alter table episodes alter column status set default 'draft';
+
+create index episodes__status__publish_at__index on episodes (status, publish_at);
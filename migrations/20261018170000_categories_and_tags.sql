@@ -0,0 +1,54 @@
+-- Categories, managed by site admins, which may be nested under a parent Category
+create table categories (
+    id text default gen_random_ulid () not null primary key,
+    created_at timestamp(3) default current_timestamp not null,
+    updated_at timestamp(3) default current_timestamp not null,
+    name text not null,
+    slug text not null,
+    parent_id text
+        references categories
+            on update cascade on delete cascade
+);
+
+create unique index categories__slug__unique on categories (slug);
+
+create index categories__parent_id__index on categories (parent_id);
+
+create trigger sync_categories_updated_at
+    before update on categories for each row
+    execute procedure sync_updated_at ();
+
+-- The Categories that each Show is listed under
+create table shows_categories (
+    show_id text not null
+        references shows
+            on update cascade on delete cascade,
+    category_id text not null
+        references categories
+            on update cascade on delete cascade,
+    primary key (show_id, category_id)
+);
+
+create index shows_categories__category_id__index on shows_categories (category_id);
+
+-- Free-form Tags, normalized to lowercase
+create table tags (
+    id text default gen_random_ulid () not null primary key,
+    created_at timestamp(3) default current_timestamp not null,
+    name text not null
+);
+
+create unique index tags__name__unique on tags (name);
+
+-- The Tags that each Show is labeled with
+create table shows_tags (
+    show_id text not null
+        references shows
+            on update cascade on delete cascade,
+    tag_id text not null
+        references tags
+            on update cascade on delete cascade,
+    primary key (show_id, tag_id)
+);
+
+create index shows_tags__tag_id__index on shows_tags (tag_id);
@@ -0,0 +1,17 @@
+-- The Shows that each Profile follows
+create table follows (
+    profile_id text not null
+        references profiles
+            on update cascade on delete cascade,
+    show_id text not null
+        references shows
+            on update cascade on delete cascade,
+    created_at timestamp(3) default current_timestamp not null,
+    primary key (profile_id, show_id)
+);
+
+create index follows__show_id__index on follows (show_id);
+
+-- Feeds list published Episodes newest first
+create index episodes__feed__index on episodes (show_id, publish_at desc, id desc)
+    where status = 'published' and deleted_at is null;
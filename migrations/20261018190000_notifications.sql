@@ -0,0 +1,16 @@
+-- In-app notifications for each User
+create table notifications (
+    id text default gen_random_ulid () not null primary key,
+    created_at timestamp(3) default current_timestamp not null,
+    user_id text not null
+        references users
+            on update cascade on delete cascade,
+    kind text not null,
+    payload jsonb not null,
+    read_at timestamp(3)
+);
+
+create index notifications__user_id__index on notifications (user_id, created_at desc, id desc);
+
+create index notifications__unread__index on notifications (user_id)
+    where read_at is null;
@@ -0,0 +1,64 @@
+-- Webhook subscriptions that notify partner systems about changes to a Show
+create table webhooks (
+    id text default gen_random_ulid () not null primary key,
+    created_at timestamp(3) default current_timestamp not null,
+    updated_at timestamp(3) default current_timestamp not null,
+    show_id text not null
+        references shows
+            on update cascade on delete cascade,
+    url text not null,
+    secret text not null,
+    event_types jsonb default '[]' not null
+);
+
+create index webhooks__show_id__index on webhooks (show_id);
+
+create trigger sync_webhooks_updated_at
+    before update on webhooks for each row
+    execute procedure sync_updated_at ();
+
+-- Changes to Shows and Episodes, written in the same transaction as the change itself and
+-- dispatched to webhooks afterwards
+create table outbox_events (
+    id text default gen_random_ulid () not null primary key,
+    created_at timestamp(3) default current_timestamp not null,
+    show_id text not null
+        references shows
+            on update cascade on delete cascade,
+    event_type text not null,
+    payload jsonb not null,
+    dispatched_at timestamp(3)
+);
+
+create index outbox_events__pending__index on outbox_events (id)
+    where dispatched_at is null;
+
+-- Each attempt to deliver an outbox event to a webhook
+create table webhook_deliveries (
+    id text default gen_random_ulid () not null primary key,
+    created_at timestamp(3) default current_timestamp not null,
+    updated_at timestamp(3) default current_timestamp not null,
+    webhook_id text not null
+        references webhooks
+            on update cascade on delete cascade,
+    event_id text not null
+        references outbox_events
+            on update cascade on delete cascade,
+    event_type text not null,
+    status text default 'pending' not null,
+    attempts integer default 0 not null,
+    next_attempt_at timestamp(3),
+    response_status integer,
+    last_error text,
+    delivered_at timestamp(3),
+    unique (webhook_id, event_id)
+);
+
+create index webhook_deliveries__webhook_id__index on webhook_deliveries (webhook_id, created_at desc);
+
+create index webhook_deliveries__due__index on webhook_deliveries (next_attempt_at)
+    where status = 'pending';
+
+create trigger sync_webhook_deliveries_updated_at
+    before update on webhook_deliveries for each row
+    execute procedure sync_updated_at ();
@@ -0,0 +1,39 @@
+-- The previous text of each edited chat Message
+create table "message_edits"
+(
+    id         text         default gen_random_ulid() not null
+        primary key,
+    created_at timestamp(3) default CURRENT_TIMESTAMP not null,
+
+    message_id text                                   not null
+        references messages
+            on update cascade on delete cascade,
+
+    text       text                                   not null
+);
+
+create index message_edits__message_id__index on message_edits (message_id, created_at);
+
+-- Profiles kept from chatting about an Episode until a given date
+create table "chat_mutes"
+(
+    id          text         default gen_random_ulid() not null
+        primary key,
+    created_at  timestamp(3) default CURRENT_TIMESTAMP not null,
+    updated_at  timestamp(3) default CURRENT_TIMESTAMP not null,
+
+    episode_id  text                                   not null
+        references episodes
+            on update cascade on delete cascade,
+    profile_id  text                                   not null
+        references profiles
+            on update cascade on delete cascade,
+
+    muted_until timestamp(3)                           not null,
+
+    unique (episode_id, profile_id)
+);
+
+create trigger sync_chat_mutes_updated_at before update on chat_mutes for each row execute procedure sync_updated_at();
+
+create index messages__episode_id__index on messages (episode_id, id);
@@ -0,0 +1,23 @@
+-- Threaded replies to chat Messages
+alter table "messages"
+    add column parent_id text
+        references messages
+            on update cascade on delete cascade;
+
+create index messages__parent_id__index on messages (parent_id, id);
+
+-- Emoji reactions to chat Messages, one of each kind per Profile
+create table "message_reactions"
+(
+    message_id text                                   not null
+        references messages
+            on update cascade on delete cascade,
+    profile_id text                                   not null
+        references profiles
+            on update cascade on delete cascade,
+    emoji      text                                   not null,
+
+    created_at timestamp(3) default CURRENT_TIMESTAMP not null,
+
+    primary key (message_id, profile_id, emoji)
+);
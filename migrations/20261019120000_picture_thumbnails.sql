@@ -0,0 +1,9 @@
+-- Thumbnails generated for uploaded pictures
+alter table "shows"
+    add column picture_thumbnail text;
+
+alter table "episodes"
+    add column picture_thumbnail text;
+
+alter table "profiles"
+    add column picture_thumbnail text;